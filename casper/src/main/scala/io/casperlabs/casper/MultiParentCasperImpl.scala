@@ -379,7 +379,8 @@ class MultiParentCasperImpl[F[_]: Bracket[?[_], Throwable]: Log: Time: FinalityD
                        merged,
                        remaining,
                        justifications,
-                       protocolVersion
+                       protocolVersion,
+                       ByteString.copyFrom(publicKey)
                      )
                    } else {
                      CreateBlockStatus.noNewDeploys.pure[F]
@@ -487,15 +488,28 @@ class MultiParentCasperImpl[F[_]: Bracket[?[_], Throwable]: Log: Time: FinalityD
       merged: ExecEngineUtil.MergeResult[ExecEngineUtil.TransformMap, Block],
       deploys: Seq[Deploy],
       justifications: Seq[Justification],
-      protocolVersion: ProtocolVersion
+      protocolVersion: ProtocolVersion,
+      proposer: ByteString
   ): F[CreateBlockStatus] =
     (for {
-      now <- Time[F].currentMillis
+      now               <- Time[F].currentMillis
+      dag               <- dag
+      justificationMsgs <- justifications.toList.traverse(j => dag.lookup(j.latestBlockHash))
+      maxRank = justificationMsgs.flatten.foldLeft(-1L) {
+        case (acc, b) => math.max(b.rank, acc)
+      }
+      number = maxRank + 1
+      blockInfo = ExecutionEngineService.BlockInfo(
+        blocktime = now,
+        blockHeight = number,
+        parentBlockHash = parents.headOption.fold(ByteString.EMPTY)(_.blockHash),
+        proposer = proposerAddress(proposer)
+      )
       result <- ExecEngineUtil
                  .computeDeploysCheckpoint[F](
                    merged,
                    deploys,
-                   now,
+                   blockInfo,
                    protocolVersion
                  )
       DeploysCheckpoint(
@@ -510,13 +524,7 @@ class MultiParentCasperImpl[F[_]: Bracket[?[_], Throwable]: Log: Time: FinalityD
         invalidNonceDeploys,
         deploysToDiscard,
         protocolVersion
-      )                 = result
-      dag               <- dag
-      justificationMsgs <- justifications.toList.traverse(j => dag.lookup(j.latestBlockHash))
-      maxRank = justificationMsgs.flatten.foldLeft(-1L) {
-        case (acc, b) => math.max(b.rank, acc)
-      }
-      number = maxRank + 1
+      ) = result
       status = if (deploysForBlock.isEmpty) {
         CreateBlockStatus.noNewDeploys
       } else {
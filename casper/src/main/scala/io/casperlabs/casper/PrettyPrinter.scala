@@ -66,8 +66,10 @@ object PrettyPrinter {
         .map(buildString)
         .mkString(",")}, {${actionThresholds.map(buildString)}}, {${accountActivity.map(buildString)})"
     case Value.Value.BytesValue(bytes) => s"ByteArray(${buildString(bytes)})"
-    case Value.Value.Contract(Contract(body, urefs, protocolVersion)) =>
-      s"Contract(${buildString(body)}, {${urefs.map(buildString).mkString(",")}}, ${buildString(protocolVersion)})"
+    case Value.Value.Contract(Contract(body, urefs, protocolVersion, entryPoints)) =>
+      s"Contract(${buildString(body)}, {${urefs.map(buildString).mkString(",")}}, ${buildString(protocolVersion)}, {${entryPoints
+        .map(buildString)
+        .mkString(",")}})"
     case Value.Value.IntList(IntList(list))       => s"List(${list.mkString(",")})"
     case Value.Value.IntValue(i)                  => s"Int32($i)"
     case Value.Value.NamedKey(nk)                 => buildString(nk)
@@ -77,6 +79,16 @@ object PrettyPrinter {
     case Value.Value.Key(key)                     => buildString(key)
     case Value.Value.LongValue(l)                 => s"Long($l)"
     case Value.Value.Unit(_)                      => "Unit"
+    case Value.Value.ContractPackage(ContractPackage(accessKey, versions)) =>
+      s"ContractPackage(${accessKey.map(buildString)}, {${versions.map(buildString).mkString(",")}})"
+  }
+
+  private def buildString(e: Contract.EntryPoint): String =
+    s"EntryPoint(${e.name}, [${e.args.map(_.name).mkString(",")}])"
+
+  private def buildString(v: ContractPackage.Version): String = v match {
+    case ContractPackage.Version(version, contract, isEnabled) =>
+      s"Version($version, ${contract.map(c => buildString(Value(Value.Value.Contract(c))))}, $isEnabled)"
   }
 
   def buildString(b: BlockMessage): String =
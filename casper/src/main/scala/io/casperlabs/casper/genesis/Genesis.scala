@@ -68,9 +68,10 @@ object Genesis {
       genesisValidators = bondsMap
         .map {
           case (publicKey, stake) =>
+            // Proof of stake knows validators by the address of their account.
             ipc
               .Bond()
-              .withValidatorPublicKey(ByteString.copyFrom(publicKey))
+              .withValidatorPublicKey(ProtoUtil.accountAddress(ByteString.copyFrom(publicKey)))
               .withStake(state.BigInt(stake.toString, 512))
         }
         .toSeq
@@ -85,7 +86,7 @@ object Genesis {
 
       request = ipc
         .GenesisRequest()
-        .withAddress(ByteString.copyFrom(accountPublicKey))
+        .withPublicKey(ProtoUtil.eeTaggedPublicKey(ByteString.copyFrom(accountPublicKey)))
         .withInitialMotes(
           state
             .BigInt(
@@ -19,6 +19,7 @@ import io.casperlabs.crypto.signatures.SignatureAlgorithm
 import io.casperlabs.ipc
 import io.casperlabs.blockstorage.BlockMetadata
 import io.casperlabs.shared.Time
+import io.casperlabs.smartcontracts.ExecutionEngineService
 import java.util.NoSuchElementException
 
 import scala.collection.immutable
@@ -335,6 +336,33 @@ object ProtoUtil {
   def blockNumber(b: Block): Long =
     b.getHeader.rank
 
+  /** The block as seen by the contracts its deploys run; its main parent is its first one. */
+  def blockInfo(b: Block): ExecutionEngineService.BlockInfo =
+    ExecutionEngineService.BlockInfo(
+      blocktime = b.getHeader.timestamp,
+      blockHeight = blockNumber(b),
+      parentBlockHash = parentHashes(b).headOption.getOrElse(ByteString.EMPTY),
+      proposer = proposerAddress(b.getHeader.validatorPublicKey)
+    )
+
+  /**
+    * Bonds keyed by validator public key, from the bonds of the execution engine which are keyed by
+    * the address of the validators' accounts. Validators are looked up among the candidate keys;
+    * addresses of none of them are kept as they are.
+    */
+  def bondsByPublicKey(bonds: Seq[Bond], candidateKeys: Seq[ByteString]): Seq[Bond] = {
+    val keysByAddress = candidateKeys.map(key => accountAddress(key) -> key).toMap
+    bonds.map { bond =>
+      keysByAddress
+        .get(bond.validatorPublicKey)
+        .fold(bond)(bond.withValidatorPublicKey)
+    }
+  }
+
+  /** Address of the account of the validator proposing a block; genesis has no proposer. */
+  def proposerAddress(validatorPublicKey: ByteString): ByteString =
+    if (validatorPublicKey.isEmpty) ByteString.EMPTY else accountAddress(validatorPublicKey)
+
   def toJustification(
       latestMessages: collection.Map[Validator, BlockMetadata]
   ): Seq[Justification] =
@@ -465,6 +493,7 @@ object ProtoUtil {
       .withAccountPublicKey(accountPublicKey)
       .withTimestamp(timestamp)
       .withNonce(nonce)
+      .withGasPrice(GAS_PRICE)
       .withBodyHash(protoHash(b))
     Deploy()
       .withDeployHash(protoHash(h))
@@ -482,25 +511,90 @@ object ProtoUtil {
   def sourceDeploy(sessionCode: ByteString, timestamp: Long): Deploy =
     basicDeploy(timestamp, sessionCode)
 
-  // https://casperlabs.atlassian.net/browse/EE-283
-  // We are hardcoding exchange rate for DEV NET at 10:1
-  // (1 gas costs you 10 motes).
-  // Later, post DEV NET, conversion rate will be part of a deploy.
+  // Gas price of the deploys created by `basicDeploy`; the price of other deploys is in their header.
   val GAS_PRICE     = 10L
   val PAYMENT_MOTES = 1000000000L
 
+  // Deploys don't carry a TTL yet; the execution engine rejects them once it has passed.
+  val DEPLOY_TTL_MILLIS = 24L * 60 * 60 * 1000
+
+  val ED25519_PUBLIC_KEY_LENGTH = 32
+
+  /**
+    * The public key as the execution engine knows it: tagged with the algorithm its length implies,
+    * and compressed for secp256k1.
+    */
+  def eeTaggedPublicKey(publicKey: ByteString): ipc.TaggedPublicKey =
+    if (publicKey.size == ED25519_PUBLIC_KEY_LENGTH)
+      ipc.TaggedPublicKey(ipc.SignatureAlgorithm.ED25519, publicKey)
+    else
+      SignatureAlgorithm.Secp256k1.compressPublicKey(publicKey.toByteArray) match {
+        case Some(compressed) =>
+          ipc.TaggedPublicKey(ipc.SignatureAlgorithm.SECP256K1, ByteString.copyFrom(compressed))
+        // Not a key of either algorithm; its address won't be that of any account.
+        case None => ipc.TaggedPublicKey(ipc.SignatureAlgorithm.ED25519, publicKey)
+      }
+
+  /** Address of the account of a public key in the execution engine. */
+  def accountAddress(publicKey: ByteString): ByteString = {
+    val tagged = eeTaggedPublicKey(publicKey)
+    ByteString.copyFrom(
+      Blake2b256.hash(Array(tagged.algorithm.value.toByte) ++ tagged.key.toByteArray)
+    )
+  }
+
+  private def eeSignatureAlgorithm(sigAlgorithm: String): ipc.SignatureAlgorithm =
+    if (sigAlgorithm == "secp256k1") ipc.SignatureAlgorithm.SECP256K1
+    else ipc.SignatureAlgorithm.ED25519
+
+  /**
+    * The approval as the execution engine expects it: secp256k1 keys compressed and signatures
+    * compact rather than DER encoded. Keys or signatures which can't be converted are passed on
+    * as they are, for the execution engine to reject.
+    */
+  private def eeApproval(a: Approval): ipc.Approval =
+    eeSignatureAlgorithm(a.getSignature.sigAlgorithm) match {
+      case ipc.SignatureAlgorithm.SECP256K1 =>
+        val secp256k1 = SignatureAlgorithm.Secp256k1
+        ipc.Approval(
+          secp256k1
+            .compressPublicKey(a.approverPublicKey.toByteArray)
+            .fold(a.approverPublicKey)(ByteString.copyFrom),
+          secp256k1
+            .toCompactSignature(a.getSignature.sig.toByteArray)
+            .fold(a.getSignature.sig)(ByteString.copyFrom),
+          ipc.SignatureAlgorithm.SECP256K1
+        )
+      case algorithm =>
+        ipc.Approval(a.approverPublicKey, a.getSignature.sig, algorithm)
+    }
+
+  private def eeDeployCode(c: Deploy.Code): ipc.DeployCode = {
+    val code = ipc.DeployCode(c.code, c.args)
+    c.storedContract match {
+      case Deploy.Code.StoredContract.StoredContractHash(hash) => code.withStoredContractHash(hash)
+      case Deploy.Code.StoredContract.StoredContractName(name) => code.withStoredContractName(name)
+      case Deploy.Code.StoredContract.Empty                    => code
+    }
+  }
+
   def deployDataToEEDeploy(d: Deploy): ipc.Deploy = ipc.Deploy(
-    address = d.getHeader.accountPublicKey,
-    session = d.getBody.session.map { case Deploy.Code(code, args) => ipc.DeployCode(code, args) },
-    payment = d.getBody.payment.map { case Deploy.Code(code, args) => ipc.DeployCode(code, args) },
+    address = accountAddress(d.getHeader.accountPublicKey),
+    session = d.getBody.session.map(eeDeployCode),
+    payment = d.getBody.payment.map(eeDeployCode),
     // The new data type doesn't have a limit field. Remove this once payment is implemented.
     motesTransferredInPayment =
-      if (d.getBody.getPayment.code.isEmpty || d.getBody.getPayment.code == d.getBody.getSession.code) {
+      if (d.getBody.getPayment.storedContract.isEmpty &&
+          (d.getBody.getPayment.code.isEmpty || d.getBody.getPayment.code == d.getBody.getSession.code)) {
         sys.env.get("CL_DEFAULT_PAYMENT_MOTES").map(_.toLong).getOrElse(PAYMENT_MOTES)
       } else 0L,
-    gasPrice = GAS_PRICE,
+    gasPrice = d.getHeader.gasPrice,
     nonce = d.getHeader.nonce,
-    authorizationKeys = d.approvals.map(_.approverPublicKey)
+    approvals = d.approvals.map(eeApproval),
+    deployHash = d.deployHash,
+    timestamp = d.getHeader.timestamp,
+    ttl = DEPLOY_TTL_MILLIS,
+    header = d.getHeader.toByteString
   )
 
   def dependenciesHashesOf(b: Block): List[BlockHash] = {
@@ -40,14 +40,14 @@ object ExecEngineUtil {
   def computeDeploysCheckpoint[F[_]: MonadThrowable: BlockStorage: Log: ExecutionEngineService](
       merged: MergeResult[TransformMap, Block],
       deploys: Seq[Deploy],
-      blocktime: Long,
+      blockInfo: ExecutionEngineService.BlockInfo,
       protocolVersion: state.ProtocolVersion
   ): F[DeploysCheckpoint] =
     for {
       preStateHash <- computePrestate[F](merged)
       processedDeploys <- processDeploys[F](
                            preStateHash,
-                           blocktime,
+                           blockInfo,
                            deploys,
                            protocolVersion
                          )
@@ -78,10 +78,13 @@ object ExecEngineUtil {
         .mkString("\n")
       _ <- Log[F]
             .info(s"Block created with effects:\n$msgBody")
+      // Validators bonded by this block's deploys bond with the key of their account.
+      candidateKeys = merged.parents.flatMap(ProtoUtil.bonds(_).map(_.validatorPublicKey)) ++
+        deploys.map(_.getHeader.accountPublicKey)
     } yield DeploysCheckpoint(
       preStateHash,
       commitResult.postStateHash,
-      commitResult.bondedValidators,
+      ProtoUtil.bondsByPublicKey(commitResult.bondedValidators, candidateKeys),
       deploysForBlock,
       invalidDeploys.invalidNonceDeploys,
       invalidDeploys.preconditionFailures,
@@ -90,12 +93,12 @@ object ExecEngineUtil {
 
   private def processDeploys[F[_]: MonadError[?[_], Throwable]: BlockStorage: ExecutionEngineService](
       prestate: StateHash,
-      blocktime: Long,
+      blockInfo: ExecutionEngineService.BlockInfo,
       deploys: Seq[Deploy],
       protocolVersion: state.ProtocolVersion
   ): F[Seq[DeployResult]] =
     ExecutionEngineService[F]
-      .exec(prestate, blocktime, deploys.map(ProtoUtil.deployDataToEEDeploy), protocolVersion)
+      .exec(prestate, blockInfo, deploys.map(ProtoUtil.deployDataToEEDeploy), protocolVersion)
       .rethrow
 
   private def processGenesisDeploys[F[_]: MonadError[?[_], Throwable]: BlockStorage: ExecutionEngineService](
@@ -181,7 +184,7 @@ object ExecEngineUtil {
   ): F[Seq[TransformEntry]] = {
     val deploys         = ProtoUtil.deploys(block).flatMap(_.deploy)
     val protocolVersion = CasperLabsProtocolVersions.thresholdsVersionMap.fromBlock(block)
-    val blocktime       = block.getHeader.timestamp
+    val blockInfo       = ProtoUtil.blockInfo(block)
 
     if (isGenesisLike(block)) {
       for {
@@ -192,7 +195,7 @@ object ExecEngineUtil {
       for {
         processedDeploys <- processDeploys[F](
                              prestate,
-                             blocktime,
+                             blockInfo,
                              deploys,
                              protocolVersion
                            )
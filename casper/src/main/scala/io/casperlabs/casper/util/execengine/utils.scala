@@ -7,9 +7,9 @@ import io.casperlabs.ipc.DeployError.Value.{Empty, ExecError, GasError}
 object utils {
   implicit val deployErrorsShow: Show[DeployError] = Show.show {
     _.value match {
-      case Empty                                          => ""
-      case GasError(DeployError.OutOfGasError())          => "OutOfGas"
-      case ExecError(DeployError.ExecutionError(message)) => message
+      case Empty                                             => ""
+      case GasError(DeployError.OutOfGasError())             => "OutOfGas"
+      case ExecError(DeployError.ExecutionError(message, _)) => message
     }
   }
 }
@@ -651,7 +651,14 @@ class ValidationImpl[F[_]: MonadThrowable: FunctorRaise[?[_], InvalidBlock]: Log
                   _ <- FunctorRaise[F, InvalidBlock]
                         .raise[Unit](InvalidPostStateHash)
                         .whenA(commitResult.postStateHash != blockPostState)
-                  _ <- bondsCache(block, commitResult.bondedValidators)
+                  // The execution engine knows validators by the address of their account.
+                  _ <- bondsCache(
+                        block,
+                        ProtoUtil.bondsByPublicKey(
+                          commitResult.bondedValidators,
+                          bonds(block).map(_.validatorPublicKey)
+                        )
+                      )
                 } yield ()
             }
       } yield ()
@@ -826,7 +826,12 @@ class ValidationTest
         deploysCheckpoint <- ExecEngineUtil.computeDeploysCheckpoint[Task](
                               ExecEngineUtil.MergeResult.empty,
                               deploys,
-                              System.currentTimeMillis,
+                              ExecutionEngineService.BlockInfo(
+                                System.currentTimeMillis,
+                                blockHeight = 0,
+                                parentBlockHash = ByteString.EMPTY,
+                                proposer = ByteString.EMPTY
+                              ),
                               ProtocolVersion(1)
                             )
         DeploysCheckpoint(
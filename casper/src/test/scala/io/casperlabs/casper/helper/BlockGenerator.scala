@@ -87,7 +87,7 @@ object BlockGenerator {
       result <- computeDeploysCheckpoint[F](
                  merged,
                  deploys,
-                 b.getHeader.timestamp,
+                 ProtoUtil.blockInfo(b),
                  ProtocolVersion(1)
                )
     } yield result
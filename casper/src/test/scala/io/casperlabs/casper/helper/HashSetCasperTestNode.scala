@@ -332,7 +332,7 @@ object HashSetCasperTestNode {
 
       override def exec(
           prestate: ByteString,
-          blocktime: Long,
+          blockInfo: ExecutionEngineService.BlockInfo,
           deploys: Seq[ipc.Deploy],
           protocolVersion: ProtocolVersion
       ): F[Either[Throwable, Seq[DeployResult]]] =
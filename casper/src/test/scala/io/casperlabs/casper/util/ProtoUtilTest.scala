@@ -4,6 +4,9 @@ import com.google.protobuf.ByteString
 import io.casperlabs.casper.Estimator.BlockHash
 import io.casperlabs.casper._
 import io.casperlabs.casper.consensus._, Block.Justification
+import io.casperlabs.crypto.hash.Blake2b256
+import io.casperlabs.crypto.signatures.SignatureAlgorithm
+import io.casperlabs.ipc
 import org.scalacheck.{Arbitrary, Gen}
 import org.scalacheck.Arbitrary.arbitrary
 import org.scalacheck.Gen.listOfN
@@ -55,4 +58,49 @@ class ProtoUtilTest extends FlatSpec with Matchers with GeneratorDrivenPropertyC
       result should contain theSameElementsAs ((justificationsHashes ++ parentsHashes).toSet)
     }
   }
+
+  "deployDataToEEDeploy" should "pass on references to stored contracts" in {
+    val hash = ByteString.copyFrom(Array.fill[Byte](32)(1))
+    val body = Deploy
+      .Body()
+      .withSession(Deploy.Code().withStoredContractName("session").withArgs(hash))
+      .withPayment(Deploy.Code().withStoredContractHash(hash))
+    val deploy = ProtoUtil.deployDataToEEDeploy(Deploy().withBody(body))
+    deploy.getSession.getStoredContractName shouldBe "session"
+    deploy.getSession.args shouldBe hash
+    deploy.getPayment.getStoredContractHash shouldBe hash
+    deploy.motesTransferredInPayment shouldBe 0L
+  }
+
+  "accountAddress" should "tag public keys with the algorithm their length implies" in {
+    val ed25519Key = ByteString.copyFrom(Array.fill[Byte](32)(1))
+    ProtoUtil.eeTaggedPublicKey(ed25519Key).algorithm shouldBe ipc.SignatureAlgorithm.ED25519
+
+    val (_, secp256k1Key) = SignatureAlgorithm.Secp256k1.newKeyPair
+    val uncompressed      = ByteString.copyFrom(secp256k1Key)
+    val compressed        = ProtoUtil.eeTaggedPublicKey(uncompressed)
+    compressed.algorithm shouldBe ipc.SignatureAlgorithm.SECP256K1
+    compressed.key.size shouldBe 33
+
+    ProtoUtil.accountAddress(ed25519Key) shouldBe ByteString.copyFrom(
+      Blake2b256.hash(Array(0.toByte) ++ ed25519Key.toByteArray)
+    )
+    ProtoUtil.accountAddress(uncompressed) shouldBe ByteString.copyFrom(
+      Blake2b256.hash(Array(1.toByte) ++ compressed.key.toByteArray)
+    )
+    ProtoUtil.accountAddress(compressed.key) shouldBe ProtoUtil.accountAddress(uncompressed)
+  }
+
+  "bondsByPublicKey" should "key the bonds of the execution engine by validator public key" in {
+    val validator = ByteString.copyFrom(Array.fill[Byte](32)(1))
+    val unknown   = ByteString.copyFrom(Array.fill[Byte](32)(2))
+    val bonds = Seq(
+      Bond(ProtoUtil.accountAddress(validator), 10),
+      Bond(ProtoUtil.accountAddress(unknown), 20)
+    )
+    ProtoUtil.bondsByPublicKey(bonds, Seq(validator)) shouldBe Seq(
+      Bond(validator, 10),
+      Bond(ProtoUtil.accountAddress(unknown), 20)
+    )
+  }
 }
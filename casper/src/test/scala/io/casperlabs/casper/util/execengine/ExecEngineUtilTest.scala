@@ -121,7 +121,12 @@ class ExecEngineUtilTest extends FlatSpec with Matchers with BlockGenerator with
                         .computeDeploysCheckpoint[Task](
                           ExecEngineUtil.MergeResult.empty,
                           deploy,
-                          blocktime,
+                          ExecutionEngineService.BlockInfo(
+                            blocktime,
+                            blockHeight = 0,
+                            parentBlockHash = ByteString.EMPTY,
+                            proposer = ByteString.EMPTY
+                          ),
                           protocolVersion
                         )
       DeploysCheckpoint(_, _, _, result, _, _, _) = computeResult
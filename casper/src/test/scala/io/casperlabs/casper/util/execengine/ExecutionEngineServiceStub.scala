@@ -52,7 +52,7 @@ object ExecutionEngineServiceStub {
       ) => F[Either[Throwable, GenesisResult]],
       execFunc: (
           ByteString,
-          Long,
+          ExecutionEngineService.BlockInfo,
           Seq[Deploy],
           ProtocolVersion
       ) => F[Either[Throwable, Seq[DeployResult]]],
@@ -71,11 +71,11 @@ object ExecutionEngineServiceStub {
       runGenesisFunc(deploys, protocolVersion)
     override def exec(
         prestate: ByteString,
-        blocktime: Long,
+        blockInfo: ExecutionEngineService.BlockInfo,
         deploys: Seq[Deploy],
         protocolVersion: ProtocolVersion
     ): F[Either[Throwable, Seq[DeployResult]]] =
-      execFunc(prestate, blocktime, deploys, protocolVersion)
+      execFunc(prestate, blockInfo, deploys, protocolVersion)
     override def commit(
         prestate: ByteString,
         effects: Seq[TransformEntry]
@@ -216,10 +216,11 @@ object SignatureAlgorithm {
     import org.bitcoin._
     import org.bouncycastle.jce.provider.BouncyCastleProvider
 
-    private val PrivateKeyLength = 32
-    private val PublicKeyLength  = 65
-    private val provider         = new BouncyCastleProvider()
-    private val curveName        = "secp256k1"
+    private val PrivateKeyLength          = 32
+    private val PublicKeyLength           = 65
+    private val CompressedPublicKeyLength = 33
+    private val provider                  = new BouncyCastleProvider()
+    private val curveName                 = "secp256k1"
 
     override def name: String = curveName
 
@@ -351,6 +352,35 @@ object SignatureAlgorithm {
       */
     def tryToPublic(seckey: PrivateKey): Option[PublicKey] =
       Try(PublicKey(NativeSecp256k1.computePubkey(seckey))).toOption
+
+    /**
+      * Compresses an uncompressed public key into the 33 bytes of its parity prefix and X coordinate,
+      * the form the execution engine expects. Compressed keys are returned as they are.
+      */
+    def compressPublicKey(pub: Array[Byte]): Option[Array[Byte]] = pub.length match {
+      case CompressedPublicKeyLength => Some(pub)
+      case PublicKeyLength if pub(0) == 4 =>
+        val prefix = if ((pub.last & 1) == 0) 2 else 3
+        Some(prefix.toByte +: pub.slice(1, 33))
+      case _ => None
+    }
+
+    /**
+      * Converts a DER encoded signature, as made by `sign`, into the 64 bytes of its R and S
+      * values, the form the execution engine expects.
+      */
+    def toCompactSignature(signature: Array[Byte]): Option[Array[Byte]] =
+      try {
+        import org.bouncycastle.asn1.{ASN1Integer, ASN1Sequence}
+        import org.bouncycastle.util.BigIntegers
+
+        val sequence = ASN1Sequence.getInstance(signature)
+        val r        = ASN1Integer.getInstance(sequence.getObjectAt(0)).getValue
+        val s        = ASN1Integer.getInstance(sequence.getObjectAt(1)).getValue
+        Some(BigIntegers.asUnsignedByteArray(32, r) ++ BigIntegers.asUnsignedByteArray(32, s))
+      } catch {
+        case NonFatal(_) => None
+      }
   }
 
 }
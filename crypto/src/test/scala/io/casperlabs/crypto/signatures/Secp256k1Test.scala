@@ -52,6 +52,26 @@ class Secp256k1Test extends FunSpec with Matchers with BeforeAndAfterEach with A
         .encode(Secp256k1.tryToPublic(sec).get)
         .toUpperCase() shouldBe "04C591A8FF19AC9C4E4E5793673B83123437E975285E7B442F4EE2654DFFCA5E2D2103ED494718C697AC9AEBCFD19612E224DB46661011863ED2FC54E71861E2A6"
     }
+    it("compresses public keys") {
+      val pub = Base16.decode(
+        "04C591A8FF19AC9C4E4E5793673B83123437E975285E7B442F4EE2654DFFCA5E2D2103ED494718C697AC9AEBCFD19612E224DB46661011863ED2FC54E71861E2A6"
+      )
+      val compressed = Secp256k1.compressPublicKey(pub).get
+      Base16
+        .encode(compressed)
+        .toUpperCase() shouldBe "02C591A8FF19AC9C4E4E5793673B83123437E975285E7B442F4EE2654DFFCA5E2D"
+      Secp256k1.compressPublicKey(compressed).get shouldBe compressed
+      Secp256k1.compressPublicKey(pub.take(32)) shouldBe None
+    }
+    it("converts DER encoded signatures into compact ones") {
+      val sig = Base16.decode(
+        "30440220182A108E1448DC8F1FB467D06A0F3BB8EA0533584CB954EF8DA112F1D60E39A202201C66F36DA211C087F3AF88B50EDF4F9BDAA6CF5FD6817E74DCA34DB12390C6E9"
+      )
+      Base16
+        .encode(Secp256k1.toCompactSignature(sig).get)
+        .toUpperCase() shouldBe "182A108E1448DC8F1FB467D06A0F3BB8EA0533584CB954EF8DA112F1D60E39A21C66F36DA211C087F3AF88B50EDF4F9BDAA6CF5FD6817E74DCA34DB12390C6E9"
+      Secp256k1.toCompactSignature(sig.take(32)) shouldBe None
+    }
     it("parses private and public PEM keys") {
       // openssl ecparam -name secp256k1 -genkey -noout -out secp256k1-private.pem
       val privateKey = """
@@ -570,6 +570,11 @@ mod proptests {
             assert!(test_serialization_roundtrip(&acct));
         }
 
+        #[test]
+        fn test_contract_package(package in contract_package_arb()) {
+            assert!(test_serialization_roundtrip(&package));
+        }
+
         #[test]
         fn test_u128_serialization(u in u128_arb()) {
             assert!(test_serialization_roundtrip(&u));
@@ -605,6 +610,11 @@ mod proptests {
             assert!(test_serialization_roundtrip(&pk))
         }
 
+        #[test]
+        fn test_tagged_public_key(pk in tagged_public_key_arb()) {
+            assert!(test_serialization_roundtrip(&pk))
+        }
+
         #[test]
         fn test_result(result in result_arb()) {
             assert!(test_serialization_roundtrip(&result))
@@ -0,0 +1,91 @@
+use crate::bytesrepr::{self, FromBytes, ToBytes, U32_SIZE};
+use crate::key::Key;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A single frame of the stack of calls leading to the currently running code.
+///
+/// The bottom of the stack is the deploy's session or payment code, run in the context of the
+/// deploying account; every `call_contract` pushes the called contract's key and entry point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallStackElement {
+    key: Key,
+    entry_point: String,
+}
+
+impl CallStackElement {
+    pub fn new(key: Key, entry_point: String) -> Self {
+        CallStackElement { key, entry_point }
+    }
+
+    /// Key of the account or contract running at this level of the stack.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Name of the entry point through which the code at this level of the stack was called.
+    pub fn entry_point(&self) -> &str {
+        &self.entry_point
+    }
+}
+
+impl ToBytes for CallStackElement {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = self.key.to_bytes()?;
+        result.append(&mut self.entry_point.to_bytes()?);
+        Ok(result)
+    }
+}
+
+impl FromBytes for CallStackElement {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem): (Key, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (entry_point, rem): (String, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((CallStackElement { key, entry_point }, rem))
+    }
+}
+
+impl FromBytes for Vec<CallStackElement> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (size, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let mut result = Vec::new();
+        result.try_reserve_exact(size as usize)?;
+        for _ in 0..size {
+            let (element, rem): (CallStackElement, &[u8]) = FromBytes::from_bytes(stream)?;
+            result.push(element);
+            stream = rem;
+        }
+        Ok((result, stream))
+    }
+}
+
+impl ToBytes for Vec<CallStackElement> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let size = self.len() as u32;
+        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE);
+        result.extend(size.to_bytes()?);
+        result.extend(
+            self.iter()
+                .map(ToBytes::to_bytes)
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .flatten(),
+        );
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_serialization_roundtrip;
+
+    #[test]
+    fn call_stack_should_serialize_roundtrip() {
+        let call_stack = vec![
+            CallStackElement::new(Key::Account([1u8; 32]), String::from("call")),
+            CallStackElement::new(Key::Hash([2u8; 32]), String::from("get_answer")),
+        ];
+        assert!(test_serialization_roundtrip(&call_stack));
+    }
+}
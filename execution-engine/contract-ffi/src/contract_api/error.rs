@@ -0,0 +1,225 @@
+use crate::bytesrepr::{self, FromBytes, ToBytes, U32_SIZE, U8_SIZE};
+use crate::system_contracts::mint;
+use alloc::vec::Vec;
+
+/// First exit code reserved for errors of the Mint contract.
+pub const MINT_ERROR_OFFSET: u32 = 65_024; // 0xFE00
+/// First exit code reserved for errors of the Proof of Stake contract.
+pub const POS_ERROR_OFFSET: u32 = 65_280; // 0xFF00
+/// First exit code reserved for errors defined by contracts themselves.
+pub const USER_ERROR_OFFSET: u32 = 65_536; // 0x10000
+
+const MISSING_ARGUMENT: u32 = 1;
+const INVALID_ARGUMENT: u32 = 2;
+const DESERIALIZE: u32 = 3;
+const MISSING_KEY: u32 = 4;
+const UNEXPECTED_KEY_VARIANT: u32 = 5;
+const UNEXPECTED_VALUE_VARIANT: u32 = 6;
+const TRANSFER: u32 = 7;
+
+/// Represents the reason why a contract reverted its execution.
+///
+/// Every variant maps to an exit code passed to the host (see `From<ApiError> for u32`), which
+/// are split into the following ranges:
+///
+/// * `[1, 0xFE00)` - standard errors of the contract API,
+/// * `[0xFE00, 0xFF00)` - errors of the Mint contract,
+/// * `[0xFF00, 0x10000)` - errors of the Proof of Stake contract,
+/// * `[0x10000, 0x20000)` - errors defined by contracts themselves.
+///
+/// Exit codes which don't belong to any variant, i.e. the ones used by contracts calling
+/// `contract_api::revert` directly, are represented as `ApiError::Unknown`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// Argument was not passed to the contract.
+    MissingArgument,
+    /// Argument passed to the contract has an unexpected type.
+    InvalidArgument,
+    /// Failed to deserialize a value.
+    Deserialize,
+    /// Key is not known to the current context under a given name.
+    MissingKey,
+    /// Key has an unexpected variant.
+    UnexpectedKeyVariant,
+    /// Value has an unexpected variant.
+    UnexpectedValueVariant,
+    /// Transfer of motes failed.
+    Transfer,
+    /// Error of the Mint contract.
+    Mint(u8),
+    /// Error of the Proof of Stake contract.
+    ProofOfStake(u8),
+    /// Error defined by the contract itself.
+    User(u16),
+    /// Exit code outside of the standard errors and the reserved ranges.
+    Unknown(u32),
+}
+
+impl From<mint::error::Error> for ApiError {
+    fn from(error: mint::error::Error) -> Self {
+        ApiError::Mint(error as u8)
+    }
+}
+
+impl From<ApiError> for u32 {
+    fn from(error: ApiError) -> u32 {
+        match error {
+            ApiError::MissingArgument => MISSING_ARGUMENT,
+            ApiError::InvalidArgument => INVALID_ARGUMENT,
+            ApiError::Deserialize => DESERIALIZE,
+            ApiError::MissingKey => MISSING_KEY,
+            ApiError::UnexpectedKeyVariant => UNEXPECTED_KEY_VARIANT,
+            ApiError::UnexpectedValueVariant => UNEXPECTED_VALUE_VARIANT,
+            ApiError::Transfer => TRANSFER,
+            ApiError::Mint(value) => MINT_ERROR_OFFSET + u32::from(value),
+            ApiError::ProofOfStake(value) => POS_ERROR_OFFSET + u32::from(value),
+            ApiError::User(value) => USER_ERROR_OFFSET + u32::from(value),
+            ApiError::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<u32> for ApiError {
+    fn from(value: u32) -> Self {
+        match value {
+            MISSING_ARGUMENT => ApiError::MissingArgument,
+            INVALID_ARGUMENT => ApiError::InvalidArgument,
+            DESERIALIZE => ApiError::Deserialize,
+            MISSING_KEY => ApiError::MissingKey,
+            UNEXPECTED_KEY_VARIANT => ApiError::UnexpectedKeyVariant,
+            UNEXPECTED_VALUE_VARIANT => ApiError::UnexpectedValueVariant,
+            TRANSFER => ApiError::Transfer,
+            d if d >= MINT_ERROR_OFFSET && d < POS_ERROR_OFFSET => {
+                ApiError::Mint((d - MINT_ERROR_OFFSET) as u8)
+            }
+            d if d >= POS_ERROR_OFFSET && d < USER_ERROR_OFFSET => {
+                ApiError::ProofOfStake((d - POS_ERROR_OFFSET) as u8)
+            }
+            d if d >= USER_ERROR_OFFSET && d - USER_ERROR_OFFSET <= u32::from(u16::max_value()) => {
+                ApiError::User((d - USER_ERROR_OFFSET) as u16)
+            }
+            d => ApiError::Unknown(d),
+        }
+    }
+}
+
+const CALL_ERROR_REVERT_TAG: u8 = 0;
+const CALL_ERROR_TRAP_TAG: u8 = 1;
+
+/// Represents the reason why a contract called with `contract_api::try_call_contract` failed.
+///
+/// All effects of the failed call are discarded by the host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallError {
+    /// Called contract reverted with given error and payload.
+    Revert(ApiError, Vec<u8>),
+    /// Called contract trapped, e.g. it panicked or tried to access a key it doesn't have
+    /// access to, or it couldn't be called at all.
+    Trap,
+}
+
+impl ToBytes for CallError {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        match self {
+            CallError::Revert(error, payload) => {
+                let mut payload_bytes = payload.to_bytes()?;
+                let mut result = Vec::with_capacity(U8_SIZE + U32_SIZE + payload_bytes.len());
+                result.push(CALL_ERROR_REVERT_TAG);
+                result.append(&mut u32::from(*error).to_bytes()?);
+                result.append(&mut payload_bytes);
+                Ok(result)
+            }
+            CallError::Trap => Ok(vec![CALL_ERROR_TRAP_TAG]),
+        }
+    }
+}
+
+impl FromBytes for CallError {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            CALL_ERROR_REVERT_TAG => {
+                let (status, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+                let (payload, rem): (Vec<u8>, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((CallError::Revert(ApiError::from(status), payload), rem))
+            }
+            CALL_ERROR_TRAP_TAG => Ok((CallError::Trap, rem)),
+            _ => Err(bytesrepr::Error::FormattingError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_serialization_roundtrip;
+
+    fn assert_roundtrip(error: ApiError) {
+        let code: u32 = error.into();
+        assert_eq!(ApiError::from(code), error);
+    }
+
+    #[test]
+    fn api_error_should_roundtrip_through_u32() {
+        assert_roundtrip(ApiError::MissingArgument);
+        assert_roundtrip(ApiError::InvalidArgument);
+        assert_roundtrip(ApiError::Deserialize);
+        assert_roundtrip(ApiError::MissingKey);
+        assert_roundtrip(ApiError::UnexpectedKeyVariant);
+        assert_roundtrip(ApiError::UnexpectedValueVariant);
+        assert_roundtrip(ApiError::Transfer);
+        assert_roundtrip(ApiError::Mint(0));
+        assert_roundtrip(ApiError::Mint(u8::max_value()));
+        assert_roundtrip(ApiError::ProofOfStake(0));
+        assert_roundtrip(ApiError::ProofOfStake(u8::max_value()));
+        assert_roundtrip(ApiError::User(0));
+        assert_roundtrip(ApiError::User(u16::max_value()));
+        assert_roundtrip(ApiError::Unknown(0));
+        assert_roundtrip(ApiError::Unknown(100));
+        assert_roundtrip(ApiError::Unknown(u32::max_value()));
+    }
+
+    #[test]
+    fn reserved_ranges_should_not_overlap() {
+        assert_eq!(u32::from(ApiError::Mint(0)), MINT_ERROR_OFFSET);
+        assert_eq!(
+            u32::from(ApiError::Mint(u8::max_value())) + 1,
+            POS_ERROR_OFFSET
+        );
+        assert_eq!(
+            u32::from(ApiError::ProofOfStake(u8::max_value())) + 1,
+            USER_ERROR_OFFSET
+        );
+        assert_eq!(
+            ApiError::from(USER_ERROR_OFFSET + u32::from(u16::max_value()) + 1),
+            ApiError::Unknown(USER_ERROR_OFFSET + u32::from(u16::max_value()) + 1)
+        );
+    }
+
+    #[test]
+    fn call_error_should_serialize_roundtrip() {
+        assert!(test_serialization_roundtrip(&CallError::Revert(
+            ApiError::User(1),
+            vec![1, 2, 3]
+        )));
+        assert!(test_serialization_roundtrip(&CallError::Revert(
+            ApiError::Unknown(100),
+            Vec::new()
+        )));
+        assert!(test_serialization_roundtrip(&CallError::Trap));
+        assert!(test_serialization_roundtrip(&Ok::<Vec<u8>, CallError>(
+            vec![4, 5]
+        )));
+        assert!(test_serialization_roundtrip(&Err::<Vec<u8>, CallError>(
+            CallError::Trap
+        )));
+    }
+
+    #[test]
+    fn mint_error_should_map_to_mint_range() {
+        assert_eq!(
+            ApiError::from(mint::error::Error::InvalidAccessRights),
+            ApiError::Mint(4)
+        );
+    }
+}
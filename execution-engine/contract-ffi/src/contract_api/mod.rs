@@ -1,17 +1,24 @@
 mod alloc_util;
 pub mod argsparser;
+pub mod call_stack;
+pub mod error;
 pub mod pointers;
 
 use self::alloc_util::*;
+use self::call_stack::CallStackElement;
+use self::error::{ApiError, CallError};
 use self::pointers::*;
-use crate::bytesrepr::{deserialize, FromBytes, ToBytes};
+use crate::bytesrepr::{deserialize, FromBytes, ToBytes, U32_SIZE, U64_SIZE};
 use crate::ext_ffi;
 use crate::key::{Key, UREF_SIZE};
-use crate::uref::URef;
+use crate::uref::{URef, UREF_SIZE_SERIALIZED};
 use crate::value::account::{
-    Account, ActionType, AddKeyFailure, BlockTime, PublicKey, PurseId, RemoveKeyFailure,
-    SetThresholdFailure, UpdateKeyFailure, Weight, BLOCKTIME_SER_SIZE, PURSE_ID_SIZE_SERIALIZED,
+    Account, ActionType, AddKeyFailure, ApprovalStatus, BlockTime, ProposalFailure, ProposedAction,
+    PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure, Weight,
+    BLOCKTIME_SER_SIZE, PUBLIC_KEY_SIZE, PURSE_ID_SIZE_SERIALIZED,
 };
+use crate::value::contract::EntryPoint;
+use crate::value::contract_package::ContractPackageFailure;
 use crate::value::{Contract, Value, U512};
 use alloc::collections::BTreeMap;
 use alloc::string::String;
@@ -19,6 +26,13 @@ use alloc::vec::Vec;
 use argsparser::ArgsParser;
 use core::convert::{TryFrom, TryInto};
 
+/// Length of the digest returned by [`blake2b`].
+pub const BLAKE2B_DIGEST_LENGTH: usize = 32;
+/// Length of the signatures checked by [`verify_ed25519`].
+pub const ED25519_SIGNATURE_LENGTH: usize = 64;
+/// Length of the hash returned by [`get_parent_hash`].
+pub const BLOCK_HASH_LENGTH: usize = 32;
+
 /// Read value under the key in the global state
 pub fn read<T>(u_ptr: UPointer<T>) -> T
 where
@@ -198,6 +212,30 @@ pub fn store_function(name: &str, known_urefs: BTreeMap<String, Key>) -> Contrac
     ContractPointer::Hash(tmp)
 }
 
+/// Stores functions exported under the names of `entry_points` as a single contract in the
+/// global state and returns the key where it is stored. The contract can then be called through
+/// any of its entry points (see `call_contract_entry_point`), with the host checking the
+/// arguments against the declared types.
+pub fn store_entry_points(
+    entry_points: BTreeMap<String, EntryPoint>,
+    known_urefs: BTreeMap<String, Key>,
+) -> ContractPointer {
+    let (entry_points_ptr, entry_points_size, _bytes1) = to_ptr(&entry_points);
+    let (urefs_ptr, urefs_size, _bytes2) = to_ptr(&known_urefs);
+    let mut tmp = [0u8; 32];
+    let tmp_ptr = tmp.as_mut_ptr();
+    unsafe {
+        ext_ffi::store_entry_points(
+            entry_points_ptr,
+            entry_points_size,
+            urefs_ptr,
+            urefs_size,
+            tmp_ptr,
+        );
+    }
+    ContractPointer::Hash(tmp)
+}
+
 /// Finds function by the name and stores it at the unforgable name.
 pub fn store_function_at(name: &str, known_urefs: BTreeMap<String, Key>, uref: UPointer<Contract>) {
     let contract = fn_by_name(name, known_urefs);
@@ -275,6 +313,55 @@ pub fn get_blocktime() -> BlockTime {
     deserialize(&bytes).unwrap()
 }
 
+/// Returns the stack of calls leading to the currently running code, starting with the
+/// deploying account and ending with the current contract.
+pub fn get_call_stack() -> Vec<CallStackElement> {
+    let bytes_size = unsafe { ext_ffi::serialize_call_stack() };
+    let dest_ptr = alloc_bytes(bytes_size);
+    let bytes = unsafe {
+        ext_ffi::get_call_stack(dest_ptr);
+        Vec::from_raw_parts(dest_ptr, bytes_size, bytes_size)
+    };
+    deserialize(&bytes).unwrap()
+}
+
+/// Returns the account or contract which called the currently running code, or `None` if it
+/// is run directly by a deploy.
+pub fn get_immediate_caller() -> Option<CallStackElement> {
+    let mut call_stack = get_call_stack();
+    call_stack.pop();
+    call_stack.pop()
+}
+
+/// Returns the height of the block in which the current deploy is executed.
+pub fn get_block_height() -> u64 {
+    let dest_ptr = alloc_bytes(U64_SIZE);
+    let bytes = unsafe {
+        ext_ffi::get_block_height(dest_ptr);
+        Vec::from_raw_parts(dest_ptr, U64_SIZE, U64_SIZE)
+    };
+    deserialize(&bytes).unwrap()
+}
+
+/// Returns the hash of the parent of the block in which the current deploy is executed.
+pub fn get_parent_hash() -> [u8; BLOCK_HASH_LENGTH] {
+    let mut parent_hash = [0u8; BLOCK_HASH_LENGTH];
+    unsafe { ext_ffi::get_parent_hash(parent_hash.as_mut_ptr()) };
+    parent_hash
+}
+
+/// Returns the public key of the validator proposing the block in which the current deploy
+/// is executed.
+pub fn get_proposer() -> PublicKey {
+    let size = PUBLIC_KEY_SIZE + U32_SIZE;
+    let dest_ptr = alloc_bytes(size);
+    let bytes = unsafe {
+        ext_ffi::get_proposer(dest_ptr);
+        Vec::from_raw_parts(dest_ptr, size, size)
+    };
+    deserialize(&bytes).unwrap()
+}
+
 /// Return `t` to the host, terminating the currently running module.
 /// Note this function is only relevant to contracts stored on chain which
 /// return a value to their caller. The return value of a directly deployed
@@ -292,6 +379,9 @@ pub fn ret<T: ToBytes>(t: &T, extra_urefs: &Vec<URef>) -> ! {
 /// the host in order to have them available to the called contract during its
 /// execution. The value returned from the contract call (see `ret` above) is
 /// returned from this function.
+///
+/// The contract is entered through its `"call"` entry point, which is the only
+/// entry point of contracts stored with `store_function`.
 #[allow(clippy::ptr_arg)]
 pub fn call_contract<A: ArgsParser, T: FromBytes>(
     c_ptr: ContractPointer,
@@ -315,6 +405,167 @@ pub fn call_contract<A: ArgsParser, T: FromBytes>(
     deserialize(&res_bytes).unwrap()
 }
 
+/// Calls the given entry point of a contract stored with `store_entry_points`,
+/// like [`call_contract`] does for its `"call"` entry point.
+#[allow(clippy::ptr_arg)]
+pub fn call_contract_entry_point<A: ArgsParser, T: FromBytes>(
+    c_ptr: ContractPointer,
+    entry_point: &str,
+    args: &A,
+    extra_urefs: &Vec<Key>,
+) -> T {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = to_ptr(&contract_key);
+    let (entry_point_ptr, entry_point_size, _bytes2) = str_ref_to_ptr(entry_point);
+    let (args_ptr, args_size, _bytes3) = ArgsParser::parse(args).map(|args| to_ptr(&args)).unwrap();
+    let (urefs_ptr, urefs_size, _bytes4) = to_ptr(extra_urefs);
+    let res_size = unsafe {
+        ext_ffi::call_contract_entry_point(
+            key_ptr,
+            key_size,
+            entry_point_ptr,
+            entry_point_size,
+            args_ptr,
+            args_size,
+            urefs_ptr,
+            urefs_size,
+        )
+    };
+    let res_ptr = alloc_bytes(res_size);
+    let res_bytes = unsafe {
+        ext_ffi::get_call_result(res_ptr);
+        Vec::from_raw_parts(res_ptr, res_size, res_size)
+    };
+    deserialize(&res_bytes).unwrap()
+}
+
+/// Calls the given entry point of a stored contract like [`call_contract_entry_point`], but
+/// doesn't abort the current execution if the called contract reverts or traps.
+///
+/// All effects of a failed call are rolled back, and the reason of the failure is returned as
+/// a [`CallError`].
+#[allow(clippy::ptr_arg)]
+pub fn try_call_contract<A: ArgsParser, T: FromBytes>(
+    c_ptr: ContractPointer,
+    entry_point: &str,
+    args: &A,
+    extra_urefs: &Vec<Key>,
+) -> Result<T, CallError> {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = to_ptr(&contract_key);
+    let (entry_point_ptr, entry_point_size, _bytes2) = str_ref_to_ptr(entry_point);
+    let (args_ptr, args_size, _bytes3) = ArgsParser::parse(args).map(|args| to_ptr(&args)).unwrap();
+    let (urefs_ptr, urefs_size, _bytes4) = to_ptr(extra_urefs);
+    let res_size = unsafe {
+        ext_ffi::try_call_contract(
+            key_ptr,
+            key_size,
+            entry_point_ptr,
+            entry_point_size,
+            args_ptr,
+            args_size,
+            urefs_ptr,
+            urefs_size,
+        )
+    };
+    let res_ptr = alloc_bytes(res_size);
+    let res_bytes = unsafe {
+        ext_ffi::get_call_result(res_ptr);
+        Vec::from_raw_parts(res_ptr, res_size, res_size)
+    };
+    let result: Result<Vec<u8>, CallError> = deserialize(&res_bytes).unwrap();
+    result.map(|bytes| deserialize(&bytes).unwrap())
+}
+
+/// Creates a new, empty contract package. Returns the hash under which the package is
+/// stored together with the access key required to add and disable its versions.
+pub fn create_contract_package() -> ([u8; 32], URef) {
+    let mut package_hash = [0u8; 32];
+    let access_key_ptr = alloc_bytes(UREF_SIZE_SERIALIZED);
+    let access_key_bytes = unsafe {
+        ext_ffi::create_contract_package(package_hash.as_mut_ptr(), access_key_ptr);
+        Vec::from_raw_parts(access_key_ptr, UREF_SIZE_SERIALIZED, UREF_SIZE_SERIALIZED)
+    };
+    let access_key: URef = deserialize(&access_key_bytes).unwrap();
+    (package_hash, access_key)
+}
+
+/// Stores the function exported under `name` as the newest version of the contract
+/// package living under `package_hash`. Returns the number of the new version.
+pub fn add_contract_version(
+    package_hash: [u8; 32],
+    access_key: URef,
+    name: &str,
+    known_urefs: BTreeMap<String, Key>,
+) -> Result<u32, ContractPackageFailure> {
+    let (access_key_ptr, _access_key_size, _bytes1) = to_ptr(&access_key);
+    let (name_ptr, name_size, _bytes2) = str_ref_to_ptr(name);
+    let (urefs_ptr, urefs_size, _bytes3) = to_ptr(&known_urefs);
+    let mut version_bytes = [0u8; 4];
+    let result = unsafe {
+        ext_ffi::add_contract_version(
+            package_hash.as_ptr(),
+            access_key_ptr,
+            name_ptr,
+            name_size,
+            urefs_ptr,
+            urefs_size,
+            version_bytes.as_mut_ptr(),
+        )
+    };
+    match result {
+        d if d == 0 => Ok(deserialize(&version_bytes).unwrap()),
+        d => Err(ContractPackageFailure::try_from(d).expect("invalid result")),
+    }
+}
+
+/// Disables given version of the contract package living under `package_hash`.
+/// Disabled versions can't be called anymore.
+pub fn disable_contract_version(
+    package_hash: [u8; 32],
+    access_key: URef,
+    version: u32,
+) -> Result<(), ContractPackageFailure> {
+    let (access_key_ptr, _access_key_size, _bytes) = to_ptr(&access_key);
+    let result = unsafe {
+        ext_ffi::disable_contract_version(package_hash.as_ptr(), access_key_ptr, version)
+    };
+    match result {
+        d if d == 0 => Ok(()),
+        d => Err(ContractPackageFailure::try_from(d).expect("invalid result")),
+    }
+}
+
+/// Calls the given version of the contract package living under `package_hash`, or
+/// its latest enabled version if `version` is `None`. Otherwise works the same as
+/// [`call_contract`].
+#[allow(clippy::ptr_arg)]
+pub fn call_versioned_contract<A: ArgsParser, T: FromBytes>(
+    package_hash: [u8; 32],
+    version: Option<u32>,
+    args: &A,
+    extra_urefs: &Vec<Key>,
+) -> T {
+    let (args_ptr, args_size, _bytes1) = ArgsParser::parse(args).map(|args| to_ptr(&args)).unwrap();
+    let (urefs_ptr, urefs_size, _bytes2) = to_ptr(extra_urefs);
+    let res_size = unsafe {
+        ext_ffi::call_versioned_contract(
+            package_hash.as_ptr(),
+            version.unwrap_or(0),
+            args_ptr,
+            args_size,
+            urefs_ptr,
+            urefs_size,
+        )
+    };
+    let res_ptr = alloc_bytes(res_size);
+    let res_bytes = unsafe {
+        ext_ffi::get_call_result(res_ptr);
+        Vec::from_raw_parts(res_ptr, res_size, res_size)
+    };
+    deserialize(&res_bytes).unwrap()
+}
+
 /// Stops execution of a contract and reverts execution effects
 /// with a given reason.
 pub fn revert(status: u32) -> ! {
@@ -323,6 +574,22 @@ pub fn revert(status: u32) -> ! {
     }
 }
 
+/// Stops execution of a contract and reverts execution effects
+/// with a given [`ApiError`].
+pub fn revert_with_error<T: Into<ApiError>>(error: T) -> ! {
+    let error: ApiError = error.into();
+    revert(error.into())
+}
+
+/// Same as `revert_with_error`, but additionally passes `payload` to the host,
+/// which reports it in the result of the deploy.
+pub fn revert_with_payload<T: Into<ApiError>>(error: T, payload: &[u8]) -> ! {
+    let error: ApiError = error.into();
+    unsafe {
+        ext_ffi::revert_with_payload(error.into(), payload.as_ptr(), payload.len());
+    }
+}
+
 /// Checks if all the keys contained in the given `Value`
 /// (rather, thing that can be turned into a `Value`) are
 /// valid, in the sense that all of the urefs (and their access rights)
@@ -335,6 +602,43 @@ pub fn is_valid<T: Into<Value>>(t: T) -> bool {
     result != 0
 }
 
+/// Computes the blake2b-256 digest of `data`.
+pub fn blake2b(data: &[u8]) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let mut digest = [0u8; BLAKE2B_DIGEST_LENGTH];
+    unsafe { ext_ffi::blake2b(data.as_ptr(), data.len(), digest.as_mut_ptr()) };
+    digest
+}
+
+/// Checks whether `signature` is a valid ed25519 signature of `message` made with the private
+/// key corresponding to `public_key`.
+///
+/// Signatures which are not [`ED25519_SIGNATURE_LENGTH`] bytes long are never valid.
+pub fn verify_ed25519(public_key: PublicKey, message: &[u8], signature: &[u8]) -> bool {
+    if signature.len() != ED25519_SIGNATURE_LENGTH {
+        return false;
+    }
+    let (public_key_ptr, _public_key_size, _bytes) = to_ptr(&public_key);
+    let result = unsafe {
+        ext_ffi::verify_ed25519(
+            public_key_ptr,
+            message.as_ptr(),
+            message.len(),
+            signature.as_ptr(),
+        )
+    };
+    result != 0
+}
+
+/// Returns `len` pseudo-random bytes.
+///
+/// The bytes are derived from the deploy hash, so re-executing the same deploy always yields the
+/// same bytes. They are therefore not suitable for secrets.
+pub fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    unsafe { ext_ffi::random_bytes(bytes.as_mut_ptr(), len) };
+    bytes
+}
+
 /// Adds a public key with associated weight to an account.
 pub fn add_associated_key(public_key: PublicKey, weight: Weight) -> Result<(), AddKeyFailure> {
     let (public_key_ptr, _public_key_size, _bytes) = to_ptr(&public_key);
@@ -385,6 +689,114 @@ pub fn set_action_threshold(
     }
 }
 
+/// Designates a public key which, together with other recovery keys meeting the recovery
+/// threshold, can manage the associated keys of the account once it has been inactive for longer
+/// than its inactivity period limit.
+pub fn add_recovery_key(public_key: PublicKey, weight: Weight) -> Result<(), AddKeyFailure> {
+    let (public_key_ptr, _public_key_size, _bytes) = to_ptr(&public_key);
+    // Cast of u8 (weight) into i32 is assumed to be always safe
+    let result = unsafe { ext_ffi::add_recovery_key(public_key_ptr, weight.value().into()) };
+    match result {
+        d if d == 0 => Ok(()),
+        d => Err(AddKeyFailure::try_from(d).expect("invalid result")),
+    }
+}
+
+/// Removes a public key from the recovery keys of an account
+pub fn remove_recovery_key(public_key: PublicKey) -> Result<(), RemoveKeyFailure> {
+    let (public_key_ptr, _public_key_size, _bytes) = to_ptr(&public_key);
+    let result = unsafe { ext_ffi::remove_recovery_key(public_key_ptr) };
+    match result {
+        d if d == 0 => Ok(()),
+        d => Err(RemoveKeyFailure::try_from(d).expect("invalid result")),
+    }
+}
+
+/// Sets the total weight of recovery keys required to recover an account
+pub fn set_recovery_threshold(threshold: Weight) -> Result<(), SetThresholdFailure> {
+    let result = unsafe { ext_ffi::set_recovery_threshold(threshold.value().into()) };
+    match result {
+        d if d == 0 => Ok(()),
+        d => Err(SetThresholdFailure::try_from(d).expect("invalid result")),
+    }
+}
+
+/// Sets how long an account has to be inactive before its recovery keys can manage its associated
+/// keys. Only fails with [`SetThresholdFailure::PermissionDeniedError`].
+pub fn set_inactivity_period_limit(limit: BlockTime) -> Result<(), SetThresholdFailure> {
+    let (limit_ptr, _limit_size, _bytes) = to_ptr(&limit);
+    let result = unsafe { ext_ffi::set_inactivity_period_limit(limit_ptr) };
+    match result {
+        d if d == 0 => Ok(()),
+        d => Err(SetThresholdFailure::try_from(d).expect("invalid result")),
+    }
+}
+
+/// Sets a threshold with a user-defined name, which contracts can check against the keys
+/// authorizing a deploy with [`meets_named_threshold`].
+pub fn set_named_threshold(name: &str, threshold: Weight) -> Result<(), SetThresholdFailure> {
+    let (name_ptr, name_size, _bytes) = str_ref_to_ptr(name);
+    let result =
+        unsafe { ext_ffi::set_named_threshold(name_ptr, name_size, threshold.value().into()) };
+    match result {
+        d if d == 0 => Ok(()),
+        d => Err(SetThresholdFailure::try_from(d).expect("invalid result")),
+    }
+}
+
+/// Checks whether the keys authorizing the current deploy meet the named threshold of the
+/// account. Returns `None` if the account has no threshold with that name.
+pub fn meets_named_threshold(name: &str) -> Option<bool> {
+    let (name_ptr, name_size, _bytes) = str_ref_to_ptr(name);
+    let result = unsafe { ext_ffi::meets_named_threshold(name_ptr, name_size) };
+    match result {
+        0 => Some(true),
+        1 => Some(false),
+        2 => None,
+        _ => panic!("invalid result"),
+    }
+}
+
+/// Proposes an action on the account, approved by the keys authorizing the current deploy.
+///
+/// If these keys don't meet the threshold of the action yet, it stays pending until
+/// `expires_at`, and deploys authorized by other keys can approve it with [`approve_action`]
+/// using the returned id. Otherwise the action is executed right away.
+pub fn propose_action(
+    action: ProposedAction,
+    expires_at: BlockTime,
+) -> Result<(ApprovalStatus, [u8; 32]), ProposalFailure> {
+    let (action_ptr, action_size, _action_bytes) = to_ptr(&action);
+    let (expires_at_ptr, _expires_at_size, _expires_at_bytes) = to_ptr(&expires_at);
+    let id_dest_ptr = alloc_bytes(32);
+    let result =
+        unsafe { ext_ffi::propose_action(action_ptr, action_size, expires_at_ptr, id_dest_ptr) };
+    let id_bytes = unsafe { Vec::from_raw_parts(id_dest_ptr, 32, 32) };
+    let status = ApprovalStatus::try_from(result)
+        .map_err(|_| ProposalFailure::try_from(result).expect("invalid result"))?;
+    let mut id = [0u8; 32];
+    id.copy_from_slice(&id_bytes);
+    Ok((status, id))
+}
+
+/// Approves the pending action with the given id using the keys authorizing the current
+/// deploy. The action is executed once the approvals meet its threshold.
+pub fn approve_action(id: [u8; 32]) -> Result<ApprovalStatus, ProposalFailure> {
+    let result = unsafe { ext_ffi::approve_action(id.as_ptr()) };
+    ApprovalStatus::try_from(result)
+        .map_err(|_| ProposalFailure::try_from(result).expect("invalid result"))
+}
+
+/// Cancels the pending action with the given id. The keys authorizing the current deploy have
+/// to meet the key management threshold of the account.
+pub fn cancel_action(id: [u8; 32]) -> Result<(), ProposalFailure> {
+    let result = unsafe { ext_ffi::cancel_action(id.as_ptr()) };
+    match result {
+        d if d == 0 => Ok(()),
+        d => Err(ProposalFailure::try_from(d).expect("invalid result")),
+    }
+}
+
 pub fn create_purse() -> PurseId {
     let purse_id_ptr = alloc_bytes(PURSE_ID_SIZE_SERIALIZED);
     unsafe {
@@ -466,6 +878,7 @@ impl From<TransferResult> for i32 {
 
 /// Transfers `amount` of tokens from default purse of the account to `target` account.
 /// If `target` does not exist it will create it.
+/// In session code, fails unless the deploy's keys meet the account's transfer threshold.
 pub fn transfer_to_account(target: PublicKey, amount: U512) -> TransferResult {
     let (target_ptr, target_size, _bytes) = to_ptr(&target);
     let (amount_ptr, amount_size, _bytes) = to_ptr(&amount);
@@ -476,6 +889,8 @@ pub fn transfer_to_account(target: PublicKey, amount: U512) -> TransferResult {
 
 /// Transfers `amount` of tokens from `source` purse to `target` account.
 /// If `target` does not exist it will create it.
+/// In session code, transfers out of the account's main purse fail unless the deploy's keys meet
+/// the account's transfer threshold.
 pub fn transfer_from_purse_to_account(
     source: PurseId,
     target: PublicKey,
@@ -527,6 +942,8 @@ impl From<PurseTransferResult> for i32 {
 }
 
 /// Transfers `amount` of tokens from `source` purse to `target` purse.
+/// In session code, transfers out of the account's main purse fail unless the deploy's keys meet
+/// the account's transfer threshold.
 pub fn transfer_from_purse_to_purse(
     source: PurseId,
     target: PurseId,
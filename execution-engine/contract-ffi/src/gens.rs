@@ -1,13 +1,16 @@
 use crate::key::*;
 use crate::uref::{AccessRights, URef};
 use crate::value::account::{
-    AccountActivity, ActionThresholds, AssociatedKeys, BlockTime, PublicKey, PurseId, Weight,
-    MAX_KEYS,
+    AccountActivity, ActionThresholds, ActionType, AssociatedKeys, BlockTime, PendingAction,
+    ProposedAction, PublicKey, PurseId, RecoveryKeys, SignatureAlgorithm, TaggedPublicKey, Weight,
+    MAX_KEYS, MAX_PENDING_ACTIONS,
 };
+use crate::value::contract::{ArgType, EntryPoint};
+use crate::value::contract_package::ContractVersion;
 use crate::value::*;
 use alloc::collections::BTreeMap;
 use alloc::string::String;
-use proptest::collection::{btree_map, vec};
+use proptest::collection::{btree_map, btree_set, vec};
 use proptest::prelude::*;
 use proptest::{array, bits, option, result};
 
@@ -56,6 +59,15 @@ pub fn public_key_arb() -> impl Strategy<Value = PublicKey> {
     u8_slice_32().prop_map(Into::into)
 }
 
+pub fn tagged_public_key_arb() -> impl Strategy<Value = TaggedPublicKey> {
+    prop_oneof![
+        u8_slice_32().prop_map(TaggedPublicKey::ed25519),
+        vec(any::<u8>(), 33).prop_map(|bytes| {
+            TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &bytes).expect("should create key")
+        }),
+    ]
+}
+
 pub fn weight_arb() -> impl Strategy<Value = Weight> {
     any::<u8>().prop_map(Weight::new)
 }
@@ -71,46 +83,131 @@ pub fn associated_keys_arb(size: usize) -> impl Strategy<Value = AssociatedKeys>
 }
 
 pub fn action_threshold_arb() -> impl Strategy<Value = ActionThresholds> {
-    Just(Default::default())
+    (weight_arb(), btree_map("\\PC*", weight_arb(), 3)).prop_map(|(transfer, named)| {
+        let mut action_thresholds: ActionThresholds = Default::default();
+        action_thresholds.set_transfer_threshold(transfer);
+        named
+            .into_iter()
+            .for_each(|(name, weight)| action_thresholds.set_named_threshold(name, weight));
+        action_thresholds
+    })
 }
 
 pub fn account_activity_arb() -> impl Strategy<Value = AccountActivity> {
     Just(AccountActivity::new(BlockTime(1), BlockTime(1000)))
 }
 
+pub fn recovery_keys_arb() -> impl Strategy<Value = RecoveryKeys> {
+    associated_keys_arb(2).prop_map(|keys| RecoveryKeys::new(keys, Weight::new(1)))
+}
+
+pub fn proposed_action_arb() -> impl Strategy<Value = ProposedAction> {
+    prop_oneof![
+        (public_key_arb(), weight_arb())
+            .prop_map(|(key, weight)| ProposedAction::AddAssociatedKey(key, weight)),
+        public_key_arb().prop_map(ProposedAction::RemoveAssociatedKey),
+        (public_key_arb(), weight_arb())
+            .prop_map(|(key, weight)| ProposedAction::UpdateAssociatedKey(key, weight)),
+        weight_arb().prop_map(|weight| ProposedAction::SetActionThreshold(
+            ActionType::KeyManagement,
+            weight
+        )),
+        (public_key_arb(), u512_arb())
+            .prop_map(|(target, amount)| ProposedAction::Transfer(target, amount)),
+    ]
+}
+
+pub fn pending_action_arb() -> impl Strategy<Value = PendingAction> {
+    (
+        proposed_action_arb(),
+        btree_set(public_key_arb(), 0..3),
+        any::<u64>(),
+    )
+        .prop_map(|(action, approvals, expires_at)| {
+            PendingAction::new(action, approvals, BlockTime(expires_at))
+        })
+}
+
 prop_compose! {
     pub fn account_arb()(
         pub_key in u8_slice_32(),
-        nonce in any::<u64>(),
         urefs in uref_map_arb(3),
         purse_id in uref_arb(),
         thresholds in action_threshold_arb(),
         account_activity in account_activity_arb(),
+        recovery_keys in recovery_keys_arb(),
         mut associated_keys in associated_keys_arb(MAX_KEYS - 1),
+        pending_actions in btree_map(u8_slice_32(), pending_action_arb(), 0..MAX_PENDING_ACTIONS),
     ) -> Account {
             let purse_id = PurseId::new(purse_id);
             associated_keys.add_key(pub_key.into(), Weight::new(1)).unwrap();
-            Account::new(
+            let mut account = Account::new(
                 pub_key,
-                nonce,
                 urefs,
                 purse_id,
                 associated_keys.clone(),
                 thresholds.clone(),
                 account_activity.clone(),
-            )
+                recovery_keys.clone(),
+            );
+            for (id, pending_action) in pending_actions {
+                account.insert_pending_action(id, pending_action);
+            }
+            account
     }
 }
 
+pub fn arg_type_arb() -> impl Strategy<Value = ArgType> {
+    prop_oneof![
+        Just(ArgType::Any),
+        Just(ArgType::Int32),
+        Just(ArgType::UInt32),
+        Just(ArgType::UInt64),
+        Just(ArgType::UInt128),
+        Just(ArgType::UInt256),
+        Just(ArgType::UInt512),
+        Just(ArgType::ByteArray),
+        Just(ArgType::String),
+        Just(ArgType::Key),
+        Just(ArgType::URef),
+        Just(ArgType::PublicKey),
+        Just(ArgType::PurseId),
+    ]
+}
+
+pub fn entry_points_arb() -> impl Strategy<Value = BTreeMap<String, EntryPoint>> {
+    btree_map(
+        "\\PC*",
+        vec(arg_type_arb(), 0..5).prop_map(EntryPoint::new),
+        0..5,
+    )
+}
+
 pub fn contract_arb() -> impl Strategy<Value = Contract> {
     any::<u64>().prop_flat_map(move |u64arb| {
         uref_map_arb(20).prop_flat_map(move |urefs| {
-            vec(any::<u8>(), 1..1000)
-                .prop_map(move |body| Contract::new(body, urefs.clone(), u64arb))
+            entry_points_arb().prop_flat_map(move |entry_points| {
+                let urefs = urefs.clone();
+                vec(any::<u8>(), 1..1000).prop_map(move |body| {
+                    Contract::with_entry_points(body, urefs.clone(), u64arb, entry_points.clone())
+                })
+            })
         })
     })
 }
 
+pub fn contract_package_arb() -> impl Strategy<Value = ContractPackage> {
+    uref_arb().prop_flat_map(|access_key| {
+        btree_map(
+            any::<u32>(),
+            (contract_arb(), any::<bool>())
+                .prop_map(|(contract, is_enabled)| ContractVersion::new(contract, is_enabled)),
+            0..5,
+        )
+        .prop_map(move |versions| ContractPackage::new(access_key, versions))
+    })
+}
+
 pub fn u128_arb() -> impl Strategy<Value = U128> {
     vec(any::<u8>(), 0..16).prop_map(|b| U128::from_little_endian(b.as_slice()))
 }
@@ -142,7 +239,8 @@ pub fn value_arb() -> impl Strategy<Value = Value> {
             | Value::Contract(_)
             | Value::Key(_)
             | Value::NamedKey(_, _)
-            | Value::Unit => (),
+            | Value::Unit
+            | Value::ContractPackage(_) => (),
         }
     };
     prop_oneof![
@@ -160,6 +258,7 @@ pub fn value_arb() -> impl Strategy<Value = Value> {
         u512_arb().prop_map(Value::UInt512),
         Just(Value::Unit),
         (any::<u64>().prop_map(Value::UInt64)),
+        contract_package_arb().prop_map(Value::ContractPackage),
     ]
 }
 
@@ -24,7 +24,7 @@ pub const UREF_SIZE: usize = KEY_ID_SIZE + UREF_SIZE_SERIALIZED;
 const LOCAL_SIZE: usize = KEY_ID_SIZE + U32_SIZE + LOCAL_KEY_SIZE;
 
 /// Creates a 32-byte BLAKE2b hash digest from a given a piece of data
-fn hash(bytes: &[u8]) -> [u8; LOCAL_KEY_SIZE] {
+pub(crate) fn hash(bytes: &[u8]) -> [u8; LOCAL_KEY_SIZE] {
     let mut ret = [0u8; LOCAL_KEY_SIZE];
     // Safe to unwrap here because our digest length is constant and valid
     let mut hasher = VarBlake2b::new(LOCAL_KEY_SIZE).unwrap();
@@ -64,6 +64,13 @@ mod ext_ffi {
             extra_urefs_size: usize,
             hash_ptr: *const u8,
         );
+        pub fn store_entry_points(
+            entry_points_ptr: *const u8,
+            entry_points_size: usize,
+            extra_urefs_ptr: *const u8,
+            extra_urefs_size: usize,
+            hash_ptr: *const u8,
+        );
         pub fn serialize_known_urefs() -> usize;
         // Can only be called after `serialize_known_urefs`.
         pub fn list_known_urefs(dest_ptr: *mut u8);
@@ -85,17 +92,60 @@ mod ext_ffi {
             extra_urefs_ptr: *const u8,
             extra_urefs_size: usize,
         ) -> usize;
+        pub fn call_contract_entry_point(
+            key_ptr: *const u8,
+            key_size: usize,
+            entry_point_ptr: *const u8,
+            entry_point_size: usize,
+            args_ptr: *const u8,
+            args_size: usize,
+            extra_urefs_ptr: *const u8,
+            extra_urefs_size: usize,
+        ) -> usize;
+        pub fn try_call_contract(
+            key_ptr: *const u8,
+            key_size: usize,
+            entry_point_ptr: *const u8,
+            entry_point_size: usize,
+            args_ptr: *const u8,
+            args_size: usize,
+            extra_urefs_ptr: *const u8,
+            extra_urefs_size: usize,
+        ) -> usize;
         pub fn get_call_result(res_ptr: *mut u8); //can only be called after `call_contract`
         pub fn get_uref(name_ptr: *const u8, name_size: usize) -> usize;
         pub fn has_uref_name(name_ptr: *const u8, name_size: usize) -> i32;
         pub fn add_uref(name_ptr: *const u8, name_size: usize, key_ptr: *const u8, key_size: usize);
         pub fn protocol_version() -> u64;
         pub fn revert(status: u32) -> !;
+        pub fn revert_with_payload(status: u32, payload_ptr: *const u8, payload_size: usize) -> !;
         pub fn is_valid(value_ptr: *const u8, value_size: usize) -> i32;
+        pub fn blake2b(in_ptr: *const u8, in_size: usize, out_ptr: *mut u8);
+        pub fn verify_ed25519(
+            public_key_ptr: *const u8,
+            message_ptr: *const u8,
+            message_size: usize,
+            signature_ptr: *const u8,
+        ) -> i32;
+        pub fn random_bytes(dest_ptr: *mut u8, len: usize);
         pub fn add_associated_key(public_key_ptr: *const u8, weight: i32) -> i32;
         pub fn remove_associated_key(public_key_ptr: *const u8) -> i32;
         pub fn update_associated_key(public_key_ptr: *const u8, weight: i32) -> i32;
         pub fn set_action_threshold(permission_level: u32, threshold: i32) -> i32;
+        pub fn add_recovery_key(public_key_ptr: *const u8, weight: i32) -> i32;
+        pub fn remove_recovery_key(public_key_ptr: *const u8) -> i32;
+        pub fn set_recovery_threshold(threshold: i32) -> i32;
+        pub fn set_inactivity_period_limit(limit_ptr: *const u8) -> i32;
+        pub fn set_named_threshold(name_ptr: *const u8, name_size: usize, threshold: i32) -> i32;
+        pub fn meets_named_threshold(name_ptr: *const u8, name_size: usize) -> i32;
+        pub fn propose_action(
+            action_ptr: *const u8,
+            action_size: usize,
+            expires_at_ptr: *const u8,
+            id_dest_ptr: *mut u8,
+        ) -> i32;
+        pub fn approve_action(id_ptr: *const u8) -> i32;
+        pub fn cancel_action(id_ptr: *const u8) -> i32;
         pub fn remove_uref(name_ptr: *const u8, name_size: usize);
         pub fn get_caller(dest_ptr: *const u8);
         pub fn create_purse(purse_id_ptr: *const u8, purse_id_size: usize) -> i32;
@@ -106,6 +156,11 @@ mod ext_ffi {
             amount_size: usize,
         ) -> i32;
         pub fn get_blocktime(dest_ptr: *const u8);
+        pub fn serialize_call_stack() -> usize;
+        pub fn get_call_stack(dest_ptr: *mut u8);
+        pub fn get_block_height(dest_ptr: *const u8);
+        pub fn get_parent_hash(dest_ptr: *const u8);
+        pub fn get_proposer(dest_ptr: *const u8);
         pub fn transfer_from_purse_to_account(
             source_ptr: *const u8,
             source_size: usize,
@@ -123,6 +178,31 @@ mod ext_ffi {
             amount_size: usize,
         ) -> i32;
         pub fn get_balance(purse_id_ptr: *const u8, purse_id_size: usize) -> i32;
+        pub fn create_contract_package(hash_ptr: *mut u8, access_key_ptr: *mut u8);
+        pub fn add_contract_version(
+            package_hash_ptr: *const u8,
+            access_key_ptr: *const u8,
+            name_ptr: *const u8,
+            name_size: usize,
+            extra_urefs_ptr: *const u8,
+            extra_urefs_size: usize,
+            version_ptr: *mut u8,
+        ) -> i32;
+        pub fn disable_contract_version(
+            package_hash_ptr: *const u8,
+            access_key_ptr: *const u8,
+            version: u32,
+        ) -> i32;
+        pub fn call_versioned_contract(
+            package_hash_ptr: *const u8,
+            // 0 calls the latest enabled version
+            version: u32,
+            args_ptr: *const u8,
+            args_size: usize,
+            // extra urefs known by the caller to make available to the callee
+            extra_urefs_ptr: *const u8,
+            extra_urefs_size: usize,
+        ) -> usize;
     }
 }
 
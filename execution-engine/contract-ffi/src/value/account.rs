@@ -1,21 +1,22 @@
-use crate::bytesrepr::{Error, FromBytes, ToBytes, U32_SIZE, U64_SIZE, U8_SIZE};
-use crate::key::{addr_to_hex, Key, UREF_SIZE};
+use crate::bytesrepr::{Error, FromBytes, ToBytes, U32_SIZE, U512_SIZE, U64_SIZE, U8_SIZE};
+use crate::key::{self, addr_to_hex, Key, UREF_SIZE};
 use crate::uref::{AccessRights, URef, UREF_SIZE_SERIALIZED};
+use crate::value::U512;
 use alloc::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::cmp;
 use core::convert::TryFrom;
 use core::fmt::{Debug, Display, Formatter};
 use failure::Fail;
 
-const DEFAULT_NONCE: u64 = 0;
-const DEFAULT_CURRENT_BLOCK_TIME: BlockTime = BlockTime(0);
-const DEFAULT_INACTIVITY_PERIOD_TIME: BlockTime = BlockTime(100);
+pub const DEFAULT_CURRENT_BLOCK_TIME: BlockTime = BlockTime(0);
+pub const DEFAULT_INACTIVITY_PERIOD_TIME: BlockTime = BlockTime(100);
 
 pub const PURSE_ID_SIZE_SERIALIZED: usize = UREF_SIZE_SERIALIZED;
 
 #[derive(Debug)]
-pub struct TryFromIntError(());
+pub struct TryFromIntError(pub(crate) ());
 
 #[derive(Debug)]
 pub struct TryFromSliceForPublicKeyError(());
@@ -46,11 +47,14 @@ impl FromBytes for PurseId {
 }
 
 #[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ActionType {
     /// Required by deploy execution.
     Deployment = 0,
     /// Required when adding/removing associated keys, changing threshold levels.
     KeyManagement = 1,
+    /// Required when transferring tokens out of the account's main purse in session code.
+    Transfer = 2,
 }
 
 /// convert from u32 representation of `[ActionType]`
@@ -64,16 +68,22 @@ impl TryFrom<u32> for ActionType {
         match value {
             d if d == ActionType::Deployment as u32 => Ok(ActionType::Deployment),
             d if d == ActionType::KeyManagement as u32 => Ok(ActionType::KeyManagement),
+            d if d == ActionType::Transfer as u32 => Ok(ActionType::Transfer),
             _ => Err(TryFromIntError(())),
         }
     }
 }
 
 /// Thresholds that has to be met when executing an action of certain type.
+///
+/// Besides the thresholds of [ActionType] variants, an account can define named thresholds which
+/// contracts check against the authorization keys of a deploy.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ActionThresholds {
     deployment: Weight,
     key_management: Weight,
+    transfer: Weight,
+    named: BTreeMap<String, Weight>,
 }
 
 /// Represents an error that occurs during the change of a thresholds on an account.
@@ -141,6 +151,7 @@ impl ActionThresholds {
         Ok(ActionThresholds {
             deployment,
             key_management,
+            ..Default::default()
         })
     }
     /// Sets new threshold for [ActionType::Deployment].
@@ -172,6 +183,19 @@ impl ActionThresholds {
         }
     }
 
+    /// Sets new threshold for [ActionType::Transfer].
+    ///
+    /// Transfers happen within deploys, so a transfer threshold lower than the deployment
+    /// threshold is allowed but has no effect.
+    pub fn set_transfer_threshold(&mut self, new_threshold: Weight) {
+        self.transfer = new_threshold;
+    }
+
+    /// Sets new threshold with a user-defined name, adding it if it doesn't exist yet.
+    pub fn set_named_threshold(&mut self, name: String, new_threshold: Weight) {
+        self.named.insert(name, new_threshold);
+    }
+
     pub fn deployment(&self) -> &Weight {
         &self.deployment
     }
@@ -180,6 +204,36 @@ impl ActionThresholds {
         &self.key_management
     }
 
+    pub fn transfer(&self) -> &Weight {
+        &self.transfer
+    }
+
+    /// Returns the threshold defined for the given [ActionType] variant.
+    pub fn threshold(&self, action_type: ActionType) -> &Weight {
+        match action_type {
+            ActionType::Deployment => self.deployment(),
+            ActionType::KeyManagement => self.key_management(),
+            ActionType::Transfer => self.transfer(),
+        }
+    }
+
+    pub fn named(&self, name: &str) -> Option<&Weight> {
+        self.named.get(name)
+    }
+
+    pub fn named_thresholds(&self) -> impl Iterator<Item = (&String, &Weight)> {
+        self.named.iter()
+    }
+
+    /// Returns the highest of all thresholds, which the total weight of associated keys has to
+    /// meet.
+    pub fn highest(&self) -> Weight {
+        self.named.values().fold(
+            self.deployment.max(self.key_management).max(self.transfer),
+            |highest, weight| highest.max(*weight),
+        )
+    }
+
     /// Unified function that takes an action type, and changes appropriate
     /// threshold defined by the [ActionType] variants.
     pub fn set_threshold(
@@ -190,6 +244,10 @@ impl ActionThresholds {
         match action_type {
             ActionType::Deployment => self.set_deployment_threshold(new_threshold),
             ActionType::KeyManagement => self.set_key_management_threshold(new_threshold),
+            ActionType::Transfer => {
+                self.set_transfer_threshold(new_threshold);
+                Ok(())
+            }
         }
     }
 }
@@ -199,6 +257,8 @@ impl Default for ActionThresholds {
         ActionThresholds {
             deployment: Weight::new(1),
             key_management: Weight::new(1),
+            transfer: Weight::new(1),
+            named: BTreeMap::new(),
         }
     }
 }
@@ -255,6 +315,14 @@ impl AccountActivity {
     pub fn inactivity_period_limit(&self) -> BlockTime {
         self.inactivity_period_limit
     }
+
+    /// Checks whether neither a deploy nor a key management action happened within the inactivity
+    /// period limit before `current_block_time`.
+    pub fn is_inactive(&self, current_block_time: BlockTime) -> bool {
+        let last_used =
+            core::cmp::max(self.key_management_last_used.0, self.deployment_last_used.0);
+        current_block_time.0.saturating_sub(last_used) > self.inactivity_period_limit.0
+    }
 }
 
 pub const KEY_SIZE: usize = 32;
@@ -278,6 +346,8 @@ impl Weight {
 
 pub const WEIGHT_SIZE: usize = U8_SIZE;
 
+/// Address of an account, or of a key associated with accounts, derived from a
+/// [`TaggedPublicKey`].
 #[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct PublicKey([u8; KEY_SIZE]);
 
@@ -293,11 +363,10 @@ impl Debug for PublicKey {
     }
 }
 
-// TODO: This needs to be updated, `PUBLIC_KEY_SIZE` is not 32 bytes as KEY_SIZE * U8_SIZE.
-// I am not changing that as I don't want to deal with ripple effect.
-
-// Public key is encoded as its underlying [u8; 32] array, which in turn
-// is serialized as u8 + [u8; 32], u8 represents the length and then 32 element array.
+// A `PublicKey` is the 32-byte account address returned by `TaggedPublicKey::to_address()`,
+// not the key itself; the tagged key is what signatures are checked against. The address is
+// serialized as its [u8; 32] array, i.e. a u32 length followed by the 32 bytes, so this size
+// leaves out the length prefix and is only used as a capacity hint.
 pub const PUBLIC_KEY_SIZE: usize = KEY_SIZE * U8_SIZE;
 
 impl PublicKey {
@@ -341,6 +410,118 @@ impl FromBytes for PublicKey {
     }
 }
 
+/// Length of an ed25519 public key.
+pub const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
+/// Length of a compressed secp256k1 public key.
+pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = 33;
+
+/// Signature algorithm of a [`TaggedPublicKey`], whose value is the tag of the key.
+#[repr(u8)]
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum SignatureAlgorithm {
+    Ed25519 = 0,
+    Secp256k1 = 1,
+}
+
+impl SignatureAlgorithm {
+    pub fn public_key_length(self) -> usize {
+        match self {
+            SignatureAlgorithm::Ed25519 => ED25519_PUBLIC_KEY_LENGTH,
+            SignatureAlgorithm::Secp256k1 => SECP256K1_PUBLIC_KEY_LENGTH,
+        }
+    }
+}
+
+impl TryFrom<u8> for SignatureAlgorithm {
+    type Error = TryFromIntError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            d if d == SignatureAlgorithm::Ed25519 as u8 => Ok(SignatureAlgorithm::Ed25519),
+            d if d == SignatureAlgorithm::Secp256k1 as u8 => Ok(SignatureAlgorithm::Secp256k1),
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
+/// Public key of one of the supported signature algorithms, tagged with its algorithm.
+///
+/// Accounts and their associated keys are identified by the [`PublicKey`] returned by
+/// [`TaggedPublicKey::to_address`], so keys of different algorithms can be mixed and can't collide.
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct TaggedPublicKey {
+    algorithm: SignatureAlgorithm,
+    bytes: Vec<u8>,
+}
+
+impl TaggedPublicKey {
+    /// Fails if `bytes` don't have the key length of `algorithm`.
+    pub fn new(
+        algorithm: SignatureAlgorithm,
+        bytes: &[u8],
+    ) -> Result<TaggedPublicKey, TryFromSliceForPublicKeyError> {
+        if bytes.len() != algorithm.public_key_length() {
+            return Err(TryFromSliceForPublicKeyError(()));
+        }
+        Ok(TaggedPublicKey {
+            algorithm,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    pub fn ed25519(key: [u8; ED25519_PUBLIC_KEY_LENGTH]) -> TaggedPublicKey {
+        TaggedPublicKey {
+            algorithm: SignatureAlgorithm::Ed25519,
+            bytes: key.to_vec(),
+        }
+    }
+
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    /// The key without its tag.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Address of the account of the key, which is also the key associated with accounts: the
+    /// blake2b-256 hash of the tag followed by the key.
+    pub fn to_address(&self) -> PublicKey {
+        let mut tagged_bytes = Vec::with_capacity(1 + self.bytes.len());
+        tagged_bytes.push(self.algorithm as u8);
+        tagged_bytes.extend_from_slice(&self.bytes);
+        PublicKey::new(key::hash(&tagged_bytes))
+    }
+}
+
+// A tagged public key is encoded as its tag followed by the key, whose length is given by the tag.
+impl ToBytes for TaggedPublicKey {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = Vec::with_capacity(1 + self.bytes.len());
+        result.push(self.algorithm as u8);
+        result.extend_from_slice(&self.bytes);
+        Ok(result)
+    }
+}
+
+impl FromBytes for TaggedPublicKey {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let algorithm = SignatureAlgorithm::try_from(tag).map_err(|_| Error::FormattingError)?;
+        let length = algorithm.public_key_length();
+        if rem.len() < length {
+            return Err(Error::EarlyEndOfStream);
+        }
+        let (key_bytes, rem) = rem.split_at(length);
+        let tagged_public_key = TaggedPublicKey {
+            algorithm,
+            bytes: key_bytes.to_vec(),
+        };
+        Ok((tagged_public_key, rem))
+    }
+}
+
 /// Represents an error that happens when trying to add a new associated key
 /// on an account.
 ///
@@ -461,6 +642,10 @@ impl TryFrom<i32> for UpdateKeyFailure {
     }
 }
 
+/// Keys associated with an account, with their weights.
+///
+/// The keys are addresses of [`TaggedPublicKey`]s, so an account can have keys of different
+/// signature algorithms.
 #[derive(Default, PartialOrd, Ord, PartialEq, Eq, Clone, Debug)]
 pub struct AssociatedKeys(BTreeMap<PublicKey, Weight>);
 
@@ -548,35 +733,253 @@ impl AssociatedKeys {
     }
 }
 
+/// Keys which can manage the associated keys of an account once it was inactive for longer than
+/// its inactivity period limit, and the weight they need to do so.
+///
+/// There are no recovery keys by default, so an account can't be recovered unless its owner
+/// designated some.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct RecoveryKeys {
+    keys: AssociatedKeys,
+    threshold: Weight,
+}
+
+impl RecoveryKeys {
+    pub fn new(keys: AssociatedKeys, threshold: Weight) -> RecoveryKeys {
+        RecoveryKeys { keys, threshold }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = (&PublicKey, &Weight)> {
+        self.keys.iter()
+    }
+
+    pub fn threshold(&self) -> &Weight {
+        &self.threshold
+    }
+
+    pub fn add_key(&mut self, key: PublicKey, weight: Weight) -> Result<(), AddKeyFailure> {
+        self.keys.add_key(key, weight)
+    }
+
+    /// Removes a recovery key.
+    ///
+    /// The remaining keys have to meet the threshold, unless no key remains, which disables
+    /// recovery of the account.
+    pub fn remove_key(&mut self, key: &PublicKey) -> Result<(), RemoveKeyFailure> {
+        if let Some(weight) = self.keys.get(key) {
+            let total_weight = self.keys.total_keys_weight();
+            let new_weight = total_weight.value().saturating_sub(weight.value());
+            if self.keys.0.len() > 1 && new_weight < self.threshold.value() {
+                return Err(RemoveKeyFailure::ThresholdViolation);
+            }
+        }
+        self.keys.remove_key(key)
+    }
+
+    /// Sets the weight recovery keys need, which can't exceed their total weight.
+    pub fn set_threshold(&mut self, threshold: Weight) -> Result<(), SetThresholdFailure> {
+        if threshold > self.keys.total_keys_weight() {
+            return Err(SetThresholdFailure::InsufficientTotalWeight);
+        }
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    /// Checks whether all authorization keys are recovery keys and the sum of their weights is
+    /// greater or equal to the recovery threshold.
+    pub fn can_authorize(&self, authorization_keys: &BTreeSet<PublicKey>) -> bool {
+        !authorization_keys.is_empty()
+            && authorization_keys
+                .iter()
+                .all(|key| self.keys.contains_key(key))
+            && self.keys.calculate_keys_weight(authorization_keys) >= self.threshold
+    }
+}
+
+impl Default for RecoveryKeys {
+    fn default() -> Self {
+        RecoveryKeys {
+            keys: AssociatedKeys::empty(),
+            threshold: Weight::new(1),
+        }
+    }
+}
+
+/// Maximum number of actions an account can have pending approval.
+pub const MAX_PENDING_ACTIONS: usize = 10;
+
+/// Maximum time in milliseconds an action can stay pending after the block proposing it. Later
+/// expiries are capped to it.
+pub const MAX_PENDING_ACTION_TTL: u64 = 7 * 24 * 60 * 60 * 1000;
+
+/// Represents an error that happens when proposing, approving or cancelling an action pending
+/// approval.
+///
+/// It is represented by `i32` to be easily able to transform this value in an out
+/// through FFI boundaries as a number. The numbering starts at 2, as 0 and 1 represent the
+/// variants of [ApprovalStatus].
+///
+/// The explicit numbering of the variants is done on purpose and whenever you plan to add
+/// new variant, you should always extend it, and add a variant that does not exist already.
+/// When adding new variants you should also remember to change
+/// `From<i32> for ProposalFailure`.
+///
+/// This way we can ensure safety and backwards compatibility. Any changes should be carefully
+/// reviewed and tested.
+#[derive(Fail, Debug, Eq, PartialEq)]
+#[repr(i32)]
+pub enum ProposalFailure {
+    #[fail(display = "Insufficient permissions to propose, approve or cancel an action")]
+    PermissionDenied = 2,
+    #[fail(display = "Unable to find a pending action with the given id")]
+    NotFound = 3,
+    #[fail(display = "Unable to approve an action which has expired")]
+    Expired = 4,
+    #[fail(display = "Unable to propose an action which does not expire after the current block")]
+    InvalidExpiry = 5,
+    #[fail(display = "Unable to propose an action because maximum amount of actions is pending")]
+    MaxPendingActionsLimit = 6,
+    #[fail(display = "The approved action failed")]
+    ActionFailed = 7,
+}
+
+/// convert from i32 representation of `[ProposalFailure]`
+impl TryFrom<i32> for ProposalFailure {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == ProposalFailure::PermissionDenied as i32 => {
+                Ok(ProposalFailure::PermissionDenied)
+            }
+            d if d == ProposalFailure::NotFound as i32 => Ok(ProposalFailure::NotFound),
+            d if d == ProposalFailure::Expired as i32 => Ok(ProposalFailure::Expired),
+            d if d == ProposalFailure::InvalidExpiry as i32 => Ok(ProposalFailure::InvalidExpiry),
+            d if d == ProposalFailure::MaxPendingActionsLimit as i32 => {
+                Ok(ProposalFailure::MaxPendingActionsLimit)
+            }
+            d if d == ProposalFailure::ActionFailed as i32 => Ok(ProposalFailure::ActionFailed),
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
+/// Outcome of successfully proposing or approving an action.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(i32)]
+pub enum ApprovalStatus {
+    /// The action needs further approvals.
+    Pending = 0,
+    /// The approvals met the threshold of the action, which was executed.
+    Executed = 1,
+}
+
+/// convert from i32 representation of `[ApprovalStatus]`
+impl TryFrom<i32> for ApprovalStatus {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == ApprovalStatus::Pending as i32 => Ok(ApprovalStatus::Pending),
+            d if d == ApprovalStatus::Executed as i32 => Ok(ApprovalStatus::Executed),
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
+/// An action which is executed once the weight of the associated keys approving it meets its
+/// threshold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProposedAction {
+    AddAssociatedKey(PublicKey, Weight),
+    RemoveAssociatedKey(PublicKey),
+    UpdateAssociatedKey(PublicKey, Weight),
+    SetActionThreshold(ActionType, Weight),
+    /// Transfers tokens out of the main purse of the account to the given account.
+    Transfer(PublicKey, U512),
+}
+
+impl ProposedAction {
+    /// The type of action whose threshold the approvals have to meet.
+    pub fn action_type(&self) -> ActionType {
+        match self {
+            ProposedAction::Transfer(_, _) => ActionType::Transfer,
+            _ => ActionType::KeyManagement,
+        }
+    }
+}
+
+/// An action proposed by one deploy, collecting approvals from the keys authorizing later
+/// deploys until it expires.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingAction {
+    action: ProposedAction,
+    approvals: BTreeSet<PublicKey>,
+    expires_at: BlockTime,
+}
+
+impl PendingAction {
+    pub fn new(
+        action: ProposedAction,
+        approvals: BTreeSet<PublicKey>,
+        expires_at: BlockTime,
+    ) -> PendingAction {
+        PendingAction {
+            action,
+            approvals,
+            expires_at,
+        }
+    }
+
+    pub fn action(&self) -> &ProposedAction {
+        &self.action
+    }
+
+    pub fn approvals(&self) -> impl Iterator<Item = &PublicKey> {
+        self.approvals.iter()
+    }
+
+    pub fn expires_at(&self) -> BlockTime {
+        self.expires_at
+    }
+
+    /// An action can be approved up to and including the block time it expires at.
+    pub fn is_expired(&self, current_block_time: BlockTime) -> bool {
+        current_block_time > self.expires_at
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Account {
     public_key: [u8; 32],
-    nonce: u64,
     known_urefs: BTreeMap<String, Key>,
     purse_id: PurseId,
     associated_keys: AssociatedKeys,
     action_thresholds: ActionThresholds,
     account_activity: AccountActivity,
+    recovery_keys: RecoveryKeys,
+    pending_actions: BTreeMap<[u8; 32], PendingAction>,
 }
 
 impl Account {
     pub fn new(
         public_key: [u8; 32],
-        nonce: u64,
         known_urefs: BTreeMap<String, Key>,
         purse_id: PurseId,
         associated_keys: AssociatedKeys,
         action_thresholds: ActionThresholds,
         account_activity: AccountActivity,
+        recovery_keys: RecoveryKeys,
     ) -> Self {
         Account {
             public_key,
-            nonce,
             known_urefs,
             purse_id,
             associated_keys,
             action_thresholds,
             account_activity,
+            recovery_keys,
+            pending_actions: BTreeMap::new(),
         }
     }
 
@@ -585,19 +988,18 @@ impl Account {
         known_urefs: BTreeMap<String, Key>,
         purse_id: PurseId,
     ) -> Self {
-        let nonce = DEFAULT_NONCE;
         let associated_keys = AssociatedKeys::new(PublicKey::new(account_addr), Weight::new(1));
         let action_thresholds: ActionThresholds = Default::default();
         let account_activity =
             AccountActivity::new(DEFAULT_CURRENT_BLOCK_TIME, DEFAULT_INACTIVITY_PERIOD_TIME);
         Account::new(
             account_addr,
-            nonce,
             known_urefs,
             purse_id,
             associated_keys,
             action_thresholds,
             account_activity,
+            Default::default(),
         )
     }
 
@@ -640,14 +1042,28 @@ impl Account {
         &self.account_activity
     }
 
-    pub fn nonce(&self) -> u64 {
-        self.nonce
+    pub fn get_account_activity_mut(&mut self) -> &mut AccountActivity {
+        &mut self.account_activity
+    }
+
+    pub fn recovery_keys(&self) -> &RecoveryKeys {
+        &self.recovery_keys
+    }
+
+    pub fn add_recovery_key(
+        &mut self,
+        public_key: PublicKey,
+        weight: Weight,
+    ) -> Result<(), AddKeyFailure> {
+        self.recovery_keys.add_key(public_key, weight)
+    }
+
+    pub fn remove_recovery_key(&mut self, public_key: PublicKey) -> Result<(), RemoveKeyFailure> {
+        self.recovery_keys.remove_key(&public_key)
     }
 
-    /// Consumes instance of account and returns new one
-    /// with old contents but with nonce increased by 1.
-    pub fn increment_nonce(&mut self) {
-        self.nonce += 1;
+    pub fn set_recovery_threshold(&mut self, threshold: Weight) -> Result<(), SetThresholdFailure> {
+        self.recovery_keys.set_threshold(threshold)
     }
 
     pub fn add_associated_key(
@@ -668,8 +1084,7 @@ impl Account {
 
         // Returns true if the new weight would be greater or equal to all of
         // the thresholds.
-        new_weight >= self.action_thresholds().deployment().value()
-            && new_weight >= self.action_thresholds().key_management().value()
+        new_weight >= self.action_thresholds().highest().value()
     }
 
     pub fn remove_associated_key(&mut self, public_key: PublicKey) -> Result<(), RemoveKeyFailure> {
@@ -715,6 +1130,18 @@ impl Account {
         self.action_thresholds.set_threshold(action_type, weight)
     }
 
+    /// Sets a threshold with a user-defined name, which can't exceed the total weight of all
+    /// associated keys.
+    pub fn set_named_threshold(
+        &mut self,
+        name: String,
+        weight: Weight,
+    ) -> Result<(), SetThresholdFailure> {
+        self.can_set_threshold(weight)?;
+        self.action_thresholds.set_named_threshold(name, weight);
+        Ok(())
+    }
+
     /// Verifies if user can set action threshold
     pub fn can_set_threshold(&self, new_threshold: Weight) -> Result<(), SetThresholdFailure> {
         let total_weight = self.associated_keys.total_keys_weight();
@@ -750,6 +1177,147 @@ impl Account {
 
         total_weight >= *self.action_thresholds().key_management()
     }
+
+    /// Checks whether the sum of the weights of all authorization keys is greater
+    /// or equal to transfer threshold.
+    pub fn can_transfer_with(&self, authorization_keys: &BTreeSet<PublicKey>) -> bool {
+        let total_weight = self
+            .associated_keys
+            .calculate_keys_weight(authorization_keys);
+
+        total_weight >= *self.action_thresholds().transfer()
+    }
+
+    /// Checks whether the sum of the weights of all authorization keys is greater or equal to the
+    /// named threshold. Returns `None` if the account has no threshold with that name.
+    pub fn meets_named_threshold(
+        &self,
+        name: &str,
+        authorization_keys: &BTreeSet<PublicKey>,
+    ) -> Option<bool> {
+        let threshold = self.action_thresholds().named(name)?;
+        let total_weight = self
+            .associated_keys
+            .calculate_keys_weight(authorization_keys);
+
+        Some(total_weight >= *threshold)
+    }
+
+    pub fn pending_actions(&self) -> impl Iterator<Item = (&[u8; 32], &PendingAction)> {
+        self.pending_actions.iter()
+    }
+
+    pub fn get_pending_action(&self, id: &[u8; 32]) -> Option<&PendingAction> {
+        self.pending_actions.get(id)
+    }
+
+    /// Inserts a pending action as is, e.g. when restoring a serialized account. Use
+    /// [Account::propose_action] to propose new actions.
+    pub fn insert_pending_action(&mut self, id: [u8; 32], pending_action: PendingAction) {
+        self.pending_actions.insert(id, pending_action);
+    }
+
+    /// Proposes an action approved by `approvals`, which expires after `expires_at`, capped to
+    /// [MAX_PENDING_ACTION_TTL] after the current block.
+    ///
+    /// Returns the action right away if the approvals already meet its threshold, otherwise the
+    /// action is pending under `id` until further approvals meet it. Expired actions are dropped
+    /// to make room for the new one.
+    pub fn propose_action(
+        &mut self,
+        id: [u8; 32],
+        action: ProposedAction,
+        approvals: BTreeSet<PublicKey>,
+        expires_at: BlockTime,
+        current_block_time: BlockTime,
+    ) -> Result<Option<ProposedAction>, ProposalFailure> {
+        if expires_at <= current_block_time {
+            return Err(ProposalFailure::InvalidExpiry);
+        }
+        let max_expires_at = current_block_time.0.saturating_add(MAX_PENDING_ACTION_TTL);
+        let expires_at = BlockTime(cmp::min(expires_at.0, max_expires_at));
+        if self.can_approve(&action, &approvals) {
+            return Ok(Some(action));
+        }
+
+        let expired: Vec<[u8; 32]> = self
+            .pending_actions
+            .iter()
+            .filter(|(_, pending_action)| pending_action.is_expired(current_block_time))
+            .map(|(id, _)| *id)
+            .collect();
+        for id in expired {
+            self.pending_actions.remove(&id);
+        }
+        if self.pending_actions.len() >= MAX_PENDING_ACTIONS {
+            return Err(ProposalFailure::MaxPendingActionsLimit);
+        }
+
+        self.pending_actions
+            .insert(id, PendingAction::new(action, approvals, expires_at));
+        Ok(None)
+    }
+
+    /// Adds `approvals` to the pending action with the given id.
+    ///
+    /// Once the approvals meet the threshold of the action, it is no longer pending and gets
+    /// returned to be executed.
+    pub fn approve_pending_action(
+        &mut self,
+        id: &[u8; 32],
+        approvals: &BTreeSet<PublicKey>,
+        current_block_time: BlockTime,
+    ) -> Result<Option<ProposedAction>, ProposalFailure> {
+        let pending_action = self
+            .pending_actions
+            .get_mut(id)
+            .ok_or(ProposalFailure::NotFound)?;
+        if pending_action.is_expired(current_block_time) {
+            return Err(ProposalFailure::Expired);
+        }
+        pending_action.approvals.extend(approvals.iter().cloned());
+
+        let approved = {
+            let pending_action = &self.pending_actions[id];
+            self.can_approve(&pending_action.action, &pending_action.approvals)
+        };
+        if approved {
+            Ok(self
+                .pending_actions
+                .remove(id)
+                .map(|pending_action| pending_action.action))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Removes the pending action with the given id. Callers are responsible for checking that
+    /// the keys cancelling it meet the key management threshold.
+    pub fn cancel_pending_action(&mut self, id: &[u8; 32]) -> Result<(), ProposalFailure> {
+        self.pending_actions
+            .remove(id)
+            .map(|_| ())
+            .ok_or(ProposalFailure::NotFound)
+    }
+
+    /// Checks whether the sum of the weights of the associated keys among `approvals` is greater
+    /// or equal to the threshold of the action.
+    fn can_approve(&self, action: &ProposedAction, approvals: &BTreeSet<PublicKey>) -> bool {
+        let total_weight = self.associated_keys.calculate_keys_weight(approvals);
+
+        total_weight >= *self.action_thresholds().threshold(action.action_type())
+    }
+
+    /// Checks whether the authorization keys are recovery keys meeting the recovery threshold and
+    /// the account was inactive for longer than its inactivity period limit.
+    pub fn can_recover_with(
+        &self,
+        authorization_keys: &BTreeSet<PublicKey>,
+        current_block_time: BlockTime,
+    ) -> bool {
+        self.account_activity.is_inactive(current_block_time)
+            && self.recovery_keys.can_authorize(authorization_keys)
+    }
 }
 
 impl ToBytes for Weight {
@@ -785,6 +1353,22 @@ impl FromBytes for AssociatedKeys {
     }
 }
 
+impl ToBytes for RecoveryKeys {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = self.keys.to_bytes()?;
+        result.append(&mut self.threshold.to_bytes()?);
+        Ok(result)
+    }
+}
+
+impl FromBytes for RecoveryKeys {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (keys, rem): (AssociatedKeys, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (threshold, rem): (Weight, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((RecoveryKeys::new(keys, threshold), rem))
+    }
+}
+
 pub const BLOCKTIME_SER_SIZE: usize = U64_SIZE;
 
 impl ToBytes for BlockTime {
@@ -802,46 +1386,64 @@ impl FromBytes for BlockTime {
 
 const DEPLOYMENT_THRESHOLD_ID: u8 = 0;
 const KEY_MANAGEMENT_THRESHOLD_ID: u8 = 1;
+const TRANSFER_THRESHOLD_ID: u8 = 2;
 
 impl ToBytes for ActionThresholds {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let mut result = Vec::with_capacity(2 * (WEIGHT_SIZE + U8_SIZE));
+        let mut result = Vec::with_capacity(3 * (WEIGHT_SIZE + U8_SIZE));
         result.push(DEPLOYMENT_THRESHOLD_ID);
         result.extend(&self.deployment.to_bytes()?);
         result.push(KEY_MANAGEMENT_THRESHOLD_ID);
         result.extend(&self.key_management.to_bytes()?);
+        result.push(TRANSFER_THRESHOLD_ID);
+        result.extend(&self.transfer.to_bytes()?);
+        result.append(&mut self.named.to_bytes()?);
         Ok(result)
     }
 }
 
+/// Parses the deployment and key management thresholds, which were the only ones before the
+/// account layout was versioned.
+fn legacy_action_thresholds_from_bytes(bytes: &[u8]) -> Result<(ActionThresholds, &[u8]), Error> {
+    let mut action_thresholds: ActionThresholds = Default::default();
+    let (id_1, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+    let (weight_1, rem2): (Weight, &[u8]) = FromBytes::from_bytes(&rem)?;
+    let (id_2, rem3): (u8, &[u8]) = FromBytes::from_bytes(&rem2)?;
+    let (weight_2, rem4): (Weight, &[u8]) = FromBytes::from_bytes(&rem3)?;
+    match (id_1, id_2) {
+        (DEPLOYMENT_THRESHOLD_ID, KEY_MANAGEMENT_THRESHOLD_ID) => {
+            action_thresholds
+                .set_key_management_threshold(weight_2)
+                .map_err(Error::custom)?;
+            action_thresholds
+                .set_deployment_threshold(weight_1)
+                .map_err(Error::custom)?;
+        }
+        (KEY_MANAGEMENT_THRESHOLD_ID, DEPLOYMENT_THRESHOLD_ID) => {
+            action_thresholds
+                .set_key_management_threshold(weight_1)
+                .map_err(Error::custom)?;
+            action_thresholds
+                .set_deployment_threshold(weight_2)
+                .map_err(Error::custom)?;
+        }
+        _ => return Err(Error::FormattingError),
+    }
+    Ok((action_thresholds, rem4))
+}
+
 impl FromBytes for ActionThresholds {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let mut action_thresholds: ActionThresholds = Default::default();
-        let (id_1, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
-        let (weight_1, rem2): (Weight, &[u8]) = FromBytes::from_bytes(&rem)?;
-        let (id_2, rem3): (u8, &[u8]) = FromBytes::from_bytes(&rem2)?;
-        let (weight_2, rem4): (Weight, &[u8]) = FromBytes::from_bytes(&rem3)?;
-        match (id_1, id_2) {
-            (DEPLOYMENT_THRESHOLD_ID, KEY_MANAGEMENT_THRESHOLD_ID) => {
-                action_thresholds
-                    .set_key_management_threshold(weight_2)
-                    .map_err(Error::custom)?;
-                action_thresholds
-                    .set_deployment_threshold(weight_1)
-                    .map_err(Error::custom)?;
-                Ok((action_thresholds, rem4))
-            }
-            (KEY_MANAGEMENT_THRESHOLD_ID, DEPLOYMENT_THRESHOLD_ID) => {
-                action_thresholds
-                    .set_key_management_threshold(weight_1)
-                    .map_err(Error::custom)?;
-                action_thresholds
-                    .set_deployment_threshold(weight_2)
-                    .map_err(Error::custom)?;
-                Ok((action_thresholds, rem4))
-            }
-            _ => Err(Error::FormattingError),
+        let (mut action_thresholds, rem4) = legacy_action_thresholds_from_bytes(bytes)?;
+        let (id_3, rem5): (u8, &[u8]) = FromBytes::from_bytes(&rem4)?;
+        if id_3 != TRANSFER_THRESHOLD_ID {
+            return Err(Error::FormattingError);
         }
+        let (transfer, rem6): (Weight, &[u8]) = FromBytes::from_bytes(&rem5)?;
+        action_thresholds.set_transfer_threshold(transfer);
+        let (named, rem7): (BTreeMap<String, Weight>, &[u8]) = FromBytes::from_bytes(&rem6)?;
+        action_thresholds.named = named;
+        Ok((action_thresholds, rem7))
     }
 }
 
@@ -887,90 +1489,249 @@ impl FromBytes for AccountActivity {
     }
 }
 
-impl ToBytes for Account {
+const ADD_ASSOCIATED_KEY_ID: u8 = 0;
+const REMOVE_ASSOCIATED_KEY_ID: u8 = 1;
+const UPDATE_ASSOCIATED_KEY_ID: u8 = 2;
+const SET_ACTION_THRESHOLD_ID: u8 = 3;
+const TRANSFER_ID: u8 = 4;
+
+impl ToBytes for ProposedAction {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let action_thresholds_size = 2 * (WEIGHT_SIZE + U8_SIZE);
-        let account_activity_size: usize = 3 * (BLOCKTIME_SER_SIZE + U8_SIZE);
-        let associated_keys_size =
-            self.associated_keys.0.len() * (PUBLIC_KEY_SIZE + WEIGHT_SIZE) + U32_SIZE;
-        let known_urefs_size = UREF_SIZE * self.known_urefs.len() + U32_SIZE;
+        let mut result = Vec::with_capacity(U8_SIZE + PUBLIC_KEY_SIZE + WEIGHT_SIZE);
+        match self {
+            ProposedAction::AddAssociatedKey(key, weight) => {
+                result.push(ADD_ASSOCIATED_KEY_ID);
+                result.append(&mut key.to_bytes()?);
+                result.append(&mut weight.to_bytes()?);
+            }
+            ProposedAction::RemoveAssociatedKey(key) => {
+                result.push(REMOVE_ASSOCIATED_KEY_ID);
+                result.append(&mut key.to_bytes()?);
+            }
+            ProposedAction::UpdateAssociatedKey(key, weight) => {
+                result.push(UPDATE_ASSOCIATED_KEY_ID);
+                result.append(&mut key.to_bytes()?);
+                result.append(&mut weight.to_bytes()?);
+            }
+            ProposedAction::SetActionThreshold(action_type, weight) => {
+                result.push(SET_ACTION_THRESHOLD_ID);
+                result.append(&mut (*action_type as u32).to_bytes()?);
+                result.append(&mut weight.to_bytes()?);
+            }
+            ProposedAction::Transfer(target, amount) => {
+                result.push(TRANSFER_ID);
+                result.append(&mut target.to_bytes()?);
+                result.append(&mut amount.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl FromBytes for ProposedAction {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (id, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match id {
+            ADD_ASSOCIATED_KEY_ID => {
+                let (key, rem): (PublicKey, &[u8]) = FromBytes::from_bytes(rem)?;
+                let (weight, rem): (Weight, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((ProposedAction::AddAssociatedKey(key, weight), rem))
+            }
+            REMOVE_ASSOCIATED_KEY_ID => {
+                let (key, rem): (PublicKey, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((ProposedAction::RemoveAssociatedKey(key), rem))
+            }
+            UPDATE_ASSOCIATED_KEY_ID => {
+                let (key, rem): (PublicKey, &[u8]) = FromBytes::from_bytes(rem)?;
+                let (weight, rem): (Weight, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((ProposedAction::UpdateAssociatedKey(key, weight), rem))
+            }
+            SET_ACTION_THRESHOLD_ID => {
+                let (action_type, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+                let action_type =
+                    ActionType::try_from(action_type).map_err(|_| Error::FormattingError)?;
+                let (weight, rem): (Weight, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((ProposedAction::SetActionThreshold(action_type, weight), rem))
+            }
+            TRANSFER_ID => {
+                let (target, rem): (PublicKey, &[u8]) = FromBytes::from_bytes(rem)?;
+                let (amount, rem): (U512, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((ProposedAction::Transfer(target, amount), rem))
+            }
+            _ => Err(Error::FormattingError),
+        }
+    }
+}
+
+impl ToBytes for PendingAction {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = self.action.to_bytes()?;
+        result.append(&mut (self.approvals.len() as u32).to_bytes()?);
+        for key in self.approvals.iter() {
+            result.append(&mut key.to_bytes()?);
+        }
+        result.append(&mut self.expires_at.to_bytes()?);
+        Ok(result)
+    }
+}
+
+impl FromBytes for PendingAction {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (action, rem): (ProposedAction, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (num_approvals, mut rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let mut approvals = BTreeSet::new();
+        for _ in 0..num_approvals {
+            let (key, rest): (PublicKey, &[u8]) = FromBytes::from_bytes(rem)?;
+            approvals.insert(key);
+            rem = rest;
+        }
+        let (expires_at, rem): (BlockTime, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((PendingAction::new(action, approvals, expires_at), rem))
+    }
+}
+
+/// Version of the serialized layout of an [Account], written before its fields.
+///
+/// Whenever the layout changes, bump it and keep parsing the previous versions.
+const ACCOUNT_VERSION: u8 = 1;
+
+impl ToBytes for Account {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let named_thresholds_size: usize = self
+            .action_thresholds
+            .named
+            .keys()
+            .map(|name| name.len() + U32_SIZE + WEIGHT_SIZE)
+            .sum::<usize>()
+            + U32_SIZE;
+        let action_thresholds_size = 3 * (WEIGHT_SIZE + U8_SIZE) + named_thresholds_size;
+        let account_activity_size: usize = 3 * (BLOCKTIME_SER_SIZE + U8_SIZE);
+        let associated_keys_size =
+            self.associated_keys.0.len() * (PUBLIC_KEY_SIZE + WEIGHT_SIZE) + U32_SIZE;
+        let recovery_keys_size = self.recovery_keys.keys.0.len() * (PUBLIC_KEY_SIZE + WEIGHT_SIZE)
+            + U32_SIZE
+            + WEIGHT_SIZE;
+        let known_urefs_size = UREF_SIZE * self.known_urefs.len() + U32_SIZE;
         let purse_id_size = UREF_SIZE;
-        let serialized_account_size = KEY_SIZE // pub key
-            + U64_SIZE // nonce
+        let pending_actions_size: usize = self
+            .pending_actions
+            .values()
+            .map(|pending_action| {
+                KEY_SIZE
+                    + U8_SIZE
+                    + PUBLIC_KEY_SIZE
+                    + U512_SIZE
+                    + pending_action.approvals.len() * PUBLIC_KEY_SIZE
+                    + U32_SIZE
+                    + BLOCKTIME_SER_SIZE
+            })
+            .sum::<usize>()
+            + U32_SIZE;
+        let serialized_account_size = U8_SIZE // version
+            + KEY_SIZE // pub key
             + known_urefs_size
             + purse_id_size
             + associated_keys_size
             + action_thresholds_size
-            + account_activity_size;
+            + account_activity_size
+            + recovery_keys_size
+            + pending_actions_size;
         if serialized_account_size >= u32::max_value() as usize {
             return Err(Error::OutOfMemoryError);
         }
         let mut result: Vec<u8> = Vec::with_capacity(serialized_account_size);
+        result.push(ACCOUNT_VERSION);
         result.extend(&self.public_key.to_bytes()?);
-        result.append(&mut self.nonce.to_bytes()?);
         result.append(&mut self.known_urefs.to_bytes()?);
         result.append(&mut self.purse_id.value().to_bytes()?);
         result.append(&mut self.associated_keys.to_bytes()?);
         result.append(&mut self.action_thresholds.to_bytes()?);
         result.append(&mut self.account_activity.to_bytes()?);
+        result.append(&mut self.recovery_keys.to_bytes()?);
+        result.append(&mut self.pending_actions.to_bytes()?);
         Ok(result)
     }
 }
 
 impl FromBytes for Account {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (public_key, rem): ([u8; 32], &[u8]) = FromBytes::from_bytes(bytes)?;
-        let (nonce, rem): (u64, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (version, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        if version != ACCOUNT_VERSION {
+            return Err(Error::FormattingError);
+        }
+        let (public_key, rem): ([u8; 32], &[u8]) = FromBytes::from_bytes(rem)?;
         let (known_urefs, rem): (BTreeMap<String, Key>, &[u8]) = FromBytes::from_bytes(rem)?;
         let (purse_id, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
         let (associated_keys, rem): (AssociatedKeys, &[u8]) = FromBytes::from_bytes(rem)?;
         let (action_thresholds, rem): (ActionThresholds, &[u8]) = FromBytes::from_bytes(rem)?;
         let (account_activity, rem): (AccountActivity, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (recovery_keys, rem): (RecoveryKeys, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (pending_actions, rem): (BTreeMap<[u8; 32], PendingAction>, &[u8]) =
+            FromBytes::from_bytes(rem)?;
         let purse_id = PurseId::new(purse_id);
         Ok((
             Account {
                 public_key,
-                nonce,
                 known_urefs,
                 purse_id,
                 associated_keys,
                 action_thresholds,
                 account_activity,
+                recovery_keys,
+                pending_actions,
             },
             rem,
         ))
     }
 }
 
+impl Account {
+    /// Parses an account serialized before its layout was versioned, when it still had a nonce
+    /// and only deployment and key management thresholds.
+    ///
+    /// The nonce is dropped, and the fields added since then get the values of a newly created
+    /// account.
+    pub(crate) fn from_legacy_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (public_key, rem): ([u8; 32], &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (_nonce, rem): (u64, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (known_urefs, rem): (BTreeMap<String, Key>, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (purse_id, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (associated_keys, rem): (AssociatedKeys, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (action_thresholds, rem) = legacy_action_thresholds_from_bytes(rem)?;
+        let (account_activity, rem): (AccountActivity, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((
+            Account::new(
+                public_key,
+                known_urefs,
+                PurseId::new(purse_id),
+                associated_keys,
+                action_thresholds,
+                account_activity,
+                Default::default(),
+            ),
+            rem,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::bytesrepr::{self, FromBytes, ToBytes};
+    use crate::key::Key;
     use crate::uref::{AccessRights, URef};
     use crate::value::account::{
         Account, AccountActivity, ActionThresholds, ActionType, AddKeyFailure, AssociatedKeys,
-        BlockTime, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
-        Weight, KEY_SIZE, MAX_KEYS,
+        BlockTime, ProposalFailure, ProposedAction, PublicKey, PurseId, RecoveryKeys,
+        RemoveKeyFailure, SetThresholdFailure, SignatureAlgorithm, TaggedPublicKey,
+        UpdateKeyFailure, Weight, KEY_SIZE, MAX_KEYS, MAX_PENDING_ACTIONS, MAX_PENDING_ACTION_TTL,
     };
+    use crate::value::Value;
     use alloc::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
+    use alloc::string::String;
     use alloc::vec::Vec;
     use core::convert::TryFrom;
     use core::iter::FromIterator;
 
-    #[test]
-    fn incremented_nonce() {
-        let mut account = Account::new(
-            [0u8; 32],
-            0,
-            BTreeMap::new(),
-            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
-            AssociatedKeys::new(PublicKey::new([0u8; 32]), Weight::new(1)),
-            Default::default(),
-            AccountActivity::new(BlockTime(0), BlockTime(0)),
-        );
-        assert_eq!(account.nonce(), 0);
-        account.increment_nonce();
-        assert_eq!(account.nonce(), 1);
-    }
-
     #[test]
     fn associated_keys_add() {
         let mut keys = AssociatedKeys::new([0u8; KEY_SIZE].into(), Weight::new(1));
@@ -1032,7 +1793,6 @@ mod tests {
 
         let account = Account::new(
             [0u8; 32],
-            0,
             BTreeMap::new(),
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             keys,
@@ -1040,6 +1800,7 @@ mod tests {
             ActionThresholds::new(Weight::new(33), Weight::new(48))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
         );
 
         assert!(account.can_authorize(&BTreeSet::from_iter(vec![key_3, key_2, key_1])));
@@ -1101,7 +1862,6 @@ mod tests {
         };
         let account = Account::new(
             [0u8; 32],
-            0,
             BTreeMap::new(),
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
@@ -1109,6 +1869,7 @@ mod tests {
             ActionThresholds::new(Weight::new(33), Weight::new(48))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
         );
 
         // sum: 22, required 33 - can't deploy
@@ -1165,7 +1926,6 @@ mod tests {
         };
         let account = Account::new(
             [0u8; 32],
-            0,
             BTreeMap::new(),
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
@@ -1173,6 +1933,7 @@ mod tests {
             ActionThresholds::new(Weight::new(11), Weight::new(33))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
         );
 
         // sum: 22, required 33 - can't manage
@@ -1248,7 +2009,6 @@ mod tests {
         };
         let mut account = Account::new(
             [0u8; 32],
-            0,
             BTreeMap::new(),
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
@@ -1256,6 +2016,7 @@ mod tests {
             ActionThresholds::new(Weight::new(33), Weight::new(48))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
         );
 
         assert_eq!(
@@ -1290,7 +2051,6 @@ mod tests {
         };
         let mut account = Account::new(
             [0u8; 32],
-            0,
             BTreeMap::new(),
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
@@ -1298,6 +2058,7 @@ mod tests {
             ActionThresholds::new(Weight::new(1 + 2 + 3 + 4), Weight::new(1 + 2 + 3 + 4 + 5))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
         );
 
         assert_eq!(
@@ -1306,6 +2067,109 @@ mod tests {
         )
     }
 
+    #[test]
+    fn remove_key_would_violate_transfer_and_named_thresholds() {
+        let identity_key = PublicKey::new([1u8; 32]);
+        let key_1 = PublicKey::new([2u8; 32]);
+        let mut associated_keys = AssociatedKeys::new(identity_key, Weight::new(1));
+        associated_keys
+            .add_key(key_1, Weight::new(2))
+            .expect("should add key 1");
+        let mut account = Account::new(
+            [0u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+            associated_keys,
+            Default::default(),
+            AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
+        );
+
+        let mut transfer_account = account.clone();
+        transfer_account
+            .set_action_threshold(ActionType::Transfer, Weight::new(3))
+            .expect("should set transfer threshold");
+        assert_eq!(
+            transfer_account.remove_associated_key(key_1).unwrap_err(),
+            RemoveKeyFailure::ThresholdViolation,
+        );
+
+        account
+            .set_named_threshold("withdraw".into(), Weight::new(2))
+            .expect("should set named threshold");
+        assert_eq!(
+            account.remove_associated_key(key_1).unwrap_err(),
+            RemoveKeyFailure::ThresholdViolation,
+        );
+        assert_eq!(
+            account
+                .set_named_threshold("withdraw".into(), Weight::new(4))
+                .unwrap_err(),
+            SetThresholdFailure::InsufficientTotalWeight,
+        );
+    }
+
+    #[test]
+    fn account_can_transfer_with() {
+        let identity_key = PublicKey::new([1u8; 32]);
+        let key_1 = PublicKey::new([2u8; 32]);
+        let mut associated_keys = AssociatedKeys::new(identity_key, Weight::new(1));
+        associated_keys
+            .add_key(key_1, Weight::new(2))
+            .expect("should add key 1");
+        let mut account = Account::new(
+            [0u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+            associated_keys,
+            Default::default(),
+            AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
+        );
+        account
+            .set_action_threshold(ActionType::Transfer, Weight::new(3))
+            .expect("should set transfer threshold");
+
+        assert!(account.can_deploy_with(&BTreeSet::from_iter(vec![identity_key])));
+        assert!(!account.can_transfer_with(&BTreeSet::from_iter(vec![identity_key])));
+        assert!(account.can_transfer_with(&BTreeSet::from_iter(vec![identity_key, key_1])));
+    }
+
+    #[test]
+    fn account_meets_named_threshold() {
+        let identity_key = PublicKey::new([1u8; 32]);
+        let key_1 = PublicKey::new([2u8; 32]);
+        let mut associated_keys = AssociatedKeys::new(identity_key, Weight::new(1));
+        associated_keys
+            .add_key(key_1, Weight::new(2))
+            .expect("should add key 1");
+        let mut account = Account::new(
+            [0u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+            associated_keys,
+            Default::default(),
+            AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
+        );
+        account
+            .set_named_threshold("withdraw".into(), Weight::new(2))
+            .expect("should set named threshold");
+
+        assert_eq!(
+            account.meets_named_threshold("withdraw", &BTreeSet::from_iter(vec![identity_key])),
+            Some(false)
+        );
+        assert_eq!(
+            account.meets_named_threshold("withdraw", &BTreeSet::from_iter(vec![key_1])),
+            Some(true)
+        );
+        assert_eq!(
+            account.meets_named_threshold("vote", &BTreeSet::from_iter(vec![key_1])),
+            None
+        );
+    }
+
     #[test]
     fn updating_key_would_violate_action_thresholds() {
         let identity_key = PublicKey::new([1u8; 32]);
@@ -1325,7 +2189,6 @@ mod tests {
         };
         let mut account = Account::new(
             [0u8; 32],
-            0,
             BTreeMap::new(),
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
@@ -1333,6 +2196,7 @@ mod tests {
             ActionThresholds::new(Weight::new(1 + 2 + 3 + 4), Weight::new(1 + 2 + 3 + 4 + 1))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
         );
 
         // Decreases by 3
@@ -1388,4 +2252,388 @@ mod tests {
             Weight::new(255u8)
         );
     }
+
+    #[test]
+    fn account_activity_is_inactive_after_limit() {
+        let mut account_activity = AccountActivity::new(BlockTime(100), BlockTime(50));
+        assert!(!account_activity.is_inactive(BlockTime(150)));
+        assert!(account_activity.is_inactive(BlockTime(151)));
+
+        // Either action resets the inactivity period
+        account_activity.update_key_management_last_used(BlockTime(120));
+        assert!(!account_activity.is_inactive(BlockTime(170)));
+        account_activity.update_deployment_last_used(BlockTime(140));
+        assert!(!account_activity.is_inactive(BlockTime(190)));
+        assert!(account_activity.is_inactive(BlockTime(191)));
+    }
+
+    #[test]
+    fn recovery_keys_threshold() {
+        let key_1 = PublicKey::new([1u8; 32]);
+        let key_2 = PublicKey::new([2u8; 32]);
+        let mut recovery_keys = RecoveryKeys::default();
+        assert!(!recovery_keys.can_authorize(&BTreeSet::from_iter(vec![key_1])));
+
+        recovery_keys.add_key(key_1, Weight::new(1)).unwrap();
+        recovery_keys.add_key(key_2, Weight::new(2)).unwrap();
+        assert_eq!(
+            recovery_keys.set_threshold(Weight::new(4)),
+            Err(SetThresholdFailure::InsufficientTotalWeight)
+        );
+        recovery_keys.set_threshold(Weight::new(3)).unwrap();
+
+        assert!(!recovery_keys.can_authorize(&BTreeSet::new()));
+        assert!(!recovery_keys.can_authorize(&BTreeSet::from_iter(vec![key_2])));
+        assert!(recovery_keys.can_authorize(&BTreeSet::from_iter(vec![key_1, key_2])));
+        assert!(!recovery_keys.can_authorize(&BTreeSet::from_iter(vec![
+            key_1,
+            key_2,
+            PublicKey::new([3u8; 32])
+        ])));
+    }
+
+    #[test]
+    fn remove_recovery_key_would_violate_threshold() {
+        let key_1 = PublicKey::new([1u8; 32]);
+        let key_2 = PublicKey::new([2u8; 32]);
+        let mut recovery_keys = RecoveryKeys::default();
+        recovery_keys.add_key(key_1, Weight::new(1)).unwrap();
+        recovery_keys.add_key(key_2, Weight::new(2)).unwrap();
+        recovery_keys.set_threshold(Weight::new(2)).unwrap();
+
+        assert_eq!(
+            recovery_keys.remove_key(&key_2),
+            Err(RemoveKeyFailure::ThresholdViolation)
+        );
+        recovery_keys.remove_key(&key_1).unwrap();
+        // Removing the last key disables recovery
+        recovery_keys.remove_key(&key_2).unwrap();
+        assert_eq!(recovery_keys.keys().count(), 0);
+    }
+
+    #[test]
+    fn account_can_recover_with() {
+        let recovery_key = PublicKey::new([2u8; 32]);
+        let mut account = Account::new(
+            [1u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+            AssociatedKeys::new(PublicKey::new([1u8; 32]), Weight::new(1)),
+            Default::default(),
+            AccountActivity::new(BlockTime(100), BlockTime(50)),
+            Default::default(),
+        );
+        account
+            .add_recovery_key(recovery_key, Weight::new(1))
+            .unwrap();
+        let authorization_keys = BTreeSet::from_iter(vec![recovery_key]);
+
+        assert!(!account.can_recover_with(&authorization_keys, BlockTime(150)));
+        assert!(account.can_recover_with(&authorization_keys, BlockTime(151)));
+        assert!(!account.can_recover_with(
+            &BTreeSet::from_iter(vec![PublicKey::new([1u8; 32])]),
+            BlockTime(151)
+        ));
+    }
+
+    fn multisig_account() -> Account {
+        let mut associated_keys = AssociatedKeys::new(PublicKey::new([1u8; 32]), Weight::new(1));
+        associated_keys
+            .add_key(PublicKey::new([2u8; 32]), Weight::new(1))
+            .expect("should add key");
+        let mut account = Account::new(
+            [1u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+            associated_keys,
+            Default::default(),
+            AccountActivity::new(BlockTime(0), BlockTime(0)),
+            Default::default(),
+        );
+        account
+            .set_action_threshold(ActionType::KeyManagement, Weight::new(2))
+            .expect("should set key management threshold");
+        account
+    }
+
+    #[test]
+    fn account_propose_and_approve_action() {
+        let mut account = multisig_account();
+        let action = ProposedAction::AddAssociatedKey(PublicKey::new([3u8; 32]), Weight::new(1));
+        let id = [42u8; 32];
+
+        assert_eq!(
+            account.propose_action(
+                id,
+                action.clone(),
+                BTreeSet::from_iter(vec![PublicKey::new([1u8; 32])]),
+                BlockTime(100),
+                BlockTime(10),
+            ),
+            Ok(None)
+        );
+        assert_eq!(account.pending_actions().count(), 1);
+        // Approving again with the same key doesn't add any weight
+        assert_eq!(
+            account.approve_pending_action(
+                &id,
+                &BTreeSet::from_iter(vec![PublicKey::new([1u8; 32])]),
+                BlockTime(20),
+            ),
+            Ok(None)
+        );
+        assert_eq!(
+            account.approve_pending_action(
+                &id,
+                &BTreeSet::from_iter(vec![PublicKey::new([2u8; 32])]),
+                BlockTime(100),
+            ),
+            Ok(Some(action))
+        );
+        assert_eq!(account.pending_actions().count(), 0);
+        assert_eq!(
+            account.approve_pending_action(
+                &id,
+                &BTreeSet::from_iter(vec![PublicKey::new([2u8; 32])]),
+                BlockTime(100),
+            ),
+            Err(ProposalFailure::NotFound)
+        );
+    }
+
+    #[test]
+    fn account_propose_action_approved_right_away() {
+        let mut account = multisig_account();
+        let action = ProposedAction::RemoveAssociatedKey(PublicKey::new([2u8; 32]));
+
+        assert_eq!(
+            account.propose_action(
+                [42u8; 32],
+                action.clone(),
+                BTreeSet::from_iter(vec![PublicKey::new([1u8; 32]), PublicKey::new([2u8; 32])]),
+                BlockTime(100),
+                BlockTime(10),
+            ),
+            Ok(Some(action))
+        );
+        assert_eq!(account.pending_actions().count(), 0);
+    }
+
+    #[test]
+    fn account_pending_action_expiry() {
+        let mut account = multisig_account();
+        let approvals = BTreeSet::from_iter(vec![PublicKey::new([1u8; 32])]);
+        let action = ProposedAction::RemoveAssociatedKey(PublicKey::new([2u8; 32]));
+
+        assert_eq!(
+            account.propose_action(
+                [0u8; 32],
+                action.clone(),
+                approvals.clone(),
+                BlockTime(10),
+                BlockTime(10),
+            ),
+            Err(ProposalFailure::InvalidExpiry)
+        );
+
+        for i in 0..MAX_PENDING_ACTIONS {
+            assert_eq!(
+                account.propose_action(
+                    [i as u8; 32],
+                    action.clone(),
+                    approvals.clone(),
+                    BlockTime(20),
+                    BlockTime(10),
+                ),
+                Ok(None)
+            );
+        }
+        assert_eq!(
+            account.propose_action(
+                [100u8; 32],
+                action.clone(),
+                approvals.clone(),
+                BlockTime(30),
+                BlockTime(20),
+            ),
+            Err(ProposalFailure::MaxPendingActionsLimit)
+        );
+        assert_eq!(
+            account.approve_pending_action(
+                &[0u8; 32],
+                &BTreeSet::from_iter(vec![PublicKey::new([2u8; 32])]),
+                BlockTime(21),
+            ),
+            Err(ProposalFailure::Expired)
+        );
+        // Expired actions make room for new ones
+        assert_eq!(
+            account.propose_action([100u8; 32], action, approvals, BlockTime(30), BlockTime(21)),
+            Ok(None)
+        );
+        assert_eq!(account.pending_actions().count(), 1);
+    }
+
+    #[test]
+    fn account_pending_action_expiry_is_capped() {
+        let mut account = multisig_account();
+        let id = [42u8; 32];
+
+        assert_eq!(
+            account.propose_action(
+                id,
+                ProposedAction::RemoveAssociatedKey(PublicKey::new([2u8; 32])),
+                BTreeSet::from_iter(vec![PublicKey::new([1u8; 32])]),
+                BlockTime(u64::max_value()),
+                BlockTime(10),
+            ),
+            Ok(None)
+        );
+        assert_eq!(
+            account
+                .get_pending_action(&id)
+                .expect("action should be pending")
+                .expires_at(),
+            BlockTime(10 + MAX_PENDING_ACTION_TTL)
+        );
+    }
+
+    #[test]
+    fn account_cancel_pending_action() {
+        let mut account = multisig_account();
+        let id = [42u8; 32];
+        account
+            .propose_action(
+                id,
+                ProposedAction::RemoveAssociatedKey(PublicKey::new([2u8; 32])),
+                BTreeSet::from_iter(vec![PublicKey::new([1u8; 32])]),
+                BlockTime(100),
+                BlockTime(10),
+            )
+            .expect("should propose action");
+
+        assert_eq!(account.cancel_pending_action(&id), Ok(()));
+        assert_eq!(account.pending_actions().count(), 0);
+        assert_eq!(
+            account.cancel_pending_action(&id),
+            Err(ProposalFailure::NotFound)
+        );
+    }
+
+    #[test]
+    fn tagged_public_key_length() {
+        assert!(TaggedPublicKey::new(SignatureAlgorithm::Ed25519, &[1u8; 32]).is_ok());
+        assert!(TaggedPublicKey::new(SignatureAlgorithm::Ed25519, &[1u8; 33]).is_err());
+        assert!(TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &[2u8; 33]).is_ok());
+        assert!(TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &[2u8; 32]).is_err());
+    }
+
+    #[test]
+    fn tagged_public_key_address_depends_on_algorithm() {
+        let mut secp256k1_bytes = [1u8; 33];
+        secp256k1_bytes[32] = 2;
+        let ed25519_key = TaggedPublicKey::ed25519([1u8; 32]);
+        let secp256k1_key = TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &secp256k1_bytes)
+            .expect("should create key");
+
+        assert_ne!(ed25519_key.to_address(), PublicKey::new([1u8; 32]));
+        assert_ne!(ed25519_key.to_address(), secp256k1_key.to_address());
+        assert_eq!(
+            ed25519_key.to_address(),
+            TaggedPublicKey::ed25519([1u8; 32]).to_address()
+        );
+    }
+
+    #[test]
+    fn associated_keys_of_mixed_algorithms() {
+        let ed25519_key = TaggedPublicKey::ed25519([1u8; 32]);
+        let secp256k1_key = TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &[2u8; 33])
+            .expect("should create key");
+        let mut keys = AssociatedKeys::new(ed25519_key.to_address(), Weight::new(1));
+        keys.add_key(secp256k1_key.to_address(), Weight::new(2))
+            .expect("should add key");
+
+        assert_eq!(keys.get(&secp256k1_key.to_address()), Some(&Weight::new(2)));
+        assert_eq!(
+            keys.calculate_keys_weight(&BTreeSet::from_iter(vec![
+                ed25519_key.to_address(),
+                secp256k1_key.to_address()
+            ])),
+            Weight::new(3)
+        );
+    }
+
+    #[test]
+    fn account_serialization_is_versioned() {
+        let account = Account::create(
+            [1u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([2u8; 32], AccessRights::READ_ADD_WRITE)),
+        );
+        let mut bytes = account.to_bytes().expect("should serialize");
+        assert_eq!(
+            bytesrepr::deserialize::<Account>(&bytes).expect("should deserialize"),
+            account
+        );
+
+        bytes[0] += 1;
+        assert_eq!(
+            bytesrepr::deserialize::<Account>(&bytes),
+            Err(bytesrepr::Error::FormattingError)
+        );
+    }
+
+    #[test]
+    fn account_from_legacy_value() {
+        let public_key = [1u8; 32];
+        let mut known_urefs = BTreeMap::new();
+        known_urefs.insert(String::from("key"), Key::Hash([3u8; 32]));
+        let purse_id = PurseId::new(URef::new([2u8; 32], AccessRights::READ_ADD_WRITE));
+        let mut associated_keys = AssociatedKeys::new(PublicKey::new(public_key), Weight::new(2));
+        associated_keys
+            .add_key(PublicKey::new([4u8; 32]), Weight::new(1))
+            .expect("should add key");
+        let account_activity = AccountActivity::new(BlockTime(5), BlockTime(100));
+
+        // Tag 4, followed by the public key, nonce, known urefs, purse, associated keys,
+        // deployment and key management thresholds and account activity.
+        let mut bytes = vec![4u8];
+        bytes.append(&mut public_key.to_bytes().unwrap());
+        bytes.append(&mut 7u64.to_bytes().unwrap());
+        bytes.append(&mut known_urefs.to_bytes().unwrap());
+        bytes.append(&mut purse_id.value().to_bytes().unwrap());
+        bytes.append(&mut associated_keys.to_bytes().unwrap());
+        bytes.extend(&[0u8, 2, 1, 3]);
+        bytes.append(&mut account_activity.to_bytes().unwrap());
+
+        let (value, rem) = Value::from_bytes(&bytes).expect("should deserialize legacy account");
+        assert!(rem.is_empty());
+        let account = match value {
+            Value::Account(account) => account,
+            other => panic!("expected account, got {:?}", other),
+        };
+        let action_thresholds = ActionThresholds::new(Weight::new(2), Weight::new(3))
+            .expect("should create thresholds");
+        assert_eq!(
+            account,
+            Account::new(
+                public_key,
+                known_urefs,
+                purse_id,
+                associated_keys,
+                action_thresholds,
+                account_activity,
+                Default::default(),
+            )
+        );
+
+        // Accounts are written back in the versioned layout.
+        let value = Value::Account(account);
+        let versioned_bytes = value.to_bytes().expect("should serialize");
+        assert_ne!(versioned_bytes[0], 4);
+        assert_eq!(
+            bytesrepr::deserialize::<Value>(&versioned_bytes).expect("should deserialize"),
+            value
+        );
+    }
 }
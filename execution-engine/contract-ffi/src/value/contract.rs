@@ -1,22 +1,155 @@
-use crate::bytesrepr::{Error, FromBytes, ToBytes, U32_SIZE, U64_SIZE};
+use crate::bytesrepr::{deserialize, Error, FromBytes, ToBytes, U32_SIZE, U64_SIZE};
 use crate::key::{Key, UREF_SIZE};
+use crate::uref::URef;
+use crate::value::account::{PublicKey, PurseId, TryFromIntError};
+use crate::value::uint::{U128, U256, U512};
 use alloc::collections::btree_map::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// Name of the only entry point of a contract stored without declared entry points.
+pub const DEFAULT_ENTRY_POINT_NAME: &str = "call";
+
+/// Type of an argument accepted by an [`EntryPoint`].
+///
+/// The explicit numbering of the variants is done on purpose as it is used to
+/// serialize the type. When adding new variants you should also remember to change
+/// `TryFrom<u8> for ArgType`.
+#[repr(u8)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ArgType {
+    /// Argument is passed to the contract without being checked by the host.
+    Any = 0,
+    Int32 = 1,
+    UInt32 = 2,
+    UInt64 = 3,
+    UInt128 = 4,
+    UInt256 = 5,
+    UInt512 = 6,
+    ByteArray = 7,
+    String = 8,
+    Key = 9,
+    URef = 10,
+    PublicKey = 11,
+    PurseId = 12,
+}
+
+/// convert from u8 representation of `[ArgType]`
+impl TryFrom<u8> for ArgType {
+    type Error = TryFromIntError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            d if d == ArgType::Any as u8 => Ok(ArgType::Any),
+            d if d == ArgType::Int32 as u8 => Ok(ArgType::Int32),
+            d if d == ArgType::UInt32 as u8 => Ok(ArgType::UInt32),
+            d if d == ArgType::UInt64 as u8 => Ok(ArgType::UInt64),
+            d if d == ArgType::UInt128 as u8 => Ok(ArgType::UInt128),
+            d if d == ArgType::UInt256 as u8 => Ok(ArgType::UInt256),
+            d if d == ArgType::UInt512 as u8 => Ok(ArgType::UInt512),
+            d if d == ArgType::ByteArray as u8 => Ok(ArgType::ByteArray),
+            d if d == ArgType::String as u8 => Ok(ArgType::String),
+            d if d == ArgType::Key as u8 => Ok(ArgType::Key),
+            d if d == ArgType::URef as u8 => Ok(ArgType::URef),
+            d if d == ArgType::PublicKey as u8 => Ok(ArgType::PublicKey),
+            d if d == ArgType::PurseId as u8 => Ok(ArgType::PurseId),
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
+impl ArgType {
+    /// Checks whether `bytes` is a valid serialized value of this type.
+    pub fn matches(self, bytes: &[u8]) -> bool {
+        match self {
+            ArgType::Any => true,
+            ArgType::Int32 => deserialize::<i32>(bytes).is_ok(),
+            ArgType::UInt32 => deserialize::<u32>(bytes).is_ok(),
+            ArgType::UInt64 => deserialize::<u64>(bytes).is_ok(),
+            ArgType::UInt128 => deserialize::<U128>(bytes).is_ok(),
+            ArgType::UInt256 => deserialize::<U256>(bytes).is_ok(),
+            ArgType::UInt512 => deserialize::<U512>(bytes).is_ok(),
+            ArgType::ByteArray => deserialize::<Vec<u8>>(bytes).is_ok(),
+            ArgType::String => deserialize::<String>(bytes).is_ok(),
+            ArgType::Key => deserialize::<Key>(bytes).is_ok(),
+            ArgType::URef => deserialize::<URef>(bytes).is_ok(),
+            ArgType::PublicKey => deserialize::<PublicKey>(bytes).is_ok(),
+            ArgType::PurseId => deserialize::<PurseId>(bytes).is_ok(),
+        }
+    }
+}
+
+/// A function exported by a stored contract which can be called by its name.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct EntryPoint {
+    args: Vec<ArgType>,
+}
+
+impl EntryPoint {
+    pub fn new(args: Vec<ArgType>) -> Self {
+        EntryPoint { args }
+    }
+
+    pub fn args(&self) -> &[ArgType] {
+        &self.args
+    }
+
+    /// Checks whether `args` match the declared argument types, both in number and in types.
+    pub fn accepts(&self, args: &[Vec<u8>]) -> bool {
+        self.args.len() == args.len()
+            && self
+                .args
+                .iter()
+                .zip(args)
+                .all(|(arg_type, arg)| arg_type.matches(arg))
+    }
+}
+
+impl ToBytes for EntryPoint {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let arg_types: Vec<u8> = self.args.iter().map(|arg_type| *arg_type as u8).collect();
+        arg_types.to_bytes()
+    }
+}
+
+impl FromBytes for EntryPoint {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (arg_types, rem): (Vec<u8>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let args = arg_types
+            .into_iter()
+            .map(|arg_type| ArgType::try_from(arg_type).map_err(|_| Error::FormattingError))
+            .collect::<Result<Vec<ArgType>, Error>>()?;
+        Ok((EntryPoint::new(args), rem))
+    }
+}
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Contract {
     bytes: Vec<u8>,
     known_urefs: BTreeMap<String, Key>,
     protocol_version: u64,
+    entry_points: BTreeMap<String, EntryPoint>,
 }
 
 impl Contract {
     pub fn new(bytes: Vec<u8>, known_urefs: BTreeMap<String, Key>, protocol_version: u64) -> Self {
+        Contract::with_entry_points(bytes, known_urefs, protocol_version, BTreeMap::new())
+    }
+
+    /// Creates a contract which exposes each of `entry_points` under its own name instead of
+    /// a single `call` export.
+    pub fn with_entry_points(
+        bytes: Vec<u8>,
+        known_urefs: BTreeMap<String, Key>,
+        protocol_version: u64,
+        entry_points: BTreeMap<String, EntryPoint>,
+    ) -> Self {
         Contract {
             bytes,
             known_urefs,
             protocol_version,
+            entry_points,
         }
     }
 
@@ -32,8 +165,20 @@ impl Contract {
         &mut self.known_urefs
     }
 
-    pub fn destructure(self) -> (Vec<u8>, BTreeMap<String, Key>, u64) {
-        (self.bytes, self.known_urefs, self.protocol_version)
+    pub fn destructure(
+        self,
+    ) -> (
+        Vec<u8>,
+        BTreeMap<String, Key>,
+        u64,
+        BTreeMap<String, EntryPoint>,
+    ) {
+        (
+            self.bytes,
+            self.known_urefs,
+            self.protocol_version,
+            self.entry_points,
+        )
     }
 
     pub fn bytes(&self) -> &[u8] {
@@ -43,6 +188,10 @@ impl Contract {
     pub fn protocol_version(&self) -> u64 {
         self.protocol_version
     }
+
+    pub fn entry_points(&self) -> &BTreeMap<String, EntryPoint> {
+        &self.entry_points
+    }
 }
 
 impl ToBytes for Contract {
@@ -56,12 +205,14 @@ impl ToBytes for Contract {
                     self.bytes.len() +                  //size for elements of bytes
                     U32_SIZE +                                 //size for length of known_urefs
                     UREF_SIZE * self.known_urefs.len() + //size for known_urefs elements
-                    U64_SIZE; // size for protocol_version
+                    U64_SIZE +                                 //size for protocol_version
+                    U32_SIZE; // size for length of entry_points
 
         let mut result = Vec::with_capacity(size);
         result.append(&mut self.bytes.to_bytes()?);
         result.append(&mut self.known_urefs.to_bytes()?);
         result.append(&mut self.protocol_version.to_bytes()?);
+        result.append(&mut self.entry_points.to_bytes()?);
         Ok(result)
     }
 }
@@ -71,13 +222,59 @@ impl FromBytes for Contract {
         let (bytes, rem1): (Vec<u8>, &[u8]) = FromBytes::from_bytes(bytes)?;
         let (known_urefs, rem2): (BTreeMap<String, Key>, &[u8]) = FromBytes::from_bytes(rem1)?;
         let (protocol_version, rem3): (u64, &[u8]) = FromBytes::from_bytes(rem2)?;
+        let (entry_points, rem4): (BTreeMap<String, EntryPoint>, &[u8]) =
+            FromBytes::from_bytes(rem3)?;
         Ok((
             Contract {
                 bytes,
                 known_urefs,
                 protocol_version,
+                entry_points,
             },
-            rem3,
+            rem4,
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ArgType, EntryPoint};
+    use crate::bytesrepr::ToBytes;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn arg_type_should_roundtrip_through_u8() {
+        for value in 0u8..=12 {
+            let arg_type = ArgType::try_from(value).expect("should be a valid arg type");
+            assert_eq!(arg_type as u8, value);
+        }
+        assert!(ArgType::try_from(13u8).is_err());
+    }
+
+    #[test]
+    fn entry_point_should_accept_matching_args() {
+        let entry_point = EntryPoint::new(vec![ArgType::String, ArgType::UInt64]);
+        let args: Vec<Vec<u8>> = vec![
+            String::from("hello").to_bytes().unwrap(),
+            42u64.to_bytes().unwrap(),
+        ];
+        assert!(entry_point.accepts(&args));
+    }
+
+    #[test]
+    fn entry_point_should_reject_wrong_number_of_args() {
+        let entry_point = EntryPoint::new(vec![ArgType::UInt64]);
+        assert!(!entry_point.accepts(&[]));
+        assert!(!entry_point.accepts(&[42u64.to_bytes().unwrap(), 42u64.to_bytes().unwrap()]));
+    }
+
+    #[test]
+    fn entry_point_should_reject_mismatched_arg_types() {
+        let entry_point = EntryPoint::new(vec![ArgType::UInt64]);
+        assert!(!entry_point.accepts(&[42i32.to_bytes().unwrap()]));
+        let any = EntryPoint::new(vec![ArgType::Any]);
+        assert!(any.accepts(&[42i32.to_bytes().unwrap()]));
+    }
+}
@@ -0,0 +1,297 @@
+use crate::bytesrepr::{Error, FromBytes, ToBytes};
+use crate::uref::{URef, UREF_SIZE_SERIALIZED};
+use crate::value::account::TryFromIntError;
+use crate::value::contract::Contract;
+use alloc::collections::btree_map::BTreeMap;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use failure::Fail;
+
+/// Number of the first version added to a [`ContractPackage`].
+pub const FIRST_CONTRACT_VERSION: u32 = 1;
+
+/// Represents an error that happens when trying to create, modify or call a contract package.
+///
+/// It is represented by `i32` to be easily able to transform this value in an out
+/// through FFI boundaries as a number.
+///
+/// The explicit numbering of the variants is done on purpose and whenever you plan to add
+/// new variant, you should always extend it, and add a variant that does not exist already.
+/// When adding new variants you should also remember to change
+/// `From<i32> for ContractPackageFailure`.
+///
+/// This way we can ensure safety and backwards compatibility. Any changes should be carefully
+/// reviewed and tested.
+#[derive(PartialEq, Eq, Fail, Debug)]
+#[repr(i32)]
+pub enum ContractPackageFailure {
+    #[fail(display = "Provided access key does not grant access to the contract package")]
+    PermissionDenied = 1,
+    #[fail(display = "Requested contract version does not exist")]
+    VersionNotFound = 2,
+    #[fail(display = "Requested contract version is disabled")]
+    VersionDisabled = 3,
+    #[fail(display = "Contract package does not have any enabled versions")]
+    NoEnabledVersion = 4,
+}
+
+/// convert from i32 representation of `[ContractPackageFailure]`
+impl TryFrom<i32> for ContractPackageFailure {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == ContractPackageFailure::PermissionDenied as i32 => {
+                Ok(ContractPackageFailure::PermissionDenied)
+            }
+            d if d == ContractPackageFailure::VersionNotFound as i32 => {
+                Ok(ContractPackageFailure::VersionNotFound)
+            }
+            d if d == ContractPackageFailure::VersionDisabled as i32 => {
+                Ok(ContractPackageFailure::VersionDisabled)
+            }
+            d if d == ContractPackageFailure::NoEnabledVersion as i32 => {
+                Ok(ContractPackageFailure::NoEnabledVersion)
+            }
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
+/// A single version of a contract held by a [`ContractPackage`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ContractVersion {
+    contract: Contract,
+    is_enabled: bool,
+}
+
+impl ContractVersion {
+    pub fn new(contract: Contract, is_enabled: bool) -> Self {
+        ContractVersion {
+            contract,
+            is_enabled,
+        }
+    }
+
+    pub fn contract(&self) -> &Contract {
+        &self.contract
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+}
+
+impl ToBytes for ContractVersion {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = self.contract.to_bytes()?;
+        // bool is encoded as a single byte
+        result.push(self.is_enabled as u8);
+        Ok(result)
+    }
+}
+
+impl FromBytes for ContractVersion {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (contract, rem1): (Contract, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (is_enabled, rem2): (u8, &[u8]) = FromBytes::from_bytes(rem1)?;
+        let is_enabled = match is_enabled {
+            0 => false,
+            1 => true,
+            _ => return Err(Error::FormattingError),
+        };
+        Ok((ContractVersion::new(contract, is_enabled), rem2))
+    }
+}
+
+/// A collection of versions of a contract stored under a single, stable key.
+///
+/// Modifying the package requires presenting its access key. Callers can
+/// either call a specific version or the latest enabled one.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ContractPackage {
+    access_key: URef,
+    versions: BTreeMap<u32, ContractVersion>,
+}
+
+impl ContractPackage {
+    pub fn new(access_key: URef, versions: BTreeMap<u32, ContractVersion>) -> Self {
+        ContractPackage {
+            access_key,
+            versions,
+        }
+    }
+
+    pub fn access_key(&self) -> URef {
+        self.access_key
+    }
+
+    pub fn versions(&self) -> &BTreeMap<u32, ContractVersion> {
+        &self.versions
+    }
+
+    /// Checks whether `access_key` points at the same URef as the package's access key.
+    pub fn is_authorized_by(&self, access_key: &URef) -> bool {
+        self.access_key.addr() == access_key.addr()
+    }
+
+    /// Adds a new enabled version of the contract and returns its number.
+    pub fn add_version(&mut self, contract: Contract) -> u32 {
+        let version = self
+            .versions
+            .keys()
+            .next_back()
+            .map(|last| last + 1)
+            .unwrap_or(FIRST_CONTRACT_VERSION);
+        self.versions
+            .insert(version, ContractVersion::new(contract, true));
+        version
+    }
+
+    /// Disables given version. Disabled versions can't be called anymore.
+    pub fn disable_version(&mut self, version: u32) -> Result<(), ContractPackageFailure> {
+        match self.versions.get_mut(&version) {
+            Some(contract_version) => {
+                contract_version.is_enabled = false;
+                Ok(())
+            }
+            None => Err(ContractPackageFailure::VersionNotFound),
+        }
+    }
+
+    /// Returns the contract under a given enabled version.
+    pub fn get_version(&self, version: u32) -> Result<&Contract, ContractPackageFailure> {
+        match self.versions.get(&version) {
+            Some(contract_version) if contract_version.is_enabled => {
+                Ok(contract_version.contract())
+            }
+            Some(_) => Err(ContractPackageFailure::VersionDisabled),
+            None => Err(ContractPackageFailure::VersionNotFound),
+        }
+    }
+
+    /// Returns the highest enabled version together with its contract.
+    pub fn latest_enabled_version(&self) -> Result<(u32, &Contract), ContractPackageFailure> {
+        self.versions
+            .iter()
+            .rev()
+            .find(|(_, contract_version)| contract_version.is_enabled)
+            .map(|(version, contract_version)| (*version, contract_version.contract()))
+            .ok_or(ContractPackageFailure::NoEnabledVersion)
+    }
+}
+
+impl ToBytes for ContractPackage {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut versions_bytes = self.versions.to_bytes()?;
+        if versions_bytes.len() >= u32::max_value() as usize - UREF_SIZE_SERIALIZED {
+            return Err(Error::OutOfMemoryError);
+        }
+        let mut result = Vec::with_capacity(UREF_SIZE_SERIALIZED + versions_bytes.len());
+        result.append(&mut self.access_key.to_bytes()?);
+        result.append(&mut versions_bytes);
+        Ok(result)
+    }
+}
+
+impl FromBytes for ContractPackage {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (access_key, rem1): (URef, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (versions, rem2): (BTreeMap<u32, ContractVersion>, &[u8]) =
+            FromBytes::from_bytes(rem1)?;
+        Ok((
+            ContractPackage {
+                access_key,
+                versions,
+            },
+            rem2,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uref::AccessRights;
+
+    fn contract(bytes: &[u8]) -> Contract {
+        Contract::new(bytes.to_vec(), BTreeMap::new(), 1)
+    }
+
+    fn package() -> ContractPackage {
+        ContractPackage::new(
+            URef::new([1u8; 32], AccessRights::READ_ADD_WRITE),
+            BTreeMap::new(),
+        )
+    }
+
+    #[test]
+    fn versions_should_start_at_one_and_increase() {
+        let mut package = package();
+        assert_eq!(package.add_version(contract(&[1])), FIRST_CONTRACT_VERSION);
+        assert_eq!(
+            package.add_version(contract(&[2])),
+            FIRST_CONTRACT_VERSION + 1
+        );
+        assert_eq!(package.versions().len(), 2);
+    }
+
+    #[test]
+    fn latest_enabled_version_should_skip_disabled_versions() {
+        let mut package = package();
+        assert_eq!(
+            package.latest_enabled_version(),
+            Err(ContractPackageFailure::NoEnabledVersion)
+        );
+        let v1 = package.add_version(contract(&[1]));
+        let v2 = package.add_version(contract(&[2]));
+        assert_eq!(package.latest_enabled_version(), Ok((v2, &contract(&[2]))));
+
+        package.disable_version(v2).expect("should disable");
+        assert_eq!(package.latest_enabled_version(), Ok((v1, &contract(&[1]))));
+        assert_eq!(
+            package.get_version(v2),
+            Err(ContractPackageFailure::VersionDisabled)
+        );
+        assert_eq!(
+            package.get_version(v2 + 1),
+            Err(ContractPackageFailure::VersionNotFound)
+        );
+        assert_eq!(
+            package.disable_version(v2 + 1),
+            Err(ContractPackageFailure::VersionNotFound)
+        );
+    }
+
+    #[test]
+    fn disabled_version_numbers_should_not_be_reused() {
+        let mut package = package();
+        let v1 = package.add_version(contract(&[1]));
+        package.disable_version(v1).expect("should disable");
+        assert_eq!(package.add_version(contract(&[2])), v1 + 1);
+    }
+
+    #[test]
+    fn should_check_access_key_by_address() {
+        let package = package();
+        assert!(package.is_authorized_by(&URef::new([1u8; 32], AccessRights::READ)));
+        assert!(!package.is_authorized_by(&URef::new([2u8; 32], AccessRights::READ_ADD_WRITE)));
+    }
+
+    #[test]
+    fn contract_package_failure_should_roundtrip_through_i32() {
+        for failure in vec![
+            ContractPackageFailure::PermissionDenied,
+            ContractPackageFailure::VersionNotFound,
+            ContractPackageFailure::VersionDisabled,
+            ContractPackageFailure::NoEnabledVersion,
+        ] {
+            let value = failure as i32;
+            assert_eq!(
+                ContractPackageFailure::try_from(value).unwrap() as i32,
+                value
+            );
+        }
+        assert!(ContractPackageFailure::try_from(0).is_err());
+    }
+}
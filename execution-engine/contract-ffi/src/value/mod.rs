@@ -1,5 +1,6 @@
 pub mod account;
 pub mod contract;
+pub mod contract_package;
 pub mod uint;
 
 use crate::bytesrepr::{
@@ -15,6 +16,7 @@ use core::mem::size_of;
 
 pub use self::account::Account;
 pub use self::contract::Contract;
+pub use self::contract_package::ContractPackage;
 pub use self::uint::{U128, U256, U512};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -33,13 +35,15 @@ pub enum Value {
     Account(account::Account),
     Contract(contract::Contract),
     Unit,
+    ContractPackage(contract_package::ContractPackage),
 }
 
 const INT32_ID: u8 = 0;
 const BYTEARRAY_ID: u8 = 1;
 const LISTINT32_ID: u8 = 2;
 const STRING_ID: u8 = 3;
-const ACCT_ID: u8 = 4;
+/// Tag of accounts serialized before their layout was versioned, only read for compatibility.
+const LEGACY_ACCT_ID: u8 = 4;
 const CONTRACT_ID: u8 = 5;
 const NAMEDKEY_ID: u8 = 6;
 const LISTSTRING_ID: u8 = 7;
@@ -49,6 +53,8 @@ const U512_ID: u8 = 10;
 const KEY_ID: u8 = 11;
 const UNIT_ID: u8 = 12;
 const U64_ID: u8 = 13;
+const CONTRACT_PACKAGE_ID: u8 = 14;
+const ACCT_ID: u8 = 15;
 
 use self::Value::*;
 
@@ -157,6 +163,9 @@ impl ToBytes for Value {
                 result.append(&mut num.to_bytes()?);
                 Ok(result)
             }
+            ContractPackage(p) => Ok(iter::once(CONTRACT_PACKAGE_ID)
+                .chain(p.to_bytes()?)
+                .collect()),
         }
     }
 }
@@ -192,6 +201,10 @@ impl FromBytes for Value {
                 let (s, rem): (String, &[u8]) = FromBytes::from_bytes(rest)?;
                 Ok((String(s), rem))
             }
+            LEGACY_ACCT_ID => {
+                let (a, rem) = account::Account::from_legacy_bytes(rest)?;
+                Ok((Account(a), rem))
+            }
             ACCT_ID => {
                 let (a, rem): (account::Account, &[u8]) = FromBytes::from_bytes(rest)?;
                 Ok((Account(a), rem))
@@ -218,6 +231,11 @@ impl FromBytes for Value {
                 let (num, rem): (u64, &[u8]) = FromBytes::from_bytes(rest)?;
                 Ok((UInt64(num), rem))
             }
+            CONTRACT_PACKAGE_ID => {
+                let (package, rem): (contract_package::ContractPackage, &[u8]) =
+                    FromBytes::from_bytes(rest)?;
+                Ok((ContractPackage(package), rem))
+            }
             _ => Err(Error::FormattingError),
         }
     }
@@ -240,6 +258,7 @@ impl Value {
             ListString(_) => String::from("List[String]"),
             Unit => String::from("Unit"),
             UInt64(_) => String::from("UInt64"),
+            ContractPackage(_) => String::from("ContractPackage"),
         }
     }
 }
@@ -278,6 +297,7 @@ from_try_from_impl!(String, String);
 from_try_from_impl!(key::Key, Key);
 from_try_from_impl!(account::Account, Account);
 from_try_from_impl!(contract::Contract, Contract);
+from_try_from_impl!(contract_package::ContractPackage, ContractPackage);
 
 impl From<URef> for Value {
     fn from(uref: URef) -> Self {
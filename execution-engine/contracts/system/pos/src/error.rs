@@ -1,79 +1,51 @@
 use core::result;
 
 use cl_std::contract_api;
+use cl_std::contract_api::error::ApiError;
 
 #[derive(Debug, PartialEq)]
 // TODO: Split this up into user errors vs. system errors.
+#[repr(u8)]
 pub enum Error {
-    NotBonded,
-    TooManyEventsInQueue,
-    CannotUnbondLastValidator,
-    SpreadTooHigh,
+    NotBonded = 0,
+    TooManyEventsInQueue = 1,
+    CannotUnbondLastValidator = 2,
+    SpreadTooHigh = 3,
     /// Returned when there is another QueueEntry in a Queue, for validator making a request.
-    MultipleRequests,
-    BondTooLarge,
-    UnbondTooLarge,
-    BondTransferFailed,
-    UnbondTransferFailed,
+    MultipleRequests = 4,
+    BondTooLarge = 5,
+    UnbondTooLarge = 6,
+    BondTransferFailed = 7,
+    UnbondTransferFailed = 8,
     // System errors
-    TimeWentBackwards,
-    StakesNotFound,
-    PaymentPurseNotFound,
-    PaymentPurseKeyUnexpectedType,
-    PaymentPurseBalanceNotFound,
-    BondingPurseNotFound,
-    BondingPurseKeyUnexpectedType,
-    RefundPurseKeyUnexpectedType,
-    RewardsPurseNotFound,
-    RewardsPurseKeyUnexpectedType,
+    TimeWentBackwards = 9,
+    StakesNotFound = 10,
+    PaymentPurseNotFound = 11,
+    PaymentPurseKeyUnexpectedType = 12,
+    PaymentPurseBalanceNotFound = 13,
+    BondingPurseNotFound = 14,
+    BondingPurseKeyUnexpectedType = 15,
+    RefundPurseKeyUnexpectedType = 16,
+    RewardsPurseNotFound = 17,
+    RewardsPurseKeyUnexpectedType = 18,
     // TODO: Put these in their own enum, and wrap them separately in `BondingError` and
     // `UnbondingError`.
-    QueueNotStoredAsByteArray,
-    QueueDeserializationFailed,
-    QueueDeserializationExtraBytes,
-    StakesKeyDeserializationFailed,
-    StakesDeserializationFailed,
-    SystemFunctionCalledByUserAccount,
-    InsufficientPaymentForAmountSpent,
-    FailedTransferToRewardsPurse,
-    FailedTransferToAccountPurse,
+    QueueNotStoredAsByteArray = 19,
+    QueueDeserializationFailed = 20,
+    QueueDeserializationExtraBytes = 21,
+    StakesKeyDeserializationFailed = 22,
+    StakesDeserializationFailed = 23,
+    SystemFunctionCalledByUserAccount = 24,
+    InsufficientPaymentForAmountSpent = 25,
+    FailedTransferToRewardsPurse = 26,
+    FailedTransferToAccountPurse = 27,
 }
 
 pub type Result<T> = result::Result<T, Error>;
 
-impl Into<u32> for Error {
-    fn into(self) -> u32 {
-        match self {
-            Error::NotBonded => 0,
-            Error::TooManyEventsInQueue => 1,
-            Error::CannotUnbondLastValidator => 2,
-            Error::SpreadTooHigh => 3,
-            Error::MultipleRequests => 4,
-            Error::BondTooLarge => 5,
-            Error::UnbondTooLarge => 6,
-            Error::BondTransferFailed => 7,
-            Error::UnbondTransferFailed => 8,
-            // System errors
-            Error::TimeWentBackwards => 256, // 0x100
-            Error::StakesNotFound => 257,
-            Error::PaymentPurseNotFound => 258,
-            Error::PaymentPurseKeyUnexpectedType => 259,
-            Error::PaymentPurseBalanceNotFound => 260,
-            Error::BondingPurseNotFound => 261,
-            Error::BondingPurseKeyUnexpectedType => 262,
-            Error::RefundPurseKeyUnexpectedType => 263,
-            Error::RewardsPurseNotFound => 264,
-            Error::RewardsPurseKeyUnexpectedType => 265,
-            Error::QueueNotStoredAsByteArray => 512, // 0x200
-            Error::QueueDeserializationFailed => 513,
-            Error::QueueDeserializationExtraBytes => 514,
-            Error::StakesKeyDeserializationFailed => 768, // 0x300
-            Error::StakesDeserializationFailed => 769,
-            Error::SystemFunctionCalledByUserAccount => 1024, // 0x400
-            Error::InsufficientPaymentForAmountSpent => 1025,
-            Error::FailedTransferToRewardsPurse => 1026,
-            Error::FailedTransferToAccountPurse => 1027,
-        }
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::ProofOfStake(error as u8)
     }
 }
 
@@ -83,7 +55,7 @@ pub trait ResultExt<T> {
 
 impl<T> ResultExt<T> for Result<T> {
     fn unwrap_or_revert(self) -> T {
-        self.unwrap_or_else(|err| contract_api::revert(err.into()))
+        self.unwrap_or_else(|err| contract_api::revert_with_error(err))
     }
 }
 
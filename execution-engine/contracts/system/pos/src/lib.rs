@@ -169,7 +169,7 @@ fn get_refund_purse() -> Option<PurseId> {
         Ok(purse_id) => Some(purse_id),
         Err(PurseLookupError::KeyNotFound) => None,
         Err(PurseLookupError::KeyUnexpectedType) => {
-            contract_api::revert(Error::RefundPurseKeyUnexpectedType.into())
+            contract_api::revert_with_error(Error::RefundPurseKeyUnexpectedType)
         }
     }
 }
@@ -183,14 +183,14 @@ fn get_refund_purse() -> Option<PurseId> {
 fn finalize_payment(amount_spent: U512, account: PublicKey) {
     let caller = contract_api::get_caller();
     if caller.value() != SYSTEM_ACCOUNT {
-        contract_api::revert(Error::SystemFunctionCalledByUserAccount.into());
+        contract_api::revert_with_error(Error::SystemFunctionCalledByUserAccount);
     }
 
     let payment_purse = get_payment_purse().unwrap_or_revert();
     let total = contract_api::get_balance(payment_purse)
-        .unwrap_or_else(|| contract_api::revert(Error::PaymentPurseBalanceNotFound.into()));
+        .unwrap_or_else(|| contract_api::revert_with_error(Error::PaymentPurseBalanceNotFound));
     if total < amount_spent {
-        contract_api::revert(Error::InsufficientPaymentForAmountSpent.into());
+        contract_api::revert_with_error(Error::InsufficientPaymentForAmountSpent);
     }
     let refund_amount = total - amount_spent;
 
@@ -202,7 +202,7 @@ fn finalize_payment(amount_spent: U512, account: PublicKey) {
     if let contract_api::PurseTransferResult::TransferError =
         contract_api::transfer_from_purse_to_purse(payment_purse, rewards_purse, amount_spent)
     {
-        contract_api::revert(Error::FailedTransferToRewardsPurse.into());
+        contract_api::revert_with_error(Error::FailedTransferToRewardsPurse);
     }
 
     // give refund
@@ -224,7 +224,7 @@ fn refund_to_account(payment_purse: PurseId, account: PublicKey, amount: U512) {
     if let contract_api::TransferResult::TransferError =
         contract_api::transfer_from_purse_to_account(payment_purse, account, amount)
     {
-        contract_api::revert(Error::FailedTransferToAccountPurse.into());
+        contract_api::revert_with_error(Error::FailedTransferToAccountPurse);
     }
 }
 
@@ -246,7 +246,7 @@ pub extern "C" fn call() {
             if contract_api::PurseTransferResult::TransferError
                 == contract_api::transfer_from_purse_to_purse(source, pos_purse, amount)
             {
-                contract_api::revert(Error::BondTransferFailed.into());
+                contract_api::revert_with_error(Error::BondTransferFailed);
             }
             bond::<QueueLocal, ContractStakes>(amount, validator, timestamp).unwrap_or_revert();
 
@@ -277,7 +277,7 @@ pub extern "C" fn call() {
                         entry.amount,
                     )
                 {
-                    contract_api::revert(Error::UnbondTransferFailed.into());
+                    contract_api::revert_with_error(Error::UnbondTransferFailed);
                 }
             }
         }
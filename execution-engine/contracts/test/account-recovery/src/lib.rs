@@ -0,0 +1,49 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api;
+use contract_ffi::value::account::{BlockTime, PublicKey, Weight};
+
+const RECOVERY_KEY: [u8; 32] = [2; 32];
+const NEW_KEY: [u8; 32] = [3; 32];
+const INACTIVITY_PERIOD_LIMIT: u64 = 1000;
+
+#[repr(u32)]
+enum Error {
+    UnknownCommand = 1,
+    AddRecoveryKey = 100,
+    SetRecoveryThreshold = 101,
+    SetInactivityPeriodLimit = 102,
+    AddAssociatedKey = 200,
+    RemoveAssociatedKey = 201,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = contract_api::get_arg(0);
+    match command.as_str() {
+        // Has to be executed with the account's own key.
+        "set-recovery" => {
+            contract_api::add_recovery_key(PublicKey::new(RECOVERY_KEY), Weight::new(1))
+                .unwrap_or_else(|_| contract_api::revert(Error::AddRecoveryKey as u32));
+            contract_api::set_recovery_threshold(Weight::new(1))
+                .unwrap_or_else(|_| contract_api::revert(Error::SetRecoveryThreshold as u32));
+            contract_api::set_inactivity_period_limit(BlockTime(INACTIVITY_PERIOD_LIMIT))
+                .unwrap_or_else(|_| contract_api::revert(Error::SetInactivityPeriodLimit as u32));
+        }
+        // Has to be executed with the recovery key; replaces the account's key with a new one.
+        "recover" => {
+            let old_key: PublicKey = contract_api::get_arg(1);
+            contract_api::add_associated_key(PublicKey::new(NEW_KEY), Weight::new(1))
+                .unwrap_or_else(|_| contract_api::revert(Error::AddAssociatedKey as u32));
+            contract_api::remove_associated_key(old_key)
+                .unwrap_or_else(|_| contract_api::revert(Error::RemoveAssociatedKey as u32));
+        }
+        _ => contract_api::revert(Error::UnknownCommand as u32),
+    }
+}
@@ -0,0 +1,56 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::{self, TransferResult};
+use contract_ffi::value::account::{ActionType, PublicKey, Weight};
+use contract_ffi::value::U512;
+
+const SECOND_KEY: [u8; 32] = [43; 32];
+const TRANSFER_TARGET: [u8; 32] = [44; 32];
+const NAMED_THRESHOLD: &str = "withdraw";
+
+#[repr(u32)]
+enum Error {
+    UnknownCommand = 1,
+    AddAssociatedKey = 100,
+    SetTransferThreshold = 101,
+    SetNamedThreshold = 102,
+    Transfer = 200,
+    NamedThresholdNotMet = 300,
+    NamedThresholdNotFound = 301,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = contract_api::get_arg(0);
+    match command.as_str() {
+        // Has to be executed with the account's own key.
+        "init" => {
+            contract_api::add_associated_key(PublicKey::new(SECOND_KEY), Weight::new(1))
+                .unwrap_or_else(|_| contract_api::revert(Error::AddAssociatedKey as u32));
+            contract_api::set_action_threshold(ActionType::Transfer, Weight::new(2))
+                .unwrap_or_else(|_| contract_api::revert(Error::SetTransferThreshold as u32));
+            contract_api::set_named_threshold(NAMED_THRESHOLD, Weight::new(2))
+                .unwrap_or_else(|_| contract_api::revert(Error::SetNamedThreshold as u32));
+        }
+        "transfer" => {
+            let amount: U512 = contract_api::get_arg(1);
+            match contract_api::transfer_to_account(PublicKey::new(TRANSFER_TARGET), amount) {
+                TransferResult::TransferredToNewAccount
+                | TransferResult::TransferredToExistingAccount => {}
+                TransferResult::TransferError => contract_api::revert(Error::Transfer as u32),
+            }
+        }
+        "check-named" => match contract_api::meets_named_threshold(NAMED_THRESHOLD) {
+            Some(true) => {}
+            Some(false) => contract_api::revert(Error::NamedThresholdNotMet as u32),
+            None => contract_api::revert(Error::NamedThresholdNotFound as u32),
+        },
+        _ => contract_api::revert(Error::UnknownCommand as u32),
+    }
+}
@@ -0,0 +1,42 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::pointers::ContractPointer;
+use contract_ffi::key::Key;
+
+#[repr(u32)]
+enum Error {
+    MissingContract = 1,
+}
+
+/// Calls itself until `remaining` reaches zero.
+#[no_mangle]
+pub extern "C" fn recurse() {
+    let remaining: u64 = contract_api::get_arg(0);
+    if remaining == 0 {
+        return;
+    }
+    let this = match contract_api::get_call_stack()
+        .last()
+        .map(|element| element.key())
+    {
+        Some(Key::Hash(hash)) => ContractPointer::Hash(hash),
+        _ => contract_api::revert(Error::MissingContract as u32),
+    };
+    contract_api::call_contract::<_, ()>(this, &(remaining - 1,), &Vec::new());
+}
+
+/// Runs `frames` nested calls of the `recurse` contract.
+#[no_mangle]
+pub extern "C" fn call() {
+    let frames: u64 = contract_api::get_arg(0);
+    let pointer = contract_api::store_function("recurse", BTreeMap::new());
+    contract_api::call_contract::<_, ()>(pointer, &(frames - 1,), &Vec::new());
+}
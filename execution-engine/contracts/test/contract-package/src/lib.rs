@@ -0,0 +1,108 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+use contract_ffi::key::Key;
+use contract_ffi::uref::{AccessRights, URef};
+use contract_ffi::value::contract_package::ContractPackageFailure;
+
+const PACKAGE_NAME: &str = "contract_package";
+const PACKAGE_ACCESS_NAME: &str = "contract_package_access";
+const CALL_RESULT_NAME: &str = "call_result";
+
+#[repr(u32)]
+enum Error {
+    UnknownCommand = 1,
+    MissingPackage = 2,
+    MissingAccessKey = 3,
+    AddVersion = 4,
+    DisableVersion = 5,
+    UnexpectedAccess = 6,
+}
+
+#[no_mangle]
+pub extern "C" fn version_1() {
+    contract_api::ret(&1u64, &Vec::new())
+}
+
+#[no_mangle]
+pub extern "C" fn version_2() {
+    contract_api::ret(&2u64, &Vec::new())
+}
+
+fn package_hash() -> [u8; 32] {
+    match contract_api::get_uref(PACKAGE_NAME) {
+        Some(Key::Hash(hash)) => hash,
+        _ => contract_api::revert(Error::MissingPackage as u32),
+    }
+}
+
+fn access_key() -> URef {
+    match contract_api::get_uref(PACKAGE_ACCESS_NAME) {
+        Some(Key::URef(uref)) => uref,
+        _ => contract_api::revert(Error::MissingAccessKey as u32),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = contract_api::get_arg(0);
+    match command.as_str() {
+        "create" => {
+            let (package_hash, access_key) = contract_api::create_contract_package();
+            contract_api::add_contract_version(
+                package_hash,
+                access_key,
+                "version_1",
+                BTreeMap::new(),
+            )
+            .unwrap_or_else(|_| contract_api::revert(Error::AddVersion as u32));
+            contract_api::add_uref(PACKAGE_NAME, &Key::Hash(package_hash));
+            contract_api::add_uref(PACKAGE_ACCESS_NAME, &Key::URef(access_key));
+        }
+        "add" => {
+            contract_api::add_contract_version(
+                package_hash(),
+                access_key(),
+                "version_2",
+                BTreeMap::new(),
+            )
+            .unwrap_or_else(|_| contract_api::revert(Error::AddVersion as u32));
+        }
+        "add_read_only" => {
+            // Read access to the access key is not enough to modify the package.
+            let read_only_key = URef::new(access_key().addr(), AccessRights::READ);
+            match contract_api::add_contract_version(
+                package_hash(),
+                read_only_key,
+                "version_2",
+                BTreeMap::new(),
+            ) {
+                Err(ContractPackageFailure::PermissionDenied) => {}
+                _ => contract_api::revert(Error::UnexpectedAccess as u32),
+            }
+        }
+        "disable" => {
+            let version: u32 = contract_api::get_arg(1);
+            contract_api::disable_contract_version(package_hash(), access_key(), version)
+                .unwrap_or_else(|_| contract_api::revert(Error::DisableVersion as u32));
+        }
+        "call" => {
+            // Version `0` calls the latest enabled version.
+            let version: u32 = contract_api::get_arg(1);
+            let version = if version == 0 { None } else { Some(version) };
+            let result: u64 =
+                contract_api::call_versioned_contract(package_hash(), version, &(), &Vec::new());
+            let result_key: Key = contract_api::new_uref(result).into();
+            contract_api::add_uref(CALL_RESULT_NAME, &result_key);
+        }
+        _ => contract_api::revert(Error::UnknownCommand as u32),
+    }
+}
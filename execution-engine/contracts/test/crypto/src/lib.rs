@@ -0,0 +1,49 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::error::ApiError;
+use contract_ffi::key::Key;
+use contract_ffi::value::account::PublicKey;
+
+const HASH_RESULT_NAME: &str = "hash_result";
+
+#[repr(u16)]
+enum Error {
+    UnknownCommand = 1,
+    InvalidSignature = 2,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = contract_api::get_arg(0);
+    match command.as_str() {
+        "hash" => {
+            let data: Vec<u8> = contract_api::get_arg(1);
+            let digest = contract_api::blake2b(&data);
+            let result_key: Key = contract_api::new_uref(digest.to_vec()).into();
+            contract_api::add_uref(HASH_RESULT_NAME, &result_key);
+        }
+        "verify" => {
+            let public_key: PublicKey = contract_api::get_arg(1);
+            let message: Vec<u8> = contract_api::get_arg(2);
+            let signature: Vec<u8> = contract_api::get_arg(3);
+            if !contract_api::verify_ed25519(public_key, &message, &signature) {
+                contract_api::revert_with_error(Error::InvalidSignature)
+            }
+        }
+        _ => contract_api::revert_with_error(Error::UnknownCommand),
+    }
+}
@@ -0,0 +1,106 @@
+#![no_std]
+#![feature(alloc)]
+
+#[macro_use]
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::pointers::ContractPointer;
+use contract_ffi::key::Key;
+use contract_ffi::value::contract::{ArgType, EntryPoint};
+
+const CONTRACT_NAME: &str = "entry_points_contract";
+const CALL_RESULT_NAME: &str = "call_result";
+
+#[repr(u32)]
+enum Error {
+    UnknownCommand = 1,
+    MissingContract = 2,
+}
+
+#[no_mangle]
+pub extern "C" fn get_answer() {
+    contract_api::ret(&42u64, &Vec::new())
+}
+
+#[no_mangle]
+pub extern "C" fn sum() {
+    let a: u64 = contract_api::get_arg(0);
+    let b: u64 = contract_api::get_arg(1);
+    contract_api::ret(&(a + b), &Vec::new())
+}
+
+fn contract_pointer() -> ContractPointer {
+    match contract_api::get_uref(CONTRACT_NAME) {
+        Some(Key::Hash(hash)) => ContractPointer::Hash(hash),
+        _ => contract_api::revert(Error::MissingContract as u32),
+    }
+}
+
+fn store_call_result(result: u64) {
+    let result_key: Key = contract_api::new_uref(result).into();
+    contract_api::add_uref(CALL_RESULT_NAME, &result_key);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = contract_api::get_arg(0);
+    match command.as_str() {
+        "store" => {
+            let mut entry_points = BTreeMap::new();
+            entry_points.insert(String::from("get_answer"), EntryPoint::new(Vec::new()));
+            entry_points.insert(
+                String::from("sum"),
+                EntryPoint::new(vec![ArgType::UInt64, ArgType::UInt64]),
+            );
+            let pointer = contract_api::store_entry_points(entry_points, BTreeMap::new());
+            contract_api::add_uref(CONTRACT_NAME, &pointer.into());
+        }
+        "call_get_answer" => {
+            let result: u64 = contract_api::call_contract_entry_point(
+                contract_pointer(),
+                "get_answer",
+                &(),
+                &Vec::new(),
+            );
+            store_call_result(result);
+        }
+        "call_sum" => {
+            let result: u64 = contract_api::call_contract_entry_point(
+                contract_pointer(),
+                "sum",
+                &(2u64, 3u64),
+                &Vec::new(),
+            );
+            store_call_result(result);
+        }
+        "call_unknown" => {
+            let result: u64 = contract_api::call_contract_entry_point(
+                contract_pointer(),
+                "unknown",
+                &(),
+                &Vec::new(),
+            );
+            store_call_result(result);
+        }
+        "call_default" => {
+            let result: u64 = contract_api::call_contract(contract_pointer(), &(), &Vec::new());
+            store_call_result(result);
+        }
+        "call_sum_mismatched" => {
+            let result: u64 = contract_api::call_contract_entry_point(
+                contract_pointer(),
+                "sum",
+                &(String::from("2"), 3u64),
+                &Vec::new(),
+            );
+            store_call_result(result);
+        }
+        _ => contract_api::revert(Error::UnknownCommand as u32),
+    }
+}
@@ -0,0 +1,31 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use contract_ffi::contract_api;
+use contract_ffi::value::account::PublicKey;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let known_block_height: u64 = contract_api::get_arg(0);
+    let known_parent_hash: [u8; 32] = contract_api::get_arg(1);
+    let known_proposer: PublicKey = contract_api::get_arg(2);
+
+    assert_eq!(
+        contract_api::get_block_height(),
+        known_block_height,
+        "actual block height not known block height"
+    );
+    assert_eq!(
+        contract_api::get_parent_hash(),
+        known_parent_hash,
+        "actual parent hash not known parent hash"
+    );
+    assert_eq!(
+        contract_api::get_proposer(),
+        known_proposer,
+        "actual proposer not known proposer"
+    );
+}
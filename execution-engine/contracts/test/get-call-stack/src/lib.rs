@@ -0,0 +1,110 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::pointers::ContractPointer;
+use contract_ffi::key::Key;
+use contract_ffi::value::contract::EntryPoint;
+
+const CONTRACT_NAME: &str = "call_stack_contract";
+const CALL_RESULT_NAME: &str = "call_result";
+
+#[repr(u32)]
+enum Error {
+    UnknownCommand = 1,
+    MissingContract = 2,
+    MissingCaller = 3,
+    UnexpectedCaller = 4,
+}
+
+fn immediate_caller_key() -> Key {
+    match contract_api::get_immediate_caller() {
+        Some(caller) => caller.key(),
+        None => contract_api::revert(Error::MissingCaller as u32),
+    }
+}
+
+/// Returns the key of the account or contract which called this entry point.
+#[no_mangle]
+pub extern "C" fn caller_key() {
+    contract_api::ret(&immediate_caller_key(), &Vec::new())
+}
+
+/// Returns the depth of the call stack.
+#[no_mangle]
+pub extern "C" fn depth() {
+    let depth = contract_api::get_call_stack().len() as u64;
+    contract_api::ret(&depth, &Vec::new())
+}
+
+/// Calls the `caller_key` entry point of this very contract and returns its result.
+#[no_mangle]
+pub extern "C" fn forward() {
+    let this = match contract_api::get_call_stack()
+        .last()
+        .map(|element| element.key())
+    {
+        Some(Key::Hash(hash)) => ContractPointer::Hash(hash),
+        _ => contract_api::revert(Error::MissingContract as u32),
+    };
+    let result: Key = contract_api::call_contract_entry_point(this, "caller_key", &(), &Vec::new());
+    contract_api::ret(&result, &Vec::new())
+}
+
+fn contract_pointer() -> ContractPointer {
+    match contract_api::get_uref(CONTRACT_NAME) {
+        Some(Key::Hash(hash)) => ContractPointer::Hash(hash),
+        _ => contract_api::revert(Error::MissingContract as u32),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = contract_api::get_arg(0);
+    match command.as_str() {
+        "store" => {
+            let mut entry_points = BTreeMap::new();
+            for name in &["caller_key", "depth", "forward"] {
+                entry_points.insert(String::from(*name), EntryPoint::new(Vec::new()));
+            }
+            let pointer = contract_api::store_entry_points(entry_points, BTreeMap::new());
+            contract_api::add_uref(CONTRACT_NAME, &pointer.into());
+        }
+        "session" => {
+            if contract_api::get_immediate_caller().is_some() {
+                contract_api::revert(Error::UnexpectedCaller as u32)
+            }
+            let depth = contract_api::get_call_stack().len() as u64;
+            let result_key: Key = contract_api::new_uref(depth).into();
+            contract_api::add_uref(CALL_RESULT_NAME, &result_key);
+        }
+        "caller_key" | "forward" => {
+            let result: Key = contract_api::call_contract_entry_point(
+                contract_pointer(),
+                &command,
+                &(),
+                &Vec::new(),
+            );
+            let result_key: Key = contract_api::new_uref(result).into();
+            contract_api::add_uref(CALL_RESULT_NAME, &result_key);
+        }
+        "depth" => {
+            let result: u64 = contract_api::call_contract_entry_point(
+                contract_pointer(),
+                &command,
+                &(),
+                &Vec::new(),
+            );
+            let result_key: Key = contract_api::new_uref(result).into();
+            contract_api::add_uref(CALL_RESULT_NAME, &result_key);
+        }
+        _ => contract_api::revert(Error::UnknownCommand as u32),
+    }
+}
@@ -0,0 +1,69 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api;
+use contract_ffi::value::account::{
+    ActionType, ApprovalStatus, BlockTime, ProposedAction, PublicKey, Weight,
+};
+
+const SECOND_KEY: [u8; 32] = [43; 32];
+const NEW_KEY: [u8; 32] = [44; 32];
+
+#[repr(u32)]
+enum Error {
+    UnknownCommand = 1,
+    AddAssociatedKey = 100,
+    SetKeyManagementThreshold = 101,
+    ProposeAction = 200,
+    ActionNotPending = 201,
+    ApproveAction = 300,
+    ActionNotExecuted = 301,
+    CancelAction = 400,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = contract_api::get_arg(0);
+    match command.as_str() {
+        // Has to be executed with the account's own key.
+        "init" => {
+            contract_api::add_associated_key(PublicKey::new(SECOND_KEY), Weight::new(1))
+                .unwrap_or_else(|_| contract_api::revert(Error::AddAssociatedKey as u32));
+            contract_api::set_action_threshold(ActionType::KeyManagement, Weight::new(2))
+                .unwrap_or_else(|_| contract_api::revert(Error::SetKeyManagementThreshold as u32));
+        }
+        // Proposes to add a new key, which a single key can't approve on its own.
+        "propose" => {
+            let expires_at: u64 = contract_api::get_arg(2);
+            let action = ProposedAction::AddAssociatedKey(PublicKey::new(NEW_KEY), Weight::new(1));
+            match contract_api::propose_action(action, BlockTime(expires_at)) {
+                Ok((ApprovalStatus::Pending, _)) => {}
+                Ok((ApprovalStatus::Executed, _)) => {
+                    contract_api::revert(Error::ActionNotPending as u32)
+                }
+                Err(_) => contract_api::revert(Error::ProposeAction as u32),
+            }
+        }
+        "approve" => {
+            let id: [u8; 32] = contract_api::get_arg(1);
+            match contract_api::approve_action(id) {
+                Ok(ApprovalStatus::Executed) => {}
+                Ok(ApprovalStatus::Pending) => {
+                    contract_api::revert(Error::ActionNotExecuted as u32)
+                }
+                Err(_) => contract_api::revert(Error::ApproveAction as u32),
+            }
+        }
+        "cancel" => {
+            let id: [u8; 32] = contract_api::get_arg(1);
+            contract_api::cancel_action(id)
+                .unwrap_or_else(|_| contract_api::revert(Error::CancelAction as u32));
+        }
+        _ => contract_api::revert(Error::UnknownCommand as u32),
+    }
+}
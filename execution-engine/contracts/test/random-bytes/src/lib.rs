@@ -0,0 +1,65 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::pointers::ContractPointer;
+use contract_ffi::key::Key;
+use contract_ffi::value::U512;
+
+const RANDOM_BYTES_LENGTH: usize = 32;
+
+#[repr(u32)]
+enum Error {
+    UnknownCommand = 1,
+}
+
+fn put_bytes(name: &str, bytes: Vec<u8>) {
+    let key: Key = contract_api::new_uref(bytes).into();
+    contract_api::add_uref(name, &key);
+}
+
+fn put_uref(name: &str) {
+    let key: Key = contract_api::new_uref(U512::from(0)).into();
+    contract_api::add_uref(name, &key);
+}
+
+/// Returns freshly drawn random bytes to the caller.
+#[no_mangle]
+pub extern "C" fn draw() {
+    let bytes = contract_api::random_bytes(RANDOM_BYTES_LENGTH);
+    contract_api::ret(&bytes, &Vec::new())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = contract_api::get_arg(0);
+    match command.as_str() {
+        // Draws twice in session code, then creates a URef.
+        "local" => {
+            let bytes1 = contract_api::random_bytes(RANDOM_BYTES_LENGTH);
+            let bytes2 = contract_api::random_bytes(RANDOM_BYTES_LENGTH);
+            put_uref("uref");
+            put_bytes("bytes1", bytes1);
+            put_bytes("bytes2", bytes2);
+        }
+        // Draws once in session code and once in a stored contract, then creates a URef.
+        "subcall" => {
+            let draw: ContractPointer = contract_api::store_function("draw", BTreeMap::new());
+            let bytes1 = contract_api::random_bytes(RANDOM_BYTES_LENGTH);
+            let bytes2: Vec<u8> = contract_api::call_contract(draw, &(), &Vec::new());
+            put_uref("uref");
+            put_bytes("bytes1", bytes1);
+            put_bytes("bytes2", bytes2);
+        }
+        // Creates a URef without drawing any random bytes.
+        "no_draw" => put_uref("uref"),
+        _ => contract_api::revert(Error::UnknownCommand as u32),
+    }
+}
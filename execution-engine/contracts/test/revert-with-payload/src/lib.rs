@@ -0,0 +1,16 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::error::ApiError;
+
+const USER_ERROR: u16 = 7;
+const PAYLOAD: [u8; 3] = [1, 2, 3];
+
+#[no_mangle]
+pub extern "C" fn call() {
+    contract_api::revert_with_payload(ApiError::User(USER_ERROR), &PAYLOAD)
+}
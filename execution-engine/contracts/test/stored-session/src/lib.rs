@@ -0,0 +1,29 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+
+use contract_ffi::contract_api;
+use contract_ffi::key::Key;
+
+const STORED_SESSION_NAME: &str = "stored_session";
+const STORED_SESSION_MARKER: &str = "stored_session_marker";
+
+#[no_mangle]
+pub extern "C" fn stored_session() {
+    // Executed in the context of the deploying account, so the marker ends up in its known urefs.
+    let marker: String = contract_api::get_arg(0);
+    let marker_key: Key = contract_api::new_uref(marker).into();
+    contract_api::add_uref(STORED_SESSION_MARKER, &marker_key);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stored_session_key: Key =
+        contract_api::store_function(STORED_SESSION_NAME, BTreeMap::new()).into();
+    contract_api::add_uref(STORED_SESSION_NAME, &stored_session_key);
+}
@@ -0,0 +1,118 @@
+#![no_std]
+#![feature(alloc)]
+
+#[macro_use]
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::error::{ApiError, CallError};
+use contract_ffi::contract_api::pointers::{ContractPointer, UPointer};
+use contract_ffi::key::Key;
+use contract_ffi::uref::URef;
+use contract_ffi::value::contract::{ArgType, EntryPoint};
+
+const CONTRACT_NAME: &str = "try_call_contract";
+const COUNTER_NAME: &str = "counter";
+const CALL_RESULT_NAME: &str = "call_result";
+
+const USER_ERROR: u16 = 7;
+const PAYLOAD: [u8; 3] = [1, 2, 3];
+
+#[repr(u32)]
+enum Error {
+    MissingContract = 1,
+    MissingCounter = 2,
+}
+
+/// Increments the counter passed as the argument, then returns successfully.
+#[no_mangle]
+pub extern "C" fn increment_and_return() {
+    increment(contract_api::get_arg(0));
+    contract_api::ret(&42u64, &Vec::new())
+}
+
+/// Increments the counter passed as the argument, then reverts.
+#[no_mangle]
+pub extern "C" fn increment_and_revert() {
+    increment(contract_api::get_arg(0));
+    contract_api::revert_with_payload(ApiError::User(USER_ERROR), &PAYLOAD)
+}
+
+/// Increments the counter passed as the argument, then traps.
+#[no_mangle]
+pub extern "C" fn increment_and_trap() {
+    increment(contract_api::get_arg(0));
+    panic!("trap")
+}
+
+fn increment(counter: URef) {
+    let pointer: UPointer<u64> = UPointer::from_uref(counter).unwrap();
+    let value = contract_api::read(pointer);
+    contract_api::write(pointer, value + 1);
+}
+
+fn contract_pointer() -> ContractPointer {
+    match contract_api::get_uref(CONTRACT_NAME) {
+        Some(Key::Hash(hash)) => ContractPointer::Hash(hash),
+        _ => contract_api::revert(Error::MissingContract as u32),
+    }
+}
+
+fn counter() -> URef {
+    match contract_api::get_uref(COUNTER_NAME) {
+        Some(Key::URef(uref)) => uref,
+        _ => contract_api::revert(Error::MissingCounter as u32),
+    }
+}
+
+/// Stores the outcome of the call as a `u64`: the returned value on success, the exit code of
+/// a revert (only if its payload was passed along), or `0` on a trap.
+fn store_call_result(result: Result<u64, CallError>) {
+    let result = match result {
+        Ok(value) => value,
+        Err(CallError::Revert(error, ref payload)) if payload.as_slice() == PAYLOAD => {
+            u64::from(u32::from(error))
+        }
+        Err(CallError::Revert(..)) => u64::max_value(),
+        Err(CallError::Trap) => 0,
+    };
+    let result_key: Key = contract_api::new_uref(result).into();
+    contract_api::add_uref(CALL_RESULT_NAME, &result_key);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = contract_api::get_arg(0);
+    match command.as_str() {
+        "store" => {
+            let mut entry_points = BTreeMap::new();
+            for name in &[
+                "increment_and_return",
+                "increment_and_revert",
+                "increment_and_trap",
+            ] {
+                entry_points.insert(String::from(*name), EntryPoint::new(vec![ArgType::URef]));
+            }
+            let pointer = contract_api::store_entry_points(entry_points, BTreeMap::new());
+            contract_api::add_uref(CONTRACT_NAME, &pointer.into());
+
+            let counter_key: Key = contract_api::new_uref(0u64).into();
+            contract_api::add_uref(COUNTER_NAME, &counter_key);
+        }
+        entry_point => {
+            let counter = counter();
+            let result = contract_api::try_call_contract(
+                contract_pointer(),
+                entry_point,
+                &(counter,),
+                &vec![Key::URef(counter)],
+            );
+            store_call_result(result);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+#![no_std]
+#![feature(alloc)]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use contract_ffi::contract_api;
+use contract_ffi::key::Key;
+
+const MIGRATED_KEY: [u8; 32] = [77u8; 32];
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // Records the PoS public URef under a local key of the system account, as a stand-in for a
+    // migration of system contract data.
+    let pos_public_uref: Key =
+        contract_api::get_uref("pos").unwrap_or_else(|| contract_api::revert(1));
+    if !contract_api::has_uref("mint") {
+        contract_api::revert(2);
+    }
+    contract_api::write_local(MIGRATED_KEY, pos_public_uref);
+}
@@ -0,0 +1,207 @@
+use ed25519_dalek;
+use secp256k1;
+
+use contract_ffi::value::account::{SignatureAlgorithm, TaggedPublicKey};
+
+use super::deploy_header::DEPLOY_HASH_LENGTH;
+
+const SECP256K1_SIGNATURE_LENGTH: usize = 64;
+const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
+const SECP256K1_COMPRESSED_PUBLIC_KEY_LENGTH: usize = 33;
+const SECP256K1_FULL_PUBLIC_KEY_LENGTH: usize = 65;
+
+/// Parses the public key of the account of a deploy, as found in the deploy header, where the
+/// algorithm isn't given: 32 bytes keys are ed25519 keys, and 33 bytes compressed or 65 bytes
+/// uncompressed keys are secp256k1 keys.
+///
+/// Secp256k1 keys are compressed, which is how the engine addresses them.
+pub fn parse_account_public_key(bytes: &[u8]) -> Option<TaggedPublicKey> {
+    let format = match bytes.len() {
+        ED25519_PUBLIC_KEY_LENGTH => {
+            let mut key = [0u8; ED25519_PUBLIC_KEY_LENGTH];
+            key.copy_from_slice(bytes);
+            return Some(TaggedPublicKey::ed25519(key));
+        }
+        SECP256K1_COMPRESSED_PUBLIC_KEY_LENGTH => secp256k1::PublicKeyFormat::Compressed,
+        SECP256K1_FULL_PUBLIC_KEY_LENGTH => secp256k1::PublicKeyFormat::Full,
+        _ => return None,
+    };
+    let public_key = secp256k1::PublicKey::parse_slice(bytes, Some(format)).ok()?;
+    TaggedPublicKey::new(
+        SignatureAlgorithm::Secp256k1,
+        &public_key.serialize_compressed()[..],
+    )
+    .ok()
+}
+
+/// Signature of a deploy hash, along with the public key it was made with.
+///
+/// The addresses of the public keys of the approvals of a deploy are its authorization keys, so
+/// the signatures are checked before authorizing the deploy with them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Approval {
+    public_key: TaggedPublicKey,
+    signature: Vec<u8>,
+}
+
+impl Approval {
+    pub fn new(public_key: TaggedPublicKey, signature: Vec<u8>) -> Self {
+        Approval {
+            public_key,
+            signature,
+        }
+    }
+
+    pub fn public_key(&self) -> &TaggedPublicKey {
+        &self.public_key
+    }
+
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// Checks whether the signature is a valid signature of `deploy_hash` for the public key, using
+    /// the key's algorithm. Malformed public keys and signatures are invalid.
+    ///
+    /// Secp256k1 signatures are 64 bytes compact ECDSA signatures of the deploy hash itself.
+    pub fn is_valid_for(&self, deploy_hash: &[u8; DEPLOY_HASH_LENGTH]) -> bool {
+        match self.public_key.algorithm() {
+            SignatureAlgorithm::Ed25519 => self.is_valid_ed25519_signature(deploy_hash),
+            SignatureAlgorithm::Secp256k1 => self.is_valid_secp256k1_signature(deploy_hash),
+        }
+    }
+
+    fn is_valid_ed25519_signature(&self, deploy_hash: &[u8; DEPLOY_HASH_LENGTH]) -> bool {
+        let public_key = match ed25519_dalek::PublicKey::from_bytes(self.public_key.as_bytes()) {
+            Ok(public_key) => public_key,
+            Err(_) => return false,
+        };
+        let signature = match ed25519_dalek::Signature::from_bytes(&self.signature) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        public_key.verify(deploy_hash, &signature).is_ok()
+    }
+
+    fn is_valid_secp256k1_signature(&self, deploy_hash: &[u8; DEPLOY_HASH_LENGTH]) -> bool {
+        let public_key = match secp256k1::PublicKey::parse_slice(
+            self.public_key.as_bytes(),
+            Some(secp256k1::PublicKeyFormat::Compressed),
+        ) {
+            Ok(public_key) => public_key,
+            Err(_) => return false,
+        };
+        if self.signature.len() != SECP256K1_SIGNATURE_LENGTH {
+            return false;
+        }
+        let mut signature_bytes = [0u8; SECP256K1_SIGNATURE_LENGTH];
+        signature_bytes.copy_from_slice(&self.signature);
+        let signature = secp256k1::Signature::parse(&signature_bytes);
+        let message = secp256k1::Message::parse(deploy_hash);
+        secp256k1::verify(&message, &signature, &public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::value::account::{SignatureAlgorithm, TaggedPublicKey};
+
+    use super::{parse_account_public_key, Approval};
+
+    // Public key of the secret key of test vector 1 of RFC 8032, section 7.1, and its signature
+    // of `DEPLOY_HASH`.
+    const ED25519_PUBLIC_KEY: [u8; 32] = [
+        215, 90, 152, 1, 130, 177, 10, 183, 213, 75, 254, 211, 201, 100, 7, 58, 14, 225, 114, 243,
+        218, 166, 35, 37, 175, 2, 26, 104, 247, 7, 81, 26,
+    ];
+    const ED25519_SIGNATURE: [u8; 64] = [
+        72, 94, 187, 83, 82, 62, 252, 198, 243, 248, 232, 17, 76, 223, 224, 197, 101, 184, 118,
+        138, 130, 64, 103, 208, 122, 0, 240, 91, 255, 113, 109, 148, 189, 220, 111, 56, 189, 98,
+        228, 238, 171, 115, 29, 4, 128, 10, 48, 235, 129, 17, 223, 140, 136, 206, 192, 48, 166,
+        202, 93, 197, 177, 36, 227, 10,
+    ];
+    // Compressed public key of the secp256k1 secret key 1, and its signature of `DEPLOY_HASH`.
+    const SECP256K1_PUBLIC_KEY: [u8; 33] = [
+        2, 121, 190, 102, 126, 249, 220, 187, 172, 85, 160, 98, 149, 206, 135, 11, 7, 2, 155, 252,
+        219, 45, 206, 40, 217, 89, 242, 129, 91, 22, 248, 23, 152,
+    ];
+    const SECP256K1_SIGNATURE: [u8; 64] = [
+        182, 114, 215, 166, 207, 128, 168, 70, 166, 161, 69, 79, 131, 23, 46, 166, 90, 186, 61,
+        117, 31, 229, 28, 217, 156, 95, 138, 250, 206, 140, 22, 221, 95, 64, 34, 49, 3, 111, 180,
+        115, 73, 194, 151, 103, 194, 124, 16, 249, 232, 24, 103, 57, 146, 245, 40, 233, 205, 208,
+        51, 250, 12, 188, 247, 72,
+    ];
+    // Uncompressed form of `SECP256K1_PUBLIC_KEY`.
+    const SECP256K1_UNCOMPRESSED_PUBLIC_KEY: [u8; 65] = [
+        4, 121, 190, 102, 126, 249, 220, 187, 172, 85, 160, 98, 149, 206, 135, 11, 7, 2, 155, 252,
+        219, 45, 206, 40, 217, 89, 242, 129, 91, 22, 248, 23, 152, 72, 58, 218, 119, 38, 163, 196,
+        101, 93, 164, 251, 252, 14, 17, 8, 168, 253, 23, 180, 72, 166, 133, 84, 25, 156, 71, 208,
+        143, 251, 16, 212, 184,
+    ];
+    const DEPLOY_HASH: [u8; 32] = [1u8; 32];
+
+    fn ed25519_approval(signature: &[u8]) -> Approval {
+        Approval::new(
+            TaggedPublicKey::ed25519(ED25519_PUBLIC_KEY),
+            signature.to_vec(),
+        )
+    }
+
+    fn secp256k1_approval(signature: &[u8]) -> Approval {
+        let public_key =
+            TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &SECP256K1_PUBLIC_KEY[..])
+                .expect("should create key");
+        Approval::new(public_key, signature.to_vec())
+    }
+
+    #[test]
+    fn should_accept_signature_of_deploy_hash() {
+        assert!(ed25519_approval(&ED25519_SIGNATURE).is_valid_for(&DEPLOY_HASH));
+        assert!(secp256k1_approval(&SECP256K1_SIGNATURE).is_valid_for(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_reject_signature_of_other_deploy_hash() {
+        assert!(!ed25519_approval(&ED25519_SIGNATURE).is_valid_for(&[2u8; 32]));
+        assert!(!secp256k1_approval(&SECP256K1_SIGNATURE).is_valid_for(&[2u8; 32]));
+    }
+
+    #[test]
+    fn should_reject_signature_for_other_public_key() {
+        let approval = Approval::new(
+            TaggedPublicKey::ed25519([1u8; 32]),
+            ED25519_SIGNATURE.to_vec(),
+        );
+
+        assert!(!approval.is_valid_for(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_reject_signature_of_other_algorithm() {
+        assert!(!ed25519_approval(&SECP256K1_SIGNATURE).is_valid_for(&DEPLOY_HASH));
+        assert!(!secp256k1_approval(&ED25519_SIGNATURE).is_valid_for(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_reject_malformed_signature() {
+        assert!(!ed25519_approval(&ED25519_SIGNATURE[..10]).is_valid_for(&DEPLOY_HASH));
+        assert!(!secp256k1_approval(&SECP256K1_SIGNATURE[..10]).is_valid_for(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_parse_account_public_key_by_length() {
+        assert_eq!(
+            parse_account_public_key(&ED25519_PUBLIC_KEY),
+            Some(TaggedPublicKey::ed25519(ED25519_PUBLIC_KEY))
+        );
+        assert_eq!(
+            parse_account_public_key(&SECP256K1_PUBLIC_KEY),
+            TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &SECP256K1_PUBLIC_KEY[..]).ok()
+        );
+        assert_eq!(
+            parse_account_public_key(&SECP256K1_UNCOMPRESSED_PUBLIC_KEY),
+            TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &SECP256K1_PUBLIC_KEY[..]).ok()
+        );
+        assert_eq!(parse_account_public_key(&SECP256K1_PUBLIC_KEY[..20]), None);
+    }
+}
@@ -0,0 +1,174 @@
+use std::cmp;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::BlockTime;
+use engine_shared::newtypes::Blake2bHash;
+
+const EXECUTED_DEPLOY_SEED: &[u8] = b"executed_deploy";
+
+pub const DEPLOY_HASH_LENGTH: usize = 32;
+
+/// Longest time a deploy stays valid after its timestamp: a day, in milliseconds like the block
+/// time. Deploys without a TTL, or with a longer one, expire after it.
+pub const MAX_DEPLOY_TTL: u64 = 24 * 60 * 60 * 1000;
+
+/// Key under which the expiry of an executed deploy is stored as a `Value::UInt64`.
+///
+/// A deploy can't be executed while a value is stored under its key. Records are never removed,
+/// as global state has no deletion and the dependencies of later deploys are checked against them.
+/// Replay protection only needs a record until the deploy expires though, at most
+/// [`MAX_DEPLOY_TTL`] after its timestamp, so records of expired deploys could be pruned without
+/// allowing replays once dependencies are checked differently.
+pub fn executed_deploy_key(deploy_hash: [u8; DEPLOY_HASH_LENGTH]) -> Key {
+    let mut bytes = EXECUTED_DEPLOY_SEED.to_vec();
+    bytes.extend_from_slice(&deploy_hash);
+    Key::Hash(Blake2bHash::new(&bytes).into())
+}
+
+/// Replay protection and validity details of a deploy.
+///
+/// A deploy can be executed once, in a block whose time is at least `not_before` and at most
+/// `ttl`, capped at [`MAX_DEPLOY_TTL`], after its `timestamp`, and only once all the deploys it
+/// depends on were executed.
+/// Times are in the same unit as the block time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeployHeader {
+    deploy_hash: [u8; DEPLOY_HASH_LENGTH],
+    timestamp: BlockTime,
+    ttl: Option<u64>,
+    not_before: Option<BlockTime>,
+    dependencies: Vec<[u8; DEPLOY_HASH_LENGTH]>,
+}
+
+impl DeployHeader {
+    /// Creates a header of a deploy which expires after [`MAX_DEPLOY_TTL`] and has no other
+    /// constraint.
+    pub fn new(deploy_hash: [u8; DEPLOY_HASH_LENGTH], timestamp: BlockTime) -> Self {
+        DeployHeader {
+            deploy_hash,
+            timestamp,
+            ttl: None,
+            not_before: None,
+            dependencies: Vec::new(),
+        }
+    }
+
+    pub fn set_ttl(mut self, ttl: u64) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn set_not_before(mut self, not_before: BlockTime) -> Self {
+        self.not_before = Some(not_before);
+        self
+    }
+
+    pub fn set_dependencies(mut self, dependencies: Vec<[u8; DEPLOY_HASH_LENGTH]>) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    pub fn deploy_hash(&self) -> [u8; DEPLOY_HASH_LENGTH] {
+        self.deploy_hash
+    }
+
+    pub fn timestamp(&self) -> BlockTime {
+        self.timestamp
+    }
+
+    pub fn ttl(&self) -> Option<u64> {
+        self.ttl
+    }
+
+    pub fn not_before(&self) -> Option<BlockTime> {
+        self.not_before
+    }
+
+    /// Hashes of the deploys which have to be executed before this one.
+    pub fn dependencies(&self) -> &[[u8; DEPLOY_HASH_LENGTH]] {
+        &self.dependencies
+    }
+
+    /// Last block time at which the deploy can be executed.
+    pub fn expiry(&self) -> BlockTime {
+        let ttl = self
+            .ttl
+            .map_or(MAX_DEPLOY_TTL, |ttl| cmp::min(ttl, MAX_DEPLOY_TTL));
+        BlockTime(self.timestamp.0.saturating_add(ttl))
+    }
+
+    pub fn is_expired(&self, block_time: BlockTime) -> bool {
+        block_time > self.expiry()
+    }
+
+    pub fn is_premature(&self, block_time: BlockTime) -> bool {
+        match self.not_before {
+            Some(not_before) => block_time < not_before,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::value::account::BlockTime;
+
+    use super::{executed_deploy_key, DeployHeader, MAX_DEPLOY_TTL};
+
+    #[test]
+    fn should_expire_after_ttl() {
+        let deploy_header = DeployHeader::new([1u8; 32], BlockTime(100)).set_ttl(50);
+
+        assert_eq!(deploy_header.expiry(), BlockTime(150));
+        assert!(!deploy_header.is_expired(BlockTime(100)));
+        assert!(!deploy_header.is_expired(BlockTime(150)));
+        assert!(deploy_header.is_expired(BlockTime(151)));
+    }
+
+    #[test]
+    fn should_expire_after_max_ttl_without_ttl() {
+        let deploy_header = DeployHeader::new([1u8; 32], BlockTime(100));
+
+        assert_eq!(deploy_header.expiry(), BlockTime(100 + MAX_DEPLOY_TTL));
+        assert!(!deploy_header.is_expired(BlockTime(100 + MAX_DEPLOY_TTL)));
+        assert!(deploy_header.is_expired(BlockTime(101 + MAX_DEPLOY_TTL)));
+    }
+
+    #[test]
+    fn should_cap_ttl() {
+        let deploy_header =
+            DeployHeader::new([1u8; 32], BlockTime(100)).set_ttl(MAX_DEPLOY_TTL + 1);
+
+        assert_eq!(deploy_header.expiry(), BlockTime(100 + MAX_DEPLOY_TTL));
+    }
+
+    #[test]
+    fn should_saturate_expiry() {
+        let deploy_header = DeployHeader::new([1u8; 32], BlockTime(std::u64::MAX - 1)).set_ttl(50);
+
+        assert_eq!(deploy_header.expiry(), BlockTime(std::u64::MAX));
+        assert!(!deploy_header.is_expired(BlockTime(std::u64::MAX)));
+    }
+
+    #[test]
+    fn should_be_premature_before_not_before() {
+        let deploy_header =
+            DeployHeader::new([1u8; 32], BlockTime(100)).set_not_before(BlockTime(200));
+
+        assert!(deploy_header.is_premature(BlockTime(199)));
+        assert!(!deploy_header.is_premature(BlockTime(200)));
+        assert!(!DeployHeader::new([1u8; 32], BlockTime(100)).is_premature(BlockTime(0)));
+    }
+
+    #[test]
+    fn should_use_distinct_keys_for_distinct_deploys() {
+        assert_eq!(
+            executed_deploy_key([1u8; 32]),
+            executed_deploy_key([1u8; 32])
+        );
+        assert_ne!(
+            executed_deploy_key([1u8; 32]),
+            executed_deploy_key([2u8; 32])
+        );
+    }
+}
@@ -1,7 +1,21 @@
+use std::cmp;
+
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::wasm_limits::WasmLimits;
+
+use super::genesis::GenesisAccount;
+use super::MAX_PAYMENT;
+
 /// The runtime configuration of the execution engine
 #[derive(Debug)]
 pub struct EngineConfig {
     use_payment_code: bool,
+    verify_deploy_signatures: bool,
+    min_gas_price: u64,
+    max_payment_cost: u64,
+    genesis_wasm_costs: Option<WasmCosts>,
+    max_memory_pages: Option<u32>,
+    genesis_accounts: Vec<GenesisAccount>,
 }
 
 impl EngineConfig {
@@ -19,12 +33,166 @@ impl EngineConfig {
     pub fn use_payment_code(&self) -> bool {
         self.use_payment_code
     }
+
+    /// Stops checking deploy hashes and the signatures of the approvals of deploys, which are
+    /// checked by default. The keys of approvals are then trusted as given, and so are the unsigned
+    /// authorization keys of deploys.
+    ///
+    /// Only meant for tests, whose deploys aren't signed.
+    pub fn without_deploy_verification_for_tests(mut self) -> EngineConfig {
+        self.verify_deploy_signatures = false;
+        self
+    }
+
+    pub fn verify_deploy_signatures(&self) -> bool {
+        self.verify_deploy_signatures
+    }
+
+    /// Sets the lowest gas price, in motes per unit of gas, a deploy can be run at. It has to be
+    /// positive, see [`EngineConfig::validate`].
+    pub fn set_min_gas_price(mut self, arg: u64) -> EngineConfig {
+        self.min_gas_price = arg;
+        self
+    }
+
+    pub fn min_gas_price(&self) -> u64 {
+        self.min_gas_price
+    }
+
+    /// Sets the amount of motes an account must hold to run a deploy, which is also what it is
+    /// charged when its payment code fails.
+    pub fn set_max_payment_cost(mut self, arg: u64) -> EngineConfig {
+        self.max_payment_cost = arg;
+        self
+    }
+
+    pub fn max_payment_cost(&self) -> u64 {
+        self.max_payment_cost
+    }
+
+    /// Sets the costs recorded at genesis, in place of the built-in costs of the genesis protocol
+    /// version.
+    pub fn set_genesis_wasm_costs(mut self, arg: WasmCosts) -> EngineConfig {
+        self.genesis_wasm_costs = Some(arg);
+        self
+    }
+
+    pub fn genesis_wasm_costs(&self) -> Option<&WasmCosts> {
+        self.genesis_wasm_costs.as_ref()
+    }
+
+    /// Sets the maximum number of memory pages a module can use. It can only lower the limit of
+    /// each protocol version, as the memory a module gets when it runs is capped by that limit,
+    /// see [`EngineConfig::validate`].
+    pub fn set_max_memory_pages(mut self, arg: u32) -> EngineConfig {
+        self.max_memory_pages = Some(arg);
+        self
+    }
+
+    pub fn max_memory_pages(&self) -> Option<u32> {
+        self.max_memory_pages
+    }
+
+    /// The limits modules are preprocessed with at the given protocol version, or `None` if the
+    /// version is unknown.
+    pub fn wasm_limits(&self, protocol_version: u64) -> Option<WasmLimits> {
+        let wasm_limits = WasmLimits::from_version(protocol_version)?;
+        Some(match self.max_memory_pages {
+            Some(max_memory_pages) => WasmLimits {
+                max_memory_pages: cmp::min(max_memory_pages, wasm_limits.max_memory_pages),
+                ..wasm_limits
+            },
+            None => wasm_limits,
+        })
+    }
+
+    /// Sets the accounts created at genesis in addition to the ones given in the genesis request.
+    pub fn set_genesis_accounts(mut self, arg: Vec<GenesisAccount>) -> EngineConfig {
+        self.genesis_accounts = arg;
+        self
+    }
+
+    pub fn genesis_accounts(&self) -> &[GenesisAccount] {
+        &self.genesis_accounts
+    }
+
+    /// Checks that the parameters can be used together. Gas price converts between gas and motes,
+    /// so the minimum gas price can't be zero.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_gas_price == 0 {
+            return Err("min_gas_price must be positive".to_string());
+        }
+        if self.max_payment_cost < self.min_gas_price {
+            return Err("max_payment_cost must pay for at least one unit of gas".to_string());
+        }
+        if let Some(max_memory_pages) = self.max_memory_pages {
+            if max_memory_pages == 0 {
+                return Err("max_memory_pages must be positive".to_string());
+            }
+            let mut protocol_version = 1;
+            while let Some(wasm_limits) = WasmLimits::from_version(protocol_version) {
+                if max_memory_pages > wasm_limits.max_memory_pages {
+                    return Err(format!(
+                        "max_memory_pages can't exceed the {} pages of protocol version {}",
+                        wasm_limits.max_memory_pages, protocol_version
+                    ));
+                }
+                protocol_version += 1;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for EngineConfig {
     fn default() -> Self {
         EngineConfig {
             use_payment_code: false,
+            verify_deploy_signatures: true,
+            min_gas_price: 1,
+            max_payment_cost: MAX_PAYMENT,
+            genesis_wasm_costs: None,
+            max_memory_pages: None,
+            genesis_accounts: Vec::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use engine_wasm_prep::wasm_limits::WasmLimits;
+
+    use super::EngineConfig;
+
+    #[test]
+    fn should_validate_min_gas_price() {
+        assert!(EngineConfig::new().validate().is_ok());
+        assert!(EngineConfig::new().set_min_gas_price(0).validate().is_err());
+    }
+
+    #[test]
+    fn should_validate_max_payment_cost_and_memory_pages() {
+        let engine_config = EngineConfig::new().set_min_gas_price(2);
+        assert!(engine_config.set_max_payment_cost(1).validate().is_err());
+        assert!(EngineConfig::new()
+            .set_max_memory_pages(0)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn should_lower_memory_pages_of_wasm_limits() {
+        let built_in = WasmLimits::from_version(1).expect("should have limits");
+        assert_eq!(EngineConfig::new().wasm_limits(1), Some(built_in));
+
+        let engine_config = EngineConfig::new().set_max_memory_pages(16);
+        let wasm_limits = engine_config.wasm_limits(1).expect("should have limits");
+        assert_eq!(wasm_limits.max_memory_pages, 16);
+        assert_eq!(wasm_limits.max_call_depth, built_in.max_call_depth);
+
+        let engine_config = EngineConfig::new().set_max_memory_pages(1024);
+        assert!(engine_config.validate().is_err());
+        assert_eq!(engine_config.wasm_limits(1), Some(built_in));
+        assert_eq!(engine_config.wasm_limits(0), None);
+    }
+}
@@ -18,6 +18,10 @@ pub enum Error {
     StorageError(engine_storage::error::Error),
     #[fail(display = "Authorization failure: not authorized.")]
     AuthorizationError,
+    #[fail(display = "Invalid deploy signature by key {}", _0)]
+    InvalidDeploySignature(String),
+    #[fail(display = "Deploy hash mismatch: {}", _0)]
+    DeployHashMismatch(String),
     #[fail(display = "Insufficient payment")]
     InsufficientPaymentError,
     #[fail(display = "Deploy error")]
@@ -26,6 +30,22 @@ pub enum Error {
     FinalizationError,
     #[fail(display = "Missing system contract association: {}", _0)]
     MissingSystemContractError(String),
+    #[fail(display = "Invalid deploy item: {}", _0)]
+    InvalidDeployItem(String),
+    #[fail(display = "Invalid protocol version: {}", _0)]
+    InvalidProtocolVersion(u64),
+    #[fail(display = "Gas price too low: minimum {}, actual {}", minimum, actual)]
+    GasPriceTooLow { minimum: u64, actual: u64 },
+    #[fail(display = "Invalid genesis account: {}", _0)]
+    InvalidGenesisAccount(String),
+    #[fail(display = "Deploy expired at block time {}", _0)]
+    DeployExpired(u64),
+    #[fail(display = "Deploy not valid before block time {}", _0)]
+    DeployNotYetValid(u64),
+    #[fail(display = "Deploy already executed")]
+    DeployAlreadyExecuted,
+    #[fail(display = "Deploy dependency not executed: {}", _0)]
+    MissingDeployDependency(String),
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
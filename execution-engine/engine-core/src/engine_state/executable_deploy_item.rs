@@ -0,0 +1,29 @@
+/// The code to be executed as the session or payment part of a deploy.
+///
+/// Either raw Wasm bytes shipped with the deploy, or a reference to a contract which is
+/// already stored in global state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutableDeployItem {
+    ModuleBytes {
+        module_bytes: Vec<u8>,
+        args: Vec<u8>,
+    },
+    StoredContractByHash {
+        hash: [u8; 32],
+        args: Vec<u8>,
+    },
+    StoredContractByName {
+        name: String,
+        args: Vec<u8>,
+    },
+}
+
+impl ExecutableDeployItem {
+    pub fn args(&self) -> &[u8] {
+        match self {
+            ExecutableDeployItem::ModuleBytes { args, .. }
+            | ExecutableDeployItem::StoredContractByHash { args, .. }
+            | ExecutableDeployItem::StoredContractByName { args, .. } => args,
+        }
+    }
+}
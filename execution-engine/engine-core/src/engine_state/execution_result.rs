@@ -4,9 +4,9 @@ use contract_ffi::key::Key;
 use contract_ffi::value::{Value, U512};
 use engine_shared::transform::Transform;
 
+use super::error;
 use super::execution_effect::ExecutionEffect;
 use super::op::Op;
-use super::{error, CONV_RATE};
 
 #[derive(Debug)]
 pub enum ExecutionResult {
@@ -23,7 +23,7 @@ pub enum ExecutionResult {
 impl ExecutionResult {
     /// Constructs [ExecutionResult::Failure] that has 0 cost and no effects.
     /// This is the case for failures that we can't (or don't want to) charge for,
-    /// like `PreprocessingError` or `InvalidNonce`.
+    /// like `PreprocessingError` or `DeployExpired`.
     pub fn precondition_failure(error: error::Error) -> ExecutionResult {
         ExecutionResult::Failure {
             error,
@@ -149,6 +149,7 @@ impl ExecutionResultBuilder {
     pub fn check_forced_transfer(
         &mut self,
         max_payment_cost: U512,
+        gas_price: u64,
         account_main_purse_balance: U512,
         payment_purse_balance: U512,
         account_main_purse: Key,
@@ -161,9 +162,9 @@ impl ExecutionResultBuilder {
         let payment_result_cost = payment_result.cost();
         let payment_result_is_failure = payment_result.is_failure();
 
-        // payment_code_spec_3_b_ii: if (balance of PoS pay purse) < (gas spent during payment code execution) * conv_rate, no session
+        // payment_code_spec_3_b_ii: if (balance of PoS pay purse) < (gas spent during payment code execution) * gas_price, no session
         let insufficient_balance_to_continue =
-            payment_purse_balance < (payment_result_cost * CONV_RATE).into();
+            payment_purse_balance < U512::from(payment_result_cost) * U512::from(gas_price);
 
         // payment_code_spec_4: insufficient payment
         if !(insufficient_balance_to_continue || payment_result_is_failure) {
@@ -192,7 +193,7 @@ impl ExecutionResultBuilder {
 
         let error = error::Error::InsufficientPaymentError;
         let effect = ExecutionEffect::new(ops, transforms);
-        let cost = (max_payment_cost / CONV_RATE).as_u64();
+        let cost = (max_payment_cost / gas_price).as_u64();
 
         Some(ExecutionResult::Failure {
             error,
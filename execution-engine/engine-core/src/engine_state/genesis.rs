@@ -9,10 +9,14 @@ use rand_chacha::ChaChaRng;
 use contract_ffi::bytesrepr::ToBytes;
 use contract_ffi::key::Key;
 use contract_ffi::uref::{AccessRights, URef};
-use contract_ffi::value::account::{PublicKey, PurseId};
+use contract_ffi::value::account::{
+    AccountActivity, ActionThresholds, AssociatedKeys, PublicKey, PurseId, Weight,
+    DEFAULT_CURRENT_BLOCK_TIME, DEFAULT_INACTIVITY_PERIOD_TIME,
+};
 use contract_ffi::value::{Account, Contract, Value, U512};
 use engine_shared::newtypes::Blake2bHash;
 use engine_shared::transform::{Transform, TypeMismatch};
+use engine_state::error::Error;
 use engine_state::execution_effect::ExecutionEffect;
 use engine_state::op::Op;
 use engine_state::utils::WasmiBytes;
@@ -37,11 +41,10 @@ pub struct GenesisURefsSource(BTreeMap<&'static str, URef>);
 
 impl GenesisURefsSource {
     fn create_genesis_rng() -> ChaChaRng {
-        // We are using easy to recover address and nonce as seeds so that the addresses
+        // We are using easy to recover address and index as seeds so that the addresses
         // can be recomputed by the EngineState for PoS purposes.
-        // This should never clash with the deploy's PRNG as there is no Ed25519 private key that
-        // corresponds to `000..00` public key. Even if there was, because we are using nonce=0
-        // and any valid deploy starts with nonce=1 the seed to deploy's PRNG will be different.
+        // This should never clash with the deploy's PRNG as deploys are seeded with their deploy
+        // hash, and finding a deploy which hashes to `000..00` is infeasible.
         execution::create_rng([0u8; 32], 0)
     }
 
@@ -82,6 +85,126 @@ impl Default for GenesisURefsSource {
     }
 }
 
+/// An account created at genesis next to the genesis account, along with its initial balance and,
+/// if it is a validator, its bonded stake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisAccount {
+    public_key: PublicKey,
+    balance: U512,
+    associated_keys: AssociatedKeys,
+    action_thresholds: ActionThresholds,
+    bonded_amount: U512,
+}
+
+impl GenesisAccount {
+    pub fn new(
+        public_key: PublicKey,
+        balance: U512,
+        associated_keys: AssociatedKeys,
+        action_thresholds: ActionThresholds,
+        bonded_amount: U512,
+    ) -> GenesisAccount {
+        GenesisAccount {
+            public_key,
+            balance,
+            associated_keys,
+            action_thresholds,
+            bonded_amount,
+        }
+    }
+
+    /// Creates an unbonded account whose only associated key is its own public key, with the
+    /// default action thresholds.
+    pub fn create(public_key: PublicKey, balance: U512) -> GenesisAccount {
+        GenesisAccount::new(
+            public_key,
+            balance,
+            AssociatedKeys::new(public_key, Weight::new(1)),
+            Default::default(),
+            U512::zero(),
+        )
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    pub fn balance(&self) -> U512 {
+        self.balance
+    }
+
+    pub fn associated_keys(&self) -> &AssociatedKeys {
+        &self.associated_keys
+    }
+
+    pub fn action_thresholds(&self) -> &ActionThresholds {
+        &self.action_thresholds
+    }
+
+    /// Stake bonded by the account at genesis, zero if it isn't a validator.
+    pub fn bonded_amount(&self) -> U512 {
+        self.bonded_amount
+    }
+
+    /// Seeds the generator of the account's purse and balance URefs. Deploys are seeded with their
+    /// deploy hash instead, so they never generate the same URefs.
+    fn create_rng(&self) -> ChaChaRng {
+        execution::create_rng(self.public_key.value(), 0)
+    }
+}
+
+/// Checks that the accounts created at genesis are distinct from each other, from the genesis
+/// account and from the system account, that bonded accounts aren't also listed as genesis
+/// validators, and that the associated keys of each account can meet its thresholds.
+pub fn validate_genesis_accounts(
+    genesis_account_addr: [u8; 32],
+    genesis_validators: &[(PublicKey, U512)],
+    genesis_accounts: &[GenesisAccount],
+) -> Result<(), Error> {
+    let mut seen = vec![PublicKey::new(genesis_account_addr)];
+    for genesis_account in genesis_accounts {
+        let public_key = genesis_account.public_key();
+        if public_key.value() == super::SYSTEM_ACCOUNT_ADDR {
+            return Err(Error::InvalidGenesisAccount(
+                "the system account can't be created at genesis".to_string(),
+            ));
+        }
+        if seen.contains(&public_key) {
+            return Err(Error::InvalidGenesisAccount(format!(
+                "account {} is created more than once",
+                public_key
+            )));
+        }
+        seen.push(public_key);
+
+        if !genesis_account.bonded_amount().is_zero()
+            && genesis_validators.iter().any(|(key, _)| *key == public_key)
+        {
+            return Err(Error::InvalidGenesisAccount(format!(
+                "account {} is bonded more than once",
+                public_key
+            )));
+        }
+
+        let action_thresholds = genesis_account.action_thresholds();
+        if action_thresholds.deployment().value() == 0 {
+            return Err(Error::InvalidGenesisAccount(format!(
+                "deployment threshold of account {} must be positive",
+                public_key
+            )));
+        }
+        if *action_thresholds.key_management()
+            > genesis_account.associated_keys().total_keys_weight()
+        {
+            return Err(Error::InvalidGenesisAccount(format!(
+                "associated keys of account {} can't meet its key management threshold",
+                public_key
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn create_uref<R: RngCore>(rng: &mut R) -> URef {
     let mut buff = [0u8; 32];
     rng.fill_bytes(&mut buff);
@@ -96,10 +219,37 @@ fn create_local_key<T: ToBytes>(
     Ok(Key::local(seed, &local_key_bytes))
 }
 
+/// All system contract public urefs MUST be added to the known_urefs of accounts created at
+/// genesis.
+fn create_system_contract_known_urefs(rng: &GenesisURefsSource) -> BTreeMap<String, Key> {
+    let mint_public_uref = rng.get_uref(MINT_PUBLIC_ADDRESS);
+    let mint_contract_uref = rng.get_uref(MINT_PRIVATE_ADDRESS);
+    let pos_public_uref = rng.get_uref(POS_PUBLIC_ADDRESS);
+    let pos_private_uref = rng.get_uref(POS_PRIVATE_ADDRESS);
+    vec![
+        (
+            String::from(execution::MINT_NAME),
+            Key::URef(mint_public_uref),
+        ),
+        (
+            String::from(execution::POS_NAME),
+            Key::URef(pos_public_uref),
+        ),
+        (pos_private_uref.as_string(), Key::URef(pos_private_uref)),
+        (
+            mint_contract_uref.as_string(),
+            Key::URef(mint_contract_uref),
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
 fn create_mint_effects(
     rng: &GenesisURefsSource,
     genesis_account_addr: [u8; 32],
     initial_tokens: U512,
+    genesis_accounts: &[GenesisAccount],
     mint_code_bytes: WasmiBytes,
     pos_bonded_balance: U512,
     protocol_version: u64,
@@ -118,26 +268,10 @@ fn create_mint_effects(
     );
 
     let purse_id_uref = rng.get_uref(GENESIS_ACCOUNT_PURSE);
-    let pos_public_uref = rng.get_uref(POS_PUBLIC_ADDRESS);
-    let pos_private_uref = rng.get_uref(POS_PRIVATE_ADDRESS);
 
     // Create genesis_account
     let genesis_account = {
-        // All system contract public urefs MUST be added to the genesis account's known_urefs
-        let known_urefs = vec![
-            (String::from(execution::MINT_NAME), Key::URef(public_uref)),
-            (
-                String::from(execution::POS_NAME),
-                Key::URef(pos_public_uref),
-            ),
-            (pos_private_uref.as_string(), Key::URef(pos_private_uref)),
-            (
-                mint_contract_uref.as_string(),
-                Key::URef(mint_contract_uref),
-            ),
-        ]
-        .into_iter()
-        .collect();
+        let known_urefs = create_system_contract_known_urefs(rng);
         let purse_id = PurseId::new(purse_id_uref);
         Account::create(genesis_account_addr, known_urefs, purse_id)
     };
@@ -196,10 +330,42 @@ fn create_mint_effects(
     let balance: Value = Value::UInt512(initial_tokens);
     tmp.insert(balance_uref_key, balance);
 
+    // Create & store the other genesis accounts, along with their purses and balances
+    let mut genesis_accounts_balance_keys = Vec::with_capacity(genesis_accounts.len());
+    for genesis_account in genesis_accounts {
+        let mut account_rng = genesis_account.create_rng();
+        let account_purse_id_uref = create_uref(&mut account_rng);
+        let account_balance_key: Key = create_uref(&mut account_rng).into();
+
+        let account_addr = genesis_account.public_key().value();
+        let account = Account::new(
+            account_addr,
+            create_system_contract_known_urefs(rng),
+            PurseId::new(account_purse_id_uref),
+            genesis_account.associated_keys().clone(),
+            genesis_account.action_thresholds().clone(),
+            AccountActivity::new(DEFAULT_CURRENT_BLOCK_TIME, DEFAULT_INACTIVITY_PERIOD_TIME),
+            Default::default(),
+        );
+        tmp.insert(Key::Account(account_addr), Value::Account(account));
+
+        let account_purse_id_local_key =
+            create_local_key(mint_contract_uref.addr(), account_purse_id_uref.addr())?;
+        tmp.insert(account_purse_id_local_key, Value::Key(account_balance_key));
+        tmp.insert(
+            account_balance_key,
+            Value::UInt512(genesis_account.balance()),
+        );
+        genesis_accounts_balance_keys.push(account_balance_key);
+    }
+
     // Create mint_contract
     let mint_known_urefs = {
         let mut ret: BTreeMap<String, Key> = BTreeMap::new();
         ret.insert(balance_uref_key.as_string(), balance_uref_key);
+        for account_balance_key in genesis_accounts_balance_keys {
+            ret.insert(account_balance_key.as_string(), account_balance_key);
+        }
         // Insert PoS balance URef and its initial stakes so that PoS.
         ret.insert(pos_bonding_balance_key.as_string(), pos_bonding_balance_key);
         ret.insert(pos_payment_balance_key.as_string(), pos_payment_balance_key);
@@ -285,13 +451,26 @@ fn create_pos_effects(
 pub fn create_genesis_effects(
     genesis_account_addr: [u8; 32],
     initial_tokens: U512,
+    genesis_accounts: &[GenesisAccount],
     mint_code_bytes: WasmiBytes,
     pos_code_bytes: WasmiBytes,
-    genesis_validators: Vec<(PublicKey, U512)>,
+    mut genesis_validators: Vec<(PublicKey, U512)>,
     protocol_version: u64,
 ) -> Result<ExecutionEffect, execution::Error> {
     let rng = GenesisURefsSource::default();
 
+    genesis_validators.extend(
+        genesis_accounts
+            .iter()
+            .filter(|genesis_account| !genesis_account.bonded_amount().is_zero())
+            .map(|genesis_account| {
+                (
+                    genesis_account.public_key(),
+                    genesis_account.bonded_amount(),
+                )
+            }),
+    );
+
     let genesis_validator_stakes: U512 = genesis_validators
         .iter()
         .map(|t| t.1)
@@ -304,6 +483,7 @@ pub fn create_genesis_effects(
         &rng,
         genesis_account_addr,
         initial_tokens,
+        genesis_accounts,
         mint_code_bytes,
         genesis_validator_stakes,
         protocol_version,
@@ -370,27 +550,34 @@ mod tests {
     use std::collections::HashMap;
 
     use contract_ffi::key::Key;
-    use contract_ffi::value::account::PublicKey;
-    use contract_ffi::value::{Contract, Value, U512};
+    use contract_ffi::value::account::{ActionThresholds, AssociatedKeys, PublicKey, Weight};
+    use contract_ffi::value::{Account, Contract, Value, U512};
     use engine_shared::test_utils;
     use engine_shared::transform::Transform;
     use engine_state::create_genesis_effects;
+    use engine_state::error::Error;
     use engine_state::genesis::{
-        GenesisURefsSource, GENESIS_ACCOUNT_PURSE, MINT_GENESIS_ACCOUNT_BALANCE_UREF,
-        MINT_POS_BONDING_BALANCE_UREF, MINT_POS_PAYMENT_BALANCE_UREF,
-        MINT_POS_REWARDS_BALANCE_UREF, MINT_PRIVATE_ADDRESS, MINT_PUBLIC_ADDRESS,
-        POS_PRIVATE_ADDRESS, POS_PUBLIC_ADDRESS,
+        validate_genesis_accounts, GenesisAccount, GenesisURefsSource, GENESIS_ACCOUNT_PURSE,
+        MINT_GENESIS_ACCOUNT_BALANCE_UREF, MINT_POS_BONDING_BALANCE_UREF,
+        MINT_POS_PAYMENT_BALANCE_UREF, MINT_POS_REWARDS_BALANCE_UREF, MINT_PRIVATE_ADDRESS,
+        MINT_PUBLIC_ADDRESS, POS_PRIVATE_ADDRESS, POS_PUBLIC_ADDRESS,
     };
     use engine_state::utils::{pos_validator_key, WasmiBytes};
+    use engine_wasm_prep::import_set::ImportSet;
     use engine_wasm_prep::wasm_costs::WasmCosts;
+    use engine_wasm_prep::wasm_limits::WasmLimits;
+    use execution;
 
-    use super::{create_local_key, POS_BONDING_PURSE};
+    use super::{create_local_key, create_uref, POS_BONDING_PURSE};
 
     const GENESIS_ACCOUNT_ADDR: [u8; 32] = [6u8; 32];
     const PROTOCOL_VERSION: u64 = 1;
     const EXPECTED_GENESIS_TRANSFORM_COUNT: usize = 13; // 11 writes for Mint and 2 for PoS.
     const INITIAL_GENESIS_ACCOUNT_BALANCE: &str = "1000";
     const INITIAL_POS_VALIDATORS_BALANCE: &str = "15000";
+    const ACCOUNT_1_ADDR: [u8; 32] = [3u8; 32];
+    const ACCOUNT_2_ADDR: [u8; 32] = [4u8; 32];
+    const ACCOUNT_2_KEY_ADDR: [u8; 32] = [5u8; 32];
 
     fn get_initial_tokens(initial_balance: &str) -> U512 {
         U512::from_dec_str(initial_balance).expect("should create U512")
@@ -398,15 +585,37 @@ mod tests {
 
     fn get_mint_code_bytes() -> WasmiBytes {
         let raw_bytes = test_utils::create_empty_wasm_module_bytes();
-        WasmiBytes::new(raw_bytes.as_slice(), WasmCosts::free()).expect("should create wasmi bytes")
+        let wasm_limits = WasmLimits::from_version(PROTOCOL_VERSION).expect("should have limits");
+        let import_set = ImportSet::from_version(PROTOCOL_VERSION).expect("should have imports");
+        WasmiBytes::new(
+            raw_bytes.as_slice(),
+            WasmCosts::free(),
+            wasm_limits,
+            import_set,
+        )
+        .expect("should create wasmi bytes")
     }
 
     fn get_pos_code_bytes() -> WasmiBytes {
         let raw_bytes = test_utils::create_empty_wasm_module_bytes();
-        WasmiBytes::new(raw_bytes.as_slice(), WasmCosts::free()).expect("should create wasmi bytes")
+        let wasm_limits = WasmLimits::from_version(PROTOCOL_VERSION).expect("should have limits");
+        let import_set = ImportSet::from_version(PROTOCOL_VERSION).expect("should have imports");
+        WasmiBytes::new(
+            raw_bytes.as_slice(),
+            WasmCosts::free(),
+            wasm_limits,
+            import_set,
+        )
+        .expect("should create wasmi bytes")
     }
 
     fn get_genesis_transforms() -> HashMap<Key, Transform> {
+        get_genesis_transforms_with_accounts(&[])
+    }
+
+    fn get_genesis_transforms_with_accounts(
+        genesis_accounts: &[GenesisAccount],
+    ) -> HashMap<Key, Transform> {
         let initial_genesis_account_balance = get_initial_tokens(INITIAL_GENESIS_ACCOUNT_BALANCE);
         let initial_pos_validators_balance = get_initial_tokens(INITIAL_POS_VALIDATORS_BALANCE);
 
@@ -417,6 +626,7 @@ mod tests {
         create_genesis_effects(
             GENESIS_ACCOUNT_ADDR,
             initial_genesis_account_balance,
+            genesis_accounts,
             mint_code_bytes,
             pos_code_bytes,
             genesis_validators,
@@ -800,4 +1010,187 @@ mod tests {
         );
     }
 
+    fn get_genesis_accounts() -> Vec<GenesisAccount> {
+        let mut associated_keys =
+            AssociatedKeys::new(PublicKey::new(ACCOUNT_2_ADDR), Weight::new(1));
+        associated_keys
+            .add_key(PublicKey::new(ACCOUNT_2_KEY_ADDR), Weight::new(2))
+            .expect("should add key");
+        let action_thresholds = ActionThresholds::new(Weight::new(2), Weight::new(3))
+            .expect("should create thresholds");
+        vec![
+            GenesisAccount::create(PublicKey::new(ACCOUNT_1_ADDR), U512::from(500)),
+            GenesisAccount::new(
+                PublicKey::new(ACCOUNT_2_ADDR),
+                U512::from(700),
+                associated_keys,
+                action_thresholds,
+                U512::from(300),
+            ),
+        ]
+    }
+
+    fn extract_transform_account(effects: &HashMap<Key, Transform>, addr: [u8; 32]) -> Account {
+        if let Transform::Write(Value::Account(account)) = effects
+            .get(&Key::Account(addr))
+            .expect("should have account")
+        {
+            account.to_owned()
+        } else {
+            panic!("transform was not a write of an account")
+        }
+    }
+
+    #[test]
+    fn create_genesis_effects_stores_genesis_accounts() {
+        let genesis_accounts = get_genesis_accounts();
+
+        let transforms = get_genesis_transforms_with_accounts(&genesis_accounts);
+
+        // 1 account, 1 local key and 1 balance for each genesis account.
+        assert_eq!(
+            transforms.len(),
+            EXPECTED_GENESIS_TRANSFORM_COUNT + 3 * genesis_accounts.len()
+        );
+
+        let genesis_account = extract_transform_account(&transforms, GENESIS_ACCOUNT_ADDR);
+        for expected_account in &genesis_accounts {
+            let account =
+                extract_transform_account(&transforms, expected_account.public_key().value());
+
+            assert_eq!(account.urefs_lookup(), genesis_account.urefs_lookup());
+            assert_eq!(
+                account.get_associated_keys().collect::<Vec<_>>(),
+                expected_account
+                    .associated_keys()
+                    .iter()
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                account.action_thresholds(),
+                expected_account.action_thresholds()
+            );
+        }
+    }
+
+    #[test]
+    fn create_genesis_effects_stores_genesis_accounts_balances_at_deterministic_urefs() {
+        let rng = GenesisURefsSource::default();
+        let mint_contract_uref = rng.get_uref(MINT_PRIVATE_ADDRESS);
+        let genesis_accounts = get_genesis_accounts();
+
+        let transforms = get_genesis_transforms_with_accounts(&genesis_accounts);
+
+        let mint_contract =
+            extract_transform_contract_bytes(&transforms, &Key::URef(mint_contract_uref))
+                .expect("should have mint contract");
+        for genesis_account in &genesis_accounts {
+            let mut account_rng = execution::create_rng(genesis_account.public_key().value(), 0);
+            let expected_purse_id_uref = create_uref(&mut account_rng);
+            let expected_balance_uref = create_uref(&mut account_rng);
+
+            let account =
+                extract_transform_account(&transforms, genesis_account.public_key().value());
+            assert_eq!(account.purse_id().value(), expected_purse_id_uref);
+
+            let purse_id_local_key =
+                create_local_key(mint_contract_uref.addr(), expected_purse_id_uref.addr())
+                    .expect("should create local key");
+            let balance_uref_key = extract_transform_key(&transforms, &purse_id_local_key)
+                .expect("transform was not a write of a key");
+            assert_eq!(balance_uref_key, Key::URef(expected_balance_uref));
+
+            let balance = extract_transform_u512(&transforms, &balance_uref_key.normalize())
+                .expect("transform was not a write of a U512");
+            assert_eq!(balance, genesis_account.balance());
+
+            assert_eq!(
+                mint_contract
+                    .urefs_lookup()
+                    .get(&balance_uref_key.as_string()),
+                Some(&balance_uref_key)
+            );
+        }
+    }
+
+    #[test]
+    fn create_genesis_effects_bonds_genesis_accounts() {
+        let rng = GenesisURefsSource::default();
+        let genesis_accounts = get_genesis_accounts();
+
+        let transforms = get_genesis_transforms_with_accounts(&genesis_accounts);
+
+        let pos_balance_uref_key =
+            Key::URef(rng.get_uref(MINT_POS_BONDING_BALANCE_UREF)).normalize();
+        let pos_validators_balance = extract_transform_u512(&transforms, &pos_balance_uref_key)
+            .expect("transform was not a write of a U512");
+        assert_eq!(
+            pos_validators_balance,
+            get_initial_tokens(INITIAL_POS_VALIDATORS_BALANCE) + U512::from(300)
+        );
+
+        let pos_contract =
+            extract_transform_contract_bytes(&transforms, &Key::URef(rng.get_pos_address()))
+                .expect("should have PoS contract");
+        assert!(pos_contract.urefs_lookup().contains_key(&pos_validator_key(
+            PublicKey::new(ACCOUNT_2_ADDR),
+            U512::from(300)
+        )));
+        // 2 for bonded validators, 3 for PoS purses, 2 for mint
+        let expected_num_known_urefs = 7;
+        assert_eq!(pos_contract.urefs_lookup().len(), expected_num_known_urefs);
+    }
+
+    #[test]
+    fn validate_genesis_accounts_accepts_distinct_accounts() {
+        let genesis_validators = vec![(PublicKey::new([1u8; 32]), U512::from(100))];
+
+        validate_genesis_accounts(
+            GENESIS_ACCOUNT_ADDR,
+            &genesis_validators,
+            &get_genesis_accounts(),
+        )
+        .expect("should be valid");
+    }
+
+    #[test]
+    fn validate_genesis_accounts_rejects_invalid_accounts() {
+        let account = |addr: [u8; 32]| GenesisAccount::create(PublicKey::new(addr), U512::one());
+        let bonded_account = GenesisAccount::new(
+            PublicKey::new(ACCOUNT_1_ADDR),
+            U512::one(),
+            AssociatedKeys::new(PublicKey::new(ACCOUNT_1_ADDR), Weight::new(1)),
+            Default::default(),
+            U512::one(),
+        );
+        let unreachable_threshold_account = GenesisAccount::new(
+            PublicKey::new(ACCOUNT_1_ADDR),
+            U512::one(),
+            AssociatedKeys::new(PublicKey::new(ACCOUNT_1_ADDR), Weight::new(1)),
+            ActionThresholds::new(Weight::new(1), Weight::new(2)).expect("should create"),
+            U512::zero(),
+        );
+        let bonded_validators = vec![(PublicKey::new(ACCOUNT_1_ADDR), U512::one())];
+
+        let invalid_cases: Vec<(Vec<(PublicKey, U512)>, Vec<GenesisAccount>)> = vec![
+            (vec![], vec![account([0u8; 32])]),
+            (vec![], vec![account(GENESIS_ACCOUNT_ADDR)]),
+            (
+                vec![],
+                vec![account(ACCOUNT_1_ADDR), account(ACCOUNT_1_ADDR)],
+            ),
+            (bonded_validators, vec![bonded_account]),
+            (vec![], vec![unreachable_threshold_account]),
+        ];
+        for (genesis_validators, genesis_accounts) in invalid_cases {
+            match validate_genesis_accounts(
+                GENESIS_ACCOUNT_ADDR,
+                &genesis_validators,
+                &genesis_accounts,
+            ) {
+                Err(Error::InvalidGenesisAccount(_)) => (),
+                other => panic!("expected invalid genesis account, got {:?}", other),
+            }
+        }
+    }
 }
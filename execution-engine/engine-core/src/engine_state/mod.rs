@@ -1,47 +1,80 @@
 use std::cell::RefCell;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::rc::Rc;
 use std::sync::Arc;
 
+use parity_wasm::elements::Module;
 use parking_lot::Mutex;
 
 use contract_ffi::bytesrepr::ToBytes;
 use contract_ffi::contract_api::argsparser::ArgsParser;
-use contract_ffi::key::Key;
+use contract_ffi::key::{addr_to_hex, Key};
 use contract_ffi::uref::AccessRights;
 use contract_ffi::value::account::{BlockTime, PublicKey, PurseId};
-use contract_ffi::value::{Account, Value, U512};
-use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use contract_ffi::value::{Account, Contract, Value, U512};
+use engine_shared::newtypes::{Blake2bHash, CorrelationId, Validated};
 use engine_shared::transform::Transform;
 use engine_state::utils::WasmiBytes;
 use engine_storage::global_state::{CommitResult, History, StateReader};
+use engine_wasm_prep::import_set::ImportSet;
 use engine_wasm_prep::wasm_costs::WasmCosts;
 use engine_wasm_prep::Preprocessor;
-use execution::{self, Executor, MINT_NAME, POS_NAME};
+use execution::{
+    self, module_cache, BlockInfo, Executor, Phase, BLOCK_HASH_LENGTH, MINT_NAME, POS_NAME,
+};
 use tracking_copy::{TrackingCopy, TrackingCopyExt};
 
+pub use self::approval::Approval;
+pub use self::deploy_header::DeployHeader;
 pub use self::engine_config::EngineConfig;
 use self::error::{Error, RootNotFound};
+pub use self::executable_deploy_item::ExecutableDeployItem;
 use self::execution_result::ExecutionResult;
-use self::genesis::{create_genesis_effects, GenesisResult};
+use self::genesis::{
+    create_genesis_effects, validate_genesis_accounts, GenesisAccount, GenesisResult,
+};
+use self::op::Op;
+use self::upgrade::{
+    protocol_data_writes, upgrade_installer_hash, ProtocolData, UpgradeConfig, UpgradeResult,
+};
 use contract_ffi::uref::URef;
-use engine_state::genesis::{POS_PAYMENT_PURSE, POS_REWARDS_PURSE};
+use engine_state::genesis::{
+    GenesisURefsSource, MINT_PRIVATE_ADDRESS, MINT_PUBLIC_ADDRESS, POS_PAYMENT_PURSE,
+    POS_PRIVATE_ADDRESS, POS_PUBLIC_ADDRESS, POS_REWARDS_PURSE,
+};
 
+pub mod approval;
+pub mod deploy_header;
 pub mod engine_config;
 pub mod error;
+pub mod executable_deploy_item;
 pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
 pub mod op;
+pub mod upgrade;
 pub mod utils;
 
-// TODO?: MAX_PAYMENT && CONV_RATE values are currently arbitrary w/ real values TBD
-// gas * CONV_RATE = motes
+// TODO?: MAX_PAYMENT value is currently arbitrary w/ real value TBD
+// gas * gas_price = motes
+/// Default of [`EngineConfig::max_payment_cost`].
 pub const MAX_PAYMENT: u64 = 10_000_000;
-pub const CONV_RATE: u64 = 10;
 
 pub const SYSTEM_ACCOUNT_ADDR: [u8; 32] = [0u8; 32];
 
+const LAST_DEPLOYMENT_SEED: &[u8] = b"last_deployment";
+
+/// Key under which the block time of the last deploy of an account is stored as a
+/// `Value::UInt64`, so that deploys don't rewrite the whole account to record it.
+///
+/// It takes precedence over the deployment time in the activity of the account, which is only
+/// set when the account is created.
+pub fn last_deployment_key(account_addr: [u8; 32]) -> Key {
+    let mut bytes = LAST_DEPLOYMENT_SEED.to_vec();
+    bytes.extend_from_slice(&account_addr);
+    Key::Hash(Blake2bHash::new(&bytes).into())
+}
+
 #[derive(Debug)]
 pub struct EngineState<H> {
     config: EngineConfig,
@@ -71,19 +104,58 @@ where
         mint_code_bytes: &[u8],
         proof_of_stake_code_bytes: &[u8],
         genesis_validators: Vec<(PublicKey, U512)>,
+        genesis_accounts: Vec<GenesisAccount>,
         protocol_version: u64,
     ) -> Result<GenesisResult, Error> {
-        let mint_code = WasmiBytes::new(mint_code_bytes, WasmCosts::free())?;
-        let pos_code = WasmiBytes::new(proof_of_stake_code_bytes, WasmCosts::free())?;
+        let genesis_accounts: Vec<GenesisAccount> = self
+            .config
+            .genesis_accounts()
+            .iter()
+            .cloned()
+            .chain(genesis_accounts)
+            .collect();
+        validate_genesis_accounts(genesis_account_addr, &genesis_validators, &genesis_accounts)?;
+
+        let wasm_limits = self
+            .config
+            .wasm_limits(protocol_version)
+            .ok_or(Error::InvalidProtocolVersion(protocol_version))?;
+        let wasm_costs = match self.config.genesis_wasm_costs() {
+            Some(wasm_costs) => wasm_costs.clone(),
+            None => WasmCosts::from_version(protocol_version)
+                .ok_or(Error::InvalidProtocolVersion(protocol_version))?,
+        };
+        let import_set = ImportSet::from_version(protocol_version)
+            .ok_or(Error::InvalidProtocolVersion(protocol_version))?;
+        let mint_code = WasmiBytes::new(
+            mint_code_bytes,
+            WasmCosts::free(),
+            wasm_limits,
+            import_set.clone(),
+        )?;
+        let pos_code = WasmiBytes::new(
+            proof_of_stake_code_bytes,
+            WasmCosts::free(),
+            wasm_limits,
+            import_set,
+        )?;
 
-        let effects = create_genesis_effects(
+        let mut effects = create_genesis_effects(
             genesis_account_addr,
             initial_tokens,
+            &genesis_accounts,
             mint_code,
             pos_code,
             genesis_validators,
             protocol_version,
         )?;
+        let protocol_data = ProtocolData::new(wasm_costs);
+        for (key, value) in protocol_data_writes(protocol_version, &protocol_data)
+            .map_err(execution::Error::from)?
+        {
+            effects.ops.insert(key, Op::Write);
+            effects.transforms.insert(key, Transform::Write(value));
+        }
         let mut state_guard = self.state.lock();
         let prestate_hash = state_guard.empty_root();
         let commit_result = state_guard
@@ -95,6 +167,180 @@ where
         Ok(genesis_result)
     }
 
+    /// Activates a new protocol version on top of the state given in `upgrade_config`.
+    ///
+    /// Records the new version and its costs in global state, replaces the code of the system
+    /// contracts while keeping their URefs and known URefs, and finally runs the upgrade
+    /// installer, if any, as the system account.
+    pub fn commit_upgrade<A, P, E>(
+        &self,
+        correlation_id: CorrelationId,
+        upgrade_config: UpgradeConfig,
+        executor: &E,
+        preprocessor: &P,
+    ) -> Result<UpgradeResult, Error>
+    where
+        P: Preprocessor<A>,
+        E: Executor<A>,
+    {
+        let protocol_version = upgrade_config.protocol_version();
+        let wasm_limits = self
+            .config
+            .wasm_limits(protocol_version)
+            .ok_or(Error::InvalidProtocolVersion(protocol_version))?;
+        let import_set = ImportSet::from_version(protocol_version)
+            .ok_or(Error::InvalidProtocolVersion(protocol_version))?;
+
+        let prestate_hash = upgrade_config.prestate_hash();
+        let tracking_copy = match self.tracking_copy(prestate_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(UpgradeResult::RootNotFound),
+        };
+
+        // States created before protocol versions were recorded can only have been created
+        // with the first version.
+        let active_protocol_version = tracking_copy
+            .borrow_mut()
+            .get_active_protocol_version(correlation_id)?
+            .unwrap_or(1);
+        if protocol_version <= active_protocol_version {
+            return Err(Error::InvalidProtocolVersion(protocol_version));
+        }
+
+        let protocol_data = ProtocolData::new(upgrade_config.wasm_costs().clone());
+        for (key, value) in protocol_data_writes(protocol_version, &protocol_data)
+            .map_err(execution::Error::from)?
+        {
+            // Safe to unwrap as the error type is `!`.
+            let validated_key = Validated::new(key, Validated::valid).unwrap();
+            let validated_value = Validated::new(value, Validated::valid).unwrap();
+            tracking_copy
+                .borrow_mut()
+                .write(validated_key, validated_value);
+        }
+
+        let urefs_source = GenesisURefsSource::default();
+        let new_system_contracts = [
+            (MINT_PRIVATE_ADDRESS, upgrade_config.new_mint_bytes()),
+            (
+                POS_PRIVATE_ADDRESS,
+                upgrade_config.new_proof_of_stake_bytes(),
+            ),
+        ];
+        for (label, new_bytes) in new_system_contracts.iter() {
+            let new_bytes = match new_bytes {
+                Some(new_bytes) => new_bytes,
+                None => continue,
+            };
+            let contract_key = Key::URef(urefs_source.get_uref(label)).normalize();
+            let contract = tracking_copy
+                .borrow_mut()
+                .get_contract(correlation_id, contract_key)?;
+            let (_, known_urefs, _, entry_points) = contract.destructure();
+            let bytes = WasmiBytes::new(
+                new_bytes,
+                WasmCosts::free(),
+                wasm_limits,
+                import_set.clone(),
+            )?
+            .into();
+            let new_contract =
+                Contract::with_entry_points(bytes, known_urefs, protocol_version, entry_points);
+            // Safe to unwrap as the error type is `!`.
+            let validated_key = Validated::new(contract_key, Validated::valid).unwrap();
+            let validated_value = Validated::new(new_contract.into(), Validated::valid).unwrap();
+            tracking_copy
+                .borrow_mut()
+                .write(validated_key, validated_value);
+        }
+
+        if let Some(installer_bytes) = upgrade_config.upgrade_installer_bytes() {
+            let installer_module = preprocessor.preprocess(installer_bytes)?;
+
+            let mut installer_keys = BTreeMap::new();
+            installer_keys.insert(
+                MINT_NAME.to_string(),
+                Key::URef(urefs_source.get_uref(MINT_PUBLIC_ADDRESS)),
+            );
+            installer_keys.insert(
+                POS_NAME.to_string(),
+                Key::URef(urefs_source.get_uref(POS_PUBLIC_ADDRESS)),
+            );
+
+            let system_account = Account::new(
+                SYSTEM_ACCOUNT_ADDR,
+                installer_keys.clone(),
+                PurseId::new(URef::new(Default::default(), AccessRights::READ_ADD_WRITE)),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+            );
+
+            // An upgrade is not part of a block.
+            let block_info = BlockInfo::new(
+                BlockTime(0),
+                0,
+                [0u8; BLOCK_HASH_LENGTH],
+                PublicKey::new(SYSTEM_ACCOUNT_ADDR),
+            );
+
+            let installer_result = executor.exec_direct(
+                installer_module,
+                &[],
+                &mut installer_keys,
+                Key::Account(SYSTEM_ACCOUNT_ADDR),
+                &system_account,
+                BTreeSet::new(),
+                block_info,
+                upgrade_installer_hash(protocol_version),
+                std::u64::MAX,
+                protocol_version,
+                correlation_id,
+                Rc::clone(&tracking_copy),
+            );
+            if let ExecutionResult::Failure { error, .. } = installer_result {
+                return Err(error);
+            }
+        }
+
+        let effects = tracking_copy.borrow().effect();
+        let commit_result = self
+            .state
+            .lock()
+            .commit(correlation_id, prestate_hash, effects.transforms.to_owned())
+            .map_err(Into::into)?;
+
+        Ok(UpgradeResult::from_commit_result(commit_result, effects))
+    }
+
+    /// Returns the Wasm costs of `protocol_version` at `prestate_hash`, or `None` if
+    /// `protocol_version` is not the protocol version active in that state.
+    pub fn get_wasm_costs(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        protocol_version: u64,
+    ) -> Result<Option<WasmCosts>, Error> {
+        let mut tracking_copy = match self.tracking_copy(prestate_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            // A missing prestate is reported by `run_deploy`.
+            None => return Ok(WasmCosts::from_version(protocol_version)),
+        };
+        match tracking_copy.get_active_protocol_version(correlation_id)? {
+            // Costs of states created before protocol versions were recorded are not stored.
+            None => Ok(WasmCosts::from_version(protocol_version)),
+            Some(active_protocol_version) if active_protocol_version != protocol_version => {
+                Ok(None)
+            }
+            Some(active_protocol_version) => {
+                let protocol_data =
+                    tracking_copy.get_protocol_data(correlation_id, active_protocol_version)?;
+                Ok(Some(protocol_data.wasm_costs().clone()))
+            }
+        }
+    }
+
     pub fn state(&self) -> Arc<Mutex<H>> {
         Arc::clone(&self.state)
     }
@@ -109,19 +355,71 @@ where
         }
     }
 
+    /// Obtains the module to execute for a given deploy item, either by preprocessing the
+    /// shipped module bytes or by loading a contract stored in global state.
+    fn get_module_from_deploy_item<A: From<Module>, P: Preprocessor<A>>(
+        &self,
+        tracking_copy: &Rc<RefCell<TrackingCopy<H::Reader>>>,
+        deploy_item: &ExecutableDeployItem,
+        account: &Account,
+        correlation_id: CorrelationId,
+        preprocessor: &P,
+    ) -> Result<A, Error> {
+        let stored_contract_key = match deploy_item {
+            ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
+                return Ok(preprocessor.preprocess(module_bytes)?);
+            }
+            ExecutableDeployItem::StoredContractByHash { hash, .. } => Key::Hash(*hash),
+            ExecutableDeployItem::StoredContractByName { name, .. } => {
+                match account.urefs_lookup().get(name) {
+                    Some(key) => key.normalize(),
+                    None => {
+                        return Err(Error::InvalidDeployItem(format!(
+                            "Unknown stored contract name: {}",
+                            name
+                        )))
+                    }
+                }
+            }
+        };
+
+        let contract = match tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, stored_contract_key)
+        {
+            Ok(contract) => contract,
+            Err(error @ execution::Error::KeyNotFound(_))
+            | Err(error @ execution::Error::TypeMismatch(_)) => {
+                return Err(Error::InvalidDeployItem(format!(
+                    "No stored contract under {:?}: {}",
+                    stored_contract_key, error
+                )))
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        // Stored contracts were already preprocessed at the time they were stored.
+        let module = module_cache::get_module(
+            correlation_id,
+            contract.bytes(),
+            contract.protocol_version(),
+        )?;
+        Ok(module.into())
+    }
+
     #[allow(clippy::too_many_arguments)]
-    pub fn run_deploy<A, P: Preprocessor<A>, E: Executor<A>>(
+    pub fn run_deploy<A: From<Module>, P: Preprocessor<A>, E: Executor<A>>(
         &self,
-        session_module_bytes: &[u8],
-        session_args: &[u8],
-        payment_module_bytes: &[u8],
-        payment_args: &[u8],
-        address: Key,                         // TODO?: rename 'base_key'
-        authorized_keys: BTreeSet<PublicKey>, //TODO?: rename authorization_keys
-        blocktime: BlockTime,
-        nonce: u64,
+        session: ExecutableDeployItem,
+        payment: ExecutableDeployItem,
+        address: Key, // TODO?: rename 'base_key'
+        approvals: Vec<Approval>,
+        unsigned_keys: BTreeSet<PublicKey>,
+        block_info: BlockInfo,
+        deploy_header: DeployHeader,
         prestate_hash: Blake2bHash,
         gas_limit: u64,
+        gas_price: u64,
         protocol_version: u64,
         correlation_id: CorrelationId,
         executor: &E,
@@ -130,6 +428,18 @@ where
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
         // DEPLOY PRECONDITIONS
 
+        // Gas price is used to convert between gas and motes; a validated config makes the
+        // minimum positive
+        let min_gas_price = self.config.min_gas_price();
+        if gas_price < min_gas_price {
+            return Ok(ExecutionResult::precondition_failure(
+                Error::GasPriceTooLow {
+                    minimum: min_gas_price,
+                    actual: gas_price,
+                },
+            ));
+        }
+
         // Create tracking copy (which functions as a deploy context)
         // validation_spec_2: prestate_hash check
         let tracking_copy = match self.tracking_copy(prestate_hash) {
@@ -162,36 +472,132 @@ where
                 ));
             }
         };
+        match tracking_copy
+            .borrow_mut()
+            .get_last_deployment(correlation_id, account_addr)
+        {
+            Ok(Some(last_deployment)) => account
+                .get_account_activity_mut()
+                .update_deployment_last_used(last_deployment),
+            Ok(None) => (),
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+        }
+
+        // The addresses of the keys which signed the deploy hash authorize the deploy. Keys
+        // without signatures are only trusted when signatures aren't verified.
+        let mut authorized_keys: BTreeSet<PublicKey> = approvals
+            .iter()
+            .map(|approval| approval.public_key().to_address())
+            .collect();
+        if self.config.verify_deploy_signatures() {
+            let deploy_hash = deploy_header.deploy_hash();
+            if let Some(approval) = approvals
+                .iter()
+                .find(|approval| !approval.is_valid_for(&deploy_hash))
+            {
+                let address = approval.public_key().to_address();
+                return Ok(ExecutionResult::precondition_failure(
+                    Error::InvalidDeploySignature(addr_to_hex(&address.value())),
+                ));
+            }
+        } else {
+            authorized_keys.extend(unsigned_keys);
+        }
 
-        // Authorize using provided authorization keys
+        // Authorize using provided authorization keys, or the recovery keys of an account which
+        // was inactive for longer than its inactivity period limit
         // validation_spec_3: account validity
-        if authorized_keys.is_empty() || !account.can_authorize(&authorized_keys) {
+        if authorized_keys.is_empty() {
             return Ok(ExecutionResult::precondition_failure(
                 ::engine_state::error::Error::AuthorizationError,
             ));
         }
+        let is_recovery = if account.can_authorize(&authorized_keys) {
+            false
+        } else if account.can_recover_with(&authorized_keys, block_info.time()) {
+            true
+        } else {
+            return Ok(ExecutionResult::precondition_failure(
+                ::engine_state::error::Error::AuthorizationError,
+            ));
+        };
 
-        // Handle nonce check & increment (NOTE: nonce is scheduled to be removed)
-        // validation_spec_3: account validity
-        if let Err(error) = tracking_copy.borrow_mut().handle_nonce(&mut account, nonce) {
-            return Ok(ExecutionResult::precondition_failure(error.into()));
+        // Reject deploys outside of their validity period, already executed deploys and deploys
+        // whose dependencies weren't executed, then record this one as executed
+        // validation_spec_4: deploy validity
+        if let Some(not_before) = deploy_header.not_before() {
+            if deploy_header.is_premature(block_info.time()) {
+                return Ok(ExecutionResult::precondition_failure(
+                    Error::DeployNotYetValid(not_before.0),
+                ));
+            }
         }
+        if deploy_header.is_expired(block_info.time()) {
+            return Ok(ExecutionResult::precondition_failure(Error::DeployExpired(
+                deploy_header.expiry().0,
+            )));
+        }
+        match tracking_copy
+            .borrow_mut()
+            .get_executed_deploy_expiry(correlation_id, deploy_header.deploy_hash())
+        {
+            Ok(None) => (),
+            Ok(Some(_)) => {
+                return Ok(ExecutionResult::precondition_failure(
+                    Error::DeployAlreadyExecuted,
+                ))
+            }
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+        }
+        // Only deploys executed in earlier blocks are recorded in the prestate.
+        for dependency in deploy_header.dependencies() {
+            match tracking_copy
+                .borrow_mut()
+                .get_executed_deploy_expiry(correlation_id, *dependency)
+            {
+                Ok(Some(_)) => (),
+                Ok(None) => {
+                    return Ok(ExecutionResult::precondition_failure(
+                        Error::MissingDeployDependency(addr_to_hex(dependency)),
+                    ))
+                }
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            }
+        }
+        tracking_copy
+            .borrow_mut()
+            .record_executed_deploy(&deploy_header);
 
-        // Check total key weight against deploy threshold
+        // Check total key weight against deploy threshold, recovery keys already met theirs
         // validation_spec_4: deploy validity
-        if !account.can_deploy_with(&authorized_keys) {
+        if !is_recovery && !account.can_deploy_with(&authorized_keys) {
             return Ok(ExecutionResult::precondition_failure(
                 // TODO?:this doesn't happen in execution any longer, should error variant be moved
                 execution::Error::DeploymentAuthorizationFailure.into(),
             ));
         }
 
-        // Create session code `A` from provided session bytes
+        // Recovery deploys don't count as activity of the account, so that a failed recovery can
+        // be retried
+        if !is_recovery {
+            tracking_copy
+                .borrow_mut()
+                .record_deployment(account_addr, block_info.time());
+        }
+
+        // Create session code `A` from provided session bytes or stored contract
         // validation_spec_1: valid wasm bytes
-        let session_module = match preprocessor.preprocess(session_module_bytes) {
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+        let session_module = match self.get_module_from_deploy_item(
+            &tracking_copy,
+            &session,
+            &account,
+            correlation_id,
+            preprocessor,
+        ) {
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
             Ok(module) => module,
         };
+        let session_args = session.args();
 
         // --- REMOVE BELOW --- //
 
@@ -206,9 +612,11 @@ where
                 address,
                 &account,
                 authorized_keys,
-                blocktime,
+                block_info,
+                deploy_header.deploy_hash(),
                 gas_limit,
                 protocol_version,
+                Phase::Session,
                 correlation_id,
                 tracking_copy,
             );
@@ -218,7 +626,7 @@ where
 
         // --- REMOVE ABOVE --- //
 
-        let max_payment_cost: U512 = MAX_PAYMENT.into();
+        let max_payment_cost: U512 = self.config.max_payment_cost().into();
 
         // Get mint system contract details
         // payment_code_spec_6: system contract validity
@@ -344,11 +752,11 @@ where
         let system_account = Account::new(
             SYSTEM_ACCOUNT_ADDR,
             Default::default(),
-            Default::default(),
             PurseId::new(URef::new(Default::default(), AccessRights::READ_ADD_WRITE)),
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
         );
 
         // `[ExecutionResultBuilder]` handles merging of multiple execution results
@@ -356,26 +764,34 @@ where
 
         // Execute provided payment code
         let payment_result = {
-            // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost / conv_rate)
-            let pay_gas_limit = MAX_PAYMENT / CONV_RATE;
+            // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost / gas_price)
+            let pay_gas_limit = self.config.max_payment_cost() / gas_price;
 
-            // Create payment code module from bytes
+            // Create payment code module from bytes or stored contract
             // validation_spec_1: valid wasm bytes
-            let payment_module = match preprocessor.preprocess(payment_module_bytes) {
-                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            let payment_module = match self.get_module_from_deploy_item(
+                &tracking_copy,
+                &payment,
+                &account,
+                correlation_id,
+                preprocessor,
+            ) {
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
                 Ok(module) => module,
             };
 
             // payment_code_spec_2: execute payment code
             executor.exec(
                 payment_module,
-                payment_args,
+                payment.args(),
                 address,
                 &account,
                 authorized_keys.clone(),
-                blocktime,
+                block_info,
+                deploy_header.deploy_hash(),
                 pay_gas_limit,
                 protocol_version,
+                Phase::Payment,
                 correlation_id,
                 Rc::clone(&tracking_copy),
             )
@@ -422,6 +838,7 @@ where
             .set_payment_execution_result(payment_result)
             .check_forced_transfer(
                 max_payment_cost,
+                gas_price,
                 account_main_purse_balance,
                 payment_purse_balance,
                 account_main_purse_balance_key,
@@ -433,10 +850,10 @@ where
 
         // session_code_spec_2: execute session code
         let session_result = {
-            // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during payment code execution) * conv_rate, yes session
-            // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate) - (gas spent during payment execution)
+            // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during payment code execution) * gas_price, yes session
+            // session_code_spec_1: gas limit = ((balance of PoS payment purse) / gas_price) - (gas spent during payment execution)
             let session_gas_limit: u64 =
-                ((payment_purse_balance / CONV_RATE) - payment_result_cost).as_u64();
+                ((payment_purse_balance / gas_price) - payment_result_cost).as_u64();
 
             executor.exec(
                 session_module,
@@ -444,9 +861,11 @@ where
                 address,
                 &account,
                 authorized_keys.clone(),
-                blocktime,
+                block_info,
+                deploy_header.deploy_hash(),
                 session_gas_limit,
                 protocol_version,
+                Phase::Session,
                 correlation_id,
                 Rc::clone(&tracking_copy),
             )
@@ -458,16 +877,19 @@ where
         // payment_code_spec_5: run finalize process
         let finalize_result = {
             // validation_spec_1: valid wasm bytes
-            let proof_of_stake_module =
-                match preprocessor.deserialize(&proof_of_stake_info.module_bytes()) {
-                    Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
-                    Ok(module) => module,
-                };
+            let proof_of_stake_module = match module_cache::get_module(
+                correlation_id,
+                proof_of_stake_info.contract().bytes(),
+                proof_of_stake_info.contract().protocol_version(),
+            ) {
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                Ok(module) => module.into(),
+            };
 
             let proof_of_stake_args = {
-                //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
+                //((gas spent during payment code execution) + (gas spent during session code execution)) * gas_price
                 let finalize_cost_motes =
-                    U512::from(execution_result_builder.total_cost() * CONV_RATE);
+                    U512::from(execution_result_builder.total_cost()) * U512::from(gas_price);
                 let args = ("finalize_payment", finalize_cost_motes, account_addr);
                 ArgsParser::parse(&args)
                     .and_then(|args| args.to_bytes())
@@ -483,7 +905,8 @@ where
                 proof_of_stake_info.inner_key(),
                 &system_account,
                 authorized_keys.clone(),
-                blocktime,
+                block_info,
+                deploy_header.deploy_hash(),
                 std::u64::MAX, // <-- this execution should be unlimited but approximating
                 protocol_version,
                 correlation_id,
@@ -0,0 +1,237 @@
+use std::fmt;
+
+use contract_ffi::bytesrepr::{self, FromBytes, ToBytes};
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+use engine_shared::newtypes::Blake2bHash;
+use engine_shared::transform::TypeMismatch;
+use engine_state::execution_effect::ExecutionEffect;
+use engine_storage::global_state::CommitResult;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+const PROTOCOL_DATA_SEED: &[u8] = b"protocol_data";
+const ACTIVE_PROTOCOL_VERSION_SEED: &[u8] = b"active_protocol_version";
+const UPGRADE_INSTALLER_SEED: &[u8] = b"upgrade_installer";
+
+/// Number of `u32` fields of [`WasmCosts`].
+const WASM_COSTS_FIELDS: usize = 9;
+
+/// Key under which the [`ProtocolData`] of `protocol_version` is stored as a
+/// `Value::ByteArray`.
+pub fn protocol_data_key(protocol_version: u64) -> Key {
+    let mut bytes = PROTOCOL_DATA_SEED.to_vec();
+    bytes.extend_from_slice(&protocol_version.to_le_bytes());
+    Key::Hash(Blake2bHash::new(&bytes).into())
+}
+
+/// Key under which the active protocol version is stored as a `Value::UInt64`.
+///
+/// States which haven't been upgraded yet don't have a value under this key.
+pub fn active_protocol_version_key() -> Key {
+    Key::Hash(Blake2bHash::new(ACTIVE_PROTOCOL_VERSION_SEED).into())
+}
+
+/// Stands in for the deploy hash when running the installer of the upgrade to
+/// `protocol_version`, so that the URefs it creates differ from those created by other upgrades.
+pub fn upgrade_installer_hash(protocol_version: u64) -> [u8; 32] {
+    let mut bytes = UPGRADE_INSTALLER_SEED.to_vec();
+    bytes.extend_from_slice(&protocol_version.to_le_bytes());
+    Blake2bHash::new(&bytes).into()
+}
+
+/// Values recording `protocol_version` as the active protocol version, along with its
+/// `protocol_data`.
+pub fn protocol_data_writes(
+    protocol_version: u64,
+    protocol_data: &ProtocolData,
+) -> Result<Vec<(Key, Value)>, bytesrepr::Error> {
+    Ok(vec![
+        (
+            protocol_data_key(protocol_version),
+            Value::ByteArray(protocol_data.to_bytes()?),
+        ),
+        (
+            active_protocol_version_key(),
+            Value::UInt64(protocol_version),
+        ),
+    ])
+}
+
+/// Parameters of a protocol version recorded in global state on upgrade.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolData {
+    wasm_costs: WasmCosts,
+}
+
+impl ProtocolData {
+    pub fn new(wasm_costs: WasmCosts) -> ProtocolData {
+        ProtocolData { wasm_costs }
+    }
+
+    pub fn wasm_costs(&self) -> &WasmCosts {
+        &self.wasm_costs
+    }
+}
+
+impl ToBytes for ProtocolData {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let costs = &self.wasm_costs;
+        let mut ret = Vec::with_capacity(WASM_COSTS_FIELDS * 4);
+        for field in &[
+            costs.regular,
+            costs.div,
+            costs.mul,
+            costs.mem,
+            costs.initial_mem,
+            costs.grow_mem,
+            costs.memcpy,
+            costs.opcodes_mul,
+            costs.opcodes_div,
+        ] {
+            ret.append(&mut field.to_bytes()?);
+        }
+        Ok(ret)
+    }
+}
+
+impl FromBytes for ProtocolData {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (regular, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (mem, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (initial_mem, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (grow_mem, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (memcpy, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (opcodes_mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (opcodes_div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let wasm_costs = WasmCosts {
+            regular,
+            div,
+            mul,
+            mem,
+            initial_mem,
+            grow_mem,
+            memcpy,
+            opcodes_mul,
+            opcodes_div,
+        };
+        Ok((ProtocolData::new(wasm_costs), rem))
+    }
+}
+
+/// Everything needed to activate a new protocol version on top of an existing state.
+pub struct UpgradeConfig {
+    prestate_hash: Blake2bHash,
+    protocol_version: u64,
+    wasm_costs: WasmCosts,
+    upgrade_installer_bytes: Option<Vec<u8>>,
+    new_mint_bytes: Option<Vec<u8>>,
+    new_proof_of_stake_bytes: Option<Vec<u8>>,
+}
+
+impl UpgradeConfig {
+    pub fn new(
+        prestate_hash: Blake2bHash,
+        protocol_version: u64,
+        wasm_costs: WasmCosts,
+        upgrade_installer_bytes: Option<Vec<u8>>,
+        new_mint_bytes: Option<Vec<u8>>,
+        new_proof_of_stake_bytes: Option<Vec<u8>>,
+    ) -> UpgradeConfig {
+        UpgradeConfig {
+            prestate_hash,
+            protocol_version,
+            wasm_costs,
+            upgrade_installer_bytes,
+            new_mint_bytes,
+            new_proof_of_stake_bytes,
+        }
+    }
+
+    pub fn prestate_hash(&self) -> Blake2bHash {
+        self.prestate_hash
+    }
+
+    pub fn protocol_version(&self) -> u64 {
+        self.protocol_version
+    }
+
+    pub fn wasm_costs(&self) -> &WasmCosts {
+        &self.wasm_costs
+    }
+
+    pub fn upgrade_installer_bytes(&self) -> Option<&[u8]> {
+        self.upgrade_installer_bytes.as_ref().map(Vec::as_slice)
+    }
+
+    pub fn new_mint_bytes(&self) -> Option<&[u8]> {
+        self.new_mint_bytes.as_ref().map(Vec::as_slice)
+    }
+
+    pub fn new_proof_of_stake_bytes(&self) -> Option<&[u8]> {
+        self.new_proof_of_stake_bytes.as_ref().map(Vec::as_slice)
+    }
+}
+
+pub enum UpgradeResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+    },
+}
+
+impl fmt::Display for UpgradeResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            UpgradeResult::RootNotFound => write!(f, "Root not found"),
+            UpgradeResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            UpgradeResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            UpgradeResult::Success {
+                post_state_hash,
+                effect,
+            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
+        }
+    }
+}
+
+impl UpgradeResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => UpgradeResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => UpgradeResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => UpgradeResult::TypeMismatch(type_mismatch),
+            CommitResult::Success(post_state_hash) => UpgradeResult::Success {
+                post_state_hash,
+                effect,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::bytesrepr::{self, ToBytes};
+    use engine_wasm_prep::wasm_costs::WasmCosts;
+
+    use super::{protocol_data_key, ProtocolData};
+
+    #[test]
+    fn protocol_data_should_round_trip() {
+        let protocol_data = ProtocolData::new(WasmCosts::from_version(1).unwrap());
+        let bytes = protocol_data.to_bytes().expect("should serialize");
+        let deserialized: ProtocolData =
+            bytesrepr::deserialize(&bytes).expect("should deserialize");
+        assert_eq!(deserialized, protocol_data);
+    }
+
+    #[test]
+    fn protocol_data_keys_should_differ_between_versions() {
+        assert_ne!(protocol_data_key(1), protocol_data_key(2));
+    }
+}
@@ -4,16 +4,24 @@ use contract_ffi::key::addr_to_hex;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
 use engine_state;
+use engine_wasm_prep::import_set::ImportSet;
 use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::wasm_limits::WasmLimits;
 use engine_wasm_prep::{Preprocessor, WasmiPreprocessor};
 
 #[derive(Debug, Clone)]
 pub struct WasmiBytes(Vec<u8>);
 
 impl WasmiBytes {
-    pub fn new(raw_bytes: &[u8], wasm_costs: WasmCosts) -> Result<Self, engine_state::Error> {
+    pub fn new(
+        raw_bytes: &[u8],
+        wasm_costs: WasmCosts,
+        wasm_limits: WasmLimits,
+        import_set: ImportSet,
+    ) -> Result<Self, engine_state::Error> {
         let mut ret = vec![];
-        let wasmi_preprocessor: WasmiPreprocessor = WasmiPreprocessor::new(wasm_costs);
+        let wasmi_preprocessor: WasmiPreprocessor =
+            WasmiPreprocessor::new(wasm_costs, wasm_limits, import_set);
         let module = wasmi_preprocessor.preprocess(raw_bytes)?;
         module.serialize(&mut ret)?;
         Ok(WasmiBytes(ret))
@@ -0,0 +1,45 @@
+use contract_ffi::value::account::{BlockTime, PublicKey};
+
+/// Length of the hash of a block.
+pub const BLOCK_HASH_LENGTH: usize = 32;
+
+/// Information about the block whose deploys are being executed, as passed by the node.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BlockInfo {
+    time: BlockTime,
+    height: u64,
+    parent_hash: [u8; BLOCK_HASH_LENGTH],
+    proposer: PublicKey,
+}
+
+impl BlockInfo {
+    pub fn new(
+        time: BlockTime,
+        height: u64,
+        parent_hash: [u8; BLOCK_HASH_LENGTH],
+        proposer: PublicKey,
+    ) -> Self {
+        BlockInfo {
+            time,
+            height,
+            parent_hash,
+            proposer,
+        }
+    }
+
+    pub fn time(&self) -> BlockTime {
+        self.time
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    pub fn parent_hash(&self) -> [u8; BLOCK_HASH_LENGTH] {
+        self.parent_hash
+    }
+
+    pub fn proposer(&self) -> PublicKey {
+        self.proposer
+    }
+}
@@ -8,8 +8,9 @@ use contract_ffi::key::Key;
 use contract_ffi::system_contracts;
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{
-    AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
+    AddKeyFailure, ProposalFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
 };
+use contract_ffi::value::contract_package::ContractPackageFailure;
 use engine_shared::transform::TypeMismatch;
 
 use resolvers::error::ResolverError;
@@ -29,23 +30,25 @@ pub enum Error {
     ArgIndexOutOfBounds(usize),
     URefNotFound(String),
     FunctionNotFound(String),
+    EntryPointNotFound(String),
+    InvalidEntryPointArgs(String),
     ParityWasm(elements::Error),
     GasLimit,
+    /// Calling a contract would exceed the maximum call depth.
+    CallDepthLimit,
     Ret(Vec<URef>),
     Rng(rand::Error),
     ResolverError(ResolverError),
-    InvalidNonce {
-        deploy_nonce: u64,
-        expected_nonce: u64,
-    },
-    /// Reverts execution with a provided status
-    Revert(u32),
+    /// Reverts execution with a provided status and an optional payload
+    Revert(u32, Vec<u8>),
     AddKeyFailure(AddKeyFailure),
     RemoveKeyFailure(RemoveKeyFailure),
     UpdateKeyFailure(UpdateKeyFailure),
     SetThresholdFailure(SetThresholdFailure),
+    ProposalFailure(ProposalFailure),
     SystemContractError(system_contracts::error::Error),
     DeploymentAuthorizationFailure,
+    ContractPackageFailure(ContractPackageFailure),
 }
 
 impl fmt::Display for Error {
@@ -116,8 +119,20 @@ impl From<SetThresholdFailure> for Error {
     }
 }
 
+impl From<ProposalFailure> for Error {
+    fn from(err: ProposalFailure) -> Error {
+        Error::ProposalFailure(err)
+    }
+}
+
 impl From<system_contracts::error::Error> for Error {
     fn from(error: system_contracts::error::Error) -> Error {
         Error::SystemContractError(error)
     }
 }
+
+impl From<ContractPackageFailure> for Error {
+    fn from(err: ContractPackageFailure) -> Error {
+        Error::ContractPackageFailure(err)
+    }
+}
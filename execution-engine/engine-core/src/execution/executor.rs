@@ -5,16 +5,21 @@ use std::rc::Rc;
 use parity_wasm::elements::Module;
 
 use contract_ffi::bytesrepr::deserialize;
+use contract_ffi::contract_api::call_stack::CallStackElement;
 use contract_ffi::key::Key;
 use contract_ffi::uref::AccessRights;
-use contract_ffi::value::account::{BlockTime, PublicKey};
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::contract::DEFAULT_ENTRY_POINT_NAME;
 use contract_ffi::value::{Account, Value};
 use engine_shared::newtypes::CorrelationId;
 use engine_state::execution_result::ExecutionResult;
 use engine_storage::global_state::StateReader;
 
-use super::Error;
-use super::{create_rng, extract_access_rights_from_keys, instance_and_memory, Runtime};
+use super::{
+    create_random_bytes_rng, create_rng, extract_access_rights_from_keys, instance_and_memory,
+    Runtime,
+};
+use super::{BlockInfo, Error, Phase};
 use runtime_context::RuntimeContext;
 use tracking_copy::TrackingCopy;
 use URefAddr;
@@ -28,9 +33,11 @@ pub trait Executor<A> {
         base_key: Key,
         account: &Account,
         authorized_keys: BTreeSet<PublicKey>,
-        blocktime: BlockTime,
+        block_info: BlockInfo,
+        deploy_hash: [u8; 32],
         gas_limit: u64,
         protocol_version: u64,
+        phase: Phase,
         correlation_id: CorrelationId,
         tc: Rc<RefCell<TrackingCopy<R>>>,
     ) -> ExecutionResult
@@ -46,7 +53,8 @@ pub trait Executor<A> {
         base_key: Key,
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
-        blocktime: BlockTime,
+        block_info: BlockInfo,
+        deploy_hash: [u8; 32],
         gas_limit: u64,
         protocol_version: u64,
         correlation_id: CorrelationId,
@@ -104,9 +112,11 @@ impl Executor<Module> for WasmiExecutor {
         base_key: Key,
         account: &Account,
         authorized_keys: BTreeSet<PublicKey>,
-        blocktime: BlockTime,
+        block_info: BlockInfo,
+        deploy_hash: [u8; 32],
         gas_limit: u64,
         protocol_version: u64,
+        phase: Phase,
         correlation_id: CorrelationId,
         tc: Rc<RefCell<TrackingCopy<R>>>,
     ) -> ExecutionResult
@@ -119,13 +129,13 @@ impl Executor<Module> for WasmiExecutor {
         let mut uref_lookup_local = account.urefs_lookup().clone();
         let known_urefs: HashMap<URefAddr, HashSet<AccessRights>> =
             extract_access_rights_from_keys(uref_lookup_local.values().cloned());
-        let account_bytes = base_key.as_account().unwrap();
-        let rng = create_rng(account_bytes, account.nonce());
+        let rng = create_rng(deploy_hash, 0);
+        let random_bytes_rng = create_random_bytes_rng(deploy_hash, 0);
         let gas_counter = 0u64;
         let fn_store_id = 0u32;
 
         // Snapshot of effects before execution, so in case of error
-        // only nonce update can be returned.
+        // only the executed deploy record can be returned.
         let effects_snapshot = tc.borrow().effect();
 
         let arguments: Vec<Vec<u8>> = if args.is_empty() {
@@ -144,12 +154,19 @@ impl Executor<Module> for WasmiExecutor {
             authorized_keys,
             &account,
             base_key,
-            blocktime,
+            vec![CallStackElement::new(
+                base_key.normalize(),
+                DEFAULT_ENTRY_POINT_NAME.to_string(),
+            )],
+            block_info,
+            deploy_hash,
             gas_limit,
             gas_counter,
             fn_store_id,
             Rc::new(RefCell::new(rng)),
+            Rc::new(RefCell::new(random_bytes_rng)),
             protocol_version,
+            phase,
             correlation_id,
         );
 
@@ -174,7 +191,8 @@ impl Executor<Module> for WasmiExecutor {
         base_key: Key,
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
-        blocktime: BlockTime,
+        block_info: BlockInfo,
+        deploy_hash: [u8; 32],
         gas_limit: u64,
         protocol_version: u64,
         correlation_id: CorrelationId,
@@ -189,13 +207,18 @@ impl Executor<Module> for WasmiExecutor {
 
         //let base_key = Key::Account(account.pub_key());
         let rng = {
-            let rng = create_rng(account.pub_key(), account.nonce());
+            let rng = create_rng(deploy_hash, 0);
+            Rc::new(RefCell::new(rng))
+        };
+        let random_bytes_rng = {
+            let rng = create_random_bytes_rng(deploy_hash, 0);
             Rc::new(RefCell::new(rng))
         };
         let gas_counter = 0u64; // maybe const?
         let fn_store_id = 0u32; // maybe const?
 
-        // Snapshot of effects before execution, so in case of error only nonce update can be returned.
+        // Snapshot of effects before execution, so in case of error only the executed deploy record
+        // can be returned.
         let effects_snapshot = state.borrow().effect();
 
         let args: Vec<Vec<u8>> = if args.is_empty() {
@@ -212,12 +235,19 @@ impl Executor<Module> for WasmiExecutor {
             authorization_keys,
             &account,
             base_key,
-            blocktime,
+            vec![CallStackElement::new(
+                base_key.normalize(),
+                DEFAULT_ENTRY_POINT_NAME.to_string(),
+            )],
+            block_info,
+            deploy_hash,
             gas_limit,
             gas_counter,
             fn_store_id,
             rng,
+            random_bytes_rng,
             protocol_version,
+            Phase::System,
             correlation_id,
         );
 
@@ -246,11 +276,11 @@ impl Executor<Module> for WasmiExecutor {
                                 cost: runtime.context().gas_counter(),
                             };
                         }
-                        Error::Revert(status) => {
+                        Error::Revert(status, payload) => {
                             // Propagate revert as revert, instead of passing it as
                             // InterpreterError.
                             return ExecutionResult::Failure {
-                                error: Error::Revert(*status).into(),
+                                error: Error::Revert(*status, payload.clone()).into(),
                                 effect: effects_snapshot,
                                 cost: runtime.context().gas_counter(),
                             };
@@ -1,14 +1,20 @@
+mod block_info;
 mod error;
 #[macro_use]
 mod executor;
+pub mod module_cache;
+mod phase;
 mod runtime;
 #[cfg(test)]
 mod tests;
 
+pub use self::block_info::{BlockInfo, BLOCK_HASH_LENGTH};
 pub use self::error::Error;
 pub use self::executor::{Executor, WasmiExecutor};
+pub use self::phase::Phase;
 pub use self::runtime::{
-    create_rng, extract_access_rights_from_keys, instance_and_memory, Runtime,
+    create_random_bytes_rng, create_rng, extract_access_rights_from_keys, instance_and_memory,
+    Runtime,
 };
 
 pub const MINT_NAME: &str = "mint";
@@ -0,0 +1,163 @@
+use linked_hash_map::LinkedHashMap;
+use parity_wasm::elements::{self, Module};
+use parking_lot::Mutex;
+
+use engine_shared::logging::{log_metric, GAUGE};
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+
+/// Maximum number of parsed modules kept by the process-wide cache.
+pub const MODULE_CACHE_CAPACITY: usize = 64;
+
+const MODULE_CACHE_HIT_RATE: &str = "module_cache_hit_rate";
+const MODULE_CACHE_ENTRIES: &str = "module_cache_entries";
+const GET: &str = "get";
+
+lazy_static! {
+    static ref MODULE_CACHE: Mutex<ModuleCache> =
+        Mutex::new(ModuleCache::new(MODULE_CACHE_CAPACITY));
+}
+
+/// Parsed modules of stored contracts, keyed by the hash of their code and their protocol
+/// version.
+///
+/// Once `capacity` modules are cached, the least recently used one is evicted.
+pub struct ModuleCache {
+    capacity: usize,
+    modules: LinkedHashMap<(Blake2bHash, u64), Module>,
+    hits: u64,
+    misses: u64,
+}
+
+impl ModuleCache {
+    pub fn new(capacity: usize) -> ModuleCache {
+        ModuleCache {
+            capacity,
+            modules: LinkedHashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the module parsed from `module_bytes`, parsing it only if it is not cached yet.
+    pub fn get_or_parse(
+        &mut self,
+        module_bytes: &[u8],
+        protocol_version: u64,
+    ) -> Result<Module, elements::Error> {
+        let key = (Blake2bHash::new(module_bytes), protocol_version);
+        if let Some(module) = self.modules.get_refresh(&key) {
+            self.hits += 1;
+            return Ok(module.clone());
+        }
+
+        self.misses += 1;
+        let module: Module = parity_wasm::deserialize_buffer(module_bytes)?;
+        self.modules.insert(key, module.clone());
+        while self.modules.len() > self.capacity {
+            self.modules.pop_front();
+        }
+        Ok(module)
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Fraction of lookups served from the cache, or `0.0` if there were none.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Returns the parsed module of a stored contract from the process-wide [`ModuleCache`].
+pub fn get_module(
+    correlation_id: CorrelationId,
+    module_bytes: &[u8],
+    protocol_version: u64,
+) -> Result<Module, elements::Error> {
+    let mut cache = MODULE_CACHE.lock();
+    let result = cache.get_or_parse(module_bytes, protocol_version);
+    log_metric(
+        correlation_id,
+        MODULE_CACHE_HIT_RATE,
+        GET,
+        GAUGE,
+        cache.hit_rate(),
+    );
+    log_metric(
+        correlation_id,
+        MODULE_CACHE_ENTRIES,
+        GET,
+        GAUGE,
+        cache.len() as f64,
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModuleCache;
+
+    fn module_bytes(functions: usize) -> Vec<u8> {
+        let wat = format!("(module {})", "(func)".repeat(functions));
+        wabt::wat2wasm(wat).expect("should parse wat")
+    }
+
+    #[test]
+    fn should_parse_once_per_code_and_protocol_version() {
+        let mut cache = ModuleCache::new(4);
+        let bytes = module_bytes(1);
+
+        let first = cache.get_or_parse(&bytes, 1).expect("should parse");
+        let second = cache.get_or_parse(&bytes, 1).expect("should parse");
+        assert_eq!(first, second);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        cache.get_or_parse(&bytes, 2).expect("should parse");
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_module() {
+        let mut cache = ModuleCache::new(2);
+        let (bytes_a, bytes_b, bytes_c) = (module_bytes(1), module_bytes(2), module_bytes(3));
+
+        cache.get_or_parse(&bytes_a, 1).expect("should parse");
+        cache.get_or_parse(&bytes_b, 1).expect("should parse");
+        // Refreshes `bytes_a`, so `bytes_b` is evicted next
+        cache.get_or_parse(&bytes_a, 1).expect("should parse");
+        cache.get_or_parse(&bytes_c, 1).expect("should parse");
+        assert_eq!(cache.len(), 2);
+
+        cache.get_or_parse(&bytes_a, 1).expect("should parse");
+        assert_eq!((cache.hits(), cache.misses()), (2, 3));
+        cache.get_or_parse(&bytes_b, 1).expect("should parse");
+        assert_eq!((cache.hits(), cache.misses()), (2, 4));
+    }
+
+    #[test]
+    fn should_not_cache_invalid_modules() {
+        let mut cache = ModuleCache::new(2);
+
+        assert!(cache.get_or_parse(&[0, 1, 2, 3], 1).is_err());
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,10 @@
+/// The part of deploy execution that code is running in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Phase {
+    /// Code executed by the system account, e.g. an upgrade installer or finalizing payment.
+    System,
+    /// Payment code of a deploy.
+    Payment,
+    /// Session code of a deploy.
+    Session,
+}
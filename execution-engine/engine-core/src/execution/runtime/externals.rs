@@ -10,6 +10,7 @@ use super::args::Args;
 use super::{Error, Runtime};
 use contract_ffi::bytesrepr::{self, ToBytes};
 use contract_ffi::value::account::{PublicKey, PurseId};
+use contract_ffi::value::contract::DEFAULT_ENTRY_POINT_NAME;
 use resolvers::v1_function_index::FunctionIndex;
 
 impl<'a, R: StateReader<Key, Value>> Externals for Runtime<'a, R>
@@ -155,7 +156,82 @@ where
                 let urefs_bytes =
                     self.bytes_from_mem(extra_urefs_ptr, extra_urefs_size as usize)?;
 
-                let size = self.call_contract(key_contract, args_bytes, urefs_bytes)?;
+                let size = self.call_contract(
+                    key_contract,
+                    DEFAULT_ENTRY_POINT_NAME,
+                    args_bytes,
+                    urefs_bytes,
+                )?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
+
+            FunctionIndex::CallContractEntryPointFuncIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to entry point name in Wasm memory
+                // args(3) = size of entry point name
+                // args(4) = pointer to function arguments in Wasm memory
+                // args(5) = size of arguments
+                // args(6) = pointer to extra supplied urefs
+                // args(7) = size of extra urefs
+                let (
+                    key_ptr,
+                    key_size,
+                    entry_point_ptr,
+                    entry_point_size,
+                    args_ptr,
+                    args_size,
+                    extra_urefs_ptr,
+                    extra_urefs_size,
+                ) = Args::parse(args)?;
+
+                // We have to explicitly tell rustc what type we expect as it cannot infer it otherwise.
+                let _args_size_u32: u32 = args_size;
+                let _extra_urefs_size_u32: u32 = extra_urefs_size;
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_point = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+                let urefs_bytes =
+                    self.bytes_from_mem(extra_urefs_ptr, extra_urefs_size as usize)?;
+
+                let size =
+                    self.call_contract(key_contract, &entry_point, args_bytes, urefs_bytes)?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
+
+            FunctionIndex::TryCallContractFuncIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to entry point name in Wasm memory
+                // args(3) = size of entry point name
+                // args(4) = pointer to function arguments in Wasm memory
+                // args(5) = size of arguments
+                // args(6) = pointer to extra supplied urefs
+                // args(7) = size of extra urefs
+                let (
+                    key_ptr,
+                    key_size,
+                    entry_point_ptr,
+                    entry_point_size,
+                    args_ptr,
+                    args_size,
+                    extra_urefs_ptr,
+                    extra_urefs_size,
+                ) = Args::parse(args)?;
+
+                // We have to explicitly tell rustc what type we expect as it cannot infer it otherwise.
+                let _args_size_u32: u32 = args_size;
+                let _extra_urefs_size_u32: u32 = extra_urefs_size;
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_point = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+                let urefs_bytes =
+                    self.bytes_from_mem(extra_urefs_ptr, extra_urefs_size as usize)?;
+
+                let size =
+                    self.try_call_contract(key_contract, &entry_point, args_bytes, urefs_bytes)?;
                 Ok(Some(RuntimeValue::I32(size as i32)))
             }
 
@@ -220,6 +296,40 @@ where
                 Ok(None)
             }
 
+            FunctionIndex::SerializeCallStackIndex => {
+                // No args, returns byte size of the serialized call stack.
+                let size = self.serialize_call_stack()?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
+
+            FunctionIndex::GetCallStackIndex => {
+                // args(0) = pointer to destination in Wasm memory
+                let dest_ptr = Args::parse(args)?;
+                self.set_mem_from_buf(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::GetBlockHeightIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.get_block_height(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::GetParentHashIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.get_parent_hash(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::GetProposerIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.get_proposer(dest_ptr)?;
+                Ok(None)
+            }
+
             FunctionIndex::GasFuncIndex => {
                 let gas: u32 = Args::parse(args)?;
                 self.gas(u64::from(gas))?;
@@ -247,6 +357,32 @@ where
                 Ok(None)
             }
 
+            FunctionIndex::StoreEntryPointsIndex => {
+                // args(0) = pointer to serialized entry points in Wasm memory
+                // args(1) = size of the entry points
+                // args(2) = pointer to additional unforgable names
+                //           to be saved with the functions
+                // args(3) = size of the additional unforgable names
+                // args(4) = pointer to a Wasm memory where we will save
+                //           hash of the new contract
+                let (entry_points_ptr, entry_points_size, urefs_ptr, urefs_size, hash_ptr): (
+                    _,
+                    u32,
+                    _,
+                    u32,
+                    _,
+                ) = Args::parse(args)?;
+                let entry_points_bytes =
+                    self.bytes_from_mem(entry_points_ptr, entry_points_size as usize)?;
+                let entry_points =
+                    bytesrepr::deserialize(&entry_points_bytes).map_err(Error::BytesRepr)?;
+                let uref_bytes = self.bytes_from_mem(urefs_ptr, urefs_size as usize)?;
+                let urefs = bytesrepr::deserialize(&uref_bytes).map_err(Error::BytesRepr)?;
+                let contract_hash = self.store_entry_points(entry_points, urefs)?;
+                self.function_address(contract_hash, hash_ptr)?;
+                Ok(None)
+            }
+
             FunctionIndex::ProtocolVersionFuncIndex => {
                 Ok(Some(self.context.protocol_version().into()))
             }
@@ -270,6 +406,46 @@ where
                 Err(self.revert(status))
             }
 
+            FunctionIndex::RevertWithPayloadFuncIndex => {
+                // args(0) = status u32
+                // args(1) = pointer to the payload in Wasm memory
+                // args(2) = size of the payload
+                let (status, payload_ptr, payload_size) = Args::parse(args)?;
+
+                Err(self.revert_with_payload(status, payload_ptr, payload_size))
+            }
+
+            FunctionIndex::Blake2bFuncIndex => {
+                // args(0) = pointer to the data to hash in Wasm memory
+                // args(1) = size of the data
+                // args(2) = pointer to the destination of the digest in Wasm memory
+                let (in_ptr, in_size, out_ptr) = Args::parse(args)?;
+                self.blake2b(in_ptr, in_size, out_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::VerifyEd25519FuncIndex => {
+                // args(0) = pointer to the serialized public key in Wasm memory
+                // args(1) = pointer to the signed message in Wasm memory
+                // args(2) = size of the message
+                // args(3) = pointer to the signature in Wasm memory
+                let (public_key_ptr, message_ptr, message_size, signature_ptr) = Args::parse(args)?;
+
+                if self.verify_ed25519(public_key_ptr, message_ptr, message_size, signature_ptr)? {
+                    Ok(Some(RuntimeValue::I32(1)))
+                } else {
+                    Ok(Some(RuntimeValue::I32(0)))
+                }
+            }
+
+            FunctionIndex::RandomBytesFuncIndex => {
+                // args(0) = pointer to the destination in Wasm memory
+                // args(1) = number of random bytes to write
+                let (dest_ptr, len) = Args::parse(args)?;
+                self.random_bytes(dest_ptr, len)?;
+                Ok(None)
+            }
+
             FunctionIndex::AddAssociatedKeyFuncIndex => {
                 // args(0) = pointer to array of bytes of a public key
                 // args(1) = weight of the key
@@ -302,6 +478,77 @@ where
                 Ok(Some(RuntimeValue::I32(value)))
             }
 
+            FunctionIndex::AddRecoveryKeyFuncIndex => {
+                // args(0) = pointer to array of bytes of a public key
+                // args(1) = weight of the key
+                let (public_key_ptr, weight_value): (u32, u8) = Args::parse(args)?;
+                let value = self.add_recovery_key(public_key_ptr, weight_value)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::RemoveRecoveryKeyFuncIndex => {
+                // args(0) = pointer to array of bytes of a public key
+                let public_key_ptr: u32 = Args::parse(args)?;
+                let value = self.remove_recovery_key(public_key_ptr)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::SetRecoveryThresholdFuncIndex => {
+                // args(0) = new threshold
+                let threshold_value: u8 = Args::parse(args)?;
+                let value = self.set_recovery_threshold(threshold_value)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::SetInactivityPeriodLimitFuncIndex => {
+                // args(0) = pointer to serialized block time
+                let limit_ptr: u32 = Args::parse(args)?;
+                let value = self.set_inactivity_period_limit(limit_ptr)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::SetNamedThresholdFuncIndex => {
+                // args(0) = pointer to threshold name in Wasm memory
+                // args(1) = size of threshold name
+                // args(2) = new threshold
+                let (name_ptr, name_size, threshold_value): (u32, u32, u8) = Args::parse(args)?;
+                let value = self.set_named_threshold(name_ptr, name_size, threshold_value)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::MeetsNamedThresholdFuncIndex => {
+                // args(0) = pointer to threshold name in Wasm memory
+                // args(1) = size of threshold name
+                let (name_ptr, name_size) = Args::parse(args)?;
+                let value = self.meets_named_threshold(name_ptr, name_size)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::ProposeActionFuncIndex => {
+                // args(0) = pointer to serialized action in Wasm memory
+                // args(1) = size of serialized action
+                // args(2) = pointer to serialized expiry block time
+                // args(3) = pointer to array for the id of the action
+                let (action_ptr, action_size, expires_at_ptr, id_dest_ptr) = Args::parse(args)?;
+                let value =
+                    self.propose_action(action_ptr, action_size, expires_at_ptr, id_dest_ptr)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::ApproveActionFuncIndex => {
+                // args(0) = pointer to the id of the action in Wasm memory
+                let id_ptr: u32 = Args::parse(args)?;
+                let value = self.approve_action(id_ptr)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::CancelActionFuncIndex => {
+                // args(0) = pointer to the id of the action in Wasm memory
+                let id_ptr: u32 = Args::parse(args)?;
+                let value = self.cancel_action(id_ptr)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
             FunctionIndex::CreatePurseIndex => {
                 // args(0) = pointer to array for return value
                 // args(1) = length of array for return value
@@ -407,6 +654,84 @@ where
 
                 Ok(Some(RuntimeValue::I32(ret)))
             }
+
+            FunctionIndex::CreateContractPackageIndex => {
+                // args(0) = pointer to a Wasm memory where we will save
+                //           hash of the new contract package
+                // args(1) = pointer to a Wasm memory where we will save
+                //           the access key of the new contract package
+                let (hash_ptr, access_key_ptr) = Args::parse(args)?;
+                self.create_contract_package(hash_ptr, access_key_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::AddContractVersionIndex => {
+                // args(0) = pointer to contract package hash in Wasm memory
+                // args(1) = pointer to access key of the contract package
+                // args(2) = pointer to function name in Wasm memory
+                // args(3) = size of the name
+                // args(4) = pointer to additional unforgable names
+                //           to be saved with the function body
+                // args(5) = size of the additional unforgable names
+                // args(6) = pointer to a Wasm memory where we will save
+                //           number of the new version
+                let (
+                    package_hash_ptr,
+                    access_key_ptr,
+                    name_ptr,
+                    name_size,
+                    urefs_ptr,
+                    urefs_size,
+                    version_ptr,
+                ) = Args::parse(args)?;
+                let ret = self.add_contract_version(
+                    package_hash_ptr,
+                    access_key_ptr,
+                    name_ptr,
+                    name_size,
+                    urefs_ptr,
+                    urefs_size,
+                    version_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(ret)))
+            }
+
+            FunctionIndex::DisableContractVersionIndex => {
+                // args(0) = pointer to contract package hash in Wasm memory
+                // args(1) = pointer to access key of the contract package
+                // args(2) = version to disable
+                let (package_hash_ptr, access_key_ptr, version) = Args::parse(args)?;
+                let ret =
+                    self.disable_contract_version(package_hash_ptr, access_key_ptr, version)?;
+                Ok(Some(RuntimeValue::I32(ret)))
+            }
+
+            FunctionIndex::CallVersionedContractIndex => {
+                // args(0) = pointer to contract package hash in Wasm memory
+                // args(1) = version to call, or 0 to call the latest enabled version
+                // args(2) = pointer to function arguments in Wasm memory
+                // args(3) = size of arguments
+                // args(4) = pointer to extra supplied urefs
+                // args(5) = size of extra urefs
+                let (
+                    package_hash_ptr,
+                    version,
+                    args_ptr,
+                    args_size,
+                    extra_urefs_ptr,
+                    extra_urefs_size,
+                ): (_, u32, _, u32, _, u32) = Args::parse(args)?;
+
+                let package_hash = self.hash_from_mem(package_hash_ptr)?;
+                let version = if version == 0 { None } else { Some(version) };
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+                let urefs_bytes =
+                    self.bytes_from_mem(extra_urefs_ptr, extra_urefs_size as usize)?;
+
+                let size =
+                    self.call_versioned_contract(package_hash, version, args_bytes, urefs_bytes)?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
         }
     }
 }
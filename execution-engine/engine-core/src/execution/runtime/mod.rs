@@ -4,28 +4,40 @@ mod externals;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::iter::IntoIterator;
+use std::mem;
 
 use blake2::digest::{Input, VariableOutput};
 use blake2::VarBlake2b;
+use ed25519_dalek;
 use itertools::Itertools;
 use parity_wasm::elements::Module;
-use rand::SeedableRng;
+use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaChaRng;
 use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
 use contract_ffi::bytesrepr::{deserialize, ToBytes, U32_SIZE};
 use contract_ffi::contract_api::argsparser::ArgsParser;
+use contract_ffi::contract_api::call_stack::CallStackElement;
+use contract_ffi::contract_api::error::{ApiError, CallError};
 use contract_ffi::contract_api::{PurseTransferResult, TransferResult};
 use contract_ffi::key::Key;
 use contract_ffi::system_contracts::{self, mint};
-use contract_ffi::uref::{AccessRights, URef};
-use contract_ffi::value::account::{ActionType, PublicKey, PurseId, Weight, PUBLIC_KEY_SIZE};
-use contract_ffi::value::{Account, Value, U512};
+use contract_ffi::uref::{AccessRights, URef, UREF_SIZE_SERIALIZED};
+use contract_ffi::value::account::{
+    ActionType, ApprovalStatus, BlockTime, ProposalFailure, ProposedAction, PublicKey, PurseId,
+    Weight, BLOCKTIME_SER_SIZE, PUBLIC_KEY_SIZE,
+};
+use contract_ffi::value::contract::{EntryPoint, DEFAULT_ENTRY_POINT_NAME};
+use contract_ffi::value::{Account, Contract, Value, U512};
+use engine_shared::newtypes::Blake2bHash;
 use engine_storage::global_state::StateReader;
+use engine_wasm_prep::wasm_limits::WasmLimits;
 
-use super::{Error, MINT_NAME, POS_NAME};
+use super::{module_cache, Error, Phase, MINT_NAME, POS_NAME};
+use engine_state::genesis::POS_PAYMENT_PURSE;
 use execution::Error::{KeyNotFound, URefNotFound};
 use resolvers::create_module_resolver;
+use resolvers::error::ResolverError;
 use resolvers::memory_resolver::MemoryResolver;
 use runtime_context::RuntimeContext;
 use URefAddr;
@@ -51,7 +63,7 @@ pub fn rename_export_to_call(module: &mut Module, name: String) {
         .unwrap()
         .field_mut();
     main_export.clear();
-    main_export.push_str("call");
+    main_export.push_str(DEFAULT_ENTRY_POINT_NAME);
 }
 
 pub fn instance_and_memory(
@@ -121,19 +133,52 @@ pub fn extract_access_rights_from_keys<I: IntoIterator<Item = Key>>(
         .collect()
 }
 
-pub fn create_rng(account_addr: [u8; 32], nonce: u64) -> ChaChaRng {
+/// Domain tag mixed into the seed of the stream served by the `random_bytes` host function.
+const RANDOM_BYTES_DOMAIN: &[u8] = b"random_bytes";
+
+fn rng_from_data(data: &[u8]) -> ChaChaRng {
     let mut seed: [u8; 32] = [0u8; 32];
-    let mut data: Vec<u8> = Vec::new();
     let mut hasher = VarBlake2b::new(32).unwrap();
-    data.extend(&account_addr);
-    data.extend_from_slice(&nonce.to_le_bytes());
     hasher.input(data);
     hasher.variable_result(|hash| seed.clone_from_slice(hash));
     ChaChaRng::from_seed(seed)
 }
 
+/// Creates the RNG generating the addresses of new URefs.
+///
+/// Deploys use their deploy hash as `seed` and index 0, while genesis uses public keys.
+pub fn create_rng(seed: [u8; 32], index: u64) -> ChaChaRng {
+    let mut data: Vec<u8> = Vec::new();
+    data.extend(&seed);
+    data.extend_from_slice(&index.to_le_bytes());
+    rng_from_data(&data)
+}
+
+/// Creates the RNG backing the `random_bytes` host function.
+///
+/// It is seeded from the same seed and index as [`create_rng`], but with a domain tag
+/// appended, so that bytes handed out to contracts never shift the addresses of new URefs.
+pub fn create_random_bytes_rng(seed: [u8; 32], index: u64) -> ChaChaRng {
+    let mut data: Vec<u8> = Vec::new();
+    data.extend(&seed);
+    data.extend_from_slice(&index.to_le_bytes());
+    data.extend_from_slice(RANDOM_BYTES_DOMAIN);
+    rng_from_data(&data)
+}
+
+/// Gas charged for a single call to the `blake2b` host function, on top of the cost per byte.
+const BLAKE2B_GAS_COST_BASE: u64 = 1_000;
+/// Gas charged for every byte hashed by the `blake2b` host function.
+const BLAKE2B_GAS_COST_PER_BYTE: u64 = 10;
+/// Gas charged for a single call to the `verify_ed25519` host function.
+const VERIFY_ED25519_GAS_COST: u64 = 10_000;
+/// Gas charged for every byte produced by the `random_bytes` host function.
+const RANDOM_BYTES_GAS_COST_PER_BYTE: u64 = 10;
+
+#[allow(clippy::too_many_arguments)]
 fn sub_call<R: StateReader<Key, Value>>(
     parity_module: Module,
+    entry_point: &str,
     args: Vec<Vec<u8>>,
     refs: &mut BTreeMap<String, Key>,
     key: Key,
@@ -146,10 +191,29 @@ fn sub_call<R: StateReader<Key, Value>>(
 where
     R::Error: Into<Error>,
 {
+    // The limit is the one of the deploy's protocol version, not of the called contract's.
+    let max_call_depth = {
+        let deploy_version = current_runtime.context.protocol_version();
+        match WasmLimits::from_version(deploy_version) {
+            Some(wasm_limits) => wasm_limits.max_call_depth,
+            None => return Err(ResolverError::UnknownProtocolVersion(deploy_version).into()),
+        }
+    };
+    if current_runtime.context.call_stack().len() >= max_call_depth {
+        return Err(Error::CallDepthLimit);
+    }
+
     let (instance, memory) = instance_and_memory(parity_module.clone(), protocol_version)?;
 
     let known_urefs = extract_access_rights_from_keys(refs.values().cloned().chain(extra_urefs));
 
+    // Access rights are stripped, so that the callee can't learn them from the call stack.
+    let mut call_stack = current_runtime.context.call_stack().clone();
+    call_stack.push(CallStackElement::new(
+        key.normalize(),
+        entry_point.to_string(),
+    ));
+
     let mut runtime = Runtime {
         memory,
         module: parity_module,
@@ -163,17 +227,21 @@ where
             current_runtime.context.authorization_keys().clone(),
             &current_runtime.context.account(),
             key,
-            current_runtime.context.get_blocktime(),
+            call_stack,
+            current_runtime.context.block_info(),
+            current_runtime.context.deploy_hash(),
             current_runtime.context.gas_limit(),
             current_runtime.context.gas_counter(),
             current_runtime.context.fn_store_id(),
             current_runtime.context.rng(),
+            current_runtime.context.random_bytes_rng(),
             protocol_version,
+            current_runtime.context.phase(),
             current_runtime.context.correlation_id(),
         ),
     };
 
-    let result = instance.invoke_export("call", &[], &mut runtime);
+    let result = instance.invoke_export(entry_point, &[], &mut runtime);
 
     match result {
         Ok(_) => Ok(runtime.result),
@@ -191,10 +259,15 @@ where
                         current_runtime.context.add_urefs(ret_urefs_map);
                         return Ok(runtime.result);
                     }
-                    Error::Revert(status) => {
+                    Error::Revert(status, payload) => {
                         // Propagate revert as revert, instead of passing it as
                         // InterpreterError.
-                        return Err(Error::Revert(*status));
+                        return Err(Error::Revert(*status, payload.clone()));
+                    }
+                    Error::CallDepthLimit => {
+                        // Propagate the limit as is, so that the deploy fails with it rather than
+                        // with the interpreter errors of every frame of the call stack.
+                        return Err(Error::CallDepthLimit);
                     }
                     _ => {}
                 }
@@ -204,6 +277,42 @@ where
     }
 }
 
+/// Returns `true` if a failed contract call must abort the whole deploy, even when the caller
+/// asked to recover from the callee's failure.
+fn is_unrecoverable(error: &Error) -> bool {
+    match error {
+        Error::GasLimit | Error::Storage(_) => true,
+        Error::Interpreter(interpreter_error) => interpreter_error
+            .as_host_error()
+            .and_then(|host_error| host_error.downcast_ref::<Error>())
+            .map(is_unrecoverable)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Checks whether `contract` can be called through `entry_point` with given `args`.
+/// Contracts stored without declared entry points can only be called through the default one,
+/// and their arguments are not checked.
+fn validate_entry_point_call(
+    contract: &Contract,
+    entry_point: &str,
+    args: &[Vec<u8>],
+) -> Result<(), Error> {
+    if contract.entry_points().is_empty() {
+        return if entry_point == DEFAULT_ENTRY_POINT_NAME {
+            Ok(())
+        } else {
+            Err(Error::EntryPointNotFound(entry_point.to_string()))
+        };
+    }
+    match contract.entry_points().get(entry_point) {
+        Some(declared) if declared.accepts(args) => Ok(()),
+        Some(_) => Err(Error::InvalidEntryPointArgs(entry_point.to_string())),
+        None => Err(Error::EntryPointNotFound(entry_point.to_string())),
+    }
+}
+
 impl<'a, R: StateReader<Key, Value>> Runtime<'a, R>
 where
     R::Error: Into<Error>,
@@ -258,6 +367,20 @@ where
         deserialize(&bytes).map_err(Into::into)
     }
 
+    /// Reads a 32 bytes long hash address from Wasm memory.
+    fn hash_from_mem(&self, hash_ptr: u32) -> Result<[u8; 32], Error> {
+        let bytes = self.bytes_from_mem(hash_ptr, 32)?;
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&bytes);
+        Ok(hash)
+    }
+
+    /// Reads serialized URef from Wasm memory.
+    fn uref_from_mem(&self, uref_ptr: u32) -> Result<URef, Error> {
+        let bytes = self.bytes_from_mem(uref_ptr, UREF_SIZE_SERIALIZED)?;
+        deserialize(&bytes).map_err(Into::into)
+    }
+
     /// Reads value (defined as `value_ptr` and `value_size` tuple) from Wasm memory.
     fn value_from_mem(&mut self, value_ptr: u32, value_size: u32) -> Result<Value, Error> {
         let bytes = self.bytes_from_mem(value_ptr, value_size as usize)?;
@@ -292,6 +415,30 @@ where
         }
     }
 
+    /// Returns the module stripped of all code not reachable from the functions exported under
+    /// `names`.
+    fn get_functions_by_names<'b, I>(&self, names: I) -> Result<Vec<u8>, Trap>
+    where
+        I: IntoIterator<Item = &'b String>,
+    {
+        let names: Vec<&str> = names.into_iter().map(String::as_str).collect();
+        if names.is_empty() {
+            return Err(Error::FunctionNotFound(String::from("no entry points")).into());
+        }
+        let exports: Vec<&str> = self
+            .module
+            .export_section()
+            .map(|es| es.entries().iter().map(|e| e.field()).collect())
+            .unwrap_or_default();
+        if let Some(missing) = names.iter().find(|name| !exports.contains(*name)) {
+            return Err(Error::FunctionNotFound(missing.to_string()).into());
+        }
+
+        let mut module = self.module.clone();
+        pwasm_utils::optimize(&mut module, names).unwrap();
+        parity_wasm::serialize(module).map_err(|e| Error::ParityWasm(e).into())
+    }
+
     pub fn value_is_valid(&mut self, value_ptr: u32, value_size: u32) -> Result<bool, Trap> {
         let value = self.value_from_mem(value_ptr, value_size)?;
 
@@ -379,6 +526,37 @@ where
             .map_err(|e| Error::Interpreter(e).into())
     }
 
+    /// Writes the height of the current block to [dest_ptr] in Wasm memory.
+    fn get_block_height(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let height = self
+            .context
+            .get_block_height()
+            .to_bytes()
+            .map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &height)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Writes the hash of the parent of the current block to [dest_ptr] in Wasm memory.
+    fn get_parent_hash(&self, dest_ptr: u32) -> Result<(), Trap> {
+        self.memory
+            .set(dest_ptr, &self.context.get_parent_hash())
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Writes the public key of the proposer of the current block to [dest_ptr] in Wasm memory.
+    fn get_proposer(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let proposer = self
+            .context
+            .get_proposer()
+            .to_bytes()
+            .map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &proposer)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
     pub fn set_mem_from_buf(&mut self, dest_ptr: u32) -> Result<(), Trap> {
         self.memory
             .set(dest_ptr, &self.host_buf)
@@ -416,10 +594,15 @@ where
         }
     }
 
-    /// Calls contract living under a `key`, with supplied `args` and extra `urefs`.
+    /// Calls the `entry_point` of a contract living under a `key`, with supplied `args` and extra
+    /// `urefs`.
+    ///
+    /// Calls to unknown entry points, or with arguments not matching the declared types, are
+    /// rejected before the contract's code is run.
     pub fn call_contract(
         &mut self,
         key: Key,
+        entry_point: &str,
         args_bytes: Vec<u8>,
         urefs_bytes: Vec<u8>,
     ) -> Result<usize, Error> {
@@ -429,7 +612,12 @@ where
                 Some(value) => {
                     if let Value::Contract(contract) = value {
                         let args: Vec<Vec<u8>> = deserialize(&args_bytes)?;
-                        let module = parity_wasm::deserialize_buffer(contract.bytes())?;
+                        validate_entry_point_call(&contract, entry_point, &args)?;
+                        let module = module_cache::get_module(
+                            self.context.correlation_id(),
+                            contract.bytes(),
+                            contract.protocol_version(),
+                        )?;
 
                         Ok((
                             args,
@@ -450,6 +638,7 @@ where
         let extra_urefs = self.context.deserialize_keys(&urefs_bytes)?;
         let result = sub_call(
             module,
+            entry_point,
             args,
             &mut refs,
             key,
@@ -461,12 +650,96 @@ where
         Ok(self.host_buf.len())
     }
 
+    /// Calls the `entry_point` of a contract like [`Runtime::call_contract`], but recovers from
+    /// the callee's failure instead of aborting the whole deploy.
+    ///
+    /// The serialized `Result<Vec<u8>, CallError>` is put in the host buffer. If the callee
+    /// reverted or trapped, all of its effects on the global state are rolled back. Running out
+    /// of gas and storage errors can't be recovered from and are propagated as usual.
+    pub fn try_call_contract(
+        &mut self,
+        key: Key,
+        entry_point: &str,
+        args_bytes: Vec<u8>,
+        urefs_bytes: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let savepoint = self.context.state().borrow().savepoint();
+        let result = match self.call_contract(key, entry_point, args_bytes, urefs_bytes) {
+            Ok(_) => Ok(mem::replace(&mut self.host_buf, Vec::new())),
+            Err(error) => {
+                let call_error = match error {
+                    Error::Revert(status, payload) => {
+                        CallError::Revert(ApiError::from(status), payload)
+                    }
+                    error => {
+                        if is_unrecoverable(&error) {
+                            return Err(error);
+                        }
+                        CallError::Trap
+                    }
+                };
+                self.context.state().borrow_mut().rollback(savepoint);
+                Err(call_error)
+            }
+        };
+        self.host_buf = result.to_bytes()?;
+        Ok(self.host_buf.len())
+    }
+
+    /// Calls a version of the contract package living under `package_hash`, or its latest
+    /// enabled version if `version` is `None`.
+    ///
+    /// The package's hash is used as the base key of the call, so the called contract can't
+    /// add named keys to itself.
+    pub fn call_versioned_contract(
+        &mut self,
+        package_hash: [u8; 32],
+        version: Option<u32>,
+        args_bytes: Vec<u8>,
+        urefs_bytes: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let contract = self.context.get_contract_version(package_hash, version)?;
+        let args: Vec<Vec<u8>> = deserialize(&args_bytes)?;
+        let protocol_version = contract.protocol_version();
+        let module = module_cache::get_module(
+            self.context.correlation_id(),
+            contract.bytes(),
+            protocol_version,
+        )?;
+        let (_, mut refs, _, _) = contract.destructure();
+
+        let extra_urefs = self.context.deserialize_keys(&urefs_bytes)?;
+        let result = sub_call(
+            module,
+            DEFAULT_ENTRY_POINT_NAME,
+            args,
+            &mut refs,
+            Key::Hash(package_hash),
+            self,
+            extra_urefs,
+            protocol_version,
+        )?;
+        self.host_buf = result;
+        Ok(self.host_buf.len())
+    }
+
     pub fn serialize_function(&mut self, name_ptr: u32, name_size: u32) -> Result<usize, Trap> {
         let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
         self.host_buf = fn_bytes;
         Ok(self.host_buf.len())
     }
 
+    fn serialize_call_stack(&mut self) -> Result<usize, Trap> {
+        let bytes: Vec<u8> = self
+            .context
+            .call_stack()
+            .to_bytes()
+            .map_err(Error::BytesRepr)?;
+        let length = bytes.len();
+        self.host_buf = bytes;
+        Ok(length)
+    }
+
     fn serialize_known_urefs(&mut self) -> Result<usize, Trap> {
         let bytes: Vec<u8> = self
             .context
@@ -494,6 +767,92 @@ where
         Ok(new_hash)
     }
 
+    /// Creates a new contract package and writes its address (at `hash_ptr`) and its serialized
+    /// access key (at `access_key_ptr`) into the Wasm memory.
+    fn create_contract_package(&mut self, hash_ptr: u32, access_key_ptr: u32) -> Result<(), Trap> {
+        let (package_hash, access_key) = self.context.create_contract_package()?;
+        self.function_address(package_hash, hash_ptr)?;
+        let access_key_bytes = access_key.to_bytes().map_err(Error::BytesRepr)?;
+        self.memory
+            .set(access_key_ptr, &access_key_bytes)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Stores the function exported under a given name as a new version of a contract package
+    /// and writes the number of the new version at `version_ptr` in the Wasm memory.
+    #[allow(clippy::too_many_arguments)]
+    fn add_contract_version(
+        &mut self,
+        package_hash_ptr: u32,
+        access_key_ptr: u32,
+        name_ptr: u32,
+        name_size: u32,
+        urefs_ptr: u32,
+        urefs_size: u32,
+        version_ptr: u32,
+    ) -> Result<i32, Trap> {
+        let package_hash = self.hash_from_mem(package_hash_ptr)?;
+        let access_key = self.uref_from_mem(access_key_ptr)?;
+        let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
+        let urefs_bytes = self.bytes_from_mem(urefs_ptr, urefs_size as usize)?;
+        let urefs: BTreeMap<String, Key> = deserialize(&urefs_bytes).map_err(Error::BytesRepr)?;
+        let contract = Contract::new(fn_bytes, urefs, self.context.protocol_version());
+
+        match self
+            .context
+            .add_contract_version(package_hash, access_key, contract)
+        {
+            Ok(version) => {
+                let version_bytes = version.to_bytes().map_err(Error::BytesRepr)?;
+                self.memory
+                    .set(version_ptr, &version_bytes)
+                    .map_err(Error::Interpreter)?;
+                Ok(0)
+            }
+            // Same as with `UpdateKeyFailure`, the first variant of `ContractPackageFailure`
+            // starts with number `1`, so `0` is safe to use for a success.
+            Err(Error::ContractPackageFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn disable_contract_version(
+        &mut self,
+        package_hash_ptr: u32,
+        access_key_ptr: u32,
+        version: u32,
+    ) -> Result<i32, Trap> {
+        let package_hash = self.hash_from_mem(package_hash_ptr)?;
+        let access_key = self.uref_from_mem(access_key_ptr)?;
+
+        match self
+            .context
+            .disable_contract_version(package_hash, access_key, version)
+        {
+            Ok(_) => Ok(0),
+            Err(Error::ContractPackageFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Stores functions exported under the names of `entry_points` as a single contract in the
+    /// GlobalState. Unlike `store_function`, the exports keep their names.
+    pub fn store_entry_points(
+        &mut self,
+        entry_points: BTreeMap<String, EntryPoint>,
+        urefs: BTreeMap<String, Key>,
+    ) -> Result<[u8; 32], Trap> {
+        let fn_bytes = self.get_functions_by_names(entry_points.keys())?;
+        let contract = Contract::with_entry_points(
+            fn_bytes,
+            urefs,
+            self.context.protocol_version(),
+            entry_points,
+        );
+        let new_hash = self.context.store_contract(contract.into())?;
+        Ok(new_hash)
+    }
+
     /// Writes function address (`hash_bytes`) into the Wasm memory (at `dest_ptr` pointer).
     fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Trap> {
         self.memory
@@ -572,7 +931,74 @@ where
 
     /// Reverts contract execution with a status specified.
     pub fn revert(&mut self, status: u32) -> Trap {
-        Error::Revert(status).into()
+        Error::Revert(status, Vec::new()).into()
+    }
+
+    /// Reverts contract execution with a status specified, passing along a payload read from the
+    /// Wasm memory.
+    pub fn revert_with_payload(
+        &mut self,
+        status: u32,
+        payload_ptr: u32,
+        payload_size: u32,
+    ) -> Trap {
+        match self.bytes_from_mem(payload_ptr, payload_size as usize) {
+            Ok(payload) => Error::Revert(status, payload).into(),
+            Err(error) => error.into(),
+        }
+    }
+
+    /// Writes the blake2b-256 digest of the data read from the Wasm memory into `out_ptr`.
+    fn blake2b(&mut self, in_ptr: u32, in_size: u32, out_ptr: u32) -> Result<(), Trap> {
+        self.gas(BLAKE2B_GAS_COST_BASE + BLAKE2B_GAS_COST_PER_BYTE * u64::from(in_size))?;
+        let data = self.bytes_from_mem(in_ptr, in_size as usize)?;
+        let digest = Blake2bHash::new(&data);
+        self.memory
+            .set(out_ptr, &digest.to_vec())
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Checks whether the ed25519 signature of a message is valid for the given public key.
+    ///
+    /// Malformed public keys and signatures are reported as invalid signatures rather than
+    /// errors.
+    fn verify_ed25519(
+        &mut self,
+        public_key_ptr: u32,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+    ) -> Result<bool, Trap> {
+        self.gas(VERIFY_ED25519_GAS_COST)?;
+        let public_key: PublicKey = {
+            let bytes = self.bytes_from_mem(public_key_ptr, PUBLIC_KEY_SIZE + U32_SIZE)?;
+            deserialize(&bytes).map_err(Error::BytesRepr)?
+        };
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+        let signature = self.bytes_from_mem(signature_ptr, ed25519_dalek::SIGNATURE_LENGTH)?;
+
+        let public_key = match ed25519_dalek::PublicKey::from_bytes(&public_key.value()) {
+            Ok(public_key) => public_key,
+            Err(_) => return Ok(false),
+        };
+        let signature = match ed25519_dalek::Signature::from_bytes(&signature) {
+            Ok(signature) => signature,
+            Err(_) => return Ok(false),
+        };
+        Ok(public_key.verify(&message, &signature).is_ok())
+    }
+
+    /// Writes `len` bytes drawn from the deploy's random bytes stream into Wasm memory.
+    fn random_bytes(&mut self, dest_ptr: u32, len: u32) -> Result<(), Trap> {
+        self.gas(RANDOM_BYTES_GAS_COST_PER_BYTE * u64::from(len))?;
+        let mut bytes = vec![0u8; len as usize];
+        self.context
+            .random_bytes_rng()
+            .borrow_mut()
+            .fill_bytes(&mut bytes);
+        self.memory
+            .set(dest_ptr, &bytes)
+            .map_err(|e| Error::Interpreter(e).into())
     }
 
     pub fn take_context(self) -> RuntimeContext<'a, R> {
@@ -663,6 +1089,166 @@ where
         }
     }
 
+    fn add_recovery_key(&mut self, public_key_ptr: u32, weight_value: u8) -> Result<i32, Trap> {
+        let public_key: PublicKey = {
+            let source_serialized =
+                self.bytes_from_mem(public_key_ptr, PUBLIC_KEY_SIZE + U32_SIZE)?;
+            deserialize(&source_serialized).map_err(Error::BytesRepr)?
+        };
+        let weight = Weight::new(weight_value);
+
+        match self.context.add_recovery_key(public_key, weight) {
+            Ok(_) => Ok(0),
+            Err(Error::AddKeyFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn remove_recovery_key(&mut self, public_key_ptr: u32) -> Result<i32, Trap> {
+        let public_key: PublicKey = {
+            let source_serialized =
+                self.bytes_from_mem(public_key_ptr, PUBLIC_KEY_SIZE + U32_SIZE)?;
+            deserialize(&source_serialized).map_err(Error::BytesRepr)?
+        };
+        match self.context.remove_recovery_key(public_key) {
+            Ok(_) => Ok(0),
+            Err(Error::RemoveKeyFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_recovery_threshold(&mut self, threshold_value: u8) -> Result<i32, Trap> {
+        let threshold = Weight::new(threshold_value);
+        match self.context.set_recovery_threshold(threshold) {
+            Ok(_) => Ok(0),
+            Err(Error::SetThresholdFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_inactivity_period_limit(&mut self, limit_ptr: u32) -> Result<i32, Trap> {
+        let limit: BlockTime = {
+            let source_serialized = self.bytes_from_mem(limit_ptr, BLOCKTIME_SER_SIZE)?;
+            deserialize(&source_serialized).map_err(Error::BytesRepr)?
+        };
+        match self.context.set_inactivity_period_limit(limit) {
+            Ok(_) => Ok(0),
+            Err(Error::SetThresholdFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_named_threshold(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        threshold_value: u8,
+    ) -> Result<i32, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let threshold = Weight::new(threshold_value);
+        match self.context.set_named_threshold(name, threshold) {
+            Ok(_) => Ok(0),
+            Err(Error::SetThresholdFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns 0 if the authorization keys meet the named threshold, 1 if they don't and 2 if
+    /// the account has no threshold with that name.
+    fn meets_named_threshold(&mut self, name_ptr: u32, name_size: u32) -> Result<i32, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        match self.context.meets_named_threshold(&name) {
+            Some(true) => Ok(0),
+            Some(false) => Ok(1),
+            None => Ok(2),
+        }
+    }
+
+    /// Proposes the action at `action_ptr` and writes its id to `id_dest_ptr`. Returns the
+    /// [`ApprovalStatus`] of the action, or the [`ProposalFailure`] if it can't be proposed.
+    fn propose_action(
+        &mut self,
+        action_ptr: u32,
+        action_size: u32,
+        expires_at_ptr: u32,
+        id_dest_ptr: u32,
+    ) -> Result<i32, Trap> {
+        let action: ProposedAction = {
+            let bytes = self.bytes_from_mem(action_ptr, action_size as usize)?;
+            deserialize(&bytes).map_err(Error::BytesRepr)?
+        };
+        let expires_at: BlockTime = {
+            let bytes = self.bytes_from_mem(expires_at_ptr, BLOCKTIME_SER_SIZE)?;
+            deserialize(&bytes).map_err(Error::BytesRepr)?
+        };
+        let (id, approved) = match self.context.propose_action(action, expires_at) {
+            Ok(result) => result,
+            Err(Error::ProposalFailure(e)) => return Ok(e as i32),
+            Err(e) => return Err(e.into()),
+        };
+        self.memory
+            .set(id_dest_ptr, &id)
+            .map_err(Error::Interpreter)?;
+        self.execute_approved_action(approved)
+    }
+
+    /// Approves the pending action with the id at `id_ptr`. Returns the [`ApprovalStatus`] of
+    /// the action, or the [`ProposalFailure`] if it can't be approved.
+    fn approve_action(&mut self, id_ptr: u32) -> Result<i32, Trap> {
+        let id: [u8; 32] = {
+            let bytes = self.bytes_from_mem(id_ptr, 32)?;
+            deserialize(&bytes).map_err(Error::BytesRepr)?
+        };
+        match self.context.approve_action(id) {
+            Ok(approved) => self.execute_approved_action(approved),
+            Err(Error::ProposalFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Cancels the pending action with the id at `id_ptr`. Returns 0 on success, or the
+    /// [`ProposalFailure`] if it can't be cancelled.
+    fn cancel_action(&mut self, id_ptr: u32) -> Result<i32, Trap> {
+        let id: [u8; 32] = {
+            let bytes = self.bytes_from_mem(id_ptr, 32)?;
+            deserialize(&bytes).map_err(Error::BytesRepr)?
+        };
+        match self.context.cancel_action(id) {
+            Ok(_) => Ok(0),
+            Err(Error::ProposalFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Executes the action if its approvals met its threshold. The action is no longer pending
+    /// at this point, so if it fails, it's up to the contract to revert the deploy.
+    fn execute_approved_action(&mut self, approved: Option<ProposedAction>) -> Result<i32, Trap> {
+        let action = match approved {
+            Some(action) => action,
+            None => return Ok(ApprovalStatus::Pending as i32),
+        };
+        let executed = match action {
+            ProposedAction::Transfer(target, amount) => {
+                let source = self.context.account().purse_id();
+                self.transfer_from_purse_to_account_unchecked(source, target, amount)?
+                    != TransferResult::TransferError
+            }
+            action => match self.context.apply_approved_action(action) {
+                Ok(_) => true,
+                Err(Error::AddKeyFailure(_))
+                | Err(Error::RemoveKeyFailure(_))
+                | Err(Error::UpdateKeyFailure(_))
+                | Err(Error::SetThresholdFailure(_)) => false,
+                Err(e) => return Err(e.into()),
+            },
+        };
+        if executed {
+            Ok(ApprovalStatus::Executed as i32)
+        } else {
+            Ok(ProposalFailure::ActionFailed as i32)
+        }
+    }
+
     /// looks up the public mint contract key in the caller's [uref_lookup] map.
     fn get_mint_contract_public_uref_key(&mut self) -> Result<Key, Error> {
         match self.context.get_uref(MINT_NAME) {
@@ -698,6 +1284,34 @@ where
         Ok(internal_mint_uref)
     }
 
+    /// Looks up the payment purse in the known urefs of the proof of stake contract.
+    fn get_pos_payment_purse(&mut self) -> Result<PurseId, Error> {
+        let pos_contract_key = Key::URef(self.get_pos_contract_uref()?);
+        match self.context.read_gs_direct(&pos_contract_key)? {
+            Some(Value::Contract(contract)) => {
+                match contract.urefs_lookup().get(POS_PAYMENT_PURSE) {
+                    Some(Key::URef(uref)) => Ok(PurseId::new(*uref)),
+                    _ => Err(URefNotFound(String::from(POS_PAYMENT_PURSE))),
+                }
+            }
+            _ => Err(KeyNotFound(pos_contract_key)),
+        }
+    }
+
+    /// Checks whether tokens can be transferred out of `source` into the `target` purse, `None`
+    /// for transfers to accounts.
+    fn can_transfer(&mut self, source: PurseId, target: Option<PurseId>) -> Result<bool, Error> {
+        let into_payment_purse = match target {
+            Some(target)
+                if self.context.phase() == Phase::Payment && self.context.is_main_purse(source) =>
+            {
+                self.get_pos_payment_purse()?.value().addr() == target.value().addr()
+            }
+            _ => false,
+        };
+        Ok(self.context.can_transfer_from(source, into_payment_purse))
+    }
+
     /// Calls the "create" method on the mint contract at the given mint contract key
     fn mint_create(&mut self, mint_contract_key: Key) -> Result<PurseId, Error> {
         let args_bytes = {
@@ -707,7 +1321,12 @@ where
 
         let urefs_bytes = Vec::<Key>::new().to_bytes()?;
 
-        self.call_contract(mint_contract_key, args_bytes, urefs_bytes)?;
+        self.call_contract(
+            mint_contract_key,
+            DEFAULT_ENTRY_POINT_NAME,
+            args_bytes,
+            urefs_bytes,
+        )?;
 
         let result: URef = deserialize(&self.host_buf)?;
 
@@ -737,7 +1356,12 @@ where
 
         let urefs_bytes = vec![Key::URef(source_value), Key::URef(target_value)].to_bytes()?;
 
-        self.call_contract(mint_contract_key, args_bytes, urefs_bytes)?;
+        self.call_contract(
+            mint_contract_key,
+            DEFAULT_ENTRY_POINT_NAME,
+            args_bytes,
+            urefs_bytes,
+        )?;
 
         // This will deserialize `host_buf` into the Result type which carries
         // mint contract error.
@@ -837,6 +1461,20 @@ where
         source: PurseId,
         target: PublicKey,
         amount: U512,
+    ) -> Result<TransferResult, Error> {
+        if !self.can_transfer(source, None)? {
+            return Ok(TransferResult::TransferError);
+        }
+        self.transfer_from_purse_to_account_unchecked(source, target, amount)
+    }
+
+    /// Transfers `amount` of tokens from `source` purse to `target` account without checking
+    /// the transfer threshold, e.g. for transfers approved through a pending action.
+    fn transfer_from_purse_to_account_unchecked(
+        &mut self,
+        source: PurseId,
+        target: PublicKey,
+        amount: U512,
     ) -> Result<TransferResult, Error> {
         let target_key = Key::Account(target.value());
         // Look up the account at the given public key's address
@@ -885,6 +1523,10 @@ where
             deserialize(&bytes).map_err(Error::BytesRepr)?
         };
 
+        if !self.can_transfer(source, Some(target))? {
+            return Ok(PurseTransferResult::TransferError);
+        }
+
         let mint_contract_key = Key::URef(self.get_mint_contract_uref()?);
 
         match self.mint_transfer(mint_contract_key, source, target, amount) {
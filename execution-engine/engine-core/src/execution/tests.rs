@@ -3,7 +3,7 @@ use rand_chacha::ChaChaRng;
 
 use super::Error;
 use engine_state::execution_result::ExecutionResult;
-use execution::create_rng;
+use execution::{create_random_bytes_rng, create_rng};
 
 fn on_fail_charge_test_helper<T>(
     f: impl Fn() -> Result<T, Error>,
@@ -92,3 +92,14 @@ fn should_generate_same_numbers_for_same_seed() {
 
     assert_eq!(random_a, random_b)
 }
+
+#[test]
+fn should_separate_random_bytes_stream_from_uref_stream() {
+    let account_addr = [0u8; 32];
+    let mut rng_a = create_rng(account_addr, 1);
+    let mut rng_b = create_random_bytes_rng(account_addr, 1);
+    let random_a = gen_random(&mut rng_a);
+    let random_b = gen_random(&mut rng_b);
+
+    assert_ne!(random_a, random_b)
+}
@@ -6,14 +6,18 @@ extern crate core;
 
 // third-party dependencies
 extern crate blake2;
+extern crate ed25519_dalek;
 extern crate failure;
 extern crate itertools;
+#[macro_use]
+extern crate lazy_static;
 extern crate linked_hash_map;
 extern crate parity_wasm;
 extern crate parking_lot;
 extern crate pwasm_utils;
 extern crate rand;
 extern crate rand_chacha;
+extern crate secp256k1;
 extern crate wasmi;
 
 // internal dependencies
@@ -28,6 +32,8 @@ extern crate engine_wasm_prep;
 extern crate matches;
 #[cfg(test)]
 extern crate proptest;
+#[cfg(test)]
+extern crate wabt;
 
 #[macro_use]
 extern crate num_derive;
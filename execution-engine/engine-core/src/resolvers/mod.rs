@@ -1,10 +1,13 @@
 pub mod error;
 pub mod memory_resolver;
+mod module_resolver;
 pub mod v1_function_index;
-mod v1_resolver;
 
 use wasmi::ModuleImportResolver;
 
+use engine_wasm_prep::import_set::ImportSet;
+use engine_wasm_prep::wasm_limits::WasmLimits;
+
 use self::error::ResolverError;
 use resolvers::memory_resolver::MemoryResolver;
 
@@ -14,10 +17,14 @@ use resolvers::memory_resolver::MemoryResolver;
 pub fn create_module_resolver(
     protocol_version: u64,
 ) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
-    match protocol_version {
-        1 => Ok(v1_resolver::RuntimeModuleImportResolver::default()),
-        _ => Err(ResolverError::UnknownProtocolVersion(protocol_version)),
-    }
+    let import_set = ImportSet::from_version(protocol_version)
+        .ok_or(ResolverError::UnknownProtocolVersion(protocol_version))?;
+    let wasm_limits = WasmLimits::from_version(protocol_version)
+        .ok_or(ResolverError::UnknownProtocolVersion(protocol_version))?;
+    Ok(module_resolver::RuntimeModuleImportResolver::new(
+        import_set,
+        wasm_limits.max_memory_pages,
+    ))
 }
 
 #[test]
@@ -29,3 +36,8 @@ fn resolve_invalid_module() {
 fn protocol_version_1_always_resolves() {
     assert!(create_module_resolver(1).is_ok());
 }
+
+#[test]
+fn protocol_version_2_always_resolves() {
+    assert!(create_module_resolver(2).is_ok());
+}
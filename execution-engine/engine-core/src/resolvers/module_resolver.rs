@@ -0,0 +1,200 @@
+use std::cell::RefCell;
+
+use wasmi::memory_units::Pages;
+use wasmi::{
+    Error as InterpreterError, FuncRef, MemoryDescriptor, MemoryInstance, Signature, ValueType,
+};
+use wasmi::{FuncInstance, MemoryRef, ModuleImportResolver};
+
+use engine_wasm_prep::import_set::{HostFunctionSignature, ImportSet};
+use parity_wasm::elements::ValueType as ParityValueType;
+
+use super::error::ResolverError;
+use super::memory_resolver::MemoryResolver;
+use super::v1_function_index::FunctionIndex;
+
+/// Resolves the imports of a module against the host functions of an [`ImportSet`].
+pub struct RuntimeModuleImportResolver {
+    import_set: ImportSet,
+    memory: RefCell<Option<MemoryRef>>,
+    max_memory: u32,
+}
+
+impl RuntimeModuleImportResolver {
+    pub fn new(import_set: ImportSet, max_memory: u32) -> Self {
+        RuntimeModuleImportResolver {
+            import_set,
+            memory: RefCell::new(None),
+            max_memory,
+        }
+    }
+}
+
+impl MemoryResolver for RuntimeModuleImportResolver {
+    fn memory_ref(&self) -> Result<MemoryRef, ResolverError> {
+        self.memory
+            .borrow()
+            .as_ref()
+            .map(Clone::clone)
+            .ok_or(ResolverError::NoImportedMemory)
+    }
+}
+
+impl ModuleImportResolver for RuntimeModuleImportResolver {
+    fn resolve_func(
+        &self,
+        field_name: &str,
+        _signature: &Signature,
+    ) -> Result<FuncRef, InterpreterError> {
+        let host_function = self.import_set.get(field_name).and_then(|signature| {
+            host_function_index(field_name).map(|function_index| (signature, function_index))
+        });
+        match host_function {
+            Some((signature, function_index)) => Ok(FuncInstance::alloc_host(
+                to_wasmi_signature(signature),
+                function_index.into(),
+            )),
+            None => Err(InterpreterError::Function(format!(
+                "host module doesn't export function with name {}",
+                field_name
+            ))),
+        }
+    }
+
+    fn resolve_memory(
+        &self,
+        field_name: &str,
+        descriptor: &MemoryDescriptor,
+    ) -> Result<MemoryRef, InterpreterError> {
+        if field_name == "memory" {
+            let effective_max = descriptor.maximum().unwrap_or(self.max_memory + 1);
+            if descriptor.initial() > self.max_memory || effective_max > self.max_memory {
+                Err(InterpreterError::Instantiation(
+                    "Module requested too much memory".to_owned(),
+                ))
+            } else {
+                // Note: each "page" is 64 KiB
+                let mem = MemoryInstance::alloc(
+                    Pages(descriptor.initial() as usize),
+                    descriptor.maximum().map(|x| Pages(x as usize)),
+                )?;
+                *self.memory.borrow_mut() = Some(mem.clone());
+                Ok(mem)
+            }
+        } else {
+            Err(InterpreterError::Instantiation(
+                "Memory imported under unknown name".to_owned(),
+            ))
+        }
+    }
+}
+
+fn to_wasmi_value_type(value_type: ParityValueType) -> ValueType {
+    match value_type {
+        ParityValueType::I32 => ValueType::I32,
+        ParityValueType::I64 => ValueType::I64,
+        ParityValueType::F32 => ValueType::F32,
+        ParityValueType::F64 => ValueType::F64,
+    }
+}
+
+fn to_wasmi_signature(signature: &HostFunctionSignature) -> Signature {
+    let params: Vec<ValueType> = signature
+        .params()
+        .iter()
+        .cloned()
+        .map(to_wasmi_value_type)
+        .collect();
+    Signature::new(params, signature.return_type().map(to_wasmi_value_type))
+}
+
+/// Returns the index under which the host function named `name` is dispatched in
+/// `Externals::invoke_index`.
+fn host_function_index(name: &str) -> Option<FunctionIndex> {
+    let function_index = match name {
+        "read_value" => FunctionIndex::ReadFuncIndex,
+        "read_value_local" => FunctionIndex::ReadLocalFuncIndex,
+        "serialize_function" => FunctionIndex::SerFnFuncIndex,
+        "serialize_known_urefs" => FunctionIndex::SerKnownURefs,
+        "write" => FunctionIndex::WriteFuncIndex,
+        "write_local" => FunctionIndex::WriteLocalFuncIndex,
+        "get_read" => FunctionIndex::GetReadFuncIndex,
+        "get_function" => FunctionIndex::GetFnFuncIndex,
+        "add" => FunctionIndex::AddFuncIndex,
+        "new_uref" => FunctionIndex::NewFuncIndex,
+        "load_arg" => FunctionIndex::LoadArgFuncIndex,
+        "get_arg" => FunctionIndex::GetArgFuncIndex,
+        "ret" => FunctionIndex::RetFuncIndex,
+        "call_contract" => FunctionIndex::CallContractFuncIndex,
+        "call_contract_entry_point" => FunctionIndex::CallContractEntryPointFuncIndex,
+        "try_call_contract" => FunctionIndex::TryCallContractFuncIndex,
+        "get_call_result" => FunctionIndex::GetCallResultFuncIndex,
+        "get_uref" => FunctionIndex::GetURefFuncIndex,
+        "has_uref_name" => FunctionIndex::HasURefFuncIndex,
+        "add_uref" => FunctionIndex::AddURefFuncIndex,
+        "gas" => FunctionIndex::GasFuncIndex,
+        "store_function" => FunctionIndex::StoreFnIndex,
+        "store_entry_points" => FunctionIndex::StoreEntryPointsIndex,
+        "protocol_version" => FunctionIndex::ProtocolVersionFuncIndex,
+        "is_valid" => FunctionIndex::IsValidFnIndex,
+        "revert" => FunctionIndex::RevertFuncIndex,
+        "revert_with_payload" => FunctionIndex::RevertWithPayloadFuncIndex,
+        "blake2b" => FunctionIndex::Blake2bFuncIndex,
+        "verify_ed25519" => FunctionIndex::VerifyEd25519FuncIndex,
+        "random_bytes" => FunctionIndex::RandomBytesFuncIndex,
+        "add_associated_key" => FunctionIndex::AddAssociatedKeyFuncIndex,
+        "remove_associated_key" => FunctionIndex::RemoveAssociatedKeyFuncIndex,
+        "update_associated_key" => FunctionIndex::UpdateAssociatedKeyFuncIndex,
+        "set_action_threshold" => FunctionIndex::SetActionThresholdFuncIndex,
+        "add_recovery_key" => FunctionIndex::AddRecoveryKeyFuncIndex,
+        "remove_recovery_key" => FunctionIndex::RemoveRecoveryKeyFuncIndex,
+        "set_recovery_threshold" => FunctionIndex::SetRecoveryThresholdFuncIndex,
+        "set_inactivity_period_limit" => FunctionIndex::SetInactivityPeriodLimitFuncIndex,
+        "set_named_threshold" => FunctionIndex::SetNamedThresholdFuncIndex,
+        "meets_named_threshold" => FunctionIndex::MeetsNamedThresholdFuncIndex,
+        "propose_action" => FunctionIndex::ProposeActionFuncIndex,
+        "approve_action" => FunctionIndex::ApproveActionFuncIndex,
+        "cancel_action" => FunctionIndex::CancelActionFuncIndex,
+        "list_known_urefs" => FunctionIndex::ListKnownURefsIndex,
+        "remove_uref" => FunctionIndex::RemoveURef,
+        "get_caller" => FunctionIndex::GetCallerIndex,
+        "get_blocktime" => FunctionIndex::GetBlocktimeIndex,
+        "serialize_call_stack" => FunctionIndex::SerializeCallStackIndex,
+        "get_call_stack" => FunctionIndex::GetCallStackIndex,
+        "get_block_height" => FunctionIndex::GetBlockHeightIndex,
+        "get_parent_hash" => FunctionIndex::GetParentHashIndex,
+        "get_proposer" => FunctionIndex::GetProposerIndex,
+        "create_purse" => FunctionIndex::CreatePurseIndex,
+        "transfer_to_account" => FunctionIndex::TransferToAccountIndex,
+        "transfer_from_purse_to_account" => FunctionIndex::TransferFromPurseToAccountIndex,
+        "transfer_from_purse_to_purse" => FunctionIndex::TransferFromPurseToPurseIndex,
+        "get_balance" => FunctionIndex::GetBalanceIndex,
+        "create_contract_package" => FunctionIndex::CreateContractPackageIndex,
+        "add_contract_version" => FunctionIndex::AddContractVersionIndex,
+        "disable_contract_version" => FunctionIndex::DisableContractVersionIndex,
+        "call_versioned_contract" => FunctionIndex::CallVersionedContractIndex,
+        _ => return None,
+    };
+    Some(function_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_wasm_prep::import_set::ImportSet;
+
+    use super::host_function_index;
+
+    #[test]
+    fn every_host_function_in_import_sets_should_have_index() {
+        for protocol_version in 1..=2 {
+            let import_set = ImportSet::from_version(protocol_version).expect("should have set");
+            for name in import_set.names() {
+                assert!(
+                    host_function_index(name).is_some(),
+                    "{} is not dispatched",
+                    name
+                );
+            }
+        }
+    }
+}
@@ -40,6 +40,31 @@ pub enum FunctionIndex {
     TransferFromPurseToAccountIndex = 33,
     TransferFromPurseToPurseIndex = 34,
     GetBalanceIndex = 35,
+    CreateContractPackageIndex = 36,
+    AddContractVersionIndex = 37,
+    DisableContractVersionIndex = 38,
+    CallVersionedContractIndex = 39,
+    StoreEntryPointsIndex = 40,
+    CallContractEntryPointFuncIndex = 41,
+    RevertWithPayloadFuncIndex = 42,
+    TryCallContractFuncIndex = 43,
+    Blake2bFuncIndex = 44,
+    VerifyEd25519FuncIndex = 45,
+    GetBlockHeightIndex = 46,
+    GetParentHashIndex = 47,
+    GetProposerIndex = 48,
+    SerializeCallStackIndex = 49,
+    GetCallStackIndex = 50,
+    RandomBytesFuncIndex = 51,
+    AddRecoveryKeyFuncIndex = 52,
+    RemoveRecoveryKeyFuncIndex = 53,
+    SetRecoveryThresholdFuncIndex = 54,
+    SetInactivityPeriodLimitFuncIndex = 55,
+    SetNamedThresholdFuncIndex = 56,
+    MeetsNamedThresholdFuncIndex = 57,
+    ProposeActionFuncIndex = 58,
+    ApproveActionFuncIndex = 59,
+    CancelActionFuncIndex = 60,
 }
 
 impl Into<usize> for FunctionIndex {
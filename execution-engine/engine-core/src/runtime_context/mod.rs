@@ -13,18 +13,20 @@ use rand::RngCore;
 use rand_chacha::ChaChaRng;
 
 use contract_ffi::bytesrepr::{deserialize, ToBytes};
+use contract_ffi::contract_api::call_stack::CallStackElement;
 use contract_ffi::key::{Key, LOCAL_SEED_SIZE};
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{
-    Account, ActionType, AddKeyFailure, BlockTime, PublicKey, RemoveKeyFailure,
-    SetThresholdFailure, UpdateKeyFailure, Weight,
+    Account, ActionType, AddKeyFailure, BlockTime, ProposalFailure, ProposedAction, PublicKey,
+    PurseId, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure, Weight,
 };
-use contract_ffi::value::{Contract, Value};
+use contract_ffi::value::contract_package::ContractPackageFailure;
+use contract_ffi::value::{Contract, ContractPackage, Value};
 use engine_shared::newtypes::{CorrelationId, Validated};
 use engine_storage::global_state::StateReader;
 
 use engine_state::execution_effect::ExecutionEffect;
-use execution::Error;
+use execution::{BlockInfo, Error, Phase, BLOCK_HASH_LENGTH};
 use tracking_copy::{AddResult, TrackingCopy};
 use URefAddr;
 
@@ -42,12 +44,19 @@ pub struct RuntimeContext<'a, R> {
     // Key pointing to the entity we are currently running
     //(could point at an account or contract in the global state)
     base_key: Key,
-    blocktime: BlockTime,
+    // Stack of calls leading to the entity we are currently running, ending with it
+    call_stack: Vec<CallStackElement>,
+    block_info: BlockInfo,
+    // Hash of the deploy being executed, unique to it
+    deploy_hash: [u8; 32],
     gas_limit: u64,
     gas_counter: u64,
     fn_store_id: u32,
     rng: Rc<RefCell<ChaChaRng>>,
+    // Separate stream serving the `random_bytes` host function
+    random_bytes_rng: Rc<RefCell<ChaChaRng>>,
     protocol_version: u64,
+    phase: Phase,
     correlation_id: CorrelationId,
 }
 
@@ -64,12 +73,16 @@ where
         authorization_keys: BTreeSet<PublicKey>,
         account: &'a Account,
         base_key: Key,
-        blocktime: BlockTime,
+        call_stack: Vec<CallStackElement>,
+        block_info: BlockInfo,
+        deploy_hash: [u8; 32],
         gas_limit: u64,
         gas_counter: u64,
         fn_store_id: u32,
         rng: Rc<RefCell<ChaChaRng>>,
+        random_bytes_rng: Rc<RefCell<ChaChaRng>>,
         protocol_version: u64,
+        phase: Phase,
         correlation_id: CorrelationId,
     ) -> Self {
         RuntimeContext {
@@ -79,13 +92,17 @@ where
             args,
             account,
             authorization_keys,
-            blocktime,
+            block_info,
+            deploy_hash,
             base_key,
+            call_stack,
             gas_limit,
             gas_counter,
             fn_store_id,
             rng,
+            random_bytes_rng,
             protocol_version,
+            phase,
             correlation_id,
         }
     }
@@ -189,8 +206,32 @@ where
         self.account.pub_key().into()
     }
 
+    pub fn call_stack(&self) -> &Vec<CallStackElement> {
+        &self.call_stack
+    }
+
+    pub fn block_info(&self) -> BlockInfo {
+        self.block_info
+    }
+
+    pub fn deploy_hash(&self) -> [u8; 32] {
+        self.deploy_hash
+    }
+
     pub fn get_blocktime(&self) -> BlockTime {
-        self.blocktime
+        self.block_info.time()
+    }
+
+    pub fn get_block_height(&self) -> u64 {
+        self.block_info.height()
+    }
+
+    pub fn get_parent_hash(&self) -> [u8; BLOCK_HASH_LENGTH] {
+        self.block_info.parent_hash()
+    }
+
+    pub fn get_proposer(&self) -> PublicKey {
+        self.block_info.proposer()
     }
 
     pub fn add_urefs(&mut self, urefs_map: HashMap<URefAddr, HashSet<AccessRights>>) {
@@ -209,6 +250,10 @@ where
         Rc::clone(&self.rng)
     }
 
+    pub fn random_bytes_rng(&self) -> Rc<RefCell<ChaChaRng>> {
+        Rc::clone(&self.random_bytes_rng)
+    }
+
     pub fn state(&self) -> Rc<RefCell<TrackingCopy<R>>> {
         Rc::clone(&self.state)
     }
@@ -246,19 +291,22 @@ where
         self.protocol_version
     }
 
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
     pub fn correlation_id(&self) -> CorrelationId {
         self.correlation_id
     }
 
     /// Generates new function address.
-    /// Function address is deterministic. It is a hash of public key, nonce and `fn_store_id`,
+    /// Function address is deterministic. It is a hash of the deploy hash and `fn_store_id`,
     /// which is a counter that is being incremented after every function generation.
-    /// If function address was based only on account's public key and deploy's nonce,
+    /// If function address was based only on the deploy hash,
     /// then all function addresses generated within one deploy would have been the same.
     pub fn new_function_address(&mut self) -> Result<[u8; 32], Error> {
-        let mut pre_hash_bytes = Vec::with_capacity(44); //32 byte pk + 8 byte nonce + 4 byte ID
-        pre_hash_bytes.extend_from_slice(&self.account().pub_key());
-        pre_hash_bytes.append(&mut self.account().nonce().to_bytes()?);
+        let mut pre_hash_bytes = Vec::with_capacity(36); //32 byte deploy hash + 4 byte ID
+        pre_hash_bytes.extend_from_slice(&self.deploy_hash);
         pre_hash_bytes.append(&mut self.fn_store_id().to_bytes()?);
 
         self.inc_fn_store_id();
@@ -416,6 +464,103 @@ where
         Ok(new_hash)
     }
 
+    /// Creates an empty contract package under a new hash address. Returns the address of the
+    /// package together with the access key required to modify it.
+    pub fn create_contract_package(&mut self) -> Result<([u8; 32], URef), Error> {
+        let access_key = match self.new_uref(Value::Unit)? {
+            Key::URef(uref) => uref,
+            other => panic!("new_uref should create a URef, got {:?}", other),
+        };
+        let package_hash = self.new_function_address()?;
+        let validated_key = Validated::new(Key::Hash(package_hash), Validated::valid)?;
+        let validated_value = Validated::new(
+            Value::ContractPackage(ContractPackage::new(access_key, BTreeMap::new())),
+            |value| self.validate_keys(&value),
+        )?;
+        self.state
+            .borrow_mut()
+            .write(validated_key, validated_value);
+        Ok((package_hash, access_key))
+    }
+
+    /// Adds `contract` as the newest version of the package stored under `package_hash`.
+    /// Returns the number of the added version.
+    pub fn add_contract_version(
+        &mut self,
+        package_hash: [u8; 32],
+        access_key: URef,
+        contract: Contract,
+    ) -> Result<u32, Error> {
+        let mut package = self.get_authorized_contract_package(package_hash, access_key)?;
+        contract
+            .urefs_lookup()
+            .values()
+            .try_for_each(|key| self.validate_key(key))?;
+        let version = package.add_version(contract);
+        self.write_contract_package(package_hash, package)?;
+        Ok(version)
+    }
+
+    /// Disables given version of the package stored under `package_hash`.
+    pub fn disable_contract_version(
+        &mut self,
+        package_hash: [u8; 32],
+        access_key: URef,
+        version: u32,
+    ) -> Result<(), Error> {
+        let mut package = self.get_authorized_contract_package(package_hash, access_key)?;
+        package.disable_version(version)?;
+        self.write_contract_package(package_hash, package)
+    }
+
+    /// Returns the contract stored as `version` of the package under `package_hash`, or the
+    /// latest enabled version of it if `version` is `None`.
+    pub fn get_contract_version(
+        &mut self,
+        package_hash: [u8; 32],
+        version: Option<u32>,
+    ) -> Result<Contract, Error> {
+        let package: ContractPackage = self.read_gs_typed(&Key::Hash(package_hash))?;
+        let contract = match version {
+            Some(version) => package.get_version(version)?,
+            None => package.latest_enabled_version()?.1,
+        };
+        Ok(contract.clone())
+    }
+
+    fn get_authorized_contract_package(
+        &mut self,
+        package_hash: [u8; 32],
+        access_key: URef,
+    ) -> Result<ContractPackage, Error> {
+        // A URef without access rights is never validated against `known_urefs`, so it can't be
+        // used to prove ownership of the package.
+        if !access_key.is_writeable() {
+            return Err(ContractPackageFailure::PermissionDenied.into());
+        }
+        self.validate_uref(&access_key)?;
+        let package: ContractPackage = self.read_gs_typed(&Key::Hash(package_hash))?;
+        if !package.is_authorized_by(&access_key) {
+            return Err(ContractPackageFailure::PermissionDenied.into());
+        }
+        Ok(package)
+    }
+
+    fn write_contract_package(
+        &mut self,
+        package_hash: [u8; 32],
+        package: ContractPackage,
+    ) -> Result<(), Error> {
+        // Keys of the existing versions were validated when they were added, and the caller
+        // might not know all of them, so the package is not validated as a whole.
+        let validated_key = Validated::new(Key::Hash(package_hash), Validated::valid)?;
+        let validated_value = Validated::new(Value::ContractPackage(package), Validated::valid)?;
+        self.state
+            .borrow_mut()
+            .write(validated_key, validated_value);
+        Ok(())
+    }
+
     pub fn insert_named_uref(&mut self, name: String, key: Validated<Key>) {
         if let Key::URef(uref) = *key {
             self.insert_uref(uref);
@@ -464,6 +609,14 @@ where
                 .urefs_lookup()
                 .values()
                 .try_for_each(|key| self.validate_key(key)),
+            Value::ContractPackage(package) => {
+                self.validate_uref(&package.access_key())?;
+                package
+                    .versions()
+                    .values()
+                    .flat_map(|version| version.contract().urefs_lookup().values())
+                    .try_for_each(|key| self.validate_key(key))
+            }
         }
     }
 
@@ -611,143 +764,242 @@ where
         public_key: PublicKey,
         weight: Weight,
     ) -> Result<(), Error> {
-        // Check permission to modify associated keys
-        if self.base_key() != Key::Account(self.account().pub_key()) {
-            // Exit early with error to avoid mutations
-            return Err(AddKeyFailure::PermissionDenied.into());
-        }
-
-        if !self
-            .account()
-            .can_manage_keys_with(&self.authorization_keys)
-        {
-            // Exit early if authorization keys weight doesn't exceed required
-            // key management threshold
-            return Err(AddKeyFailure::PermissionDenied.into());
-        }
-
-        // Converts an account's public key into a URef
-        let key = Key::Account(self.account().pub_key());
-
-        // Take an account out of the global state
-        let mut account: Account = self.read_gs_typed(&key)?;
-
-        // Exit early in case of error without updating global state
-        account
-            .add_associated_key(public_key, weight)
-            .map_err(Error::from)?;
+        self.manage_account(AddKeyFailure::PermissionDenied, true, |account| {
+            account.add_associated_key(public_key, weight)
+        })
+    }
 
-        let validated_uref = Validated::new(key, Validated::valid)?;
-        let validated_value =
-            Validated::new(Value::Account(account), |value| self.validate_keys(value))?;
+    pub fn remove_associated_key(&mut self, public_key: PublicKey) -> Result<(), Error> {
+        self.manage_account(RemoveKeyFailure::PermissionDenied, true, |account| {
+            account.remove_associated_key(public_key)
+        })
+    }
 
-        self.state
-            .borrow_mut()
-            .write(validated_uref, validated_value);
+    pub fn update_associated_key(
+        &mut self,
+        public_key: PublicKey,
+        weight: Weight,
+    ) -> Result<(), Error> {
+        self.manage_account(UpdateKeyFailure::PermissionDenied, true, |account| {
+            account.update_associated_key(public_key, weight)
+        })
+    }
 
-        Ok(())
+    pub fn set_action_threshold(
+        &mut self,
+        action_type: ActionType,
+        threshold: Weight,
+    ) -> Result<(), Error> {
+        self.manage_account(
+            SetThresholdFailure::PermissionDeniedError,
+            true,
+            |account| account.set_action_threshold(action_type, threshold),
+        )
     }
 
-    pub fn remove_associated_key(&mut self, public_key: PublicKey) -> Result<(), Error> {
-        // Check permission to modify associated keys
-        if self.base_key() != Key::Account(self.account().pub_key()) {
-            // Exit early with error to avoid mutations
-            return Err(RemoveKeyFailure::PermissionDenied.into());
-        }
+    pub fn add_recovery_key(&mut self, public_key: PublicKey, weight: Weight) -> Result<(), Error> {
+        self.manage_account(AddKeyFailure::PermissionDenied, false, |account| {
+            account.add_recovery_key(public_key, weight)
+        })
+    }
 
-        if !self
-            .account()
-            .can_manage_keys_with(&self.authorization_keys)
-        {
-            // Exit early if authorization keys weight doesn't exceed required
-            // key management threshold
-            return Err(RemoveKeyFailure::PermissionDenied.into());
-        }
+    pub fn remove_recovery_key(&mut self, public_key: PublicKey) -> Result<(), Error> {
+        self.manage_account(RemoveKeyFailure::PermissionDenied, false, |account| {
+            account.remove_recovery_key(public_key)
+        })
+    }
 
-        // Converts an account's public key into a URef
-        let key = Key::Account(self.account().pub_key());
+    pub fn set_recovery_threshold(&mut self, threshold: Weight) -> Result<(), Error> {
+        self.manage_account(
+            SetThresholdFailure::PermissionDeniedError,
+            false,
+            |account| account.set_recovery_threshold(threshold),
+        )
+    }
 
-        // Take an account out of the global state
-        let mut account: Account = self.read_gs_typed(&key)?;
+    pub fn set_inactivity_period_limit(&mut self, limit: BlockTime) -> Result<(), Error> {
+        self.manage_account(
+            SetThresholdFailure::PermissionDeniedError,
+            false,
+            |account| {
+                account
+                    .get_account_activity_mut()
+                    .update_inactivity_period_limit(limit);
+                Ok(())
+            },
+        )
+    }
 
-        // Exit early in case of error without updating global state
-        account
-            .remove_associated_key(public_key)
-            .map_err(Error::from)?;
+    pub fn set_named_threshold(&mut self, name: String, threshold: Weight) -> Result<(), Error> {
+        self.manage_account(
+            SetThresholdFailure::PermissionDeniedError,
+            true,
+            |account| account.set_named_threshold(name, threshold),
+        )
+    }
 
-        let validated_uref = Validated::new(key, Validated::valid)?;
-        let validated_value =
-            Validated::new(Value::Account(account), |value| self.validate_keys(value))?;
+    /// Checks whether the authorization keys meet the named threshold of the account, `None` if
+    /// it has no threshold with that name. Like all other thresholds, it is checked against the
+    /// account as it was before the deploy.
+    pub fn meets_named_threshold(&self, name: &str) -> Option<bool> {
+        self.account()
+            .meets_named_threshold(name, &self.authorization_keys)
+    }
 
-        self.state
-            .borrow_mut()
-            .write(validated_uref, validated_value);
+    /// Checks whether `purse` is the main purse of the account, whatever its access rights.
+    pub fn is_main_purse(&self, purse: PurseId) -> bool {
+        purse.value().addr() == self.account().purse_id().value().addr()
+    }
 
-        Ok(())
+    /// Checks whether tokens can be transferred out of `source`, `into_payment_purse` telling
+    /// whether they go into the payment purse of the proof of stake contract.
+    ///
+    /// Only transfers out of the main purse of the account are restricted. Session code needs
+    /// authorization keys meeting the transfer threshold of the account, and payment code can
+    /// only transfer into the payment purse.
+    pub fn can_transfer_from(&self, source: PurseId, into_payment_purse: bool) -> bool {
+        if !self.is_main_purse(source) {
+            return true;
+        }
+        match self.phase {
+            Phase::Session => self.account().can_transfer_with(&self.authorization_keys),
+            Phase::Payment => into_payment_purse,
+            Phase::System => true,
+        }
     }
 
-    pub fn update_associated_key(
+    /// Proposes `action` on the account, approved by the authorization keys and pending until
+    /// `expires_at`. Returns the id of the action, along with the action itself if the
+    /// authorization keys already meet its threshold and it has to be executed right away.
+    pub fn propose_action(
         &mut self,
-        public_key: PublicKey,
-        weight: Weight,
-    ) -> Result<(), Error> {
-        // Check permission to modify associated keys
+        action: ProposedAction,
+        expires_at: BlockTime,
+    ) -> Result<([u8; 32], Option<ProposedAction>), Error> {
         if self.base_key() != Key::Account(self.account().pub_key()) {
-            // Exit early with error to avoid mutations
-            return Err(UpdateKeyFailure::PermissionDenied.into());
+            return Err(ProposalFailure::PermissionDenied.into());
         }
 
-        if !self
-            .account()
-            .can_manage_keys_with(&self.authorization_keys)
-        {
-            // Exit early if authorization keys weight doesn't exceed required
-            // key management threshold
-            return Err(UpdateKeyFailure::PermissionDenied.into());
-        }
+        let mut id = [0u8; 32];
+        self.rng.borrow_mut().fill_bytes(&mut id);
 
-        // Converts an account's public key into a URef
-        let key = Key::Account(self.account().pub_key());
-
-        // Take an account out of the global state
-        let mut account: Account = self.read_gs_typed(&key)?;
+        let blocktime = self.get_blocktime();
+        let approvals = self.authorization_keys.clone();
+        let mut approved = None;
+        self.update_account(|account| -> Result<(), ProposalFailure> {
+            approved = account.propose_action(id, action, approvals, expires_at, blocktime)?;
+            Ok(())
+        })?;
+        Ok((id, approved))
+    }
 
-        // Exit early in case of error without updating global state
-        account
-            .update_associated_key(public_key, weight)
-            .map_err(Error::from)?;
+    /// Approves the pending action with the given id by the authorization keys. Returns the
+    /// action if its approvals met its threshold and it has to be executed.
+    pub fn approve_action(&mut self, id: [u8; 32]) -> Result<Option<ProposedAction>, Error> {
+        if self.base_key() != Key::Account(self.account().pub_key()) {
+            return Err(ProposalFailure::PermissionDenied.into());
+        }
 
-        let validated_uref = Validated::new(key, Validated::valid)?;
-        let validated_value =
-            Validated::new(Value::Account(account), |value| self.validate_keys(value))?;
+        let blocktime = self.get_blocktime();
+        let approvals = self.authorization_keys.clone();
+        let mut approved = None;
+        self.update_account(|account| -> Result<(), ProposalFailure> {
+            approved = account.approve_pending_action(&id, &approvals, blocktime)?;
+            Ok(())
+        })?;
+        Ok(approved)
+    }
 
-        self.state
-            .borrow_mut()
-            .write(validated_uref, validated_value);
+    /// Cancels the pending action with the given id, which requires authorization keys meeting
+    /// the key management threshold.
+    pub fn cancel_action(&mut self, id: [u8; 32]) -> Result<(), Error> {
+        self.manage_account(ProposalFailure::PermissionDenied, false, |account| {
+            account.cancel_pending_action(&id)
+        })
+    }
 
-        Ok(())
+    /// Applies an approved key management action to the account. As the approvals already met
+    /// the key management threshold, the authorization keys aren't checked again.
+    pub fn apply_approved_action(&mut self, action: ProposedAction) -> Result<(), Error> {
+        let blocktime = self.get_blocktime();
+        self.update_account(|account| -> Result<(), Error> {
+            match action {
+                ProposedAction::AddAssociatedKey(public_key, weight) => {
+                    account.add_associated_key(public_key, weight)?
+                }
+                ProposedAction::RemoveAssociatedKey(public_key) => {
+                    account.remove_associated_key(public_key)?
+                }
+                ProposedAction::UpdateAssociatedKey(public_key, weight) => {
+                    account.update_associated_key(public_key, weight)?
+                }
+                ProposedAction::SetActionThreshold(action_type, threshold) => {
+                    account.set_action_threshold(action_type, threshold)?
+                }
+                // Transfers are executed by the runtime, which has access to the mint
+                ProposedAction::Transfer(_, _) => {
+                    return Err(ProposalFailure::ActionFailed.into());
+                }
+            }
+            account
+                .get_account_activity_mut()
+                .update_key_management_last_used(blocktime);
+            Ok(())
+        })
     }
 
-    pub fn set_action_threshold(
+    /// Applies `update` to the account in the global state and records the block time as the
+    /// last time keys of the account were managed.
+    ///
+    /// Fails with `permission_denied` unless running in the context of the account with
+    /// authorization keys which meet its key management threshold. If `allow_recovery` is set,
+    /// the recovery keys of an inactive account are accepted as well.
+    fn manage_account<E, F>(
         &mut self,
-        action_type: ActionType,
-        threshold: Weight,
-    ) -> Result<(), Error> {
+        permission_denied: E,
+        allow_recovery: bool,
+        update: F,
+    ) -> Result<(), Error>
+    where
+        E: Into<Error>,
+        F: FnOnce(&mut Account) -> Result<(), E>,
+    {
         // Check permission to modify associated keys
         if self.base_key() != Key::Account(self.account().pub_key()) {
             // Exit early with error to avoid mutations
-            return Err(SetThresholdFailure::PermissionDeniedError.into());
+            return Err(permission_denied.into());
         }
 
-        if !self
-            .account()
-            .can_manage_keys_with(&self.authorization_keys)
+        let can_recover = allow_recovery
+            && self
+                .account()
+                .can_recover_with(&self.authorization_keys, self.get_blocktime());
+        if !can_recover
+            && !self
+                .account()
+                .can_manage_keys_with(&self.authorization_keys)
         {
             // Exit early if authorization keys weight doesn't exceed required
             // key management threshold
-            return Err(SetThresholdFailure::PermissionDeniedError.into());
+            return Err(permission_denied.into());
         }
 
+        let blocktime = self.get_blocktime();
+        self.update_account(|account| -> Result<(), E> {
+            update(account)?;
+            account
+                .get_account_activity_mut()
+                .update_key_management_last_used(blocktime);
+            Ok(())
+        })
+    }
+
+    /// Applies `update` to the account in the global state.
+    fn update_account<E, F>(&mut self, update: F) -> Result<(), Error>
+    where
+        E: Into<Error>,
+        F: FnOnce(&mut Account) -> Result<(), E>,
+    {
         // Converts an account's public key into a URef
         let key = Key::Account(self.account().pub_key());
 
@@ -755,9 +1007,7 @@ where
         let mut account: Account = self.read_gs_typed(&key)?;
 
         // Exit early in case of error without updating global state
-        account
-            .set_action_threshold(action_type, threshold)
-            .map_err(Error::from)?;
+        update(&mut account).map_err(Into::into)?;
 
         let validated_uref = Validated::new(key, Validated::valid)?;
         let validated_value =
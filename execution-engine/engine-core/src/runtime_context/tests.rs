@@ -6,6 +6,7 @@ use std::rc::Rc;
 use rand::RngCore;
 use rand_chacha::ChaChaRng;
 
+use contract_ffi::contract_api::call_stack::CallStackElement;
 use contract_ffi::key::{Key, LOCAL_SEED_SIZE};
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::{self, Account, Contract, Value};
@@ -15,11 +16,13 @@ use engine_storage::global_state::{CommitResult, History};
 
 use super::{Error, RuntimeContext, URefAddr, Validated};
 use contract_ffi::value::account::{
-    AccountActivity, ActionType, AddKeyFailure, AssociatedKeys, BlockTime, PublicKey, PurseId,
-    RemoveKeyFailure, SetThresholdFailure, Weight,
+    AccountActivity, ActionType, AddKeyFailure, AssociatedKeys, BlockTime, ProposalFailure,
+    ProposedAction, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure, Weight,
 };
 use engine_shared::newtypes::CorrelationId;
-use execution::{create_rng, extract_access_rights_from_keys};
+use execution::{
+    create_random_bytes_rng, create_rng, extract_access_rights_from_keys, BlockInfo, Phase,
+};
 use tracking_copy::TrackingCopy;
 
 fn mock_tc(init_key: Key, init_account: value::Account) -> TrackingCopy<InMemoryGlobalState> {
@@ -51,12 +54,12 @@ fn mock_account_with_purse_id(addr: [u8; 32], purse_id: [u8; 32]) -> (Key, value
     let associated_keys = AssociatedKeys::new(PublicKey::new(addr), Weight::new(1));
     let account = value::account::Account::new(
         addr,
-        0,
         BTreeMap::new(),
         PurseId::new(URef::new(purse_id, AccessRights::READ_ADD_WRITE)),
         associated_keys,
         Default::default(),
         AccountActivity::new(BlockTime(0), BlockTime(100)),
+        Default::default(),
     );
     let key = Key::Account(addr);
 
@@ -94,6 +97,10 @@ fn random_local_key<G: RngCore>(entropy_source: &mut G, seed: [u8; LOCAL_SEED_SI
     Key::local(seed, &key)
 }
 
+fn mock_block_info() -> BlockInfo {
+    BlockInfo::new(BlockTime(0), 0, [0; 32], PublicKey::new([0; 32]))
+}
+
 fn mock_runtime_context<'a>(
     account: &'a Account,
     base_key: Key,
@@ -110,12 +117,16 @@ fn mock_runtime_context<'a>(
         BTreeSet::from_iter(vec![PublicKey::new([0; 32])]),
         &account,
         base_key,
-        BlockTime(0),
+        vec![CallStackElement::new(base_key, String::from("call"))],
+        mock_block_info(),
+        [0u8; 32],
         0,
         0,
         0,
         Rc::new(RefCell::new(rng)),
+        Rc::new(RefCell::new(create_random_bytes_rng([0u8; 32], 0))),
         1,
+        Phase::Session,
         CorrelationId::new(),
     )
 }
@@ -401,12 +412,16 @@ fn contract_key_addable_valid() {
         BTreeSet::from_iter(vec![PublicKey::new(base_acc_addr)]),
         &account,
         contract_key,
-        BlockTime(0),
+        vec![CallStackElement::new(contract_key, String::from("call"))],
+        mock_block_info(),
+        [0u8; 32],
         0,
         0,
         0,
         Rc::new(RefCell::new(chacha_rng)),
+        Rc::new(RefCell::new(create_random_bytes_rng(base_acc_addr, 0))),
         1,
+        Phase::Session,
         CorrelationId::new(),
     );
 
@@ -454,12 +469,19 @@ fn contract_key_addable_invalid() {
         BTreeSet::from_iter(vec![PublicKey::new(base_acc_addr)]),
         &account,
         other_contract_key,
-        BlockTime(0),
+        vec![CallStackElement::new(
+            other_contract_key,
+            String::from("call"),
+        )],
+        mock_block_info(),
+        [0u8; 32],
         0,
         0,
         0,
         Rc::new(RefCell::new(chacha_rng)),
+        Rc::new(RefCell::new(create_random_bytes_rng(base_acc_addr, 0))),
         1,
+        Phase::Session,
         CorrelationId::new(),
     );
 
@@ -784,6 +806,176 @@ fn should_verify_ownership_before_setting_action_threshold() {
     let _ = test(known_urefs, query);
 }
 
+#[test]
+fn should_record_key_management_last_used() {
+    let known_urefs = HashMap::new();
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalState>| {
+        runtime_context.block_info =
+            BlockInfo::new(BlockTime(42), 0, [0; 32], PublicKey::new([0; 32]));
+        runtime_context
+            .add_associated_key(PublicKey::new([42; 32]), Weight::new(1))
+            .expect("Unable to add key");
+
+        let effect = runtime_context.effect();
+        let transform = effect.transforms.get(&runtime_context.base_key()).unwrap();
+        let account = match transform {
+            Transform::Write(Value::Account(account)) => account,
+            _ => panic!("Invalid transform operation found"),
+        };
+        assert_eq!(
+            account.account_activity().key_management_last_used(),
+            BlockTime(42)
+        );
+
+        Ok(())
+    };
+    let _ = test(known_urefs, query);
+}
+
+#[test]
+fn should_allow_recovery_keys_to_manage_keys_of_inactive_account() {
+    // The mock account was last used at block time 0 and becomes inactive after 100
+    let recovery_key = PublicKey::new([7; 32]);
+    let base_acc_addr = [0u8; 32];
+    let (key, mut account) = mock_account(base_acc_addr);
+    account
+        .add_recovery_key(recovery_key, Weight::new(1))
+        .expect("Unable to add recovery key");
+    let mut uref_map = BTreeMap::new();
+    let chacha_rng = create_rng(base_acc_addr, 0);
+    let mut runtime_context =
+        mock_runtime_context(&account, key, &mut uref_map, HashMap::new(), chacha_rng);
+    runtime_context.authorization_keys = BTreeSet::from_iter(vec![recovery_key]);
+
+    runtime_context.block_info = BlockInfo::new(BlockTime(100), 0, [0; 32], recovery_key);
+    match runtime_context.add_associated_key(PublicKey::new([42; 32]), Weight::new(1)) {
+        Err(Error::AddKeyFailure(AddKeyFailure::PermissionDenied)) => {}
+        other => panic!("Active account shouldn't be recoverable: {:?}", other),
+    }
+
+    runtime_context.block_info = BlockInfo::new(BlockTime(101), 0, [0; 32], recovery_key);
+    runtime_context
+        .add_associated_key(PublicKey::new([42; 32]), Weight::new(1))
+        .expect("Recovery keys should add a key to an inactive account");
+    runtime_context
+        .remove_associated_key(PublicKey::new(base_acc_addr))
+        .expect("Recovery keys should remove a key of an inactive account");
+
+    // Recovery keys can't designate other recovery keys
+    match runtime_context.add_recovery_key(PublicKey::new([8; 32]), Weight::new(1)) {
+        Err(Error::AddKeyFailure(AddKeyFailure::PermissionDenied)) => {}
+        other => panic!("Recovery keys shouldn't add recovery keys: {:?}", other),
+    }
+}
+
+#[test]
+fn should_verify_key_management_threshold_before_managing_recovery() {
+    let known_urefs = HashMap::new();
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalState>| {
+        runtime_context
+            .add_recovery_key(PublicKey::new([7; 32]), Weight::new(2))
+            .expect("Unable to add recovery key");
+        runtime_context
+            .set_recovery_threshold(Weight::new(2))
+            .expect("Unable to set recovery threshold");
+        runtime_context
+            .set_inactivity_period_limit(BlockTime(1000))
+            .expect("Unable to set inactivity period limit");
+
+        let effect = runtime_context.effect();
+        let transform = effect.transforms.get(&runtime_context.base_key()).unwrap();
+        let account = match transform {
+            Transform::Write(Value::Account(account)) => account,
+            _ => panic!("Invalid transform operation found"),
+        };
+        assert_eq!(account.recovery_keys().threshold(), &Weight::new(2));
+        assert_eq!(
+            account.account_activity().inactivity_period_limit(),
+            BlockTime(1000)
+        );
+
+        runtime_context.authorization_keys = BTreeSet::from_iter(vec![PublicKey::new([7; 32])]);
+        match runtime_context.remove_recovery_key(PublicKey::new([7; 32])) {
+            Err(Error::RemoveKeyFailure(RemoveKeyFailure::PermissionDenied)) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+
+        Ok(())
+    };
+    let _ = test(known_urefs, query);
+}
+
+#[test]
+fn should_restrict_transfers_from_main_purse() {
+    let base_acc_addr = [0u8; 32];
+    let (key, account) = {
+        let (key, account) = mock_account(base_acc_addr);
+        let mut action_thresholds = account.action_thresholds().clone();
+        action_thresholds
+            .set_threshold(ActionType::Transfer, Weight::new(2))
+            .expect("should set transfer threshold");
+        let account = Account::new(
+            base_acc_addr,
+            BTreeMap::new(),
+            account.purse_id(),
+            AssociatedKeys::new(PublicKey::new(base_acc_addr), Weight::new(1)),
+            action_thresholds,
+            account.account_activity().clone(),
+            Default::default(),
+        );
+        (key, account)
+    };
+    let mut uref_map = BTreeMap::new();
+    let chacha_rng = create_rng(base_acc_addr, 0);
+    let mut runtime_context =
+        mock_runtime_context(&account, key, &mut uref_map, HashMap::new(), chacha_rng);
+    let other_purse = PurseId::new(URef::new([1; 32], AccessRights::READ_ADD_WRITE));
+    let main_purse_read_only = PurseId::new(URef::new(
+        account.purse_id().value().addr(),
+        AccessRights::READ,
+    ));
+
+    assert!(!runtime_context.can_transfer_from(account.purse_id(), false));
+    assert!(!runtime_context.can_transfer_from(main_purse_read_only, false));
+    assert!(runtime_context.can_transfer_from(other_purse, false));
+
+    runtime_context.phase = Phase::Payment;
+    assert!(runtime_context.can_transfer_from(account.purse_id(), true));
+    assert!(!runtime_context.can_transfer_from(account.purse_id(), false));
+    assert!(runtime_context.can_transfer_from(other_purse, false));
+}
+
+#[test]
+fn should_set_and_check_named_threshold() {
+    let known_urefs = HashMap::new();
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalState>| {
+        assert_eq!(runtime_context.meets_named_threshold("withdraw"), None);
+
+        runtime_context
+            .set_named_threshold("withdraw".to_string(), Weight::new(1))
+            .expect("Unable to set named threshold");
+
+        let effect = runtime_context.effect();
+        let transform = effect.transforms.get(&runtime_context.base_key()).unwrap();
+        let account = match transform {
+            Transform::Write(Value::Account(account)) => account,
+            _ => panic!("Invalid transform operation found"),
+        };
+        assert_eq!(
+            account.action_thresholds().named("withdraw"),
+            Some(&Weight::new(1))
+        );
+
+        match runtime_context.set_named_threshold("withdraw".to_string(), Weight::new(2)) {
+            Err(Error::SetThresholdFailure(SetThresholdFailure::InsufficientTotalWeight)) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+
+        Ok(())
+    };
+    let _ = test(known_urefs, query);
+}
+
 #[test]
 fn can_roundtrip_key_value_pairs_into_local_state() {
     let known_urefs = HashMap::new();
@@ -862,3 +1054,52 @@ fn validate_valid_purse_id_of_an_account() {
     let purse_id = URef::new([53; 32], AccessRights::READ_ADD_WRITE);
     assert!(runtime_context.validate_uref(&purse_id).is_err());
 }
+
+#[test]
+fn should_collect_approvals_for_pending_action() {
+    let known_urefs = HashMap::new();
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalState>| {
+        runtime_context
+            .add_associated_key(PublicKey::new([1; 32]), Weight::new(1))
+            .expect("Unable to add associated key");
+        runtime_context
+            .set_action_threshold(ActionType::KeyManagement, Weight::new(2))
+            .expect("Unable to set key management threshold");
+
+        let action = ProposedAction::AddAssociatedKey(PublicKey::new([2; 32]), Weight::new(1));
+        let (id, approved) = runtime_context
+            .propose_action(action.clone(), BlockTime(100))
+            .expect("Unable to propose action");
+        assert_eq!(approved, None);
+
+        match runtime_context.approve_action([42; 32]) {
+            Err(Error::ProposalFailure(ProposalFailure::NotFound)) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+
+        runtime_context.authorization_keys = BTreeSet::from_iter(vec![PublicKey::new([1; 32])]);
+        let approved = runtime_context
+            .approve_action(id)
+            .expect("Unable to approve action");
+        assert_eq!(approved, Some(action.clone()));
+        runtime_context
+            .apply_approved_action(action)
+            .expect("Unable to apply approved action");
+
+        let effect = runtime_context.effect();
+        let transform = effect.transforms.get(&runtime_context.base_key()).unwrap();
+        let account = match transform {
+            Transform::Write(Value::Account(account)) => account,
+            _ => panic!("Invalid transform operation found"),
+        };
+        assert_eq!(
+            account.get_associated_keys().count(),
+            3,
+            "the approved key should be added"
+        );
+        assert_eq!(account.pending_actions().count(), 0);
+
+        Ok(())
+    };
+    let _ = test(known_urefs, query);
+}
@@ -2,7 +2,9 @@ use std::collections::btree_map::BTreeMap;
 
 use contract_ffi::bytesrepr::I32_SIZE;
 use contract_ffi::key::Key;
-use contract_ffi::value::{Account, Contract, Value};
+use contract_ffi::value::contract::{ArgType, EntryPoint};
+use contract_ffi::value::contract_package::ContractVersion;
+use contract_ffi::value::{Account, Contract, ContractPackage, Value};
 
 /// Returns byte size of the element - both heap size and stack size.
 pub trait ByteSize {
@@ -67,6 +69,7 @@ impl ByteSize for Value {
                 Value::NamedKey(name, _key) => name.heap_size(),
                 Value::Account(account) => account.heap_size(),
                 Value::Contract(contract) => contract.heap_size(),
+                Value::ContractPackage(package) => package.heap_size(),
             }
     }
 }
@@ -91,7 +94,24 @@ impl HeapSizeOf for Account {
 
 impl HeapSizeOf for Contract {
     fn heap_size(&self) -> usize {
-        self.urefs_lookup().heap_size() + self.bytes().len()
+        self.urefs_lookup().heap_size() + self.bytes().len() + self.entry_points().heap_size()
+    }
+}
+
+impl HeapSizeOf for EntryPoint {
+    fn heap_size(&self) -> usize {
+        self.args().len() * std::mem::size_of::<ArgType>()
+    }
+}
+
+// NOTE: We're ignoring size of the tree's nodes.
+impl HeapSizeOf for ContractPackage {
+    fn heap_size(&self) -> usize {
+        self.versions().values().fold(0, |sum, version| {
+            sum + std::mem::size_of::<u32>()
+                + std::mem::size_of::<ContractVersion>()
+                + version.contract().heap_size()
+        })
     }
 }
 
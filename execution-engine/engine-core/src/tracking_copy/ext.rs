@@ -1,10 +1,14 @@
-use contract_ffi::bytesrepr::ToBytes;
+use contract_ffi::bytesrepr::{self, ToBytes};
 use contract_ffi::key::Key;
 use contract_ffi::uref::URef;
+use contract_ffi::value::account::BlockTime;
 use contract_ffi::value::{Account, Contract, Value, U512};
 
 use engine_shared::newtypes::{CorrelationId, Validated};
 use engine_shared::transform::TypeMismatch;
+use engine_state::deploy_header::{executed_deploy_key, DeployHeader, DEPLOY_HASH_LENGTH};
+use engine_state::last_deployment_key;
+use engine_state::upgrade::{active_protocol_version_key, protocol_data_key, ProtocolData};
 use engine_storage::global_state::StateReader;
 use execution;
 use tracking_copy::{QueryResult, TrackingCopy};
@@ -75,7 +79,45 @@ pub trait TrackingCopyExt<R> {
         outer_key: Key,
     ) -> Result<SystemContractInfo, Self::Error>;
 
-    fn handle_nonce(&mut self, account: &mut Account, nonce: u64) -> Result<(), Self::Error>;
+    /// Gets the contract stored under a given key
+    fn get_contract(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: Key,
+    ) -> Result<Contract, Self::Error>;
+
+    /// Gets the expiry recorded for an executed deploy, or `None` if it wasn't executed
+    fn get_executed_deploy_expiry(
+        &mut self,
+        correlation_id: CorrelationId,
+        deploy_hash: [u8; DEPLOY_HASH_LENGTH],
+    ) -> Result<Option<BlockTime>, Self::Error>;
+
+    /// Records a deploy as executed, along with its expiry
+    fn record_executed_deploy(&mut self, deploy_header: &DeployHeader);
+
+    /// Gets the last time the account deployed, or `None` if it never did
+    fn get_last_deployment(
+        &mut self,
+        correlation_id: CorrelationId,
+        account_address: [u8; 32],
+    ) -> Result<Option<BlockTime>, Self::Error>;
+
+    /// Records `block_time` as the last time the account deployed
+    fn record_deployment(&mut self, account_address: [u8; 32], block_time: BlockTime);
+
+    /// Gets the active protocol version, or `None` if it was never recorded
+    fn get_active_protocol_version(
+        &mut self,
+        correlation_id: CorrelationId,
+    ) -> Result<Option<u64>, Self::Error>;
+
+    /// Gets the protocol data recorded for a given protocol version
+    fn get_protocol_data(
+        &mut self,
+        correlation_id: CorrelationId,
+        protocol_version: u64,
+    ) -> Result<ProtocolData, Self::Error>;
 }
 
 impl<R: StateReader<Key, Value>> TrackingCopyExt<R> for TrackingCopy<R>
@@ -176,22 +218,103 @@ where
         Ok(SystemContractInfo::new(outer_key, inner_uref_key, contract))
     }
 
-    fn handle_nonce(&mut self, account: &mut Account, nonce: u64) -> Result<(), Self::Error> {
-        if nonce.checked_sub(account.nonce()).unwrap_or(0) != 1 {
-            return Err(execution::Error::InvalidNonce {
-                deploy_nonce: nonce,
-                expected_nonce: account.nonce() + 1,
-            });
-        } else {
-            account.increment_nonce();
+    fn get_contract(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: Key,
+    ) -> Result<Contract, Self::Error> {
+        match self.get(correlation_id, &key).map_err(Into::into)? {
+            Some(Value::Contract(contract)) => Ok(contract),
+            Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "Value::Contract".to_string(),
+                other.type_string(),
+            ))),
+            None => Err(execution::Error::KeyNotFound(key)),
+        }
+    }
+
+    fn get_executed_deploy_expiry(
+        &mut self,
+        correlation_id: CorrelationId,
+        deploy_hash: [u8; DEPLOY_HASH_LENGTH],
+    ) -> Result<Option<BlockTime>, Self::Error> {
+        let key = executed_deploy_key(deploy_hash);
+        match self.get(correlation_id, &key).map_err(Into::into)? {
+            Some(Value::UInt64(expiry)) => Ok(Some(BlockTime(expiry))),
+            Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "Value::UInt64".to_string(),
+                other.type_string(),
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    fn record_executed_deploy(&mut self, deploy_header: &DeployHeader) {
+        // Safe to unwrap in the following cases as the error type is `!`.
+        let validated_key: Validated<Key> = Validated::new::<!, _>(
+            executed_deploy_key(deploy_header.deploy_hash()),
+            Validated::valid,
+        )
+        .unwrap();
+        let expiry = deploy_header.expiry().0;
+        let validated_value: Validated<Value> =
+            Validated::new(Value::UInt64(expiry), Validated::valid).unwrap();
+        self.write(validated_key, validated_value);
+    }
+
+    fn get_last_deployment(
+        &mut self,
+        correlation_id: CorrelationId,
+        account_address: [u8; 32],
+    ) -> Result<Option<BlockTime>, Self::Error> {
+        let key = last_deployment_key(account_address);
+        match self.get(correlation_id, &key).map_err(Into::into)? {
+            Some(Value::UInt64(block_time)) => Ok(Some(BlockTime(block_time))),
+            Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "Value::UInt64".to_string(),
+                other.type_string(),
+            ))),
+            None => Ok(None),
         }
+    }
+
+    fn record_deployment(&mut self, account_address: [u8; 32], block_time: BlockTime) {
         // Safe to unwrap in the following cases as the error type is `!`.
         let validated_key: Validated<Key> =
-            Validated::new::<!, _>(Key::Account(account.pub_key()), Validated::valid).unwrap();
-        let validated_account: Validated<Value> =
-            Validated::new(account.clone().into(), Validated::valid).unwrap();
-        // Store updated account with new nonce
-        self.write(validated_key, validated_account);
-        Ok(())
+            Validated::new::<!, _>(last_deployment_key(account_address), Validated::valid).unwrap();
+        let validated_value: Validated<Value> =
+            Validated::new(Value::UInt64(block_time.0), Validated::valid).unwrap();
+        self.write(validated_key, validated_value);
+    }
+
+    fn get_active_protocol_version(
+        &mut self,
+        correlation_id: CorrelationId,
+    ) -> Result<Option<u64>, Self::Error> {
+        let key = active_protocol_version_key();
+        match self.get(correlation_id, &key).map_err(Into::into)? {
+            Some(Value::UInt64(protocol_version)) => Ok(Some(protocol_version)),
+            Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "Value::UInt64".to_string(),
+                other.type_string(),
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    fn get_protocol_data(
+        &mut self,
+        correlation_id: CorrelationId,
+        protocol_version: u64,
+    ) -> Result<ProtocolData, Self::Error> {
+        let key = protocol_data_key(protocol_version);
+        match self.get(correlation_id, &key).map_err(Into::into)? {
+            Some(Value::ByteArray(bytes)) => Ok(bytesrepr::deserialize(&bytes)?),
+            Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "Value::ByteArray".to_string(),
+                other.type_string(),
+            ))),
+            None => Err(execution::Error::KeyNotFound(key)),
+        }
     }
 }
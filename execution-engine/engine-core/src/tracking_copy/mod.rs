@@ -88,6 +88,14 @@ impl<M: Meter<Key, Value>> TrackingCopyCache<M> {
     }
 }
 
+/// Changes tracked by a [`TrackingCopy`] at some point of execution, which can be restored
+/// with [`TrackingCopy::rollback`].
+pub struct Savepoint {
+    muts_cached: HashMap<Key, Value>,
+    ops: HashMap<Key, Op>,
+    fns: HashMap<Key, Transform>,
+}
+
 pub struct TrackingCopy<R> {
     reader: R,
     cache: TrackingCopyCache<HeapSize>,
@@ -200,6 +208,37 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
         ExecutionEffect::new(self.ops.clone(), self.fns.clone())
     }
 
+    /// Takes a snapshot of all writes and adds tracked so far.
+    pub fn savepoint(&self) -> Savepoint {
+        Savepoint {
+            muts_cached: self.cache.muts_cached.clone(),
+            ops: self.ops.clone(),
+            fns: self.fns.clone(),
+        }
+    }
+
+    /// Discards all writes and adds tracked since `savepoint` was taken.
+    ///
+    /// Keys accessed in the meantime are still recorded as read, as whatever happens after the
+    /// rollback may depend on their values.
+    pub fn rollback(&mut self, savepoint: Savepoint) {
+        let accessed_keys: Vec<Key> = self
+            .ops
+            .iter()
+            .filter(|(key, op)| savepoint.ops.get(key) != Some(op))
+            .map(|(key, _)| *key)
+            .collect();
+
+        self.cache.muts_cached = savepoint.muts_cached;
+        self.ops = savepoint.ops;
+        self.fns = savepoint.fns;
+
+        for key in accessed_keys {
+            utils::add(&mut self.ops, key, Op::Read);
+            utils::add(&mut self.fns, key, Transform::Identity);
+        }
+    }
+
     pub fn query(
         &mut self,
         correlation_id: CorrelationId,
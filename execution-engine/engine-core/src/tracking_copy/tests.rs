@@ -214,12 +214,12 @@ fn tracking_copy_add_named_key() {
     let associated_keys = AssociatedKeys::new(PublicKey::new([0u8; KEY_SIZE]), Weight::new(1));
     let account = contract_ffi::value::Account::new(
         [0u8; KEY_SIZE],
-        0u64,
         BTreeMap::new(),
         PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
         associated_keys,
         Default::default(),
         AccountActivity::new(BlockTime(0), BlockTime(100)),
+        Default::default(),
     );
     let db = CountingDb::new_init(Value::Account(account));
     let mut tc = TrackingCopy::new(db);
@@ -276,6 +276,74 @@ fn tracking_copy_add_named_key() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Add));
 }
 
+#[test]
+fn tracking_copy_rollback() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    let k1 = Key::Hash([1u8; 32]);
+    let k2 = Key::Hash([2u8; 32]);
+    let k3 = Key::Hash([3u8; 32]);
+
+    tc.write(
+        Validated::new(k1, Validated::valid).unwrap(),
+        Validated::new(Value::Int32(1), Validated::valid).unwrap(),
+    );
+    let savepoint = tc.savepoint();
+
+    // changes made after the savepoint should be discarded
+    tc.write(
+        Validated::new(k1, Validated::valid).unwrap(),
+        Validated::new(Value::Int32(2), Validated::valid).unwrap(),
+    );
+    tc.write(
+        Validated::new(k2, Validated::valid).unwrap(),
+        Validated::new(Value::Int32(3), Validated::valid).unwrap(),
+    );
+    let _ = tc.read(
+        correlation_id,
+        &Validated::new(k3, Validated::valid).unwrap(),
+    );
+    tc.rollback(savepoint);
+
+    assert_eq!(tc.fns.get(&k1), Some(&Transform::Write(Value::Int32(1))));
+    assert_eq!(tc.ops.get(&k1), Some(&Op::Write));
+    assert_eq!(tc.get(correlation_id, &k1).unwrap(), Some(Value::Int32(1)));
+    // keys accessed after the savepoint are still tracked as read
+    assert_eq!(tc.fns.get(&k2), Some(&Transform::Identity));
+    assert_eq!(tc.ops.get(&k2), Some(&Op::Read));
+    assert_ne!(tc.get(correlation_id, &k2).unwrap(), Some(Value::Int32(3)));
+    assert_eq!(tc.fns.get(&k3), Some(&Transform::Identity));
+    assert_eq!(tc.ops.get(&k3), Some(&Op::Read));
+}
+
+#[test]
+fn tracking_copy_rollback_nested() {
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    let outer = tc.savepoint();
+    tc.write(
+        Validated::new(k, Validated::valid).unwrap(),
+        Validated::new(Value::Int32(1), Validated::valid).unwrap(),
+    );
+    let inner = tc.savepoint();
+    tc.write(
+        Validated::new(k, Validated::valid).unwrap(),
+        Validated::new(Value::Int32(2), Validated::valid).unwrap(),
+    );
+
+    tc.rollback(inner);
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Write(Value::Int32(1))));
+
+    tc.rollback(outer);
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Identity));
+    assert_eq!(tc.ops.get(&k), Some(&Op::Read));
+}
+
 #[test]
 fn tracking_copy_rw() {
     let correlation_id = CorrelationId::new();
@@ -412,7 +480,6 @@ proptest! {
         name in "\\PC*", // human-readable name for state
         missing_name in "\\PC*",
         pk in u8_slice_32(), // account public key
-        nonce in any::<u64>(), // account nonce
         address in u8_slice_32(), // address for account key
     ) {
         let correlation_id = CorrelationId::new();
@@ -421,12 +488,12 @@ proptest! {
         let associated_keys = AssociatedKeys::new(PublicKey::new(pk), Weight::new(1));
         let account = Account::new(
             pk,
-            nonce,
             known_urefs,
             purse_id,
             associated_keys,
             Default::default(),
-            AccountActivity::new(BlockTime(0), BlockTime(100))
+            AccountActivity::new(BlockTime(0), BlockTime(100)),
+            Default::default(),
         );
         let account_key = Key::Account(address);
 
@@ -455,7 +522,6 @@ proptest! {
         state_name in "\\PC*", // human-readable name for state
         contract_name in "\\PC*", // human-readable name for contract
         pk in u8_slice_32(), // account public key
-        nonce in any::<u64>(), // account nonce
         address in u8_slice_32(), // address for account key
         body in vec(any::<u8>(), 1..1000), //contract body
         hash in u8_slice_32(), // hash for contract key
@@ -474,12 +540,12 @@ proptest! {
         let associated_keys = AssociatedKeys::new(PublicKey::new(pk), Weight::new(1));
         let account = Account::new(
             pk,
-            nonce,
             account_known_urefs,
             purse_id,
             associated_keys,
             Default::default(),
-            AccountActivity::new(BlockTime(0), BlockTime(100))
+            AccountActivity::new(BlockTime(0), BlockTime(100)),
+            Default::default(),
         );
         let account_key = Key::Account(address);
 
@@ -1,11 +1,15 @@
 extern crate protoc_rust_grpc;
 
 fn main() {
+    println!(
+        "cargo:rerun-if-changed=../../protobuf/io/casperlabs/casper/consensus/consensus.proto"
+    );
     println!("cargo:rerun-if-changed=../../protobuf/io/casperlabs/casper/consensus/state.proto");
     println!("cargo:rerun-if-changed=../../protobuf/io/casperlabs/ipc/ipc.proto");
     protoc_rust_grpc::run(protoc_rust_grpc::Args {
         out_dir: "src/engine_server",
         input: &[
+            "../../protobuf/io/casperlabs/casper/consensus/consensus.proto",
             "../../protobuf/io/casperlabs/casper/consensus/state.proto",
             "../../protobuf/io/casperlabs/ipc/ipc.proto",
         ],
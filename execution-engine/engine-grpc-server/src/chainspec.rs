@@ -0,0 +1,291 @@
+//! Engine parameters of a network, loaded from a TOML chainspec file.
+//!
+//! A chainspec looks like this, where the `[wasm_limits]` and `[genesis_wasm_costs]` tables are
+//! optional:
+//!
+//! ```toml
+//! [deploys]
+//! use_payment_code = true
+//! min_gas_price = 1
+//! max_payment_cost = 10000000
+//!
+//! [wasm_limits]
+//! max_memory_pages = 64
+//!
+//! [genesis_wasm_costs]
+//! regular = 1
+//! div = 16
+//! mul = 4
+//! mem = 2
+//! initial_mem = 4096
+//! grow_mem = 8192
+//! memcpy = 1
+//! opcodes_mul = 3
+//! opcodes_div = 8
+//! ```
+//!
+//! `max_memory_pages` lowers the memory limit of every protocol version. The costs replace the
+//! built-in costs recorded at genesis only: later protocol versions get the costs of the upgrade
+//! activating them.
+//!
+//! The address of the system account isn't part of the chainspec, as the proof of stake contract
+//! is compiled with it.
+//!
+//! Nor is whether deploy signatures are verified, as they always are outside of tests.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use engine_core::engine_state::EngineConfig;
+use engine_shared::newtypes::Blake2bHash;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+#[derive(Debug)]
+pub enum ChainspecError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for ChainspecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChainspecError::Io(error) => write!(f, "Could not read chainspec: {}", error),
+            ChainspecError::Parse(error) => write!(f, "Could not parse chainspec: {}", error),
+            ChainspecError::Invalid(message) => write!(f, "Invalid chainspec: {}", message),
+        }
+    }
+}
+
+impl From<io::Error> for ChainspecError {
+    fn from(error: io::Error) -> Self {
+        ChainspecError::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for ChainspecError {
+    fn from(error: toml::de::Error) -> Self {
+        ChainspecError::Parse(error)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DeploysConfig {
+    use_payment_code: bool,
+    min_gas_price: u64,
+    max_payment_cost: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WasmLimitsConfig {
+    max_memory_pages: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WasmCostsConfig {
+    regular: u32,
+    div: u32,
+    mul: u32,
+    mem: u32,
+    initial_mem: u32,
+    grow_mem: u32,
+    memcpy: u32,
+    opcodes_mul: u32,
+    opcodes_div: u32,
+}
+
+impl From<&WasmCostsConfig> for WasmCosts {
+    fn from(config: &WasmCostsConfig) -> Self {
+        WasmCosts {
+            regular: config.regular,
+            div: config.div,
+            mul: config.mul,
+            mem: config.mem,
+            initial_mem: config.initial_mem,
+            grow_mem: config.grow_mem,
+            memcpy: config.memcpy,
+            opcodes_mul: config.opcodes_mul,
+            opcodes_div: config.opcodes_div,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChainspecConfig {
+    deploys: DeploysConfig,
+    wasm_limits: Option<WasmLimitsConfig>,
+    genesis_wasm_costs: Option<WasmCostsConfig>,
+}
+
+/// A validated chainspec, along with the hash of the file it was read from.
+#[derive(Debug)]
+pub struct Chainspec {
+    config: ChainspecConfig,
+    hash: Blake2bHash,
+}
+
+impl Chainspec {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Chainspec, ChainspecError> {
+        let bytes = fs::read(path)?;
+        Chainspec::from_bytes(&bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Chainspec, ChainspecError> {
+        let config: ChainspecConfig = toml::from_slice(bytes)?;
+        validate(&config)?;
+        let hash = Blake2bHash::new(bytes);
+        let chainspec = Chainspec { config, hash };
+        chainspec
+            .engine_config()
+            .validate()
+            .map_err(ChainspecError::Invalid)?;
+        Ok(chainspec)
+    }
+
+    /// Hash of the chainspec file, so that nodes can check they run with the same parameters.
+    pub fn hash(&self) -> Blake2bHash {
+        self.hash
+    }
+
+    pub fn engine_config(&self) -> EngineConfig {
+        let deploys = &self.config.deploys;
+        let engine_config = EngineConfig::new()
+            .set_use_payment_code(deploys.use_payment_code)
+            .set_min_gas_price(deploys.min_gas_price)
+            .set_max_payment_cost(deploys.max_payment_cost);
+        let engine_config = match self.config.wasm_limits {
+            Some(ref wasm_limits) => {
+                engine_config.set_max_memory_pages(wasm_limits.max_memory_pages)
+            }
+            None => engine_config,
+        };
+        match self.config.genesis_wasm_costs {
+            Some(ref wasm_costs) => engine_config.set_genesis_wasm_costs(wasm_costs.into()),
+            None => engine_config,
+        }
+    }
+}
+
+/// Checks the parts of `config` which the engine config built from it doesn't validate.
+fn validate(config: &ChainspecConfig) -> Result<(), ChainspecError> {
+    if let Some(ref wasm_costs) = config.genesis_wasm_costs {
+        if wasm_costs.opcodes_div == 0 {
+            return Err(ChainspecError::Invalid(
+                "genesis_wasm_costs.opcodes_div must be positive".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_wasm_prep::wasm_costs::WasmCosts;
+
+    use super::{Chainspec, ChainspecError};
+
+    const CHAINSPEC: &str = include_str!("../chainspec.toml");
+
+    fn deploys_toml(use_payment_code: bool, min_gas_price: u64, max_payment_cost: u64) -> String {
+        format!(
+            "[deploys]\nuse_payment_code = {}\nmin_gas_price = {}\nmax_payment_cost = {}\n",
+            use_payment_code, min_gas_price, max_payment_cost
+        )
+    }
+
+    #[test]
+    fn should_load_default_chainspec() {
+        let chainspec = Chainspec::from_bytes(CHAINSPEC.as_bytes()).expect("should load");
+        let engine_config = chainspec.engine_config();
+
+        assert!(engine_config.use_payment_code());
+        assert!(engine_config.verify_deploy_signatures());
+        assert_eq!(engine_config.min_gas_price(), 1);
+        assert_eq!(engine_config.max_payment_cost(), 10_000_000);
+        assert_eq!(engine_config.max_memory_pages(), Some(64));
+        assert_eq!(
+            engine_config.genesis_wasm_costs(),
+            WasmCosts::from_version(1).as_ref()
+        );
+    }
+
+    #[test]
+    fn should_default_to_built_in_wasm_costs() {
+        let toml = deploys_toml(false, 2, 10);
+        let chainspec = Chainspec::from_bytes(toml.as_bytes()).expect("should load");
+        let engine_config = chainspec.engine_config();
+
+        assert!(!engine_config.use_payment_code());
+        assert!(engine_config.verify_deploy_signatures());
+        assert_eq!(engine_config.min_gas_price(), 2);
+        assert_eq!(engine_config.max_payment_cost(), 10);
+        assert_eq!(engine_config.max_memory_pages(), None);
+        assert_eq!(engine_config.genesis_wasm_costs(), None);
+    }
+
+    #[test]
+    fn should_hash_file_contents() {
+        let toml = deploys_toml(true, 1, 10);
+        let other_toml = deploys_toml(true, 1, 11);
+
+        let chainspec = Chainspec::from_bytes(toml.as_bytes()).expect("should load");
+        let other_chainspec = Chainspec::from_bytes(other_toml.as_bytes()).expect("should load");
+
+        assert_ne!(chainspec.hash(), other_chainspec.hash());
+    }
+
+    #[test]
+    fn should_reject_zero_min_gas_price() {
+        let toml = deploys_toml(true, 0, 10);
+
+        match Chainspec::from_bytes(toml.as_bytes()) {
+            Err(ChainspecError::Invalid(_)) => (),
+            other => panic!("expected invalid chainspec, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_max_payment_cost_below_min_gas_price() {
+        let toml = deploys_toml(true, 2, 1);
+
+        match Chainspec::from_bytes(toml.as_bytes()) {
+            Err(ChainspecError::Invalid(_)) => (),
+            other => panic!("expected invalid chainspec, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_memory_pages_out_of_range() {
+        for max_memory_pages in &[0, 65] {
+            let mut toml = deploys_toml(true, 1, 10);
+            toml.push_str(&format!(
+                "[wasm_limits]\nmax_memory_pages = {}\n",
+                max_memory_pages
+            ));
+
+            match Chainspec::from_bytes(toml.as_bytes()) {
+                Err(ChainspecError::Invalid(_)) => (),
+                other => panic!("expected invalid chainspec, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_fields() {
+        let mut toml = deploys_toml(true, 1, 10);
+        toml.push_str("conv_rate = 10\n");
+
+        match Chainspec::from_bytes(toml.as_bytes()) {
+            Err(ChainspecError::Parse(_)) => (),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+}
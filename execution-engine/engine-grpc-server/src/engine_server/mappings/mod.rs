@@ -3,25 +3,35 @@ use std::convert::{TryFrom, TryInto};
 use std::fmt::Display;
 use std::string::ToString;
 
-use protobuf::ProtobufEnum;
+use protobuf::{Message, ProtobufEnum};
 
+use contract_ffi::contract_api::error::ApiError;
 use contract_ffi::uref::URef;
 use contract_ffi::value::account::{
-    AccountActivity, ActionThresholds, AssociatedKeys, BlockTime, PublicKey, PurseId, Weight,
+    AccountActivity, ActionThresholds, ActionType, AssociatedKeys, BlockTime, PendingAction,
+    ProposedAction, PublicKey, PurseId, RecoveryKeys, SignatureAlgorithm, TaggedPublicKey, Weight,
 };
+use contract_ffi::value::contract::{ArgType, EntryPoint};
+use contract_ffi::value::contract_package::ContractVersion;
 use contract_ffi::value::U512;
+use engine_core::engine_state::approval;
+use engine_core::engine_state::deploy_header::{DeployHeader, DEPLOY_HASH_LENGTH};
 use engine_core::engine_state::error::{Error as EngineError, RootNotFound};
 use engine_core::engine_state::execution_effect::ExecutionEffect;
 use engine_core::engine_state::execution_result::ExecutionResult;
+use engine_core::engine_state::genesis::GenesisAccount;
 use engine_core::engine_state::op::Op;
-use engine_core::execution::Error as ExecutionError;
+use engine_core::engine_state::upgrade::UpgradeConfig;
+use engine_core::engine_state::ExecutableDeployItem;
+use engine_core::execution::{BlockInfo, Error as ExecutionError, BLOCK_HASH_LENGTH};
 use engine_core::tracking_copy::utils;
-use engine_server::{ipc, state};
+use engine_server::{consensus, ipc, state};
 use engine_shared::logging;
 use engine_shared::logging::log_level;
 use engine_shared::newtypes::Blake2bHash;
 use engine_shared::transform::{self, TypeMismatch};
 use engine_storage::global_state::{CommitResult, History};
+use engine_wasm_prep::wasm_costs::WasmCosts;
 
 mod uint;
 
@@ -131,7 +141,7 @@ impl TryFrom<&super::ipc::Transform> for transform::Transform {
 
 impl From<contract_ffi::value::Contract> for super::state::Contract {
     fn from(contract: contract_ffi::value::Contract) -> Self {
-        let (bytes, known_urefs, protocol_version) = contract.destructure();
+        let (bytes, known_urefs, protocol_version, entry_points) = contract.destructure();
         let mut contract = super::state::Contract::new();
         let urefs = URefMap(known_urefs).into();
         contract.set_body(bytes);
@@ -139,6 +149,24 @@ impl From<contract_ffi::value::Contract> for super::state::Contract {
         let mut protocol = super::state::ProtocolVersion::new();
         protocol.set_value(protocol_version);
         contract.set_protocol_version(protocol);
+        let entry_points: Vec<super::state::Contract_EntryPoint> = entry_points
+            .into_iter()
+            .map(|(name, entry_point)| {
+                let mut ipc_entry_point = super::state::Contract_EntryPoint::new();
+                ipc_entry_point.set_name(name);
+                let args = entry_point
+                    .args()
+                    .iter()
+                    .map(|arg_type| {
+                        super::state::Contract_EntryPoint_ArgType::from_i32(*arg_type as i32)
+                            .unwrap()
+                    })
+                    .collect();
+                ipc_entry_point.set_args(args);
+                ipc_entry_point
+            })
+            .collect();
+        contract.set_entry_points(protobuf::RepeatedField::from_vec(entry_points));
         contract
     }
 }
@@ -148,10 +176,80 @@ impl TryFrom<&super::state::Contract> for contract_ffi::value::Contract {
 
     fn try_from(value: &super::state::Contract) -> Result<Self, Self::Error> {
         let known_urefs: URefMap = value.get_known_urefs().try_into()?;
-        Ok(contract_ffi::value::Contract::new(
+        let mut entry_points = BTreeMap::new();
+        for ipc_entry_point in value.get_entry_points() {
+            let args = ipc_entry_point
+                .get_args()
+                .iter()
+                .map(|arg_type| {
+                    ArgType::try_from(arg_type.value() as u8).map_err(|_| {
+                        ParsingError(format!("Unknown entry point arg type {:?}.", arg_type))
+                    })
+                })
+                .collect::<Result<Vec<ArgType>, ParsingError>>()?;
+            if entry_points
+                .insert(
+                    ipc_entry_point.get_name().to_string(),
+                    EntryPoint::new(args),
+                )
+                .is_some()
+            {
+                return parse_error(format!(
+                    "Duplicated entry point {} in contract.",
+                    ipc_entry_point.get_name()
+                ));
+            }
+        }
+        Ok(contract_ffi::value::Contract::with_entry_points(
             value.get_body().to_vec(),
             known_urefs.0,
             value.get_protocol_version().value,
+            entry_points,
+        ))
+    }
+}
+
+impl From<contract_ffi::value::ContractPackage> for super::state::ContractPackage {
+    fn from(package: contract_ffi::value::ContractPackage) -> Self {
+        let mut ipc_package = super::state::ContractPackage::new();
+        ipc_package.set_access_key(package.access_key().into());
+        let versions: Vec<super::state::ContractPackage_Version> = package
+            .versions()
+            .iter()
+            .map(|(version, contract_version)| {
+                let mut ipc_version = super::state::ContractPackage_Version::new();
+                ipc_version.set_version(*version);
+                ipc_version.set_contract(contract_version.contract().clone().into());
+                ipc_version.set_is_enabled(contract_version.is_enabled());
+                ipc_version
+            })
+            .collect();
+        ipc_package.set_versions(protobuf::RepeatedField::from_vec(versions));
+        ipc_package
+    }
+}
+
+impl TryFrom<&super::state::ContractPackage> for contract_ffi::value::ContractPackage {
+    type Error = ParsingError;
+
+    fn try_from(value: &super::state::ContractPackage) -> Result<Self, Self::Error> {
+        let access_key: URef = value.get_access_key().try_into()?;
+        let mut versions = BTreeMap::new();
+        for ipc_version in value.get_versions() {
+            let contract: contract_ffi::value::Contract = ipc_version.get_contract().try_into()?;
+            let contract_version = ContractVersion::new(contract, ipc_version.get_is_enabled());
+            if versions
+                .insert(ipc_version.get_version(), contract_version)
+                .is_some()
+            {
+                return parse_error(format!(
+                    "Duplicated contract version {} in contract package.",
+                    ipc_version.get_version()
+                ));
+            }
+        }
+        Ok(contract_ffi::value::ContractPackage::new(
+            access_key, versions,
         ))
     }
 }
@@ -200,6 +298,9 @@ impl From<contract_ffi::value::Value> for super::state::Value {
             }
             contract_ffi::value::Value::Unit => tv.set_unit(state::Unit::new()),
             contract_ffi::value::Value::UInt64(num) => tv.set_long_value(num),
+            contract_ffi::value::Value::ContractPackage(package) => {
+                tv.set_contract_package(package.into());
+            }
         };
         tv
     }
@@ -245,6 +346,10 @@ impl TryFrom<&super::state::Value> for contract_ffi::value::Value {
             Ok(contract_ffi::value::Value::Unit)
         } else if value.has_long_value() {
             Ok(contract_ffi::value::Value::UInt64(value.get_long_value()))
+        } else if value.has_contract_package() {
+            Ok(contract_ffi::value::Value::ContractPackage(
+                value.get_contract_package().try_into()?,
+            ))
         } else {
             parse_error(format!(
                 "IPC Value {:?} couldn't be parsed to domain representation.",
@@ -258,16 +363,10 @@ impl From<contract_ffi::value::account::Account> for super::state::Account {
     fn from(account: contract_ffi::value::account::Account) -> Self {
         let mut ipc_account = super::state::Account::new();
         ipc_account.set_public_key(account.pub_key().to_vec());
-        ipc_account.set_nonce(account.nonce());
         ipc_account.set_purse_id(account.purse_id().value().into());
         let associated_keys: Vec<super::state::Account_AssociatedKey> = account
             .get_associated_keys()
-            .map(|(key, weight)| {
-                let mut ipc_associated_key = super::state::Account_AssociatedKey::new();
-                ipc_associated_key.set_public_key(key.value().to_vec());
-                ipc_associated_key.set_weight(u32::from(weight.value()));
-                ipc_associated_key
-            })
+            .map(ipc_associated_key)
             .collect();
         let action_thresholds = {
             let mut tmp = state::Account_ActionThresholds::new();
@@ -277,6 +376,18 @@ impl From<contract_ffi::value::account::Account> for super::state::Account {
             tmp.set_deployment_threshold(u32::from(
                 account.action_thresholds().deployment().value(),
             ));
+            tmp.set_transfer_threshold(u32::from(account.action_thresholds().transfer().value()));
+            let named_thresholds: Vec<state::Account_NamedThreshold> = account
+                .action_thresholds()
+                .named_thresholds()
+                .map(|(name, weight)| {
+                    let mut named_threshold = state::Account_NamedThreshold::new();
+                    named_threshold.set_name(name.to_owned());
+                    named_threshold.set_weight(u32::from(weight.value()));
+                    named_threshold
+                })
+                .collect();
+            tmp.set_named_thresholds(named_thresholds.into());
             tmp
         };
         ipc_account.set_action_thresholds(action_thresholds);
@@ -289,16 +400,72 @@ impl From<contract_ffi::value::account::Account> for super::state::Account {
             tmp.set_inactivity_period_limit(account.account_activity().inactivity_period_limit().0);
             tmp
         };
+        let recovery_keys = {
+            let mut tmp = state::Account_RecoveryKeys::new();
+            tmp.set_keys(
+                account
+                    .recovery_keys()
+                    .keys()
+                    .map(ipc_associated_key)
+                    .collect::<Vec<_>>()
+                    .into(),
+            );
+            tmp.set_threshold(u32::from(account.recovery_keys().threshold().value()));
+            tmp
+        };
+        let pending_actions: Vec<state::Account_PendingAction> = account
+            .pending_actions()
+            .map(|(id, pending_action)| {
+                let mut tmp = state::Account_PendingAction::new();
+                tmp.set_id(id.to_vec());
+                tmp.set_action(pending_action.action().into());
+                tmp.set_approvals(
+                    pending_action
+                        .approvals()
+                        .map(|key| key.value().to_vec())
+                        .collect::<Vec<_>>()
+                        .into(),
+                );
+                tmp.set_expires_at(pending_action.expires_at().0);
+                tmp
+            })
+            .collect();
         let account_urefs = account.urefs_lookup();
         let account_urefs_lookup = URefMap(account_urefs.clone());
         let ipc_urefs: Vec<super::state::NamedKey> = account_urefs_lookup.into();
         ipc_account.set_known_urefs(ipc_urefs.into());
         ipc_account.set_associated_keys(associated_keys.into());
         ipc_account.set_account_activity(account_activity);
+        ipc_account.set_recovery_keys(recovery_keys);
+        ipc_account.set_pending_actions(pending_actions.into());
         ipc_account
     }
 }
 
+fn ipc_associated_key((key, weight): (&PublicKey, &Weight)) -> state::Account_AssociatedKey {
+    let mut ipc_associated_key = state::Account_AssociatedKey::new();
+    ipc_associated_key.set_public_key(key.value().to_vec());
+    ipc_associated_key.set_weight(u32::from(weight.value()));
+    ipc_associated_key
+}
+
+fn parse_associated_keys(
+    ipc_keys: &[state::Account_AssociatedKey],
+) -> Result<AssociatedKeys, ParsingError> {
+    let mut keys = AssociatedKeys::empty();
+    ipc_keys.iter().try_for_each(|k| {
+        let (pub_key, weight) = k.try_into()?;
+        match keys.add_key(pub_key, weight) {
+            Err(add_key_failure) => parse_error(format!(
+                "Error when parsing associated keys: {:?}",
+                add_key_failure
+            )),
+            Ok(_) => Ok(()),
+        }
+    })?;
+    Ok(keys)
+}
+
 impl TryFrom<&super::state::Account> for contract_ffi::value::account::Account {
     type Error = ParsingError;
 
@@ -313,20 +480,7 @@ impl TryFrom<&super::state::Account> for contract_ffi::value::account::Account {
         };
         let uref_map: URefMap = value.get_known_urefs().try_into()?;
         let purse_id: PurseId = PurseId::new(value.get_purse_id().try_into()?);
-        let associated_keys: AssociatedKeys = {
-            let mut keys = AssociatedKeys::empty();
-            value.get_associated_keys().iter().try_for_each(|k| {
-                let (pub_key, weight) = k.try_into()?;
-                match keys.add_key(pub_key, weight) {
-                    Err(add_key_failure) => parse_error(format!(
-                        "Error when parsing associated keys: {:?}",
-                        add_key_failure
-                    )),
-                    Ok(_) => Ok(()),
-                }
-            })?;
-            keys
-        };
+        let associated_keys: AssociatedKeys = parse_associated_keys(value.get_associated_keys())?;
         let action_thresholds: ActionThresholds = {
             if !value.has_action_thresholds() {
                 return parse_error(
@@ -335,11 +489,21 @@ impl TryFrom<&super::state::Account> for contract_ffi::value::account::Account {
             };
             let action_thresholds_ipc = value.get_action_thresholds();
 
-            ActionThresholds::new(
+            let mut action_thresholds = ActionThresholds::new(
                 Weight::new(action_thresholds_ipc.get_deployment_threshold() as u8),
                 Weight::new(action_thresholds_ipc.get_key_management_threshold() as u8),
             )
-            .map_err(ParsingError::custom)?
+            .map_err(ParsingError::custom)?;
+            action_thresholds.set_transfer_threshold(Weight::new(
+                action_thresholds_ipc.get_transfer_threshold() as u8,
+            ));
+            for named_threshold in action_thresholds_ipc.get_named_thresholds() {
+                action_thresholds.set_named_threshold(
+                    named_threshold.get_name().to_owned(),
+                    Weight::new(named_threshold.get_weight() as u8),
+                );
+            }
+            action_thresholds
         };
         let account_activity: AccountActivity = {
             if !value.has_account_activity() {
@@ -358,15 +522,130 @@ impl TryFrom<&super::state::Account> for contract_ffi::value::account::Account {
             ));
             tmp
         };
-        Ok(contract_ffi::value::Account::new(
+        // Accounts serialized before recovery keys existed have none.
+        let recovery_keys: RecoveryKeys = if value.has_recovery_keys() {
+            let recovery_keys_ipc = value.get_recovery_keys();
+            if recovery_keys_ipc.get_threshold() > u8::max_value().into() {
+                return parse_error("Recovery threshold cannot be bigger than 255.".to_string());
+            }
+            RecoveryKeys::new(
+                parse_associated_keys(recovery_keys_ipc.get_keys())?,
+                Weight::new(recovery_keys_ipc.get_threshold() as u8),
+            )
+        } else {
+            Default::default()
+        };
+        let mut account = contract_ffi::value::Account::new(
             pub_key,
-            value.nonce,
             uref_map.0,
             purse_id,
             associated_keys,
             action_thresholds,
             account_activity,
-        ))
+            recovery_keys,
+        );
+        for pending_action_ipc in value.get_pending_actions() {
+            let id = {
+                let source = pending_action_ipc.get_id();
+                if source.len() != 32 {
+                    return parse_error("Pending action id has to be 32 bytes long.".to_string());
+                }
+                let mut id = [0u8; 32];
+                id.copy_from_slice(source);
+                id
+            };
+            if !pending_action_ipc.has_action() {
+                return parse_error(
+                    "Missing ProposedAction object of the PendingAction IPC message.".to_string(),
+                );
+            }
+            let action: ProposedAction = pending_action_ipc.get_action().try_into()?;
+            let approvals = pending_action_ipc
+                .get_approvals()
+                .iter()
+                .map(|key| parse_public_key(key))
+                .collect::<Result<_, _>>()?;
+            let expires_at = BlockTime(pending_action_ipc.get_expires_at());
+            account.insert_pending_action(id, PendingAction::new(action, approvals, expires_at));
+        }
+        Ok(account)
+    }
+}
+
+fn parse_public_key(bytes: &[u8]) -> Result<PublicKey, ParsingError> {
+    if bytes.len() != 32 {
+        return parse_error("Public key has to be exactly 32 bytes long.".to_string());
+    }
+    let mut pub_key = [0u8; 32];
+    pub_key.copy_from_slice(bytes);
+    Ok(PublicKey::new(pub_key))
+}
+
+impl From<&ProposedAction> for state::Account_ProposedAction {
+    fn from(action: &ProposedAction) -> Self {
+        let mut ipc_action = state::Account_ProposedAction::new();
+        match action {
+            ProposedAction::AddAssociatedKey(key, weight) => {
+                ipc_action.set_add_associated_key(ipc_associated_key((key, weight)))
+            }
+            ProposedAction::RemoveAssociatedKey(key) => {
+                ipc_action.set_remove_associated_key(key.value().to_vec())
+            }
+            ProposedAction::UpdateAssociatedKey(key, weight) => {
+                ipc_action.set_update_associated_key(ipc_associated_key((key, weight)))
+            }
+            ProposedAction::SetActionThreshold(action_type, threshold) => {
+                let mut tmp = state::Account_ProposedAction_SetActionThreshold::new();
+                tmp.set_action_type(*action_type as u32);
+                tmp.set_threshold(u32::from(threshold.value()));
+                ipc_action.set_set_action_threshold(tmp);
+            }
+            ProposedAction::Transfer(target, amount) => {
+                let mut tmp = state::Account_ProposedAction_Transfer::new();
+                tmp.set_target(target.value().to_vec());
+                tmp.set_amount((*amount).into());
+                ipc_action.set_transfer(tmp);
+            }
+        }
+        ipc_action
+    }
+}
+
+impl TryFrom<&state::Account_ProposedAction> for ProposedAction {
+    type Error = ParsingError;
+
+    fn try_from(value: &state::Account_ProposedAction) -> Result<Self, Self::Error> {
+        if value.has_add_associated_key() {
+            let (key, weight) = value.get_add_associated_key().try_into()?;
+            Ok(ProposedAction::AddAssociatedKey(key, weight))
+        } else if value.has_remove_associated_key() {
+            let key = parse_public_key(value.get_remove_associated_key())?;
+            Ok(ProposedAction::RemoveAssociatedKey(key))
+        } else if value.has_update_associated_key() {
+            let (key, weight) = value.get_update_associated_key().try_into()?;
+            Ok(ProposedAction::UpdateAssociatedKey(key, weight))
+        } else if value.has_set_action_threshold() {
+            let set_action_threshold = value.get_set_action_threshold();
+            let action_type = ActionType::try_from(set_action_threshold.get_action_type())
+                .map_err(|_| {
+                    ParsingError(format!(
+                        "Unknown action type: {}",
+                        set_action_threshold.get_action_type()
+                    ))
+                })?;
+            if set_action_threshold.get_threshold() > u8::max_value().into() {
+                return parse_error("Action threshold cannot be bigger than 255.".to_string());
+            }
+            let threshold = Weight::new(set_action_threshold.get_threshold() as u8);
+            Ok(ProposedAction::SetActionThreshold(action_type, threshold))
+        } else if value.has_transfer() {
+            let transfer = value.get_transfer();
+            let target = parse_public_key(transfer.get_target())?;
+            let amount: U512 = transfer.get_amount().try_into()?;
+            Ok(ProposedAction::Transfer(target, amount))
+        } else {
+            parse_error("ProposedAction couldn't be parsed to known action.".to_string())
+        }
     }
 }
 
@@ -491,6 +770,84 @@ impl TryFrom<&state::Account_AssociatedKey> for (PublicKey, Weight) {
     }
 }
 
+impl From<ipc::SignatureAlgorithm> for SignatureAlgorithm {
+    fn from(algorithm: ipc::SignatureAlgorithm) -> Self {
+        match algorithm {
+            ipc::SignatureAlgorithm::ED25519 => SignatureAlgorithm::Ed25519,
+            ipc::SignatureAlgorithm::SECP256K1 => SignatureAlgorithm::Secp256k1,
+        }
+    }
+}
+
+impl TryFrom<&ipc::TaggedPublicKey> for TaggedPublicKey {
+    type Error = ParsingError;
+
+    fn try_from(value: &ipc::TaggedPublicKey) -> Result<Self, Self::Error> {
+        let algorithm: SignatureAlgorithm = value.get_algorithm().into();
+        TaggedPublicKey::new(algorithm, value.get_key()).map_err(|_| {
+            ParsingError(format!(
+                "{:?} public key has to be exactly {} bytes long.",
+                algorithm,
+                algorithm.public_key_length()
+            ))
+        })
+    }
+}
+
+impl TryFrom<&ipc::GenesisAccount> for GenesisAccount {
+    type Error = ParsingError;
+
+    /// The address of an account with a tagged public key is derived from it. Accounts without
+    /// associated keys get their own address with weight 1, and accounts without thresholds get
+    /// the default ones.
+    fn try_from(value: &ipc::GenesisAccount) -> Result<Self, Self::Error> {
+        let public_key = if value.has_tagged_public_key() {
+            let tagged_public_key: TaggedPublicKey = value.get_tagged_public_key().try_into()?;
+            tagged_public_key.to_address()
+        } else {
+            parse_public_key(value.get_public_key())?
+        };
+        let balance: U512 = value.get_balance().try_into()?;
+        let associated_keys = if value.get_associated_keys().is_empty() {
+            AssociatedKeys::new(public_key, Weight::new(1))
+        } else {
+            let mut keys = AssociatedKeys::empty();
+            for associated_key in value.get_associated_keys() {
+                let (key, weight) = associated_key.try_into()?;
+                keys.add_key(key, weight).map_err(ParsingError::custom)?;
+            }
+            keys
+        };
+        let action_thresholds = if value.has_action_thresholds() {
+            let action_thresholds_ipc = value.get_action_thresholds();
+            let deployment = action_thresholds_ipc.get_deployment_threshold();
+            let key_management = action_thresholds_ipc.get_key_management_threshold();
+            if deployment > u8::max_value().into() || key_management > u8::max_value().into() {
+                return parse_error("Threshold cannot be bigger than 255.".to_string());
+            }
+            ActionThresholds::new(
+                Weight::new(deployment as u8),
+                Weight::new(key_management as u8),
+            )
+            .map_err(ParsingError::custom)?
+        } else {
+            Default::default()
+        };
+        let bonded_amount: U512 = if value.has_bonded_amount() {
+            value.get_bonded_amount().try_into()?
+        } else {
+            U512::zero()
+        };
+        Ok(GenesisAccount::new(
+            public_key,
+            balance,
+            associated_keys,
+            action_thresholds,
+            bonded_amount,
+        ))
+    }
+}
+
 impl From<&contract_ffi::key::Key> for super::state::Key {
     fn from(key: &contract_ffi::key::Key) -> super::state::Key {
         let mut k = super::state::Key::new();
@@ -566,6 +923,242 @@ impl TryFrom<&super::state::Key> for contract_ffi::key::Key {
     }
 }
 
+impl TryFrom<&super::ipc::DeployCode> for ExecutableDeployItem {
+    type Error = ParsingError;
+
+    fn try_from(deploy_code: &super::ipc::DeployCode) -> Result<Self, ParsingError> {
+        let args = deploy_code.get_args().to_vec();
+        if deploy_code.has_stored_contract_hash() {
+            let source = deploy_code.get_stored_contract_hash();
+            if source.len() != 32 {
+                return parse_error("Stored contract hash has to be 32 bytes long.".to_string());
+            }
+            let mut hash = [0u8; 32];
+            hash.copy_from_slice(source);
+            Ok(ExecutableDeployItem::StoredContractByHash { hash, args })
+        } else if deploy_code.has_stored_contract_name() {
+            let name = deploy_code.get_stored_contract_name().to_string();
+            Ok(ExecutableDeployItem::StoredContractByName { name, args })
+        } else {
+            let module_bytes = deploy_code.get_code().to_vec();
+            Ok(ExecutableDeployItem::ModuleBytes { module_bytes, args })
+        }
+    }
+}
+
+/// Copies `bytes` into a fixed-size array, treating empty bytes as all zeros.
+fn bytes_or_zeros(bytes: &[u8], field: &str) -> Result<[u8; 32], ParsingError> {
+    let mut result = [0u8; 32];
+    match bytes.len() {
+        0 => Ok(result),
+        32 => {
+            result.copy_from_slice(bytes);
+            Ok(result)
+        }
+        len => parse_error(format!(
+            "{} has to be empty or 32 bytes long, got {} bytes.",
+            field, len
+        )),
+    }
+}
+
+impl TryFrom<&super::ipc::ExecRequest> for BlockInfo {
+    type Error = ParsingError;
+
+    fn try_from(exec_request: &super::ipc::ExecRequest) -> Result<Self, ParsingError> {
+        let parent_hash: [u8; BLOCK_HASH_LENGTH] =
+            bytes_or_zeros(exec_request.get_parent_block_hash(), "Parent block hash")?;
+        let proposer = bytes_or_zeros(exec_request.get_proposer(), "Proposer")?;
+        Ok(BlockInfo::new(
+            BlockTime(exec_request.get_block_time()),
+            exec_request.get_block_height(),
+            parent_hash,
+            PublicKey::new(proposer),
+        ))
+    }
+}
+
+impl TryFrom<&super::ipc::Deploy> for DeployHeader {
+    type Error = ParsingError;
+
+    fn try_from(deploy: &super::ipc::Deploy) -> Result<Self, ParsingError> {
+        let deploy_hash = deploy.get_deploy_hash();
+        if deploy_hash.len() != DEPLOY_HASH_LENGTH {
+            return parse_error(format!(
+                "Deploy hash has to be exactly {} bytes long, got {}.",
+                DEPLOY_HASH_LENGTH,
+                deploy_hash.len()
+            ));
+        }
+        let mut buff = [0u8; DEPLOY_HASH_LENGTH];
+        buff.copy_from_slice(deploy_hash);
+        let dependencies = deploy
+            .get_dependencies()
+            .iter()
+            .map(|dependency| {
+                if dependency.len() != DEPLOY_HASH_LENGTH {
+                    return parse_error(format!(
+                        "Deploy dependency has to be exactly {} bytes long, got {}.",
+                        DEPLOY_HASH_LENGTH,
+                        dependency.len()
+                    ));
+                }
+                let mut buff = [0u8; DEPLOY_HASH_LENGTH];
+                buff.copy_from_slice(dependency);
+                Ok(buff)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut deploy_header = DeployHeader::new(buff, BlockTime(deploy.get_timestamp()))
+            .set_dependencies(dependencies);
+        // Zero stands for unset
+        if deploy.get_ttl() != 0 {
+            deploy_header = deploy_header.set_ttl(deploy.get_ttl());
+        }
+        if deploy.get_not_before() != 0 {
+            deploy_header = deploy_header.set_not_before(BlockTime(deploy.get_not_before()));
+        }
+        Ok(deploy_header)
+    }
+}
+
+/// Converts the code of a deploy into the code of a deploy body, as the node signs it.
+fn to_consensus_code(deploy_code: &ipc::DeployCode) -> consensus::Deploy_Code {
+    let mut code = consensus::Deploy_Code::new();
+    code.set_code(deploy_code.get_code().to_vec());
+    code.set_args(deploy_code.get_args().to_vec());
+    if deploy_code.has_stored_contract_hash() {
+        code.set_stored_contract_hash(deploy_code.get_stored_contract_hash().to_vec());
+    } else if deploy_code.has_stored_contract_name() {
+        code.set_stored_contract_name(deploy_code.get_stored_contract_name().to_string());
+    }
+    code
+}
+
+/// Hash of the deploy body holding the session and payment code of `deploy`.
+fn deploy_body_hash(deploy: &ipc::Deploy) -> Option<Blake2bHash> {
+    let mut body = consensus::Deploy_Body::new();
+    if deploy.has_session() {
+        body.set_session(to_consensus_code(deploy.get_session()));
+    }
+    if deploy.has_payment() {
+        body.set_payment(to_consensus_code(deploy.get_payment()));
+    }
+    let body_bytes = body.write_to_bytes().ok()?;
+    Some(Blake2bHash::new(&body_bytes))
+}
+
+/// Checks that the deploy hash is the hash of the header of `deploy`, and that the header is the
+/// one of what the engine runs: the account, gas price, timestamp, and session and payment code of
+/// the deploy.
+///
+/// The approvals of a deploy sign its deploy hash, so this is what makes them approve the deploy.
+pub fn verify_deploy_hash(deploy: &ipc::Deploy) -> Result<(), EngineError> {
+    let mismatch = |field: &str| Err(EngineError::DeployHashMismatch(field.to_string()));
+
+    let header_bytes = deploy.get_header();
+    if Blake2bHash::new(header_bytes).to_vec().as_slice() != deploy.get_deploy_hash() {
+        return mismatch("header");
+    }
+    let header: consensus::Deploy_Header = match protobuf::parse_from_bytes(header_bytes) {
+        Ok(header) => header,
+        Err(_) => return mismatch("header"),
+    };
+
+    let account_address = approval::parse_account_public_key(header.get_account_public_key())
+        .map(|public_key| public_key.to_address().value());
+    if account_address.as_ref().map(|address| &address[..]) != Some(deploy.get_address()) {
+        return mismatch("account");
+    }
+    if header.get_gas_price() != deploy.get_gas_price() {
+        return mismatch("gas price");
+    }
+    if header.get_timestamp() != deploy.get_timestamp() {
+        return mismatch("timestamp");
+    }
+    match deploy_body_hash(deploy) {
+        Some(body_hash) if body_hash.to_vec().as_slice() == header.get_body_hash() => Ok(()),
+        _ => mismatch("body"),
+    }
+}
+
+impl From<&super::ipc::CostTable> for WasmCosts {
+    fn from(cost_table: &super::ipc::CostTable) -> Self {
+        WasmCosts {
+            regular: cost_table.get_regular(),
+            div: cost_table.get_div(),
+            mul: cost_table.get_mul(),
+            mem: cost_table.get_mem(),
+            initial_mem: cost_table.get_initial_mem(),
+            grow_mem: cost_table.get_grow_mem(),
+            memcpy: cost_table.get_memcpy(),
+            opcodes_mul: cost_table.get_opcodes_mul(),
+            opcodes_div: cost_table.get_opcodes_div(),
+        }
+    }
+}
+
+impl From<WasmCosts> for super::ipc::CostTable {
+    fn from(wasm_costs: WasmCosts) -> Self {
+        let mut cost_table = super::ipc::CostTable::new();
+        cost_table.set_regular(wasm_costs.regular);
+        cost_table.set_div(wasm_costs.div);
+        cost_table.set_mul(wasm_costs.mul);
+        cost_table.set_mem(wasm_costs.mem);
+        cost_table.set_initial_mem(wasm_costs.initial_mem);
+        cost_table.set_grow_mem(wasm_costs.grow_mem);
+        cost_table.set_memcpy(wasm_costs.memcpy);
+        cost_table.set_opcodes_mul(wasm_costs.opcodes_mul);
+        cost_table.set_opcodes_div(wasm_costs.opcodes_div);
+        cost_table
+    }
+}
+
+impl TryFrom<&super::ipc::UpgradeRequest> for UpgradeConfig {
+    type Error = ParsingError;
+
+    fn try_from(upgrade_request: &super::ipc::UpgradeRequest) -> Result<Self, ParsingError> {
+        let prestate_hash: Blake2bHash = upgrade_request
+            .get_parent_state_hash()
+            .try_into()
+            .map_err(|_| ParsingError("Parent state hash has to be 32 bytes long.".to_string()))?;
+
+        if !upgrade_request.has_new_costs() {
+            return parse_error("Upgrade request has no cost table.".to_string());
+        }
+
+        // Same check as for the costs of a chainspec: opcode costs are divided by `opcodes_div`.
+        if upgrade_request.get_new_costs().get_opcodes_div() == 0 {
+            return parse_error("Cost table opcodes_div must be positive.".to_string());
+        }
+
+        let optional_code = |has_code: bool, deploy_code: &super::ipc::DeployCode| {
+            if has_code {
+                Some(deploy_code.get_code().to_vec())
+            } else {
+                None
+            }
+        };
+
+        Ok(UpgradeConfig::new(
+            prestate_hash,
+            upgrade_request.get_protocol_version().value,
+            upgrade_request.get_new_costs().into(),
+            optional_code(
+                upgrade_request.has_upgrade_installer(),
+                upgrade_request.get_upgrade_installer(),
+            ),
+            optional_code(
+                upgrade_request.has_new_mint_code(),
+                upgrade_request.get_new_mint_code(),
+            ),
+            optional_code(
+                upgrade_request.has_new_proof_of_stake_code(),
+                upgrade_request.get_new_proof_of_stake_code(),
+            ),
+        ))
+    }
+}
+
 impl From<Op> for super::ipc::Op {
     fn from(op: Op) -> super::ipc::Op {
         let mut ipc_op = super::ipc::Op::new();
@@ -719,6 +1312,36 @@ impl From<ExecutionResult> for ipc::DeployResult {
                     error @ EngineError::AuthorizationError => {
                         precondition_failure(error.to_string())
                     }
+                    error @ EngineError::InvalidDeploySignature(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::DeployHashMismatch(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::InvalidDeployItem(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::InvalidProtocolVersion(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::GasPriceTooLow { .. } => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::InvalidGenesisAccount(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::DeployExpired(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::DeployNotYetValid(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::DeployAlreadyExecuted => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::MissingDeployDependency(_) => {
+                        precondition_failure(error.to_string())
+                    }
                     EngineError::MissingSystemContractError(msg) => {
                         execution_error(msg, cost, effect)
                     }
@@ -757,29 +1380,8 @@ impl From<ExecutionResult> for ipc::DeployResult {
                             let msg = format!("Key {:?} not found.", key);
                             execution_error(msg, cost, effect)
                         }
-                        ExecutionError::InvalidNonce {
-                            deploy_nonce,
-                            expected_nonce,
-                        } if deploy_nonce <= expected_nonce => {
-                            // Deploys with nonce lower than (or equal to) current account's nonce will always fail.
-                            // They won't be repeated so we treat them as precondition failures.
-                            let error_msg = format!("Deploy nonce: {:?} was lower (or equal to) than expected nonce {:?}", deploy_nonce, expected_nonce);
-                            precondition_failure(error_msg)
-                        }
-                        ExecutionError::InvalidNonce {
-                            deploy_nonce,
-                            expected_nonce,
-                        } => {
-                            let mut deploy_result = ipc::DeployResult::new();
-                            let mut invalid_nonce = ipc::DeployResult_InvalidNonce::new();
-                            invalid_nonce.set_deploy_nonce(deploy_nonce);
-                            invalid_nonce.set_expected_nonce(expected_nonce);
-                            deploy_result.set_invalid_nonce(invalid_nonce);
-                            deploy_result
-                        }
-                        ExecutionError::Revert(status) => {
-                            let error_msg = format!("Exit code: {}", status);
-                            execution_error(error_msg, cost, effect)
+                        ExecutionError::Revert(status, payload) => {
+                            revert_error(status, payload, cost, effect)
                         }
                         ExecutionError::Interpreter(error) => {
                             // If the error happens during contract execution it's mapped to HostError
@@ -792,9 +1394,8 @@ impl From<ExecutionResult> for ipc::DeployResult {
                                     let downcasted_error =
                                         host_error.downcast_ref::<ExecutionError>().unwrap();
                                     match downcasted_error {
-                                        ExecutionError::Revert(status) => {
-                                            let errors_msg = format!("Exit code: {}", status);
-                                            execution_error(errors_msg, cost, effect)
+                                        ExecutionError::Revert(status, payload) => {
+                                            revert_error(*status, payload.clone(), cost, effect)
                                         }
                                         ExecutionError::KeyNotFound(key) => {
                                             let errors_msg = format!("Key {:?} not found.", key);
@@ -889,7 +1490,7 @@ where
 }
 
 /// Constructs an instance of [[ipc::DeployResult]] with an error set to [[ipc::DeployError_PreconditionFailure]].
-fn precondition_failure(msg: String) -> ipc::DeployResult {
+pub(crate) fn precondition_failure(msg: String) -> ipc::DeployResult {
     let mut deploy_result = ipc::DeployResult::new();
     let mut precondition_failure = ipc::DeployResult_PreconditionFailure::new();
     precondition_failure.set_message(msg);
@@ -918,6 +1519,47 @@ fn execution_error(msg: String, cost: u64, effect: ExecutionEffect) -> ipc::Depl
     deploy_result
 }
 
+/// Reports a revert as an execution error carrying the reason decoded from the exit code.
+fn revert_error(
+    status: u32,
+    payload: Vec<u8>,
+    cost: u64,
+    effect: ExecutionEffect,
+) -> ipc::DeployResult {
+    let mut revert = ipc::DeployError_RevertError::new();
+    revert.set_code(status);
+    let (category, value) = match ApiError::from(status) {
+        ApiError::Mint(value) => (
+            ipc::DeployError_RevertError_Category::MINT,
+            u32::from(value),
+        ),
+        ApiError::ProofOfStake(value) => (
+            ipc::DeployError_RevertError_Category::PROOF_OF_STAKE,
+            u32::from(value),
+        ),
+        ApiError::User(value) => (
+            ipc::DeployError_RevertError_Category::USER,
+            u32::from(value),
+        ),
+        ApiError::Unknown(value) => (ipc::DeployError_RevertError_Category::UNKNOWN, value),
+        standard => (
+            ipc::DeployError_RevertError_Category::STANDARD,
+            standard.into(),
+        ),
+    };
+    revert.set_category(category);
+    revert.set_value(value);
+    revert.set_payload(payload);
+
+    let mut deploy_result = execution_error(format!("Exit code: {}", status), cost, effect);
+    deploy_result
+        .mut_execution_result()
+        .mut_error()
+        .mut_exec_error()
+        .set_revert(revert);
+    deploy_result
+}
+
 pub fn to_domain_validators(bond: &ipc::Bond) -> Result<(PublicKey, U512), String> {
     let pk = PublicKey::try_from(bond.get_validator_public_key())
         .map_err(|_| "Public key has to be exactly 32 bytes long.")?;
@@ -936,23 +1578,36 @@ mod tests {
     use std::convert::TryInto;
 
     use proptest::prelude::*;
+    use protobuf::Message;
 
-    use contract_ffi::gens::{account_arb, contract_arb, key_arb, uref_map_arb, value_arb};
+    use contract_ffi::contract_api::error::ApiError;
+    use contract_ffi::gens::{
+        account_arb, contract_arb, contract_package_arb, key_arb, uref_map_arb, value_arb,
+    };
     use contract_ffi::key::Key;
     use contract_ffi::uref::{AccessRights, URef};
+    use contract_ffi::value::account::{
+        ActionThresholds, AssociatedKeys, BlockTime, PublicKey, PurseId, RecoveryKeys,
+        SignatureAlgorithm, TaggedPublicKey, Weight,
+    };
+    use contract_ffi::value::{Account, U512};
+    use engine_core::engine_state::deploy_header::DeployHeader;
     use engine_core::engine_state::error::Error::ExecError;
     use engine_core::engine_state::error::{Error as EngineError, RootNotFound};
     use engine_core::engine_state::execution_effect::ExecutionEffect;
     use engine_core::engine_state::execution_result::ExecutionResult;
-    use engine_core::execution::Error;
+    use engine_core::engine_state::genesis::GenesisAccount;
+    use engine_core::engine_state::ExecutableDeployItem;
+    use engine_core::execution::{BlockInfo, Error};
     use engine_server::mappings::CommitTransforms;
     use engine_shared::newtypes::Blake2bHash;
     use engine_shared::transform::gens::transform_arb;
     use engine_shared::transform::Transform;
+    use engine_wasm_prep::wasm_costs::WasmCosts;
 
-    use super::execution_error;
     use super::ipc;
     use super::state;
+    use super::{consensus, execution_error, verify_deploy_hash};
 
     // Test that wasm_error function actually returns DeployResult with result set to WasmError
     #[test]
@@ -1083,9 +1738,231 @@ mod tests {
         assert_eq!(expected_transform, *commit_transform.unwrap())
     }
 
+    #[test]
+    fn deploy_code_maps_to_executable_deploy_item() {
+        let args = vec![1u8, 2, 3];
+
+        let mut module_bytes_code = ipc::DeployCode::new();
+        module_bytes_code.set_code(vec![0u8; 8]);
+        module_bytes_code.set_args(args.clone());
+        let item: ExecutableDeployItem = (&module_bytes_code).try_into().expect("should parse");
+        assert_eq!(
+            item,
+            ExecutableDeployItem::ModuleBytes {
+                module_bytes: vec![0u8; 8],
+                args: args.clone()
+            }
+        );
+
+        let mut by_hash_code = ipc::DeployCode::new();
+        by_hash_code.set_stored_contract_hash(vec![7u8; 32]);
+        by_hash_code.set_args(args.clone());
+        let item: ExecutableDeployItem = (&by_hash_code).try_into().expect("should parse");
+        assert_eq!(
+            item,
+            ExecutableDeployItem::StoredContractByHash {
+                hash: [7u8; 32],
+                args: args.clone()
+            }
+        );
+
+        let mut by_name_code = ipc::DeployCode::new();
+        by_name_code.set_stored_contract_name("standard_payment".to_string());
+        by_name_code.set_args(args.clone());
+        let item: ExecutableDeployItem = (&by_name_code).try_into().expect("should parse");
+        assert_eq!(
+            item,
+            ExecutableDeployItem::StoredContractByName {
+                name: "standard_payment".to_string(),
+                args
+            }
+        );
+
+        let mut invalid_hash_code = ipc::DeployCode::new();
+        invalid_hash_code.set_stored_contract_hash(vec![7u8; 31]);
+        let result: Result<ExecutableDeployItem, _> = (&invalid_hash_code).try_into();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn exec_request_maps_to_block_info() {
+        let mut exec_request = ipc::ExecRequest::new();
+        exec_request.set_block_time(10);
+        exec_request.set_block_height(3);
+        exec_request.set_parent_block_hash(vec![1u8; 32]);
+        exec_request.set_proposer(vec![2u8; 32]);
+        let block_info: BlockInfo = (&exec_request).try_into().expect("should parse");
+        assert_eq!(
+            block_info,
+            BlockInfo::new(BlockTime(10), 3, [1u8; 32], PublicKey::new([2u8; 32]))
+        );
+
+        let block_info: BlockInfo = (&ipc::ExecRequest::new()).try_into().expect("should parse");
+        assert_eq!(
+            block_info,
+            BlockInfo::new(BlockTime(0), 0, [0u8; 32], PublicKey::new([0u8; 32]))
+        );
+
+        exec_request.set_proposer(vec![2u8; 31]);
+        let result: Result<BlockInfo, _> = (&exec_request).try_into();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deploy_maps_to_deploy_header() {
+        let mut deploy = ipc::Deploy::new();
+        deploy.set_deploy_hash(vec![1u8; 32]);
+        deploy.set_timestamp(10);
+        let deploy_header: DeployHeader = (&deploy).try_into().expect("should parse");
+        assert_eq!(deploy_header, DeployHeader::new([1u8; 32], BlockTime(10)));
+
+        deploy.set_ttl(20);
+        deploy.set_not_before(15);
+        deploy.set_dependencies(vec![vec![2u8; 32], vec![3u8; 32]].into());
+        let deploy_header: DeployHeader = (&deploy).try_into().expect("should parse");
+        assert_eq!(
+            deploy_header,
+            DeployHeader::new([1u8; 32], BlockTime(10))
+                .set_ttl(20)
+                .set_not_before(BlockTime(15))
+                .set_dependencies(vec![[2u8; 32], [3u8; 32]])
+        );
+
+        deploy.set_dependencies(vec![vec![2u8; 31]].into());
+        let result: Result<DeployHeader, _> = (&deploy).try_into();
+        assert!(result.is_err());
+
+        deploy.set_dependencies(Default::default());
+        deploy.set_deploy_hash(vec![1u8; 31]);
+        let result: Result<DeployHeader, _> = (&deploy).try_into();
+        assert!(result.is_err());
+    }
+
+    /// Deploy running `session_code`, along with the header its deploy hash is the hash of.
+    fn hashed_deploy(session_code: &[u8]) -> ipc::Deploy {
+        let mut session = ipc::DeployCode::new();
+        session.set_code(session_code.to_vec());
+        session.set_args(vec![1, 2, 3]);
+
+        let mut code = consensus::Deploy_Code::new();
+        code.set_code(session.get_code().to_vec());
+        code.set_args(session.get_args().to_vec());
+        hashed_deploy_of_session(session, code)
+    }
+
+    /// Deploy running `session`, along with the header of a body with `code` as its session.
+    fn hashed_deploy_of_session(
+        session: ipc::DeployCode,
+        code: consensus::Deploy_Code,
+    ) -> ipc::Deploy {
+        let account_public_key = [3u8; 32];
+        let mut body = consensus::Deploy_Body::new();
+        body.set_session(code);
+        let body_bytes = body.write_to_bytes().expect("should serialize body");
+
+        let mut header = consensus::Deploy_Header::new();
+        header.set_account_public_key(account_public_key.to_vec());
+        header.set_timestamp(10);
+        header.set_gas_price(2);
+        header.set_body_hash(Blake2bHash::new(&body_bytes).to_vec());
+        let header_bytes = header.write_to_bytes().expect("should serialize header");
+
+        let mut deploy = ipc::Deploy::new();
+        deploy.set_address(
+            TaggedPublicKey::ed25519(account_public_key)
+                .to_address()
+                .value()
+                .to_vec(),
+        );
+        deploy.set_session(session);
+        deploy.set_timestamp(10);
+        deploy.set_gas_price(2);
+        deploy.set_deploy_hash(Blake2bHash::new(&header_bytes).to_vec());
+        deploy.set_header(header_bytes);
+        deploy
+    }
+
+    fn assert_deploy_hash_mismatch(deploy: &ipc::Deploy, field: &str) {
+        match verify_deploy_hash(deploy) {
+            Err(EngineError::DeployHashMismatch(ref mismatch)) if mismatch == field => (),
+            other => panic!("expected a {} mismatch, got {:?}", field, other),
+        }
+    }
+
+    #[test]
+    fn should_verify_deploy_hash_of_header() {
+        let deploy = hashed_deploy(&[0, 97, 115, 109]);
+        assert!(verify_deploy_hash(&deploy).is_ok());
+
+        let mut other_hash = deploy.clone();
+        other_hash.set_deploy_hash(vec![1u8; 32]);
+        assert_deploy_hash_mismatch(&other_hash, "header");
+
+        let mut other_address = deploy.clone();
+        other_address.set_address(vec![3u8; 32]);
+        assert_deploy_hash_mismatch(&other_address, "account");
+
+        let mut other_gas_price = deploy.clone();
+        other_gas_price.set_gas_price(1);
+        assert_deploy_hash_mismatch(&other_gas_price, "gas price");
+
+        let mut other_timestamp = deploy.clone();
+        other_timestamp.set_timestamp(11);
+        assert_deploy_hash_mismatch(&other_timestamp, "timestamp");
+
+        let mut other_code = deploy.clone();
+        other_code.mut_session().set_code(vec![0, 97, 115, 110]);
+        assert_deploy_hash_mismatch(&other_code, "body");
+
+        let mut payment = deploy.clone();
+        payment.set_payment(deploy.get_session().clone());
+        assert_deploy_hash_mismatch(&payment, "body");
+
+        let mut stored_session = deploy;
+        stored_session
+            .mut_session()
+            .set_stored_contract_hash(vec![2u8; 32]);
+        assert_deploy_hash_mismatch(&stored_session, "body");
+    }
+
+    #[test]
+    fn should_verify_deploy_hash_of_stored_contract_references() {
+        let mut session = ipc::DeployCode::new();
+        session.set_args(vec![1, 2, 3]);
+        session.set_stored_contract_name("session".to_string());
+        let mut code = consensus::Deploy_Code::new();
+        code.set_args(session.get_args().to_vec());
+        code.set_stored_contract_name("session".to_string());
+        let deploy = hashed_deploy_of_session(session, code.clone());
+        assert!(verify_deploy_hash(&deploy).is_ok());
+
+        let mut other_name = deploy.clone();
+        other_name
+            .mut_session()
+            .set_stored_contract_name("other".to_string());
+        assert_deploy_hash_mismatch(&other_name, "body");
+
+        let mut by_hash = deploy.clone();
+        by_hash
+            .mut_session()
+            .set_stored_contract_hash(vec![2u8; 32]);
+        assert_deploy_hash_mismatch(&by_hash, "body");
+
+        code.set_stored_contract_hash(vec![2u8; 32]);
+        let deploy = hashed_deploy_of_session(by_hash.take_session(), code);
+        assert!(verify_deploy_hash(&deploy).is_ok());
+    }
+
+    #[test]
+    fn cost_table_roundtrip() {
+        let wasm_costs = WasmCosts::from_version(1).expect("should have costs");
+        let cost_table: ipc::CostTable = wasm_costs.clone().into();
+        assert_eq!(WasmCosts::from(&cost_table), wasm_costs);
+    }
+
     #[test]
     fn revert_error_maps_to_execution_error() {
-        let revert_error = Error::Revert(10);
+        let revert_error = Error::Revert(10, Vec::new());
         let exec_result = ExecutionResult::Failure {
             error: ExecError(revert_error),
             effect: Default::default(),
@@ -1101,6 +1978,170 @@ mod tests {
         );
     }
 
+    #[test]
+    fn revert_error_should_carry_reason_and_payload() {
+        let status: u32 = ApiError::ProofOfStake(5).into();
+        let exec_result = ExecutionResult::Failure {
+            error: ExecError(Error::Revert(status, vec![1, 2, 3])),
+            effect: Default::default(),
+            cost: 10,
+        };
+        let ipc_result: ipc::DeployResult = exec_result.into();
+        let exec_error = ipc_result
+            .get_execution_result()
+            .get_error()
+            .get_exec_error();
+        assert_eq!(exec_error.message, format!("Exit code: {}", status));
+        let revert = exec_error.get_revert();
+        assert_eq!(revert.code, status);
+        assert_eq!(
+            revert.category,
+            ipc::DeployError_RevertError_Category::PROOF_OF_STAKE
+        );
+        assert_eq!(revert.value, 5);
+        assert_eq!(revert.payload, vec![1, 2, 3]);
+
+        let exec_result = ExecutionResult::Failure {
+            error: ExecError(Error::Revert(100, Vec::new())),
+            effect: Default::default(),
+            cost: 10,
+        };
+        let ipc_result: ipc::DeployResult = exec_result.into();
+        let revert = ipc_result
+            .get_execution_result()
+            .get_error()
+            .get_exec_error()
+            .get_revert();
+        assert_eq!(
+            revert.category,
+            ipc::DeployError_RevertError_Category::UNKNOWN
+        );
+        assert_eq!(revert.value, 100);
+    }
+
+    #[test]
+    fn genesis_account_should_default_to_own_key_and_default_thresholds() {
+        let public_key = PublicKey::new([3u8; 32]);
+        let balance = U512::from(100);
+        let mut ipc_genesis_account = ipc::GenesisAccount::new();
+        ipc_genesis_account.set_public_key(public_key.value().to_vec());
+        ipc_genesis_account.set_balance(balance.into());
+
+        let genesis_account: GenesisAccount = (&ipc_genesis_account)
+            .try_into()
+            .expect("should parse genesis account");
+
+        assert_eq!(genesis_account, GenesisAccount::create(public_key, balance));
+    }
+
+    #[test]
+    fn genesis_account_should_parse_keys_thresholds_and_bond() {
+        let public_key = PublicKey::new([3u8; 32]);
+        let other_public_key = PublicKey::new([4u8; 32]);
+        let mut ipc_genesis_account = ipc::GenesisAccount::new();
+        ipc_genesis_account.set_public_key(public_key.value().to_vec());
+        ipc_genesis_account.set_balance(U512::from(100).into());
+        let ipc_associated_keys: Vec<state::Account_AssociatedKey> =
+            vec![(public_key, 1), (other_public_key, 2)]
+                .into_iter()
+                .map(|(key, weight)| {
+                    let mut ipc_associated_key = state::Account_AssociatedKey::new();
+                    ipc_associated_key.set_public_key(key.value().to_vec());
+                    ipc_associated_key.set_weight(weight);
+                    ipc_associated_key
+                })
+                .collect();
+        ipc_genesis_account.set_associated_keys(ipc_associated_keys.into());
+        let mut ipc_action_thresholds = state::Account_ActionThresholds::new();
+        ipc_action_thresholds.set_deployment_threshold(2);
+        ipc_action_thresholds.set_key_management_threshold(3);
+        ipc_genesis_account.set_action_thresholds(ipc_action_thresholds);
+        ipc_genesis_account.set_bonded_amount(U512::from(50).into());
+
+        let genesis_account: GenesisAccount = (&ipc_genesis_account)
+            .try_into()
+            .expect("should parse genesis account");
+
+        let mut associated_keys = AssociatedKeys::new(public_key, Weight::new(1));
+        associated_keys
+            .add_key(other_public_key, Weight::new(2))
+            .expect("should add key");
+        let action_thresholds = ActionThresholds::new(Weight::new(2), Weight::new(3))
+            .expect("should create thresholds");
+        assert_eq!(
+            genesis_account,
+            GenesisAccount::new(
+                public_key,
+                U512::from(100),
+                associated_keys,
+                action_thresholds,
+                U512::from(50),
+            )
+        );
+    }
+
+    #[test]
+    fn genesis_account_should_derive_address_from_tagged_public_key() {
+        let tagged_public_key = TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &[3u8; 33])
+            .expect("should create key");
+        let balance = U512::from(100);
+        let mut ipc_tagged_public_key = ipc::TaggedPublicKey::new();
+        ipc_tagged_public_key.set_algorithm(ipc::SignatureAlgorithm::SECP256K1);
+        ipc_tagged_public_key.set_key(tagged_public_key.as_bytes().to_vec());
+        let mut ipc_genesis_account = ipc::GenesisAccount::new();
+        ipc_genesis_account.set_tagged_public_key(ipc_tagged_public_key);
+        ipc_genesis_account.set_balance(balance.into());
+
+        let genesis_account: GenesisAccount = (&ipc_genesis_account)
+            .try_into()
+            .expect("should parse genesis account");
+
+        assert_eq!(
+            genesis_account,
+            GenesisAccount::create(tagged_public_key.to_address(), balance)
+        );
+    }
+
+    #[test]
+    fn genesis_account_should_reject_tagged_public_key_of_wrong_length() {
+        let mut ipc_tagged_public_key = ipc::TaggedPublicKey::new();
+        ipc_tagged_public_key.set_algorithm(ipc::SignatureAlgorithm::SECP256K1);
+        ipc_tagged_public_key.set_key(vec![3u8; 32]);
+        let mut ipc_genesis_account = ipc::GenesisAccount::new();
+        ipc_genesis_account.set_tagged_public_key(ipc_tagged_public_key);
+        ipc_genesis_account.set_balance(U512::from(100).into());
+
+        let result: Result<GenesisAccount, _> = (&ipc_genesis_account).try_into();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn genesis_account_should_reject_short_public_key() {
+        let mut ipc_genesis_account = ipc::GenesisAccount::new();
+        ipc_genesis_account.set_public_key(vec![3u8; 31]);
+        ipc_genesis_account.set_balance(U512::from(100).into());
+
+        let result: Result<GenesisAccount, _> = (&ipc_genesis_account).try_into();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn account_without_recovery_keys_should_have_default_recovery_keys() {
+        let purse_id = PurseId::new(URef::new([1u8; 32], AccessRights::READ_ADD_WRITE));
+        let mut account = Account::create([2u8; 32], Default::default(), purse_id);
+        account
+            .add_recovery_key(PublicKey::new([3u8; 32]), Weight::new(1))
+            .expect("should add recovery key");
+        let mut ipc_account: super::state::Account = account.into();
+        ipc_account.clear_recovery_keys();
+
+        let account: Account = (&ipc_account).try_into().expect("should parse");
+
+        assert_eq!(account.recovery_keys(), &RecoveryKeys::default());
+    }
+
     proptest! {
         #[test]
         fn key_roundtrip(key in key_arb()) {
@@ -1135,6 +2176,14 @@ mod tests {
             assert_eq!(contract, contract_back)
         }
 
+        #[test]
+        fn contract_package_roundtrip(package in contract_package_arb()) {
+            let ipc_package: super::state::ContractPackage = package.clone().into();
+            let package_back = (&ipc_package).try_into()
+                .expect("Transforming state::ContractPackage into domain ContractPackage should succeed.");
+            assert_eq!(package, package_back)
+        }
+
         #[test]
         fn value_roundtrip(value in value_arb()) {
             let ipc_value: super::state::Value = value.clone().into();
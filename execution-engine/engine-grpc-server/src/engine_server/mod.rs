@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::fmt::Debug;
@@ -7,27 +7,32 @@ use std::marker::{Send, Sync};
 use std::time::Instant;
 
 use contract_ffi::key::Key;
-use contract_ffi::value::account::{BlockTime, PublicKey};
+use contract_ffi::value::account::{PublicKey, SignatureAlgorithm, TaggedPublicKey};
 use contract_ffi::value::U512;
+use engine_core::engine_state::deploy_header::DeployHeader;
 use engine_core::engine_state::error::Error as EngineError;
 use engine_core::engine_state::execution_result::ExecutionResult;
-use engine_core::engine_state::genesis::GenesisURefsSource;
+use engine_core::engine_state::genesis::{GenesisAccount, GenesisURefsSource};
+use engine_core::engine_state::upgrade::{UpgradeConfig, UpgradeResult};
 use engine_core::engine_state::{
-    genesis::GenesisResult, get_bonded_validators, EngineState, GetBondedValidatorsError,
+    genesis::GenesisResult, get_bonded_validators, Approval, EngineState, ExecutableDeployItem,
+    GetBondedValidatorsError,
 };
-use engine_core::execution::{Executor, WasmiExecutor};
+use engine_core::execution::{BlockInfo, Executor, WasmiExecutor};
 use engine_core::tracking_copy::QueryResult;
 use engine_server::ipc::CommitResponse;
 use engine_shared::logging;
 use engine_shared::logging::{log_duration, log_info};
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 use engine_storage::global_state::{CommitResult, History};
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::import_set::ImportSet;
 use engine_wasm_prep::{Preprocessor, WasmiPreprocessor};
+use parity_wasm::elements::Module;
 
 use self::ipc_grpc::ExecutionEngineService;
 use self::mappings::*;
 
+pub mod consensus;
 pub mod ipc;
 pub mod ipc_grpc;
 pub mod mappings;
@@ -40,12 +45,14 @@ const METRIC_DURATION_EXEC: &str = "exec_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
 const METRIC_DURATION_VALIDATE: &str = "validate_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
+const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
 const TAG_RESPONSE_VALIDATE: &str = "validate_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
+const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
 
 // Idea is that Engine will represent the core of the execution engine project.
 // It will act as an entry point for execution of Wasm binaries.
@@ -160,14 +167,56 @@ where
         // TODO: don't unwrap
         let prestate_hash: Blake2bHash = exec_request.get_parent_state_hash().try_into().unwrap();
 
-        let blocktime = BlockTime(exec_request.get_block_time());
+        let deploys = exec_request.get_deploys();
 
-        // TODO: don't unwrap
-        let wasm_costs = WasmCosts::from_version(protocol_version.value).unwrap();
+        let wasm_costs =
+            match self.get_wasm_costs(correlation_id, prestate_hash, protocol_version.value) {
+                Ok(Some(wasm_costs)) => wasm_costs,
+                Ok(None) => {
+                    let message = format!(
+                        "Protocol version {} is not active at the parent state",
+                        protocol_version.value
+                    );
+                    return grpc::SingleResponse::completed(exec_precondition_failure(
+                        deploys, message,
+                    ));
+                }
+                Err(error) => {
+                    return grpc::SingleResponse::completed(exec_precondition_failure(
+                        deploys,
+                        error.to_string(),
+                    ));
+                }
+            };
 
-        let deploys = exec_request.get_deploys();
+        let wasm_limits_and_imports =
+            self.config()
+                .wasm_limits(protocol_version.value)
+                .and_then(|limits| {
+                    ImportSet::from_version(protocol_version.value).map(|imports| (limits, imports))
+                });
+        let (wasm_limits, import_set) = match wasm_limits_and_imports {
+            Some(wasm_limits_and_imports) => wasm_limits_and_imports,
+            None => {
+                let error = EngineError::InvalidProtocolVersion(protocol_version.value);
+                return grpc::SingleResponse::completed(exec_precondition_failure(
+                    deploys,
+                    error.to_string(),
+                ));
+            }
+        };
 
-        let preprocessor: WasmiPreprocessor = WasmiPreprocessor::new(wasm_costs);
+        let block_info: BlockInfo = match (&exec_request).try_into() {
+            Ok(block_info) => block_info,
+            Err(ParsingError(message)) => {
+                return grpc::SingleResponse::completed(exec_precondition_failure(
+                    deploys, message,
+                ));
+            }
+        };
+
+        let preprocessor: WasmiPreprocessor =
+            WasmiPreprocessor::new(wasm_costs, wasm_limits, import_set);
 
         let executor = WasmiExecutor;
 
@@ -176,7 +225,7 @@ where
             &executor,
             &preprocessor,
             prestate_hash,
-            blocktime,
+            block_info,
             deploys,
             protocol_version,
             correlation_id,
@@ -337,11 +386,34 @@ where
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
-        let genesis_account_addr = {
+        let genesis_account_addr = if genesis_request.has_public_key() {
+            let public_key: Result<TaggedPublicKey, ParsingError> =
+                genesis_request.get_public_key().try_into();
+            match public_key {
+                Ok(public_key) => public_key.to_address().value(),
+                Err(ParsingError(err_msg)) => {
+                    logging::log_error(&err_msg);
+
+                    let mut genesis_response = ipc::GenesisResponse::new();
+                    let mut genesis_deploy_error = ipc::GenesisDeployError::new();
+                    genesis_deploy_error.set_message(err_msg);
+                    genesis_response.set_failed_deploy(genesis_deploy_error);
+
+                    log_duration(
+                        correlation_id,
+                        METRIC_DURATION_GENESIS,
+                        TAG_RESPONSE_GENESIS,
+                        start.elapsed(),
+                    );
+
+                    return grpc::SingleResponse::completed(genesis_response);
+                }
+            }
+        } else {
             let address = genesis_request.get_address();
             if address.len() != 32 {
                 let err_msg =
-                    "genesis account public key has to be exactly 32 bytes long.".to_string();
+                    "genesis account address has to be exactly 32 bytes long.".to_string();
                 logging::log_error(&err_msg);
 
                 let mut genesis_response = ipc::GenesisResponse::new();
@@ -420,6 +492,33 @@ where
             }
         };
 
+        let genesis_accounts_result: Result<Vec<GenesisAccount>, ParsingError> = genesis_request
+            .get_accounts()
+            .iter()
+            .map(TryInto::try_into)
+            .collect();
+
+        let genesis_accounts = match genesis_accounts_result {
+            Ok(genesis_accounts) => genesis_accounts,
+            Err(ParsingError(err_msg)) => {
+                logging::log_error(&err_msg);
+
+                let mut genesis_response = ipc::GenesisResponse::new();
+                let mut genesis_deploy_error = ipc::GenesisDeployError::new();
+                genesis_deploy_error.set_message(err_msg);
+                genesis_response.set_failed_deploy(genesis_deploy_error);
+
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GENESIS,
+                    TAG_RESPONSE_GENESIS,
+                    start.elapsed(),
+                );
+
+                return grpc::SingleResponse::completed(genesis_response);
+            }
+        };
+
         let protocol_version = genesis_request.get_protocol_version().value;
 
         let genesis_response = match self.commit_genesis(
@@ -429,6 +528,7 @@ where
             mint_code_bytes,
             proof_of_stake_code_bytes,
             genesis_validators,
+            genesis_accounts,
             protocol_version,
         ) {
             Ok(GenesisResult::Success {
@@ -476,6 +576,119 @@ where
 
         grpc::SingleResponse::completed(genesis_response)
     }
+
+    fn upgrade(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        upgrade_request: ipc::UpgradeRequest,
+    ) -> grpc::SingleResponse<ipc::UpgradeResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let upgrade_config: UpgradeConfig = match (&upgrade_request).try_into() {
+            Ok(upgrade_config) => upgrade_config,
+            Err(ParsingError(err_msg)) => {
+                logging::log_error(&err_msg);
+                let upgrade_response = upgrade_failure(err_msg);
+
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_UPGRADE,
+                    TAG_RESPONSE_UPGRADE,
+                    start.elapsed(),
+                );
+
+                return grpc::SingleResponse::completed(upgrade_response);
+            }
+        };
+
+        let protocol_version = upgrade_config.protocol_version();
+        let wasm_limits_and_imports =
+            self.config()
+                .wasm_limits(protocol_version)
+                .and_then(|limits| {
+                    ImportSet::from_version(protocol_version).map(|imports| (limits, imports))
+                });
+        let preprocessor = match wasm_limits_and_imports {
+            Some((wasm_limits, import_set)) => {
+                WasmiPreprocessor::new(upgrade_config.wasm_costs().clone(), wasm_limits, import_set)
+            }
+            None => {
+                let err_msg = EngineError::InvalidProtocolVersion(protocol_version).to_string();
+                logging::log_error(&err_msg);
+                let upgrade_response = upgrade_failure(err_msg);
+
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_UPGRADE,
+                    TAG_RESPONSE_UPGRADE,
+                    start.elapsed(),
+                );
+
+                return grpc::SingleResponse::completed(upgrade_response);
+            }
+        };
+
+        let executor = WasmiExecutor;
+
+        let upgrade_response =
+            match self.commit_upgrade(correlation_id, upgrade_config, &executor, &preprocessor) {
+                Ok(UpgradeResult::Success {
+                    post_state_hash,
+                    effect,
+                }) => {
+                    let success_message = format!("upgrade successful: {}", post_state_hash);
+                    log_info(&success_message);
+
+                    let mut upgrade_response = ipc::UpgradeResponse::new();
+                    let mut upgrade_result = ipc::UpgradeResult::new();
+                    upgrade_result.set_poststate_hash(post_state_hash.to_vec());
+                    upgrade_result.set_effect(effect.into());
+                    upgrade_response.set_success(upgrade_result);
+                    upgrade_response
+                }
+                Ok(upgrade_result) => {
+                    let err_msg = upgrade_result.to_string();
+                    logging::log_error(&err_msg);
+                    upgrade_failure(err_msg)
+                }
+                Err(err) => {
+                    let err_msg = err.to_string();
+                    logging::log_error(&err_msg);
+                    upgrade_failure(err_msg)
+                }
+            };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_UPGRADE,
+            TAG_RESPONSE_UPGRADE,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(upgrade_response)
+    }
+}
+
+fn upgrade_failure(err_msg: String) -> ipc::UpgradeResponse {
+    let mut upgrade_response = ipc::UpgradeResponse::new();
+    let mut upgrade_deploy_error = ipc::UpgradeDeployError::new();
+    upgrade_deploy_error.set_message(err_msg);
+    upgrade_response.set_failed_deploy(upgrade_deploy_error);
+    upgrade_response
+}
+
+/// Fails each of `deploys` with the same precondition failure.
+fn exec_precondition_failure(deploys: &[ipc::Deploy], message: String) -> ipc::ExecResponse {
+    let deploy_results = deploys
+        .iter()
+        .map(|_| precondition_failure(message.clone()))
+        .collect();
+    let mut exec_response = ipc::ExecResponse::new();
+    let mut exec_result = ipc::ExecResult::new();
+    exec_result.set_deploy_results(protobuf::RepeatedField::from_vec(deploy_results));
+    exec_response.set_success(exec_result);
+    exec_response
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -484,12 +697,13 @@ fn run_deploys<A, H, E, P>(
     executor: &E,
     preprocessor: &P,
     prestate_hash: Blake2bHash,
-    blocktime: BlockTime,
+    block_info: BlockInfo,
     deploys: &[ipc::Deploy],
     protocol_version: &state::ProtocolVersion,
     correlation_id: CorrelationId,
 ) -> Result<Vec<ipc::DeployResult>, ipc::RootNotFound>
 where
+    A: From<Module>,
     H: History,
     E: Executor<A>,
     P: Preprocessor<A>,
@@ -501,16 +715,37 @@ where
     // the same prestate and all of them would fail.
     // Iterator (Result<_, _> + collect()) will short circuit the execution
     // when run_deploy returns Err.
+    // Deploys sharing the prestate don't see each other's executed deploy records, so repeated
+    // deploy hashes within the block are rejected here.
+    let mut deploy_hashes = HashSet::new();
     deploys
         .iter()
         .map(|deploy| {
-            let session = deploy.get_session();
-            let session_module_bytes = &session.code;
-            let session_args = &session.args;
+            let session: ExecutableDeployItem = match deploy.get_session().try_into() {
+                Ok(session) => session,
+                Err(ParsingError(message)) => return Ok(precondition_failure(message)),
+            };
 
-            let payment = deploy.get_payment();
-            let payment_module_bytes = &payment.code;
-            let payment_args = &payment.args;
+            let payment: ExecutableDeployItem = match deploy.get_payment().try_into() {
+                Ok(payment) => payment,
+                Err(ParsingError(message)) => return Ok(precondition_failure(message)),
+            };
+
+            let deploy_header: DeployHeader = match deploy.try_into() {
+                Ok(deploy_header) => deploy_header,
+                Err(ParsingError(message)) => return Ok(precondition_failure(message)),
+            };
+            // Approvals sign the deploy hash, so it is checked along with their signatures.
+            if engine_state.config().verify_deploy_signatures() {
+                if let Err(error) = verify_deploy_hash(deploy) {
+                    return Ok(ExecutionResult::precondition_failure(error).into());
+                }
+            }
+            if !deploy_hashes.insert(deploy_header.deploy_hash()) {
+                let failure =
+                    ExecutionResult::precondition_failure(EngineError::DeployAlreadyExecuted);
+                return Ok(failure.into());
+            }
 
             let address = {
                 let address_len = deploy.address.len();
@@ -527,14 +762,39 @@ where
                 Key::Account(dest)
             };
 
-            // Parse all authorization keys from IPC into a vector
-            let authorized_keys: BTreeSet<PublicKey> = {
+            // Parse all approvals from IPC into a vector
+            let approvals: Vec<Approval> = {
+                let maybe_approvals: Result<Vec<_>, EngineError> = deploy
+                    .get_approvals()
+                    .iter()
+                    .map(|approval| {
+                        // Try to convert the public key bytes into a possibly
+                        // valid TaggedPublicKey of the approval's algorithm
+                        let algorithm: SignatureAlgorithm = approval.get_algorithm().into();
+                        let key_bytes = approval.get_public_key();
+                        TaggedPublicKey::new(algorithm, key_bytes)
+                            .map(|public_key| {
+                                Approval::new(public_key, approval.get_signature().to_vec())
+                            })
+                            .map_err(|_| EngineError::InvalidPublicKeyLength {
+                                expected: algorithm.public_key_length(),
+                                actual: key_bytes.len(),
+                            })
+                    })
+                    .collect();
+
+                match maybe_approvals {
+                    Ok(approvals) => approvals,
+                    Err(error) => return Ok(ExecutionResult::precondition_failure(error).into()),
+                }
+            };
+
+            // Parse all unsigned authorization keys from IPC into a vector
+            let unsigned_keys: BTreeSet<PublicKey> = {
                 let maybe_keys: Result<BTreeSet<_>, EngineError> = deploy
                     .authorization_keys
                     .iter()
                     .map(|key_bytes| {
-                        // Try to convert an element of bytes into a possibly
-                        // valid PublicKey with error handling
                         PublicKey::try_from(key_bytes.as_slice()).map_err(|_| {
                             EngineError::InvalidPublicKeyLength {
                                 expected: EXPECTED_PUBLIC_KEY_LENGTH,
@@ -550,23 +810,26 @@ where
                 }
             };
 
-            let nonce = deploy.nonce;
+            let gas_price = deploy.gas_price;
             // TODO: is the rounding in this division ok?
-            let gas_limit =
-                (deploy.motes_transferred_in_payment as u64) / (deploy.gas_price as u64);
+            // A zero gas price is rejected by `run_deploy`.
+            let gas_limit = deploy
+                .motes_transferred_in_payment
+                .checked_div(gas_price)
+                .unwrap_or_default();
             let protocol_version = protocol_version.value;
             engine_state
                 .run_deploy(
-                    session_module_bytes,
-                    session_args,
-                    payment_module_bytes,
-                    payment_args,
+                    session,
+                    payment,
                     address,
-                    authorized_keys,
-                    blocktime,
-                    nonce,
+                    approvals,
+                    unsigned_keys,
+                    block_info,
+                    deploy_header,
                     prestate_hash,
                     gas_limit,
+                    gas_price,
                     protocol_version,
                     correlation_id,
                     executor,
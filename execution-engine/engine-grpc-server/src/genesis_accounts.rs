@@ -0,0 +1,347 @@
+//! Accounts created at genesis in addition to the ones given in the genesis request, loaded from a
+//! TOML file.
+//!
+//! Public keys are hex encoded and amounts are decimal strings, as they don't fit in a TOML
+//! integer. The `algorithm` of a public key is either `"ed25519"`, the default, or `"secp256k1"`
+//! for compressed secp256k1 keys, and the addresses of accounts and associated keys are derived
+//! from their tagged public keys. Only `public_key` and `balance` are required: an account without
+//! associated keys gets its own public key with weight 1, thresholds default to 1, and an account
+//! without a `bonded_amount` isn't a validator.
+//!
+//! ```toml
+//! [[accounts]]
+//! public_key = "0303030303030303030303030303030303030303030303030303030303030303"
+//! balance = "1000000000"
+//! bonded_amount = "1000"
+//! deployment_threshold = 1
+//! key_management_threshold = 2
+//!
+//! [[accounts.associated_keys]]
+//! public_key = "0303030303030303030303030303030303030303030303030303030303030303"
+//! weight = 1
+//!
+//! [[accounts.associated_keys]]
+//! public_key = "020404040404040404040404040404040404040404040404040404040404040404"
+//! algorithm = "secp256k1"
+//! weight = 1
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use contract_ffi::value::account::{
+    ActionThresholds, AssociatedKeys, PublicKey, SignatureAlgorithm, TaggedPublicKey, Weight,
+};
+use contract_ffi::value::U512;
+use engine_core::engine_state::genesis::GenesisAccount;
+
+#[derive(Debug)]
+pub enum GenesisAccountsError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for GenesisAccountsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenesisAccountsError::Io(error) => {
+                write!(f, "Could not read genesis accounts: {}", error)
+            }
+            GenesisAccountsError::Parse(error) => {
+                write!(f, "Could not parse genesis accounts: {}", error)
+            }
+            GenesisAccountsError::Invalid(message) => {
+                write!(f, "Invalid genesis accounts: {}", message)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for GenesisAccountsError {
+    fn from(error: io::Error) -> Self {
+        GenesisAccountsError::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for GenesisAccountsError {
+    fn from(error: toml::de::Error) -> Self {
+        GenesisAccountsError::Parse(error)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AssociatedKeyConfig {
+    public_key: String,
+    algorithm: Option<String>,
+    weight: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisAccountConfig {
+    public_key: String,
+    algorithm: Option<String>,
+    balance: String,
+    bonded_amount: Option<String>,
+    #[serde(default)]
+    associated_keys: Vec<AssociatedKeyConfig>,
+    deployment_threshold: Option<u8>,
+    key_management_threshold: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisAccountsConfig {
+    #[serde(default)]
+    accounts: Vec<GenesisAccountConfig>,
+}
+
+/// Reads the accounts listed in the file at `path`.
+pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Vec<GenesisAccount>, GenesisAccountsError> {
+    let bytes = fs::read(path)?;
+    from_bytes(&bytes)
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Result<Vec<GenesisAccount>, GenesisAccountsError> {
+    let config: GenesisAccountsConfig = toml::from_slice(bytes)?;
+    config.accounts.iter().map(to_genesis_account).collect()
+}
+
+fn to_genesis_account(
+    config: &GenesisAccountConfig,
+) -> Result<GenesisAccount, GenesisAccountsError> {
+    let public_key = parse_public_key(&config.public_key, &config.algorithm)?;
+    let balance = parse_amount(&config.balance)?;
+    let bonded_amount = match config.bonded_amount {
+        Some(ref bonded_amount) => parse_amount(bonded_amount)?,
+        None => U512::zero(),
+    };
+    let associated_keys = if config.associated_keys.is_empty() {
+        AssociatedKeys::new(public_key, Weight::new(1))
+    } else {
+        let mut associated_keys = AssociatedKeys::empty();
+        for associated_key in &config.associated_keys {
+            let key = parse_public_key(&associated_key.public_key, &associated_key.algorithm)?;
+            associated_keys
+                .add_key(key, Weight::new(associated_key.weight))
+                .map_err(|error| GenesisAccountsError::Invalid(error.to_string()))?;
+        }
+        associated_keys
+    };
+    let action_thresholds = ActionThresholds::new(
+        Weight::new(config.deployment_threshold.unwrap_or(1)),
+        Weight::new(config.key_management_threshold.unwrap_or(1)),
+    )
+    .map_err(|error| GenesisAccountsError::Invalid(error.to_string()))?;
+    Ok(GenesisAccount::new(
+        public_key,
+        balance,
+        associated_keys,
+        action_thresholds,
+        bonded_amount,
+    ))
+}
+
+/// Parses the public key and returns its address.
+fn parse_public_key(
+    hex: &str,
+    algorithm: &Option<String>,
+) -> Result<PublicKey, GenesisAccountsError> {
+    let algorithm = match algorithm.as_ref().map(String::as_str) {
+        None | Some("ed25519") => SignatureAlgorithm::Ed25519,
+        Some("secp256k1") => SignatureAlgorithm::Secp256k1,
+        Some(other) => {
+            return Err(GenesisAccountsError::Invalid(format!(
+                "unknown algorithm {:?}",
+                other
+            )))
+        }
+    };
+    let invalid = || GenesisAccountsError::Invalid(format!("invalid public key {:?}", hex));
+    if hex.len() != 2 * algorithm.public_key_length() || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let mut bytes = vec![0u8; algorithm.public_key_length()];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * index..2 * index + 2], 16).map_err(|_| invalid())?;
+    }
+    let tagged_public_key = TaggedPublicKey::new(algorithm, &bytes).map_err(|_| invalid())?;
+    Ok(tagged_public_key.to_address())
+}
+
+fn parse_amount(amount: &str) -> Result<U512, GenesisAccountsError> {
+    U512::from_dec_str(amount)
+        .map_err(|_| GenesisAccountsError::Invalid(format!("invalid amount {:?}", amount)))
+}
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::value::account::{
+        ActionThresholds, AssociatedKeys, PublicKey, SignatureAlgorithm, TaggedPublicKey, Weight,
+    };
+    use contract_ffi::value::U512;
+    use engine_core::engine_state::genesis::GenesisAccount;
+
+    use super::GenesisAccountsError;
+
+    const ACCOUNT_1_KEY: &str = "0303030303030303030303030303030303030303030303030303030303030303";
+    const ACCOUNT_2_KEY: &str = "0404040404040404040404040404040404040404040404040404040404040404";
+    const SECP256K1_KEY: &str =
+        "020404040404040404040404040404040404040404040404040404040404040404";
+
+    fn address(key: [u8; 32]) -> PublicKey {
+        TaggedPublicKey::ed25519(key).to_address()
+    }
+
+    fn account_toml(public_key: &str, balance: &str) -> String {
+        format!(
+            "[[accounts]]\npublic_key = \"{}\"\nbalance = \"{}\"\n",
+            public_key, balance
+        )
+    }
+
+    #[test]
+    fn should_load_accounts_with_defaults() {
+        let mut toml = account_toml(ACCOUNT_1_KEY, "100");
+        toml.push_str(&account_toml(ACCOUNT_2_KEY, "200"));
+
+        let genesis_accounts = super::from_bytes(toml.as_bytes()).expect("should load");
+
+        assert_eq!(
+            genesis_accounts,
+            vec![
+                GenesisAccount::create(address([3u8; 32]), U512::from(100)),
+                GenesisAccount::create(address([4u8; 32]), U512::from(200)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_load_keys_thresholds_and_bond() {
+        let mut toml = account_toml(ACCOUNT_1_KEY, "100");
+        toml.push_str("bonded_amount = \"50\"\n");
+        toml.push_str("deployment_threshold = 1\nkey_management_threshold = 2\n");
+        for public_key in &[ACCOUNT_1_KEY, ACCOUNT_2_KEY] {
+            toml.push_str(&format!(
+                "[[accounts.associated_keys]]\npublic_key = \"{}\"\nweight = 1\n",
+                public_key
+            ));
+        }
+
+        let genesis_accounts = super::from_bytes(toml.as_bytes()).expect("should load");
+
+        let mut associated_keys = AssociatedKeys::new(address([3u8; 32]), Weight::new(1));
+        associated_keys
+            .add_key(address([4u8; 32]), Weight::new(1))
+            .expect("should add key");
+        let action_thresholds = ActionThresholds::new(Weight::new(1), Weight::new(2))
+            .expect("should create thresholds");
+        assert_eq!(
+            genesis_accounts,
+            vec![GenesisAccount::new(
+                address([3u8; 32]),
+                U512::from(100),
+                associated_keys,
+                action_thresholds,
+                U512::from(50),
+            )]
+        );
+    }
+
+    #[test]
+    fn should_load_accounts_with_mixed_key_algorithms() {
+        let mut toml = account_toml(SECP256K1_KEY, "100");
+        toml.push_str("algorithm = \"secp256k1\"\n");
+        for (public_key, algorithm) in &[(SECP256K1_KEY, "secp256k1"), (ACCOUNT_1_KEY, "ed25519")] {
+            toml.push_str(&format!(
+                "[[accounts.associated_keys]]\npublic_key = \"{}\"\nalgorithm = \"{}\"\n\
+                 weight = 1\n",
+                public_key, algorithm
+            ));
+        }
+
+        let genesis_accounts = super::from_bytes(toml.as_bytes()).expect("should load");
+
+        let mut secp256k1_bytes = [4u8; 33];
+        secp256k1_bytes[0] = 2;
+        let secp256k1_address =
+            TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, &secp256k1_bytes)
+                .expect("should create key")
+                .to_address();
+        let mut associated_keys = AssociatedKeys::new(secp256k1_address, Weight::new(1));
+        associated_keys
+            .add_key(address([3u8; 32]), Weight::new(1))
+            .expect("should add key");
+        let action_thresholds = ActionThresholds::new(Weight::new(1), Weight::new(1))
+            .expect("should create thresholds");
+        assert_eq!(
+            genesis_accounts,
+            vec![GenesisAccount::new(
+                secp256k1_address,
+                U512::from(100),
+                associated_keys,
+                action_thresholds,
+                U512::zero(),
+            )]
+        );
+    }
+
+    #[test]
+    fn should_reject_public_key_of_other_algorithm() {
+        let mut toml = account_toml(ACCOUNT_1_KEY, "100");
+        toml.push_str("algorithm = \"secp256k1\"\n");
+
+        match super::from_bytes(toml.as_bytes()) {
+            Err(GenesisAccountsError::Invalid(_)) => (),
+            other => panic!("expected invalid genesis accounts, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_algorithm() {
+        let mut toml = account_toml(ACCOUNT_1_KEY, "100");
+        toml.push_str("algorithm = \"rsa\"\n");
+
+        match super::from_bytes(toml.as_bytes()) {
+            Err(GenesisAccountsError::Invalid(_)) => (),
+            other => panic!("expected invalid genesis accounts, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_public_key() {
+        let toml = account_toml(&ACCOUNT_1_KEY[1..], "100");
+
+        match super::from_bytes(toml.as_bytes()) {
+            Err(GenesisAccountsError::Invalid(_)) => (),
+            other => panic!("expected invalid genesis accounts, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_balance() {
+        let toml = account_toml(ACCOUNT_1_KEY, "-100");
+
+        match super::from_bytes(toml.as_bytes()) {
+            Err(GenesisAccountsError::Invalid(_)) => (),
+            other => panic!("expected invalid genesis accounts, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_deployment_threshold_above_key_management_threshold() {
+        let mut toml = account_toml(ACCOUNT_1_KEY, "100");
+        toml.push_str("deployment_threshold = 2\nkey_management_threshold = 1\n");
+
+        match super::from_bytes(toml.as_bytes()) {
+            Err(GenesisAccountsError::Invalid(_)) => (),
+            other => panic!("expected invalid genesis accounts, got {:?}", other),
+        }
+    }
+}
@@ -7,9 +7,13 @@ extern crate grpc;
 extern crate lmdb;
 extern crate proptest;
 extern crate protobuf;
+extern crate serde;
+extern crate toml;
 extern crate wabt;
 
 #[cfg(test)]
 extern crate parity_wasm;
 
+pub mod chainspec;
 pub mod engine_server;
+pub mod genesis_accounts;
@@ -14,6 +14,7 @@ extern crate engine_storage;
 use std::collections::btree_map::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::process;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -31,7 +32,9 @@ use engine_shared::{logging, socket};
 use engine_storage::global_state::lmdb::LmdbGlobalState;
 use engine_storage::trie_store::lmdb::{LmdbEnvironment, LmdbTrieStore};
 
+use casperlabs_engine_grpc_server::chainspec::Chainspec;
 use casperlabs_engine_grpc_server::engine_server;
+use casperlabs_engine_grpc_server::genesis_accounts;
 
 // exe / proc
 const PROC_NAME: &str = "casperlabs-engine-grpc-server";
@@ -82,6 +85,28 @@ const ARG_USE_PAYMENT_CODE: &str = "use-payment-code";
 const ARG_USE_PAYMENT_CODE_SHORT: &str = "x";
 const ARG_USE_PAYMENT_CODE_HELP: &str = "Enables the use of payment code";
 
+// min-gas-price
+const ARG_MIN_GAS_PRICE: &str = "min-gas-price";
+const ARG_MIN_GAS_PRICE_VALUE: &str = "MOTES";
+const ARG_MIN_GAS_PRICE_HELP: &str =
+    "Sets the lowest gas price, in motes per unit of gas, deploys are run at";
+const INVALID_MIN_GAS_PRICE_MESSAGE: &str = "Invalid min-gas-price argument";
+const INVALID_ENGINE_CONFIG_MESSAGE: &str = "Invalid engine parameters";
+
+// chainspec
+const ARG_CHAINSPEC: &str = "chainspec";
+const ARG_CHAINSPEC_VALUE: &str = "FILE";
+const ARG_CHAINSPEC_HELP: &str =
+    "Sets engine parameters from a chainspec file; other arguments override them";
+const CHAINSPEC_LOADED_TEMPLATE: &str = "loaded chainspec {path} with hash {hash}";
+
+// genesis-accounts
+const ARG_GENESIS_ACCOUNTS: &str = "genesis-accounts";
+const ARG_GENESIS_ACCOUNTS_VALUE: &str = "FILE";
+const ARG_GENESIS_ACCOUNTS_HELP: &str =
+    "Creates the accounts listed in a TOML file at genesis, next to the requested ones";
+const GENESIS_ACCOUNTS_LOADED_TEMPLATE: &str = "loaded {count} genesis accounts from {path}";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
@@ -186,6 +211,27 @@ fn get_args() -> ArgMatches<'static> {
                 .long(ARG_USE_PAYMENT_CODE)
                 .help(ARG_USE_PAYMENT_CODE_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_MIN_GAS_PRICE)
+                .long(ARG_MIN_GAS_PRICE)
+                .value_name(ARG_MIN_GAS_PRICE_VALUE)
+                .help(ARG_MIN_GAS_PRICE_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_CHAINSPEC)
+                .long(ARG_CHAINSPEC)
+                .value_name(ARG_CHAINSPEC_VALUE)
+                .help(ARG_CHAINSPEC_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_GENESIS_ACCOUNTS)
+                .long(ARG_GENESIS_ACCOUNTS)
+                .value_name(ARG_GENESIS_ACCOUNTS_VALUE)
+                .help(ARG_GENESIS_ACCOUNTS_HELP)
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
                 .required(true)
@@ -238,10 +284,54 @@ fn get_map_size(matches: &ArgMatches) -> usize {
     page_size * pages
 }
 
-/// Parses `use-payment-code` argument and returns an [`EngineConfig`].
+/// Loads the `chainspec` argument, if any, applies the `use-payment-code`, `min-gas-price` and
+/// `genesis-accounts` arguments on top of it, and validates the result to return an
+/// [`EngineConfig`]. Exits if any of them is invalid.
 fn get_engine_config(matches: &ArgMatches) -> EngineConfig {
-    let use_payment_code = matches.is_present(ARG_USE_PAYMENT_CODE);
-    EngineConfig::new().set_use_payment_code(use_payment_code)
+    let engine_config = match matches.value_of(ARG_CHAINSPEC) {
+        Some(path) => {
+            let chainspec = match Chainspec::from_file(path) {
+                Ok(chainspec) => chainspec,
+                Err(error) => exit_with_error(&error.to_string()),
+            };
+            log_chainspec_loaded_message(path, &chainspec);
+            chainspec.engine_config()
+        }
+        None => EngineConfig::new(),
+    };
+    let engine_config = if matches.is_present(ARG_USE_PAYMENT_CODE) {
+        engine_config.set_use_payment_code(true)
+    } else {
+        engine_config
+    };
+    let engine_config = match matches.value_of(ARG_MIN_GAS_PRICE) {
+        Some(min_gas_price) => match u64::from_str(min_gas_price) {
+            Ok(min_gas_price) => engine_config.set_min_gas_price(min_gas_price),
+            Err(error) => exit_with_error(&format!("{}: {}", INVALID_MIN_GAS_PRICE_MESSAGE, error)),
+        },
+        None => engine_config,
+    };
+    let engine_config = match matches.value_of(ARG_GENESIS_ACCOUNTS) {
+        Some(path) => {
+            let genesis_accounts = match genesis_accounts::from_file(path) {
+                Ok(genesis_accounts) => genesis_accounts,
+                Err(error) => exit_with_error(&error.to_string()),
+            };
+            log_genesis_accounts_loaded_message(path, genesis_accounts.len());
+            engine_config.set_genesis_accounts(genesis_accounts)
+        }
+        None => engine_config,
+    };
+    if let Err(message) = engine_config.validate() {
+        exit_with_error(&format!("{}: {}", INVALID_ENGINE_CONFIG_MESSAGE, message));
+    }
+    engine_config
+}
+
+/// Logs a fatal error and exits
+fn exit_with_error(message: &str) -> ! {
+    logging::log_fatal(message);
+    process::exit(1)
 }
 
 /// Builds and returns a gRPC server.
@@ -303,3 +393,31 @@ fn log_listening_message(socket: &socket::Socket) {
         properties,
     );
 }
+
+/// Logs chainspec loaded message
+fn log_chainspec_loaded_message(path: &str, chainspec: &Chainspec) {
+    let mut properties: BTreeMap<String, String> = BTreeMap::new();
+
+    properties.insert("path".to_string(), path.to_owned());
+    properties.insert("hash".to_string(), chainspec.hash().to_string());
+
+    logging::log_details(
+        log_level::LogLevel::Info,
+        CHAINSPEC_LOADED_TEMPLATE.to_string(),
+        properties,
+    );
+}
+
+/// Logs genesis accounts loaded message
+fn log_genesis_accounts_loaded_message(path: &str, count: usize) {
+    let mut properties: BTreeMap<String, String> = BTreeMap::new();
+
+    properties.insert("path".to_string(), path.to_owned());
+    properties.insert("count".to_string(), count.to_string());
+
+    logging::log_details(
+        log_level::LogLevel::Info,
+        GENESIS_ACCOUNTS_LOADED_TEMPLATE.to_string(),
+        properties,
+    );
+}
@@ -11,7 +11,7 @@ use grpc::RequestOptions;
 
 use casperlabs_engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use contract_ffi::value::account::PublicKey;
-use engine_core::engine_state::EngineState;
+use engine_core::engine_state::{EngineConfig, EngineState};
 use engine_storage::global_state::in_memory::InMemoryGlobalState;
 
 use test_support::DEFAULT_BLOCK_TIME;
@@ -25,7 +25,10 @@ const GENESIS_ADDR: [u8; 32] = [0u8; 32];
 #[test]
 fn should_execute_contracts_which_provide_extra_urefs() {
     let global_state = InMemoryGlobalState::empty().unwrap();
-    let engine_state = EngineState::new(global_state, Default::default());
+    let engine_state = EngineState::new(
+        global_state,
+        EngineConfig::new().without_deploy_verification_for_tests(),
+    );
 
     // run genesis
 
@@ -0,0 +1,151 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::{BlockTime, PublicKey, Weight};
+use contract_ffi::value::{Account, Value};
+use engine_core::engine_state;
+
+use test_support::{DeployBuilder, ExecRequestBuilder, UpgradeRequestBuilder, WasmTestBuilder};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+const RECOVERY_KEY: [u8; 32] = [2u8; 32];
+const NEW_KEY: [u8; 32] = [3u8; 32];
+const INACTIVITY_PERIOD_LIMIT: u64 = 1000;
+const SET_RECOVERY_BLOCK_TIME: u64 = 10;
+
+fn exec_account_recovery(
+    builder: &mut WasmTestBuilder,
+    command: &str,
+    key: [u8; 32],
+    deploy_hash: [u8; 32],
+    block_time: u64,
+) {
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_session_code(
+            "account_recovery.wasm",
+            (String::from(command), PublicKey::new(GENESIS_ADDR)),
+        )
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(&[PublicKey::new(key)])
+        .build();
+    let exec_request = ExecRequestBuilder::new()
+        .with_protocol_version(2)
+        .with_block_time(block_time)
+        .push_deploy(deploy)
+        .build();
+    builder.exec_with_exec_request(exec_request);
+}
+
+fn setup() -> WasmTestBuilder {
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build());
+    exec_account_recovery(
+        &mut builder,
+        "set-recovery",
+        GENESIS_ADDR,
+        [1u8; 32],
+        SET_RECOVERY_BLOCK_TIME,
+    );
+    builder.expect_success().commit();
+    builder
+}
+
+fn get_account(builder: &WasmTestBuilder) -> Account {
+    match builder.query(None, Key::Account(GENESIS_ADDR), &[]) {
+        Some(Value::Account(account)) => account,
+        other => panic!("expected account, got {:?}", other),
+    }
+}
+
+fn get_last_deployment(builder: &WasmTestBuilder) -> BlockTime {
+    match builder.query(None, engine_state::last_deployment_key(GENESIS_ADDR), &[]) {
+        Some(Value::UInt64(block_time)) => BlockTime(block_time),
+        other => panic!("expected last deployment, got {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_configure_recovery_keys_and_record_deployment() {
+    let builder = setup();
+
+    let account = get_account(&builder);
+    let recovery_keys: Vec<(&PublicKey, &Weight)> = account.recovery_keys().keys().collect();
+    assert_eq!(
+        recovery_keys,
+        vec![(&PublicKey::new(RECOVERY_KEY), &Weight::new(1))]
+    );
+    assert_eq!(account.recovery_keys().threshold(), &Weight::new(1));
+
+    let account_activity = account.account_activity();
+    assert_eq!(
+        account_activity.inactivity_period_limit(),
+        BlockTime(INACTIVITY_PERIOD_LIMIT)
+    );
+    assert_eq!(
+        get_last_deployment(&builder),
+        BlockTime(SET_RECOVERY_BLOCK_TIME)
+    );
+    assert_eq!(
+        account_activity.key_management_last_used(),
+        BlockTime(SET_RECOVERY_BLOCK_TIME)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_recover_active_account() {
+    let mut builder = setup();
+
+    let block_time = SET_RECOVERY_BLOCK_TIME + INACTIVITY_PERIOD_LIMIT;
+    exec_account_recovery(&mut builder, "recover", RECOVERY_KEY, [2u8; 32], block_time);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure.get_message(),
+        format!("{}", engine_state::error::Error::AuthorizationError)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_recover_inactive_account() {
+    let mut builder = setup();
+
+    let block_time = SET_RECOVERY_BLOCK_TIME + INACTIVITY_PERIOD_LIMIT + 1;
+    exec_account_recovery(&mut builder, "recover", RECOVERY_KEY, [2u8; 32], block_time);
+    builder.expect_success().commit();
+
+    let account = get_account(&builder);
+    let associated_keys: Vec<(&PublicKey, &Weight)> = account.get_associated_keys().collect();
+    assert_eq!(
+        associated_keys,
+        vec![(&PublicKey::new(NEW_KEY), &Weight::new(1))]
+    );
+
+    // Recovery deploys only count as key management, not as regular activity.
+    assert_eq!(
+        get_last_deployment(&builder),
+        BlockTime(SET_RECOVERY_BLOCK_TIME)
+    );
+    assert_eq!(
+        account.account_activity().key_management_last_used(),
+        BlockTime(block_time)
+    );
+}
@@ -0,0 +1,95 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+
+use test_support::{DeployBuilder, ExecRequestBuilder, UpgradeRequestBuilder, WasmTestBuilder};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+const SECOND_KEY: [u8; 32] = [43u8; 32];
+const TRANSFER_AMOUNT: u64 = 1_000;
+
+fn exec_action_thresholds(
+    builder: &mut WasmTestBuilder,
+    command: &str,
+    keys: &[[u8; 32]],
+    deploy_hash: [u8; 32],
+) {
+    let authorization_keys: Vec<PublicKey> = keys.iter().cloned().map(PublicKey::new).collect();
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_session_code(
+            "action_thresholds.wasm",
+            (String::from(command), U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(&authorization_keys)
+        .build();
+    let exec_request = ExecRequestBuilder::new()
+        .with_protocol_version(2)
+        .push_deploy(deploy)
+        .build();
+    builder.exec_with_exec_request(exec_request);
+}
+
+fn setup() -> WasmTestBuilder {
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build());
+    exec_action_thresholds(&mut builder, "init", &[GENESIS_ADDR], [1u8; 32]);
+    builder.expect_success().commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_below_transfer_threshold() {
+    let mut builder = setup();
+
+    exec_action_thresholds(&mut builder, "transfer", &[GENESIS_ADDR], [2u8; 32]);
+
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_transfer_with_transfer_threshold() {
+    let mut builder = setup();
+
+    exec_action_thresholds(
+        &mut builder,
+        "transfer",
+        &[GENESIS_ADDR, SECOND_KEY],
+        [2u8; 32],
+    );
+
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_check_named_threshold_against_authorization_keys() {
+    let mut builder = setup();
+
+    exec_action_thresholds(&mut builder, "check-named", &[GENESIS_ADDR], [2u8; 32]);
+    assert!(builder.is_error());
+
+    exec_action_thresholds(
+        &mut builder,
+        "check-named",
+        &[GENESIS_ADDR, SECOND_KEY],
+        [3u8; 32],
+    );
+    builder.expect_success();
+}
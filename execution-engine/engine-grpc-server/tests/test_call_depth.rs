@@ -0,0 +1,63 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate engine_wasm_prep;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use engine_wasm_prep::wasm_limits::WasmLimits;
+
+use test_support::{UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+const CALL_DEPTH_WASM: &str = "call_depth.wasm";
+
+fn max_call_depth() -> u64 {
+    WasmLimits::from_version(2)
+        .expect("should have wasm limits")
+        .max_call_depth as u64
+}
+
+fn run_nested_calls(frames: u64) -> WasmTestBuilder {
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .exec_with_args(
+            GENESIS_ADDR,
+            CALL_DEPTH_WASM,
+            DEFAULT_BLOCK_TIME,
+            1,
+            (frames,),
+        )
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_allow_nested_calls_up_to_max_call_depth() {
+    // The session code itself takes up one frame of the call stack
+    let builder = run_nested_calls(max_call_depth() - 1);
+
+    assert!(!builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_fail_nested_calls_beyond_max_call_depth() {
+    let builder = run_nested_calls(max_call_depth());
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let execution_result = test_support::get_success_result(response);
+    let error_message = test_support::get_error_message(execution_result);
+    assert_eq!(error_message, "CallDepthLimit", "expected call depth limit");
+}
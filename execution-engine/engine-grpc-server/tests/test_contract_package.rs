@@ -0,0 +1,162 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+
+use test_support::{UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const CONTRACT_PACKAGE_WASM: &str = "contract_package.wasm";
+const CALL_RESULT_NAME: &str = "call_result";
+const LATEST_VERSION: u32 = 0;
+
+/// Creates a contract package with two versions, returning `1` and `2` respectively.
+fn create_package_with_two_versions() -> WasmTestBuilder {
+    let result = WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .exec_with_args(
+            GENESIS_ADDR,
+            CONTRACT_PACKAGE_WASM,
+            DEFAULT_BLOCK_TIME,
+            1,
+            ("create",),
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args(
+            GENESIS_ADDR,
+            CONTRACT_PACKAGE_WASM,
+            DEFAULT_BLOCK_TIME,
+            2,
+            ("add",),
+        )
+        .expect_success()
+        .commit()
+        .finish();
+    WasmTestBuilder::from_result(result)
+}
+
+fn get_call_result(builder: &WasmTestBuilder) -> Option<Value> {
+    builder.query(None, Key::Account(GENESIS_ADDR), &[CALL_RESULT_NAME])
+}
+
+#[ignore]
+#[test]
+fn should_call_latest_enabled_version() {
+    let mut builder = create_package_with_two_versions();
+
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            CONTRACT_PACKAGE_WASM,
+            DEFAULT_BLOCK_TIME,
+            3,
+            ("call", LATEST_VERSION),
+        )
+        .expect_success()
+        .commit();
+
+    assert_eq!(get_call_result(&builder), Some(Value::UInt64(2)));
+}
+
+#[ignore]
+#[test]
+fn should_call_specific_version() {
+    let mut builder = create_package_with_two_versions();
+
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            CONTRACT_PACKAGE_WASM,
+            DEFAULT_BLOCK_TIME,
+            3,
+            ("call", 1u32),
+        )
+        .expect_success()
+        .commit();
+
+    assert_eq!(get_call_result(&builder), Some(Value::UInt64(1)));
+}
+
+#[ignore]
+#[test]
+fn should_call_previous_version_after_disabling_latest() {
+    let mut builder = create_package_with_two_versions();
+
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            CONTRACT_PACKAGE_WASM,
+            DEFAULT_BLOCK_TIME,
+            3,
+            ("disable", 2u32),
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args(
+            GENESIS_ADDR,
+            CONTRACT_PACKAGE_WASM,
+            DEFAULT_BLOCK_TIME,
+            4,
+            ("call", LATEST_VERSION),
+        )
+        .expect_success()
+        .commit();
+
+    assert_eq!(get_call_result(&builder), Some(Value::UInt64(1)));
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_call_disabled_version() {
+    let mut builder = create_package_with_two_versions();
+
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            CONTRACT_PACKAGE_WASM,
+            DEFAULT_BLOCK_TIME,
+            3,
+            ("disable", 1u32),
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args(
+            GENESIS_ADDR,
+            CONTRACT_PACKAGE_WASM,
+            DEFAULT_BLOCK_TIME,
+            4,
+            ("call", 1u32),
+        )
+        .commit();
+
+    assert!(builder.is_error());
+    assert_eq!(get_call_result(&builder), None);
+}
+
+#[ignore]
+#[test]
+fn should_not_modify_package_with_read_only_access_key() {
+    // The contract reverts unless adding a version with a read only access key is denied.
+    create_package_with_two_versions()
+        .exec_with_args(
+            GENESIS_ADDR,
+            CONTRACT_PACKAGE_WASM,
+            DEFAULT_BLOCK_TIME,
+            3,
+            ("add_read_only",),
+        )
+        .expect_success()
+        .commit();
+}
@@ -0,0 +1,115 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::Value;
+use engine_shared::newtypes::Blake2bHash;
+
+use test_support::{UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const CRYPTO_WASM: &str = "crypto.wasm";
+const HASH_RESULT_NAME: &str = "hash_result";
+
+// Test vector 2 of RFC 8032, section 7.1.
+const PUBLIC_KEY: [u8; 32] = [
+    61, 64, 23, 195, 232, 67, 137, 90, 146, 183, 10, 167, 77, 27, 126, 188, 156, 152, 44, 207, 46,
+    196, 150, 140, 192, 205, 85, 241, 42, 244, 102, 12,
+];
+const MESSAGE: [u8; 1] = [0x72];
+const SIGNATURE: [u8; 64] = [
+    146, 160, 9, 169, 240, 212, 202, 184, 114, 14, 130, 11, 95, 100, 37, 64, 162, 178, 123, 84, 22,
+    80, 63, 143, 179, 118, 34, 35, 235, 219, 105, 218, 8, 90, 193, 228, 62, 21, 153, 110, 69, 143,
+    54, 19, 208, 241, 29, 140, 56, 123, 46, 174, 180, 48, 42, 238, 176, 13, 41, 22, 18, 187, 12, 0,
+];
+
+fn verify(public_key: [u8; 32], message: &[u8], signature: &[u8]) -> WasmTestBuilder {
+    let result = WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .exec_with_args(
+            GENESIS_ADDR,
+            CRYPTO_WASM,
+            DEFAULT_BLOCK_TIME,
+            1,
+            (
+                "verify",
+                PublicKey::new(public_key),
+                message.to_vec(),
+                signature.to_vec(),
+            ),
+        )
+        .commit()
+        .finish();
+    WasmTestBuilder::from_result(result)
+}
+
+#[ignore]
+#[test]
+fn should_hash_with_blake2b() {
+    let data = b"hello".to_vec();
+    let result = WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .exec_with_args(
+            GENESIS_ADDR,
+            CRYPTO_WASM,
+            DEFAULT_BLOCK_TIME,
+            1,
+            ("hash", data.clone()),
+        )
+        .expect_success()
+        .commit()
+        .finish();
+    let builder = WasmTestBuilder::from_result(result);
+
+    let hash_result = builder.query(None, Key::Account(GENESIS_ADDR), &[HASH_RESULT_NAME]);
+    assert_eq!(
+        hash_result,
+        Some(Value::ByteArray(Blake2bHash::new(&data).to_vec()))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_accept_valid_ed25519_signature() {
+    let builder = verify(PUBLIC_KEY, &MESSAGE, &SIGNATURE);
+
+    assert!(!builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_reject_ed25519_signature_of_other_message() {
+    let builder = verify(PUBLIC_KEY, &[0x73], &SIGNATURE);
+
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_reject_tampered_ed25519_signature() {
+    let mut signature = SIGNATURE;
+    signature[0] ^= 1;
+    let builder = verify(PUBLIC_KEY, &MESSAGE, &signature);
+
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_reject_ed25519_signature_of_invalid_length() {
+    let builder = verify(PUBLIC_KEY, &MESSAGE, &SIGNATURE[..32]);
+
+    assert!(builder.is_error());
+}
@@ -0,0 +1,275 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate ed25519_dalek;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+extern crate secp256k1;
+
+use std::collections::HashMap;
+
+use casperlabs_engine_grpc_server::engine_server::ipc::{Deploy, ExecRequest};
+use contract_ffi::key::{addr_to_hex, Key};
+use contract_ffi::value::account::{SignatureAlgorithm, TaggedPublicKey};
+use contract_ffi::value::Value;
+use engine_core::engine_state::error::Error;
+use engine_core::engine_state::EngineConfig;
+
+use test_support::{DeployBuilder, ExecRequestBuilder, WasmTestBuilder};
+
+#[allow(dead_code)]
+mod test_support;
+
+// Secret key of test vector 1 of RFC 8032, section 7.1.
+const ED25519_SECRET_KEY: [u8; 32] = [
+    157, 97, 177, 157, 239, 253, 90, 96, 186, 132, 74, 244, 146, 236, 44, 196, 68, 73, 197, 105,
+    123, 50, 105, 25, 112, 59, 172, 3, 28, 174, 127, 96,
+];
+const SECP256K1_SECRET_KEY: [u8; 32] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+];
+const BLOCK_TIME: u64 = 42;
+const STORED_SESSION_NAME: &str = "stored_session";
+const STORED_SESSION_MARKER: &str = "stored_session_marker";
+const MARKER_VALUE: &str = "Hello, stored session!";
+
+/// Keys of an account, as found in deploy headers and in approvals, along with how it signs.
+struct Signer {
+    header_public_key: Vec<u8>,
+    public_key: TaggedPublicKey,
+    sign: fn(&[u8]) -> Vec<u8>,
+}
+
+fn ed25519_keypair() -> ed25519_dalek::Keypair {
+    let secret =
+        ed25519_dalek::SecretKey::from_bytes(&ED25519_SECRET_KEY).expect("should create key");
+    let public = ed25519_dalek::PublicKey::from(&secret);
+    ed25519_dalek::Keypair { secret, public }
+}
+
+fn ed25519_signer() -> Signer {
+    let public_key = ed25519_keypair().public.to_bytes();
+    Signer {
+        header_public_key: public_key.to_vec(),
+        public_key: TaggedPublicKey::ed25519(public_key),
+        sign: |deploy_hash| ed25519_keypair().sign(deploy_hash).to_bytes().to_vec(),
+    }
+}
+
+fn secp256k1_secret_key() -> secp256k1::SecretKey {
+    secp256k1::SecretKey::parse(&SECP256K1_SECRET_KEY).expect("should create key")
+}
+
+/// The node puts uncompressed secp256k1 keys in deploy headers, and the engine addresses their
+/// compressed form.
+fn secp256k1_signer() -> Signer {
+    let public_key = secp256k1::PublicKey::from_secret_key(&secp256k1_secret_key());
+    Signer {
+        header_public_key: public_key.serialize().to_vec(),
+        public_key: TaggedPublicKey::new(
+            SignatureAlgorithm::Secp256k1,
+            &public_key.serialize_compressed()[..],
+        )
+        .expect("should create key"),
+        sign: |deploy_hash| {
+            let mut message = [0u8; 32];
+            message.copy_from_slice(deploy_hash);
+            let (signature, _) = secp256k1::sign(
+                &secp256k1::Message::parse(&message),
+                &secp256k1_secret_key(),
+            )
+            .expect("should sign");
+            signature.serialize().to_vec()
+        },
+    }
+}
+
+fn deploy_builder(signer: &Signer) -> DeployBuilder {
+    DeployBuilder::new()
+        .with_address(signer.public_key.to_address().value())
+        .with_session_code("get_blocktime.wasm", BLOCK_TIME)
+}
+
+/// Approves `deploy` with the signature of its deploy hash made by `signer`, as the key of
+/// `approver`.
+fn approve(mut deploy: Deploy, signer: &Signer, approver: &Signer) -> Deploy {
+    let signature = (signer.sign)(deploy.get_deploy_hash());
+    deploy.mut_approvals().push(test_support::create_approval(
+        &approver.public_key,
+        signature,
+    ));
+    deploy
+}
+
+fn signed_deploy(signer: &Signer) -> Deploy {
+    let deploy = deploy_builder(signer)
+        .with_header(&signer.header_public_key)
+        .build();
+    approve(deploy, signer, signer)
+}
+
+fn exec_request(deploy: Deploy) -> ExecRequest {
+    ExecRequestBuilder::new()
+        .with_block_time(BLOCK_TIME)
+        .push_deploy(deploy)
+        .build()
+}
+
+fn verifying_builder(signer: &Signer) -> WasmTestBuilder {
+    let mut builder = WasmTestBuilder::new(EngineConfig::new());
+    builder.run_genesis(signer.public_key.to_address().value(), HashMap::new());
+    builder
+}
+
+fn assert_precondition_failure(builder: &WasmTestBuilder, expected_error: Error) {
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(precondition_failure.message, format!("{}", expected_error));
+}
+
+#[ignore]
+#[test]
+fn should_run_deploy_with_valid_ed25519_signature() {
+    let signer = ed25519_signer();
+    let mut builder = verifying_builder(&signer);
+
+    builder
+        .exec_with_exec_request(exec_request(signed_deploy(&signer)))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_run_deploy_with_valid_secp256k1_signature() {
+    let signer = secp256k1_signer();
+    let mut builder = verifying_builder(&signer);
+
+    builder
+        .exec_with_exec_request(exec_request(signed_deploy(&signer)))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_run_signed_deploy_of_stored_contract() {
+    let signer = ed25519_signer();
+    let address = signer.public_key.to_address();
+    let mut builder = verifying_builder(&signer);
+
+    let store_deploy = DeployBuilder::new()
+        .with_address(address.value())
+        .with_session_code("stored_session.wasm", ())
+        .with_header(&signer.header_public_key)
+        .build();
+    builder
+        .exec_with_exec_request(exec_request(approve(store_deploy, &signer, &signer)))
+        .expect_success()
+        .commit();
+
+    let stored_session_deploy = DeployBuilder::new()
+        .with_address(address.value())
+        .with_stored_session_named_key(STORED_SESSION_NAME, (MARKER_VALUE.to_string(),))
+        .with_header(&signer.header_public_key)
+        .build();
+    builder
+        .exec_with_exec_request(exec_request(approve(
+            stored_session_deploy,
+            &signer,
+            &signer,
+        )))
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        builder.query(
+            None,
+            Key::Account(address.value()),
+            &[STORED_SESSION_MARKER]
+        ),
+        Some(Value::String(MARKER_VALUE.to_string()))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_invalid_deploy_signature() {
+    let signer = ed25519_signer();
+    let mut builder = verifying_builder(&signer);
+
+    let mut deploy = signed_deploy(&signer);
+    deploy.mut_approvals()[0].mut_signature()[0] ^= 1;
+    builder.exec_with_exec_request(exec_request(deploy));
+
+    let address = signer.public_key.to_address();
+    assert_precondition_failure(
+        &builder,
+        Error::InvalidDeploySignature(addr_to_hex(&address.value())),
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_signature_of_other_algorithm() {
+    let signer = secp256k1_signer();
+    let mut builder = verifying_builder(&signer);
+
+    let deploy = deploy_builder(&signer)
+        .with_header(&signer.header_public_key)
+        .build();
+    builder.exec_with_exec_request(exec_request(approve(deploy, &ed25519_signer(), &signer)));
+
+    let address = signer.public_key.to_address();
+    assert_precondition_failure(
+        &builder,
+        Error::InvalidDeploySignature(addr_to_hex(&address.value())),
+    );
+}
+
+#[ignore]
+#[test]
+fn should_ignore_unsigned_authorization_keys() {
+    let signer = ed25519_signer();
+    let address = signer.public_key.to_address();
+    let mut builder = verifying_builder(&signer);
+
+    let deploy = deploy_builder(&signer)
+        .with_header(&signer.header_public_key)
+        .with_authorization_keys(&[address])
+        .build();
+    builder.exec_with_exec_request(exec_request(deploy));
+
+    assert_precondition_failure(&builder, Error::AuthorizationError);
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_deploy_hash_of_other_code() {
+    let signer = ed25519_signer();
+    let mut builder = verifying_builder(&signer);
+
+    let mut deploy = signed_deploy(&signer);
+    deploy.mut_session().set_args(Vec::new());
+    builder.exec_with_exec_request(exec_request(deploy));
+
+    assert_precondition_failure(&builder, Error::DeployHashMismatch("body".to_string()));
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_deploy_hash_of_other_header() {
+    let signer = ed25519_signer();
+    let mut builder = verifying_builder(&signer);
+
+    let deploy = deploy_builder(&signer)
+        .with_header(&signer.header_public_key)
+        .with_deploy_hash([1u8; 32])
+        .build();
+    builder.exec_with_exec_request(exec_request(approve(deploy, &signer, &signer)));
+
+    assert_precondition_failure(&builder, Error::DeployHashMismatch("header".to_string()));
+}
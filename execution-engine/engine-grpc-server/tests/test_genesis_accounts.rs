@@ -0,0 +1,155 @@
+extern crate grpc;
+
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use contract_ffi::bytesrepr::ToBytes;
+use contract_ffi::key::Key;
+use contract_ffi::value::account::{ActionThresholds, AssociatedKeys, PublicKey, Weight};
+use contract_ffi::value::{Account, Value, U512};
+use engine_core::engine_state::genesis::GenesisAccount;
+use engine_core::engine_state::EngineConfig;
+use test_support::{WasmTestBuilder, DEFAULT_BLOCK_TIME};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+const ACCOUNT_2_KEY_ADDR: [u8; 32] = [3u8; 32];
+const ACCOUNT_3_ADDR: [u8; 32] = [4u8; 32];
+
+const ACCOUNT_1_BALANCE: u64 = 1_000_000;
+const ACCOUNT_2_BALANCE: u64 = 2_000_000;
+const ACCOUNT_2_BONDED_AMOUNT: u64 = 5_000;
+const TRANSFER_AMOUNT: u64 = 1_000;
+
+fn get_genesis_accounts() -> Vec<GenesisAccount> {
+    let mut associated_keys = AssociatedKeys::new(PublicKey::new(ACCOUNT_2_ADDR), Weight::new(1));
+    associated_keys
+        .add_key(PublicKey::new(ACCOUNT_2_KEY_ADDR), Weight::new(1))
+        .expect("should add key");
+    let action_thresholds =
+        ActionThresholds::new(Weight::new(1), Weight::new(2)).expect("should create thresholds");
+
+    vec![
+        GenesisAccount::create(
+            PublicKey::new(ACCOUNT_1_ADDR),
+            U512::from(ACCOUNT_1_BALANCE),
+        ),
+        GenesisAccount::new(
+            PublicKey::new(ACCOUNT_2_ADDR),
+            U512::from(ACCOUNT_2_BALANCE),
+            associated_keys,
+            action_thresholds,
+            U512::from(ACCOUNT_2_BONDED_AMOUNT),
+        ),
+    ]
+}
+
+fn get_account(builder: &WasmTestBuilder, account_addr: [u8; 32]) -> Account {
+    match builder.query(None, Key::Account(account_addr), &[]) {
+        Some(Value::Account(account)) => account,
+        other => panic!("expected account, got {:?}", other),
+    }
+}
+
+fn get_main_purse_balance(builder: &WasmTestBuilder, account_addr: [u8; 32]) -> U512 {
+    let purse_bytes = get_account(builder, account_addr)
+        .purse_id()
+        .value()
+        .addr()
+        .to_bytes()
+        .expect("should be able to serialize purse bytes");
+
+    let mint = builder.get_mint_contract_uref();
+    let balance_mapping_key = Key::local(mint.addr(), &purse_bytes);
+    let balance_uref = builder
+        .query(None, balance_mapping_key, &[])
+        .and_then(|v| v.try_into().ok())
+        .expect("should find balance uref");
+
+    builder
+        .query(None, balance_uref, &[])
+        .and_then(|v| v.try_into().ok())
+        .expect("should parse balance into a U512")
+}
+
+#[ignore]
+#[test]
+fn should_create_genesis_accounts_with_balances_and_keys() {
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_genesis_accounts(get_genesis_accounts());
+
+    let mut builder = WasmTestBuilder::new(engine_config);
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    assert_eq!(
+        get_main_purse_balance(&builder, ACCOUNT_1_ADDR),
+        U512::from(ACCOUNT_1_BALANCE)
+    );
+    assert_eq!(
+        get_main_purse_balance(&builder, ACCOUNT_2_ADDR),
+        U512::from(ACCOUNT_2_BALANCE)
+    );
+
+    let account_2 = get_account(&builder, ACCOUNT_2_ADDR);
+    assert_eq!(
+        account_2.get_associated_key_weight(PublicKey::new(ACCOUNT_2_KEY_ADDR)),
+        Some(&Weight::new(1))
+    );
+    assert_eq!(
+        account_2.action_thresholds().key_management(),
+        &Weight::new(2)
+    );
+    assert_eq!(
+        account_2.urefs_lookup(),
+        builder.get_genesis_account().urefs_lookup(),
+        "genesis accounts should know the system contracts"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_deploy_from_genesis_account_and_bond_its_stake() {
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_genesis_accounts(get_genesis_accounts());
+
+    let mut builder = WasmTestBuilder::new(engine_config);
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            ACCOUNT_1_ADDR,
+            "transfer_purse_to_account.wasm",
+            DEFAULT_BLOCK_TIME,
+            1,
+            (PublicKey::new(ACCOUNT_3_ADDR), U512::from(TRANSFER_AMOUNT)),
+        )
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        get_main_purse_balance(&builder, ACCOUNT_1_ADDR),
+        U512::from(ACCOUNT_1_BALANCE - TRANSFER_AMOUNT)
+    );
+    assert_eq!(
+        get_main_purse_balance(&builder, ACCOUNT_3_ADDR),
+        U512::from(TRANSFER_AMOUNT)
+    );
+
+    let bonded_validators = builder.get_bonded_validators();
+    assert_eq!(
+        bonded_validators[1].get(&PublicKey::new(ACCOUNT_2_ADDR)),
+        Some(&U512::from(ACCOUNT_2_BONDED_AMOUNT))
+    );
+    assert!(!bonded_validators[1].contains_key(&PublicKey::new(ACCOUNT_1_ADDR)));
+}
@@ -0,0 +1,79 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+
+#[allow(dead_code)]
+mod test_support;
+
+use std::collections::HashMap;
+
+use contract_ffi::value::account::PublicKey;
+
+use test_support::{DeployBuilder, ExecRequestBuilder, UpgradeRequestBuilder, WasmTestBuilder};
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+const GET_BLOCK_INFO_WASM: &str = "get_block_info.wasm";
+
+#[ignore]
+#[test]
+fn should_run_get_block_info_contract() {
+    let block_height: u64 = 42;
+    let parent_hash = [3u8; 32];
+    let proposer = [4u8; 32];
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_session_code(
+                GET_BLOCK_INFO_WASM,
+                (block_height, parent_hash, PublicKey::new(proposer)),
+            )
+            .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+            .build();
+
+        ExecRequestBuilder::new()
+            .with_protocol_version(2)
+            .with_block_height(block_height)
+            .with_parent_block_hash(&parent_hash)
+            .with_proposer(&proposer)
+            .push_deploy(deploy)
+            .build()
+    };
+
+    WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .exec_with_exec_request(exec_request)
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_default_to_zeroed_block_info() {
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_session_code(
+                GET_BLOCK_INFO_WASM,
+                (0u64, [0u8; 32], PublicKey::new([0u8; 32])),
+            )
+            .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+            .build();
+
+        ExecRequestBuilder::new()
+            .with_protocol_version(2)
+            .push_deploy(deploy)
+            .build()
+    };
+
+    WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .exec_with_exec_request(exec_request)
+        .commit()
+        .expect_success();
+}
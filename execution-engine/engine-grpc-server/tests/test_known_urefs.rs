@@ -35,8 +35,8 @@ fn should_run_known_urefs_contract() {
     let transform = transforms
         .get(0)
         .expect("Should have at least one transform");
-    // Execution yields 3 transformations 2 of which are uref
-    assert_eq!(transform.len(), 3);
+    // Execution yields 4 transformations: 2 urefs, the account and the executed deploy record
+    assert_eq!(transform.len(), 4);
     let string_value = transform
         .iter()
         .filter_map(|(k, v)| {
@@ -13,7 +13,7 @@ mod test_support;
 
 use grpc::RequestOptions;
 
-use engine_core::engine_state::EngineState;
+use engine_core::engine_state::{EngineConfig, EngineState};
 use engine_shared::logging::log_level::LogLevel;
 use engine_shared::logging::log_settings::{self, LogLevelFilter, LogSettings};
 use engine_shared::logging::logger::{self, LogBufferProvider, BUFFERED_LOGGER};
@@ -102,7 +102,10 @@ fn should_exec_with_metrics() {
     let mocked_account = test_utils::mocked_account(test_support::MOCKED_ACCOUNT_ADDRESS);
     let global_state = InMemoryGlobalState::from_pairs(correlation_id, &mocked_account).unwrap();
     let root_hash = global_state.root_hash.to_vec();
-    let engine_state = EngineState::new(global_state, Default::default());
+    let engine_state = EngineState::new(
+        global_state,
+        EngineConfig::new().without_deploy_verification_for_tests(),
+    );
 
     let mut exec_request = ExecRequest::new();
     {
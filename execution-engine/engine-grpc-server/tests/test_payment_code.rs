@@ -14,9 +14,11 @@ use contract_ffi::key::Key;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::{Value, U512};
 
-use engine_core::engine_state::{EngineConfig, CONV_RATE, MAX_PAYMENT};
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
 use engine_shared::transform::Transform;
-use test_support::{DeployBuilder, ExecRequestBuilder, WasmTestBuilder, GENESIS_INITIAL_BALANCE};
+use test_support::{
+    DeployBuilder, ExecRequestBuilder, WasmTestBuilder, DEFAULT_GAS_PRICE, GENESIS_INITIAL_BALANCE,
+};
 
 #[allow(dead_code)]
 mod test_support;
@@ -30,7 +32,9 @@ fn should_raise_insufficient_payment_when_caller_lacks_minimum_balance() {
     let genesis_public_key = PublicKey::new(GENESIS_ADDR);
     let account_1_public_key = PublicKey::new(ACCOUNT_1_ADDR);
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
@@ -41,7 +45,6 @@ fn should_raise_insufficient_payment_when_caller_lacks_minimum_balance() {
             )
             .with_payment_code("standard_payment.wasm", U512::from(MAX_PAYMENT))
             .with_authorization_keys(&[genesis_public_key])
-            .with_nonce(1)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -64,7 +67,6 @@ fn should_raise_insufficient_payment_when_caller_lacks_minimum_balance() {
             .with_session_code("revert.wasm", ())
             .with_payment_code("standard_payment.wasm", U512::from(MAX_PAYMENT - 1))
             .with_authorization_keys(&[account_1_public_key])
-            .with_nonce(1)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -104,7 +106,9 @@ fn should_raise_insufficient_payment_when_payment_code_does_not_pay_enough() {
     let genesis_public_key = PublicKey::new(GENESIS_ADDR);
     let account_1_public_key = PublicKey::new(ACCOUNT_1_ADDR);
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
@@ -115,7 +119,6 @@ fn should_raise_insufficient_payment_when_payment_code_does_not_pay_enough() {
             )
             .with_payment_code("standard_payment.wasm", U512::from(1))
             .with_authorization_keys(&[genesis_public_key])
-            .with_nonce(1)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -193,7 +196,9 @@ fn should_raise_insufficient_payment_when_payment_code_fails() {
     let transferred_amount = U512::from(1);
     let expected_transfers_count = 2;
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
@@ -204,7 +209,6 @@ fn should_raise_insufficient_payment_when_payment_code_fails() {
                 (account_1_public_key, transferred_amount),
             )
             .with_authorization_keys(&[genesis_public_key])
-            .with_nonce(1)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -275,6 +279,60 @@ fn should_raise_insufficient_payment_when_payment_code_fails() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_raise_insufficient_payment_when_payment_code_transfers_elsewhere() {
+    let genesis_addr = GENESIS_ADDR;
+    let genesis_public_key = PublicKey::new(genesis_addr);
+    let account_1_public_key = PublicKey::new(ACCOUNT_1_ADDR);
+    let payment_purse_amount: U512 = U512::from(1_000_000);
+
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(genesis_addr)
+            .with_payment_code(
+                "transfer_purse_to_account.wasm",
+                (account_1_public_key, payment_purse_amount),
+            )
+            .with_session_code("revert.wasm", ())
+            .with_authorization_keys(&[genesis_public_key])
+            .build();
+
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let transfer_result = WasmTestBuilder::new(engine_config)
+        .run_genesis(genesis_addr, HashMap::default())
+        .exec_with_exec_request(exec_request)
+        .commit()
+        .finish();
+
+    let response = transfer_result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .clone();
+
+    let execution_result = test_support::get_success_result(&response);
+    let error_message = test_support::get_error_message(execution_result);
+
+    assert_eq!(
+        error_message, "Insufficient payment",
+        "expected insufficient payment"
+    );
+    assert_eq!(
+        transfer_result
+            .builder()
+            .query(None, Key::Account(ACCOUNT_1_ADDR), &[]),
+        None,
+        "payment code should not create the target account"
+    );
+}
+
 #[ignore]
 #[test]
 fn should_run_out_of_gas_when_session_code_exceeds_gas_limit() {
@@ -284,7 +342,9 @@ fn should_run_out_of_gas_when_session_code_exceeds_gas_limit() {
     let payment_purse_amount = 10_000_000;
     let transferred_amount = 1;
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
@@ -295,7 +355,6 @@ fn should_run_out_of_gas_when_session_code_exceeds_gas_limit() {
                 (account_1_public_key, U512::from(transferred_amount)),
             )
             .with_authorization_keys(&[genesis_public_key])
-            .with_nonce(1)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -329,7 +388,9 @@ fn should_correctly_charge_when_session_code_runs_out_of_gas() {
     let genesis_account_key = Key::Account(genesis_addr);
     let payment_purse_amount = 10_000_000;
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
@@ -337,7 +398,6 @@ fn should_correctly_charge_when_session_code_runs_out_of_gas() {
             .with_payment_code("standard_payment.wasm", U512::from(payment_purse_amount))
             .with_session_code("endless_loop.wasm", ())
             .with_authorization_keys(&[genesis_public_key])
-            .with_nonce(1)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -403,7 +463,7 @@ fn should_correctly_charge_when_session_code_runs_out_of_gas() {
         .expect("there should be a response")
         .clone();
 
-    let motes = test_support::get_success_result(&response).cost * CONV_RATE;
+    let motes = test_support::get_success_result(&response).cost * DEFAULT_GAS_PRICE;
 
     let tally = U512::from(motes) + modified_balance;
 
@@ -428,7 +488,9 @@ fn should_correctly_charge_when_session_code_fails() {
     let payment_purse_amount = 10_000_000;
     let transferred_amount = 1;
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
@@ -439,7 +501,6 @@ fn should_correctly_charge_when_session_code_fails() {
                 (account_1_public_key, U512::from(transferred_amount)),
             )
             .with_authorization_keys(&[genesis_public_key])
-            .with_nonce(1)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -505,7 +566,7 @@ fn should_correctly_charge_when_session_code_fails() {
         .expect("there should be a response")
         .clone();
 
-    let motes = test_support::get_success_result(&response).cost * CONV_RATE;
+    let motes = test_support::get_success_result(&response).cost * DEFAULT_GAS_PRICE;
 
     let tally = U512::from(motes) + modified_balance;
 
@@ -525,7 +586,9 @@ fn should_correctly_charge_when_session_code_succeeds() {
     let payment_purse_amount = 10_000_000;
     let transferred_amount = 1;
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
@@ -536,7 +599,6 @@ fn should_correctly_charge_when_session_code_succeeds() {
             )
             .with_payment_code("standard_payment.wasm", U512::from(payment_purse_amount))
             .with_authorization_keys(&[genesis_public_key])
-            .with_nonce(1)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -604,7 +666,7 @@ fn should_correctly_charge_when_session_code_succeeds() {
         .expect("there should be a response")
         .clone();
 
-    let motes = test_support::get_success_result(&response).cost * CONV_RATE;
+    let motes = test_support::get_success_result(&response).cost * DEFAULT_GAS_PRICE;
 
     let tally = U512::from(motes + transferred_amount) + modified_balance;
 
@@ -613,3 +675,167 @@ fn should_correctly_charge_when_session_code_succeeds() {
         "no net resources should be gained or lost post-distribution"
     );
 }
+
+fn get_main_purse_balance(builder: &WasmTestBuilder, account_addr: [u8; 32]) -> U512 {
+    let account = match builder.query(None, Key::Account(account_addr), &[]) {
+        Some(Value::Account(account)) => account,
+        other => panic!("expected account, got {:?}", other),
+    };
+    let purse_bytes = account
+        .purse_id()
+        .value()
+        .addr()
+        .to_bytes()
+        .expect("should be able to serialize purse bytes");
+
+    let mint = builder.get_mint_contract_uref();
+    let balance_mapping_key = Key::local(mint.addr(), &purse_bytes);
+    let balance_uref = builder
+        .query(None, balance_mapping_key, &[])
+        .and_then(|v| v.try_into().ok())
+        .expect("should find balance uref");
+
+    builder
+        .query(None, balance_uref, &[])
+        .and_then(|v| v.try_into().ok())
+        .expect("should parse balance into a U512")
+}
+
+/// Transfers `transferred_amount` from the genesis account at `gas_price`, and returns the gas
+/// cost of the deploy along with the genesis account's balance afterwards.
+fn transfer_at_gas_price(gas_price: u64, transferred_amount: u64) -> (u64, U512) {
+    let genesis_public_key = PublicKey::new(GENESIS_ADDR);
+    let account_1_public_key = PublicKey::new(ACCOUNT_1_ADDR);
+    let payment_purse_amount = 10_000_000 * gas_price;
+
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_session_code(
+                "transfer_purse_to_account.wasm",
+                (account_1_public_key, U512::from(transferred_amount)),
+            )
+            .with_payment_code("standard_payment.wasm", U512::from(payment_purse_amount))
+            .with_authorization_keys(&[genesis_public_key])
+            .with_gas_price(gas_price)
+            .build();
+
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = WasmTestBuilder::new(engine_config);
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .clone();
+    let cost = test_support::get_success_result(&response).cost;
+
+    (cost, get_main_purse_balance(&builder, GENESIS_ADDR))
+}
+
+#[ignore]
+#[test]
+fn should_charge_and_refund_at_deploy_gas_price() {
+    let transferred_amount = 1;
+    let initial_balance: U512 = U512::from(GENESIS_INITIAL_BALANCE);
+
+    let (cost_at_1, balance_at_1) = transfer_at_gas_price(1, transferred_amount);
+    let (cost_at_3, balance_at_3) = transfer_at_gas_price(3, transferred_amount);
+
+    assert_eq!(
+        cost_at_1, cost_at_3,
+        "gas spent should not depend on the gas price"
+    );
+
+    // Whatever was put in the payment purse beyond the charged motes is refunded.
+    assert_eq!(
+        initial_balance,
+        balance_at_1 + U512::from(cost_at_1 + transferred_amount),
+        "no net resources should be gained or lost post-distribution"
+    );
+    assert_eq!(
+        initial_balance,
+        balance_at_3 + U512::from(cost_at_3 * 3 + transferred_amount),
+        "no net resources should be gained or lost post-distribution"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_below_minimum_gas_price() {
+    let genesis_public_key = PublicKey::new(GENESIS_ADDR);
+
+    for gas_price in &[0, 1] {
+        let exec_request = {
+            let deploy = DeployBuilder::new()
+                .with_address(GENESIS_ADDR)
+                .with_session_code("revert.wasm", ())
+                .with_payment_code("standard_payment.wasm", U512::from(MAX_PAYMENT))
+                .with_authorization_keys(&[genesis_public_key])
+                .with_gas_price(*gas_price)
+                .build();
+
+            ExecRequestBuilder::new().push_deploy(deploy).build()
+        };
+
+        let engine_config = EngineConfig::new()
+            .without_deploy_verification_for_tests()
+            .set_use_payment_code(true)
+            .set_min_gas_price(2);
+
+        let mut builder = WasmTestBuilder::new(engine_config);
+        builder
+            .run_genesis(GENESIS_ADDR, HashMap::default())
+            .exec_with_exec_request(exec_request);
+
+        let response = builder
+            .get_exec_response(0)
+            .expect("there should be a response");
+        let precondition_failure = test_support::get_precondition_failure(response);
+        assert_eq!(
+            precondition_failure.get_message(),
+            format!("Gas price too low: minimum 2, actual {}", gas_price)
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_reject_zero_gas_price() {
+    let genesis_public_key = PublicKey::new(GENESIS_ADDR);
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_session_code("revert.wasm", ())
+            .with_authorization_keys(&[genesis_public_key])
+            .with_gas_price(0)
+            .build();
+
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_exec_request(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure.get_message(),
+        "Gas price too low: minimum 1, actual 0"
+    );
+}
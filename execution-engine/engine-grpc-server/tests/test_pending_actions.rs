@@ -0,0 +1,175 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::{PublicKey, Weight};
+use contract_ffi::value::{Account, Value};
+
+use test_support::{DeployBuilder, ExecRequestBuilder, UpgradeRequestBuilder, WasmTestBuilder};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+const SECOND_KEY: [u8; 32] = [43u8; 32];
+const NEW_KEY: [u8; 32] = [44u8; 32];
+const PROPOSE_BLOCK_TIME: u64 = 10;
+const EXPIRES_AT: u64 = 100;
+
+fn exec_pending_actions(
+    builder: &mut WasmTestBuilder,
+    command: &str,
+    id: [u8; 32],
+    key: [u8; 32],
+    deploy_hash: [u8; 32],
+    block_time: u64,
+) {
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_session_code(
+            "pending_actions.wasm",
+            (String::from(command), id, EXPIRES_AT),
+        )
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(&[PublicKey::new(key)])
+        .build();
+    let exec_request = ExecRequestBuilder::new()
+        .with_protocol_version(2)
+        .with_block_time(block_time)
+        .push_deploy(deploy)
+        .build();
+    builder.exec_with_exec_request(exec_request);
+}
+
+fn get_account(builder: &WasmTestBuilder) -> Account {
+    match builder.query(None, Key::Account(GENESIS_ADDR), &[]) {
+        Some(Value::Account(account)) => account,
+        other => panic!("expected account, got {:?}", other),
+    }
+}
+
+/// Proposes to add a new key and returns the id of the pending action.
+fn setup() -> (WasmTestBuilder, [u8; 32]) {
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build());
+    exec_pending_actions(&mut builder, "init", [0u8; 32], GENESIS_ADDR, [1u8; 32], 0);
+    builder.expect_success().commit();
+    exec_pending_actions(
+        &mut builder,
+        "propose",
+        [0u8; 32],
+        GENESIS_ADDR,
+        [2u8; 32],
+        PROPOSE_BLOCK_TIME,
+    );
+    builder.expect_success().commit();
+
+    let account = get_account(&builder);
+    let ids: Vec<[u8; 32]> = account.pending_actions().map(|(id, _)| *id).collect();
+    assert_eq!(ids.len(), 1, "the proposed action should be pending");
+    (builder, ids[0])
+}
+
+#[ignore]
+#[test]
+fn should_execute_action_approved_by_another_deploy() {
+    let (mut builder, id) = setup();
+
+    exec_pending_actions(
+        &mut builder,
+        "approve",
+        id,
+        SECOND_KEY,
+        [3u8; 32],
+        EXPIRES_AT,
+    );
+    builder.expect_success().commit();
+
+    let account = get_account(&builder);
+    assert_eq!(
+        account
+            .get_associated_keys()
+            .find(|(key, _)| **key == PublicKey::new(NEW_KEY)),
+        Some((&PublicKey::new(NEW_KEY), &Weight::new(1)))
+    );
+    assert_eq!(account.pending_actions().count(), 0);
+}
+
+#[ignore]
+#[test]
+fn should_not_approve_action_twice_with_same_key() {
+    let (mut builder, id) = setup();
+
+    exec_pending_actions(
+        &mut builder,
+        "approve",
+        id,
+        GENESIS_ADDR,
+        [3u8; 32],
+        PROPOSE_BLOCK_TIME,
+    );
+
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_not_approve_expired_action() {
+    let (mut builder, id) = setup();
+
+    exec_pending_actions(
+        &mut builder,
+        "approve",
+        id,
+        SECOND_KEY,
+        [3u8; 32],
+        EXPIRES_AT + 1,
+    );
+
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_cancel_action_with_key_management_threshold() {
+    let (mut builder, id) = setup();
+
+    exec_pending_actions(
+        &mut builder,
+        "cancel",
+        id,
+        SECOND_KEY,
+        [3u8; 32],
+        PROPOSE_BLOCK_TIME,
+    );
+    assert!(builder.is_error());
+
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_session_code(
+            "pending_actions.wasm",
+            (String::from("cancel"), id, EXPIRES_AT),
+        )
+        .with_deploy_hash([4u8; 32])
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR), PublicKey::new(SECOND_KEY)])
+        .build();
+    let exec_request = ExecRequestBuilder::new()
+        .with_protocol_version(2)
+        .with_block_time(PROPOSE_BLOCK_TIME)
+        .push_deploy(deploy)
+        .build();
+    builder
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+
+    assert_eq!(get_account(&builder).pending_actions().count(), 0);
+}
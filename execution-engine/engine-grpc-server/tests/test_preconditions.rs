@@ -10,7 +10,7 @@ use std::collections::HashMap;
 
 use contract_ffi::value::U512;
 
-use casperlabs_engine_grpc_server::engine_server::ipc::{DeployResult, ExecResponse_oneof_result};
+use casperlabs_engine_grpc_server::engine_server::ipc::{Deploy, ExecRequest};
 use contract_ffi::value::account::PublicKey;
 use engine_core::engine_state::EngineConfig;
 use test_support::{DeployBuilder, ExecRequestBuilder, WasmTestBuilder};
@@ -21,63 +21,192 @@ mod test_support;
 const GENESIS_ADDR: [u8; 32] = [12; 32];
 const ACCOUNT_1_ADDR: [u8; 32] = [42u8; 32];
 
+fn transfer_deploy(deploy_hash: [u8; 32]) -> DeployBuilder {
+    DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_session_code(
+            "transfer_purse_to_account.wasm",
+            (PublicKey::new(ACCOUNT_1_ADDR), U512::from(42)),
+        )
+        .with_payment_code("standard_payment.wasm", U512::from(10_000_000))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .with_deploy_hash(deploy_hash)
+}
+
+fn exec_request_at(deploy: Deploy, block_time: u64) -> ExecRequest {
+    ExecRequestBuilder::new()
+        .with_block_time(block_time)
+        .push_deploy(deploy)
+        .build()
+}
+
 #[ignore]
 #[test]
-fn should_raise_precondition_invalid_nonce() {
-    let genesis_public_key = PublicKey::new(GENESIS_ADDR);
-    let account_1_public_key = PublicKey::new(ACCOUNT_1_ADDR);
+fn should_raise_precondition_deploy_already_executed() {
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
+
+    let transfer_result = WasmTestBuilder::new(engine_config)
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_exec_request(exec_request_at(transfer_deploy([1u8; 32]).build(), 0))
+        .expect_success()
+        .commit()
+        .exec_with_exec_request(exec_request_at(transfer_deploy([1u8; 32]).build(), 0))
+        .commit()
+        .finish();
+
+    let response = transfer_result
+        .builder()
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure.get_message(),
+        "Deploy already executed"
+    );
+
+    let transforms = transfer_result.builder().get_transforms();
+    assert_eq!(
+        transforms[1].len(),
+        0,
+        "there should be no transforms as there are no changes (including charging for exec)"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_deploy_expired() {
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
+
+    let transfer_result = WasmTestBuilder::new(engine_config)
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_exec_request(exec_request_at(
+            transfer_deploy([1u8; 32]).with_ttl(10).build(),
+            11,
+        ))
+        .commit()
+        .finish();
+
+    let response = transfer_result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure.get_message(),
+        "Deploy expired at block time 10"
+    );
+
+    let transforms = transfer_result.builder().get_transforms();
+    assert_eq!(
+        transforms[0].len(),
+        0,
+        "there should be no transforms as there are no changes (including charging for exec)"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_deploy_not_yet_valid() {
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
+    let deploy = transfer_deploy([1u8; 32]).with_not_before(20).build();
 
-    let invalid_nonce = 2;
+    let transfer_result = WasmTestBuilder::new(engine_config)
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_exec_request(exec_request_at(deploy.clone(), 19))
+        .commit()
+        .exec_with_exec_request(exec_request_at(deploy, 20))
+        .expect_success()
+        .commit()
+        .finish();
+
+    let response = transfer_result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure.get_message(),
+        "Deploy not valid before block time 20"
+    );
+}
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+#[ignore]
+#[test]
+fn should_raise_precondition_missing_deploy_dependency() {
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
+    let dependent_deploy = transfer_deploy([2u8; 32])
+        .with_dependencies(&[[1u8; 32]])
+        .build();
+
+    let transfer_result = WasmTestBuilder::new(engine_config)
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_exec_request(exec_request_at(dependent_deploy.clone(), 0))
+        .commit()
+        .exec_with_exec_request(exec_request_at(transfer_deploy([1u8; 32]).build(), 0))
+        .expect_success()
+        .commit()
+        .exec_with_exec_request(exec_request_at(dependent_deploy, 0))
+        .expect_success()
+        .commit()
+        .finish();
+
+    let response = transfer_result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure.get_message(),
+        format!("Deploy dependency not executed: {}", "01".repeat(32))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_for_repeated_deploy_hash_in_block() {
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
             .with_address(GENESIS_ADDR)
             .with_session_code(
                 "transfer_purse_to_account.wasm",
-                (account_1_public_key, U512::from(42)),
+                (PublicKey::new(ACCOUNT_1_ADDR), U512::from(42)),
             )
             .with_payment_code("standard_payment.wasm", U512::from(10_000_000))
-            .with_authorization_keys(&[genesis_public_key])
-            .with_nonce(invalid_nonce)
+            .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
             .build();
 
-        ExecRequestBuilder::new().push_deploy(deploy).build()
+        ExecRequestBuilder::new()
+            .push_deploy(deploy.clone())
+            .push_deploy(deploy)
+            .build()
     };
 
-    let transfer_result = WasmTestBuilder::new(engine_config)
+    let mut builder = WasmTestBuilder::new(engine_config);
+    builder
         .run_genesis(GENESIS_ADDR, HashMap::default())
-        .exec_with_exec_request(exec_request)
-        .commit()
-        .finish();
+        .exec_with_exec_request(exec_request);
 
-    let response = transfer_result
-        .builder()
+    let deploy_results = builder
         .get_exec_response(0)
         .expect("there should be a response")
-        .clone();
-
-    let has_invalid_nonce: bool = match &response.result.expect("result expected") {
-        ExecResponse_oneof_result::success(result) => {
-            let first: &DeployResult = result
-                .deploy_results
-                .first()
-                .expect("should have a deploy result");
-            first.has_invalid_nonce()
-        }
-        ExecResponse_oneof_result::missing_parent(_) => false,
-    };
-
-    assert!(has_invalid_nonce, "expected invalid nonce");
-
-    let transforms = transfer_result.builder().get_transforms();
-    let transform = &transforms[0];
-
+        .get_success()
+        .get_deploy_results();
+    assert!(deploy_results[0].has_execution_result());
     assert_eq!(
-        transform.len(),
-        0,
-        "there should be no transforms as there are no changes (including charging for exec)"
+        deploy_results[1].get_precondition_failure().get_message(),
+        "Deploy already executed"
     );
 }
 
@@ -90,7 +219,9 @@ fn should_raise_precondition_authorization_failure_invalid_account() {
     let payment_purse_amount = 10_000_000;
     let transferred_amount = 1;
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
@@ -102,7 +233,6 @@ fn should_raise_precondition_authorization_failure_invalid_account() {
             .with_address(nonexistent_account_addr)
             .with_payment_code("standard_payment.wasm", U512::from(payment_purse_amount))
             .with_authorization_keys(&[PublicKey::new(nonexistent_account_addr)])
-            .with_nonce(0)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -132,14 +262,15 @@ fn should_raise_precondition_authorization_failure_invalid_account() {
 fn should_raise_precondition_authorization_failure_empty_authorized_keys() {
     let genesis_addr = GENESIS_ADDR;
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
             .with_address(genesis_addr)
             // empty authorization keys to force error
             .with_authorization_keys(&[])
-            .with_nonce(0)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -173,7 +304,9 @@ fn should_raise_precondition_authorization_failure_invalid_authorized_keys() {
     let payment_purse_amount = 10_000_000;
     let transferred_amount = 1;
 
-    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_config = EngineConfig::new()
+        .without_deploy_verification_for_tests()
+        .set_use_payment_code(true);
 
     let exec_request = {
         let deploy = DeployBuilder::new()
@@ -185,7 +318,6 @@ fn should_raise_precondition_authorization_failure_invalid_authorized_keys() {
             .with_payment_code("standard_payment.wasm", U512::from(payment_purse_amount))
             // invalid authorization key to force error
             .with_authorization_keys(&[PublicKey::new(nonexistent_account_addr)])
-            .with_nonce(0)
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -0,0 +1,106 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+
+use test_support::{UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const RANDOM_BYTES_WASM: &str = "random_bytes.wasm";
+
+fn run(command: &str, deploy_index: u64) -> WasmTestBuilder {
+    let result = WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .exec_with_args(
+            GENESIS_ADDR,
+            RANDOM_BYTES_WASM,
+            DEFAULT_BLOCK_TIME,
+            deploy_index,
+            (command,),
+        )
+        .expect_success()
+        .commit()
+        .finish();
+    WasmTestBuilder::from_result(result)
+}
+
+fn get_bytes(builder: &WasmTestBuilder, name: &str) -> Vec<u8> {
+    match builder.query(None, Key::Account(GENESIS_ADDR), &[name]) {
+        Some(Value::ByteArray(bytes)) => bytes,
+        other => panic!("expected byte array under {}, got {:?}", name, other),
+    }
+}
+
+fn get_uref(builder: &WasmTestBuilder) -> Key {
+    match builder.query(None, Key::Account(GENESIS_ADDR), &[]) {
+        Some(Value::Account(account)) => *account
+            .urefs_lookup()
+            .get("uref")
+            .expect("should have created uref"),
+        other => panic!("expected account, got {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_return_same_bytes_on_re_execution() {
+    let first = run("local", 1);
+    let second = run("local", 1);
+
+    let bytes1 = get_bytes(&first, "bytes1");
+    let bytes2 = get_bytes(&first, "bytes2");
+    assert_eq!(bytes1.len(), 32);
+    assert_ne!(bytes1, bytes2);
+    assert_eq!(bytes1, get_bytes(&second, "bytes1"));
+    assert_eq!(bytes2, get_bytes(&second, "bytes2"));
+}
+
+#[ignore]
+#[test]
+fn should_return_different_bytes_for_different_deploys() {
+    let mut builder = run("local", 1);
+    let first_bytes = get_bytes(&builder, "bytes1");
+
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            RANDOM_BYTES_WASM,
+            DEFAULT_BLOCK_TIME,
+            2,
+            ("local",),
+        )
+        .expect_success()
+        .commit();
+
+    assert_ne!(first_bytes, get_bytes(&builder, "bytes1"));
+}
+
+#[ignore]
+#[test]
+fn should_share_random_bytes_stream_with_subcalls() {
+    let local = run("local", 1);
+    let subcall = run("subcall", 1);
+
+    assert_eq!(get_bytes(&local, "bytes1"), get_bytes(&subcall, "bytes1"));
+    assert_eq!(get_bytes(&local, "bytes2"), get_bytes(&subcall, "bytes2"));
+}
+
+#[ignore]
+#[test]
+fn should_not_advance_uref_rng() {
+    let with_draw = run("local", 1);
+    let without_draw = run("no_draw", 1);
+
+    assert_eq!(get_uref(&with_draw), get_uref(&without_draw));
+}
@@ -10,10 +10,13 @@ mod test_support;
 
 use std::collections::HashMap;
 
-use test_support::WasmTestBuilder;
+use casperlabs_engine_grpc_server::engine_server::ipc::DeployError_RevertError_Category;
+use contract_ffi::contract_api::error::ApiError;
+use test_support::{UpgradeRequestBuilder, WasmTestBuilder};
 
 const GENESIS_ADDR: [u8; 32] = [7u8; 32];
 const REVERT_WASM: &str = "revert.wasm";
+const REVERT_WITH_PAYLOAD_WASM: &str = "revert_with_payload.wasm";
 const BLOCK_TIME: u64 = 42;
 
 #[ignore]
@@ -25,3 +28,39 @@ fn should_revert() {
         .commit()
         .is_error();
 }
+
+#[ignore]
+#[test]
+fn should_revert_with_error_and_payload() {
+    let result = WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .exec(GENESIS_ADDR, REVERT_WITH_PAYLOAD_WASM, BLOCK_TIME, 1)
+        .commit()
+        .finish();
+
+    let deploy_result = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have exec response")
+        .get_success()
+        .get_deploy_results()
+        .get(0)
+        .expect("should have at least one deploy result");
+
+    let exec_error = deploy_result
+        .get_execution_result()
+        .get_error()
+        .get_exec_error();
+    let code: u32 = ApiError::User(7).into();
+    assert_eq!(exec_error.get_message(), format!("Exit code: {}", code));
+
+    let revert = exec_error.get_revert();
+    assert_eq!(revert.get_code(), code);
+    assert_eq!(
+        revert.get_category(),
+        DeployError_RevertError_Category::USER
+    );
+    assert_eq!(revert.get_value(), 7);
+    assert_eq!(revert.get_payload(), &[1, 2, 3]);
+}
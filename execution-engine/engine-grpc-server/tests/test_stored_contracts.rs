@@ -0,0 +1,123 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+
+use test_support::{DeployBuilder, ExecRequestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const STORED_SESSION_NAME: &str = "stored_session";
+const STORED_SESSION_MARKER: &str = "stored_session_marker";
+const MARKER_VALUE: &str = "Hello, stored session!";
+
+fn store_session_contract() -> WasmTestBuilder {
+    let result = WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec(GENESIS_ADDR, "stored_session.wasm", DEFAULT_BLOCK_TIME, 1)
+        .expect_success()
+        .commit()
+        .finish();
+    WasmTestBuilder::from_result(result)
+}
+
+fn get_marker_value(builder: &WasmTestBuilder) -> Option<Value> {
+    builder.query(None, Key::Account(GENESIS_ADDR), &[STORED_SESSION_MARKER])
+}
+
+#[ignore]
+#[test]
+fn should_exec_stored_session_by_name() {
+    let mut builder = store_session_contract();
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_stored_session_named_key(STORED_SESSION_NAME, (MARKER_VALUE.to_string(),))
+            .with_deploy_hash([2u8; 32])
+            .with_authorization_keys(&[contract_ffi::value::account::PublicKey::new(GENESIS_ADDR)])
+            .build();
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        get_marker_value(&builder),
+        Some(Value::String(MARKER_VALUE.to_string()))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_exec_stored_session_by_hash() {
+    let mut builder = store_session_contract();
+
+    let account = match builder.query(None, Key::Account(GENESIS_ADDR), &[]) {
+        Some(Value::Account(account)) => account,
+        other => panic!("Expected genesis account, got {:?}", other),
+    };
+
+    let stored_session_hash = match account.urefs_lookup().get(STORED_SESSION_NAME) {
+        Some(Key::Hash(hash)) => *hash,
+        other => panic!("Expected stored session under a hash key, got {:?}", other),
+    };
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_stored_session_hash(stored_session_hash, (MARKER_VALUE.to_string(),))
+            .with_deploy_hash([2u8; 32])
+            .with_authorization_keys(&[contract_ffi::value::account::PublicKey::new(GENESIS_ADDR)])
+            .build();
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        get_marker_value(&builder),
+        Some(Value::String(MARKER_VALUE.to_string()))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_precondition_when_stored_session_name_is_unknown() {
+    let mut builder = store_session_contract();
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_stored_session_named_key("no_such_contract", (MARKER_VALUE.to_string(),))
+            .with_deploy_hash([2u8; 32])
+            .with_authorization_keys(&[contract_ffi::value::account::PublicKey::new(GENESIS_ADDR)])
+            .build();
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder.exec_with_exec_request(exec_request);
+
+    let exec_response = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    let precondition_failure = test_support::get_precondition_failure(exec_response);
+    assert!(precondition_failure
+        .get_message()
+        .contains("no_such_contract"));
+}
@@ -5,6 +5,7 @@ extern crate engine_shared;
 extern crate engine_storage;
 extern crate engine_wasm_prep;
 extern crate grpc;
+extern crate protobuf;
 
 use std::collections::HashMap;
 use std::convert::TryInto;
@@ -13,11 +14,12 @@ use std::rc::Rc;
 
 use grpc::RequestOptions;
 
+use casperlabs_engine_grpc_server::engine_server::consensus;
 use casperlabs_engine_grpc_server::engine_server::ipc;
 use casperlabs_engine_grpc_server::engine_server::ipc::{
-    CommitRequest, Deploy, DeployCode, DeployResult, DeployResult_ExecutionResult,
+    Approval, CommitRequest, Deploy, DeployCode, DeployResult, DeployResult_ExecutionResult,
     DeployResult_PreconditionFailure, ExecRequest, ExecResponse, GenesisRequest, GenesisResponse,
-    QueryRequest, TransformEntry,
+    QueryRequest, TransformEntry, UpgradeRequest, UpgradeResponse,
 };
 use casperlabs_engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use casperlabs_engine_grpc_server::engine_server::mappings::{
@@ -26,6 +28,7 @@ use casperlabs_engine_grpc_server::engine_server::mappings::{
 use casperlabs_engine_grpc_server::engine_server::state::{BigInt, ProtocolVersion};
 use engine_core::engine_state::utils::WasmiBytes;
 use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_shared::newtypes::Blake2bHash;
 use engine_shared::test_utils;
 use engine_shared::transform::Transform;
 use engine_storage::global_state::in_memory::InMemoryGlobalState;
@@ -34,6 +37,9 @@ pub const DEFAULT_BLOCK_TIME: u64 = 0;
 pub const MOCKED_ACCOUNT_ADDRESS: [u8; 32] = [48u8; 32];
 pub const COMPILED_WASM_PATH: &str = "../target/wasm32-unknown-unknown/release";
 pub const GENESIS_INITIAL_BALANCE: u64 = 100_000_000_000;
+pub const DEFAULT_GAS_PRICE: u64 = 1;
+pub const DEFAULT_DEPLOY_HASH: [u8; 32] = [1u8; 32];
+pub const DEFAULT_TTL: u64 = 3_600_000;
 
 pub struct DeployBuilder {
     deploy: Deploy,
@@ -83,11 +89,78 @@ impl DeployBuilder {
         self
     }
 
-    pub fn with_nonce(mut self, nonce: u64) -> Self {
-        self.deploy.set_nonce(nonce);
+    pub fn with_stored_session_hash(
+        mut self,
+        hash: [u8; 32],
+        args: impl contract_ffi::contract_api::argsparser::ArgsParser,
+    ) -> Self {
+        let mut session = DeployCode::new();
+        session.set_stored_contract_hash(hash.to_vec());
+        session.set_args(serialize_args(args));
+        self.deploy.set_session(session);
+        self
+    }
+
+    pub fn with_stored_session_named_key(
+        mut self,
+        name: &str,
+        args: impl contract_ffi::contract_api::argsparser::ArgsParser,
+    ) -> Self {
+        let mut session = DeployCode::new();
+        session.set_stored_contract_name(name.to_string());
+        session.set_args(serialize_args(args));
+        self.deploy.set_session(session);
+        self
+    }
+
+    pub fn with_stored_payment_named_key(
+        mut self,
+        name: &str,
+        args: impl contract_ffi::contract_api::argsparser::ArgsParser,
+    ) -> Self {
+        let mut payment = DeployCode::new();
+        payment.set_stored_contract_name(name.to_string());
+        payment.set_args(serialize_args(args));
+        self.deploy.set_payment(payment);
+        self
+    }
+
+    pub fn with_deploy_hash(mut self, deploy_hash: [u8; 32]) -> Self {
+        self.deploy.set_deploy_hash(deploy_hash.to_vec());
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.deploy.set_timestamp(timestamp);
+        self
+    }
+
+    pub fn with_ttl(mut self, ttl: u64) -> Self {
+        self.deploy.set_ttl(ttl);
+        self
+    }
+
+    pub fn with_not_before(mut self, not_before: u64) -> Self {
+        self.deploy.set_not_before(not_before);
+        self
+    }
+
+    pub fn with_dependencies(mut self, dependencies: &[[u8; 32]]) -> Self {
+        let dependencies = dependencies
+            .iter()
+            .map(|dependency| dependency.to_vec())
+            .collect();
+        self.deploy.set_dependencies(dependencies);
+        self
+    }
+
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.deploy.set_gas_price(gas_price);
         self
     }
 
+    /// Authorizes the deploy with the given key addresses, without signatures. Only accepted by an
+    /// engine which doesn't verify deploy signatures.
     pub fn with_authorization_keys(
         mut self,
         authorization_keys: &[contract_ffi::value::account::PublicKey],
@@ -100,6 +173,41 @@ impl DeployBuilder {
         self
     }
 
+    pub fn with_approvals(
+        mut self,
+        approvals: &[(contract_ffi::value::account::TaggedPublicKey, Vec<u8>)],
+    ) -> Self {
+        let approvals = approvals
+            .iter()
+            .map(|(public_key, signature)| create_approval(public_key, signature.clone()))
+            .collect();
+        self.deploy.set_approvals(approvals);
+        self
+    }
+
+    /// Sets the header of the deploy to the one the node makes for an account with the given
+    /// public key, and the deploy hash to the hash of the header. Call after setting the code, gas
+    /// price and timestamp of the deploy.
+    pub fn with_header(mut self, account_public_key: &[u8]) -> Self {
+        let mut body = consensus::Deploy_Body::new();
+        if self.deploy.has_session() {
+            body.set_session(to_consensus_code(self.deploy.get_session()));
+        }
+        if self.deploy.has_payment() {
+            body.set_payment(to_consensus_code(self.deploy.get_payment()));
+        }
+        let mut header = consensus::Deploy_Header::new();
+        header.set_account_public_key(account_public_key.to_vec());
+        header.set_timestamp(self.deploy.get_timestamp());
+        header.set_gas_price(self.deploy.get_gas_price());
+        header.set_body_hash(Blake2bHash::new(&serialize_message(&body)).to_vec());
+        let header_bytes = serialize_message(&header);
+        self.deploy
+            .set_deploy_hash(Blake2bHash::new(&header_bytes).to_vec());
+        self.deploy.set_header(header_bytes);
+        self
+    }
+
     pub fn build(self) -> Deploy {
         self.deploy
     }
@@ -109,7 +217,10 @@ impl Default for DeployBuilder {
     fn default() -> Self {
         let mut deploy = Deploy::new();
         deploy.set_motes_transferred_in_payment(1_000_000_000);
-        deploy.set_gas_price(1);
+        deploy.set_gas_price(DEFAULT_GAS_PRICE);
+        deploy.set_deploy_hash(DEFAULT_DEPLOY_HASH.to_vec());
+        deploy.set_timestamp(DEFAULT_BLOCK_TIME);
+        deploy.set_ttl(DEFAULT_TTL);
         DeployBuilder { deploy }
     }
 }
@@ -140,6 +251,22 @@ impl ExecRequestBuilder {
         self
     }
 
+    pub fn with_block_height(mut self, block_height: u64) -> Self {
+        self.exec_request.set_block_height(block_height);
+        self
+    }
+
+    pub fn with_parent_block_hash(mut self, parent_block_hash: &[u8]) -> Self {
+        self.exec_request
+            .set_parent_block_hash(parent_block_hash.to_vec());
+        self
+    }
+
+    pub fn with_proposer(mut self, proposer: &[u8]) -> Self {
+        self.exec_request.set_proposer(proposer.to_vec());
+        self
+    }
+
     pub fn with_protocol_version(mut self, version: u64) -> Self {
         let mut protocol_version = ProtocolVersion::new();
         protocol_version.set_value(version);
@@ -172,6 +299,68 @@ impl Default for ExecRequestBuilder {
     }
 }
 
+pub struct UpgradeRequestBuilder {
+    upgrade_request: UpgradeRequest,
+}
+
+impl UpgradeRequestBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_protocol_version(mut self, version: u64) -> Self {
+        let mut protocol_version = ProtocolVersion::new();
+        protocol_version.set_value(version);
+        self.upgrade_request.set_protocol_version(protocol_version);
+        self
+    }
+
+    pub fn with_new_costs(mut self, wasm_costs: engine_wasm_prep::wasm_costs::WasmCosts) -> Self {
+        self.upgrade_request.set_new_costs(wasm_costs.into());
+        self
+    }
+
+    pub fn with_upgrade_installer(mut self, file_name: &str) -> Self {
+        let mut upgrade_installer = DeployCode::new();
+        upgrade_installer.set_code(read_wasm_file_bytes(file_name));
+        self.upgrade_request
+            .set_upgrade_installer(upgrade_installer);
+        self
+    }
+
+    pub fn with_new_mint_code(mut self, file_name: &str) -> Self {
+        let mut mint_code = DeployCode::new();
+        mint_code.set_code(read_wasm_file_bytes(file_name));
+        self.upgrade_request.set_new_mint_code(mint_code);
+        self
+    }
+
+    pub fn with_new_proof_of_stake_code(mut self, file_name: &str) -> Self {
+        let mut proof_of_stake_code = DeployCode::new();
+        proof_of_stake_code.set_code(read_wasm_file_bytes(file_name));
+        self.upgrade_request
+            .set_new_proof_of_stake_code(proof_of_stake_code);
+        self
+    }
+
+    pub fn build(self) -> UpgradeRequest {
+        self.upgrade_request
+    }
+}
+
+impl Default for UpgradeRequestBuilder {
+    fn default() -> Self {
+        let mut upgrade_request = UpgradeRequest::new();
+        let mut protocol_version = ProtocolVersion::new();
+        protocol_version.set_value(2);
+        upgrade_request.set_protocol_version(protocol_version);
+        let wasm_costs =
+            engine_wasm_prep::wasm_costs::WasmCosts::from_version(1).expect("should have costs");
+        upgrade_request.set_new_costs(wasm_costs.into());
+        UpgradeRequestBuilder { upgrade_request }
+    }
+}
+
 pub fn get_protocol_version() -> ProtocolVersion {
     let mut protocol_version: ProtocolVersion = ProtocolVersion::new();
     protocol_version.set_value(1);
@@ -182,14 +371,56 @@ pub fn get_mock_deploy() -> Deploy {
     let mut deploy = Deploy::new();
     deploy.set_address(MOCKED_ACCOUNT_ADDRESS.to_vec());
     deploy.set_motes_transferred_in_payment(1000);
-    deploy.set_gas_price(1);
-    deploy.set_nonce(1);
+    deploy.set_gas_price(DEFAULT_GAS_PRICE);
+    deploy.set_deploy_hash(DEFAULT_DEPLOY_HASH.to_vec());
+    deploy.set_ttl(DEFAULT_TTL);
     let mut deploy_code = DeployCode::new();
     deploy_code.set_code(test_utils::create_empty_wasm_module_bytes());
     deploy.set_session(deploy_code);
     deploy
 }
 
+pub fn create_approval(
+    public_key: &contract_ffi::value::account::TaggedPublicKey,
+    signature: Vec<u8>,
+) -> Approval {
+    let algorithm = match public_key.algorithm() {
+        contract_ffi::value::account::SignatureAlgorithm::Ed25519 => {
+            ipc::SignatureAlgorithm::ED25519
+        }
+        contract_ffi::value::account::SignatureAlgorithm::Secp256k1 => {
+            ipc::SignatureAlgorithm::SECP256K1
+        }
+    };
+    let mut approval = Approval::new();
+    approval.set_public_key(public_key.as_bytes().to_vec());
+    approval.set_signature(signature);
+    approval.set_algorithm(algorithm);
+    approval
+}
+
+fn to_consensus_code(deploy_code: &DeployCode) -> consensus::Deploy_Code {
+    let mut code = consensus::Deploy_Code::new();
+    code.set_code(deploy_code.get_code().to_vec());
+    code.set_args(deploy_code.get_args().to_vec());
+    if deploy_code.has_stored_contract_hash() {
+        code.set_stored_contract_hash(deploy_code.get_stored_contract_hash().to_vec());
+    } else if deploy_code.has_stored_contract_name() {
+        code.set_stored_contract_name(deploy_code.get_stored_contract_name().to_string());
+    }
+    code
+}
+
+fn serialize_message(message: &impl protobuf::Message) -> Vec<u8> {
+    protobuf::Message::write_to_bytes(message).expect("should serialize message")
+}
+
+fn serialize_args(args: impl contract_ffi::contract_api::argsparser::ArgsParser) -> Vec<u8> {
+    args.parse()
+        .and_then(|args_bytes| contract_ffi::bytesrepr::ToBytes::to_bytes(&args_bytes))
+        .expect("should serialize args")
+}
+
 fn get_compiled_wasm_path(contract_file: PathBuf) -> PathBuf {
     let mut path = std::env::current_dir().expect("should get working directory");
     path.push(PathBuf::from(COMPILED_WASM_PATH));
@@ -230,9 +461,15 @@ pub fn create_genesis_request(
         let mut ret = DeployCode::new();
         let contract_file = "mint_token.wasm";
         let wasm_bytes = read_wasm_file_bytes(contract_file);
-        let wasmi_bytes =
-            WasmiBytes::new(&wasm_bytes, engine_wasm_prep::wasm_costs::WasmCosts::free())
-                .expect("should have wasmi bytes");
+        let wasmi_bytes = WasmiBytes::new(
+            &wasm_bytes,
+            engine_wasm_prep::wasm_costs::WasmCosts::free(),
+            engine_wasm_prep::wasm_limits::WasmLimits::from_version(get_protocol_version().value)
+                .expect("should have wasm limits"),
+            engine_wasm_prep::import_set::ImportSet::from_version(get_protocol_version().value)
+                .expect("should have import set"),
+        )
+        .expect("should have wasmi bytes");
         contracts.insert(SystemContractType::Mint, wasmi_bytes);
         ret.set_code(wasm_bytes);
         ret
@@ -242,9 +479,15 @@ pub fn create_genesis_request(
         let mut ret = DeployCode::new();
         let contract_file = "pos.wasm";
         let wasm_bytes = read_wasm_file_bytes(contract_file);
-        let wasmi_bytes =
-            WasmiBytes::new(&wasm_bytes, engine_wasm_prep::wasm_costs::WasmCosts::free())
-                .expect("should have wasmi bytes");
+        let wasmi_bytes = WasmiBytes::new(
+            &wasm_bytes,
+            engine_wasm_prep::wasm_costs::WasmCosts::free(),
+            engine_wasm_prep::wasm_limits::WasmLimits::from_version(get_protocol_version().value)
+                .expect("should have wasm limits"),
+            engine_wasm_prep::import_set::ImportSet::from_version(get_protocol_version().value)
+                .expect("should have import set"),
+        )
+        .expect("should have wasmi bytes");
         contracts.insert(SystemContractType::ProofOfStake, wasmi_bytes);
         ret.set_code(wasm_bytes);
         ret
@@ -291,18 +534,26 @@ pub fn create_query_request(
     query_request
 }
 
+/// Derives a deploy hash from the deploying account and an index telling apart its deploys.
+pub fn get_deploy_hash(address: [u8; 32], deploy_index: u64) -> [u8; 32] {
+    let mut bytes = address.to_vec();
+    bytes.extend_from_slice(&deploy_index.to_le_bytes());
+    Blake2bHash::new(&bytes).into()
+}
+
 pub fn create_exec_request(
     address: [u8; 32],
     session_contract_file_name: &str,
     pre_state_hash: &[u8],
     block_time: u64,
-    nonce: u64,
+    deploy_index: u64,
     arguments: impl contract_ffi::contract_api::argsparser::ArgsParser,
     authorized_keys: Vec<contract_ffi::value::account::PublicKey>,
 ) -> ExecRequest {
     let deploy = DeployBuilder::new()
         .with_session_code(session_contract_file_name, arguments)
-        .with_nonce(nonce)
+        .with_deploy_hash(get_deploy_hash(address, deploy_index))
+        .with_timestamp(block_time)
         .with_address(address)
         .with_authorization_keys(&authorized_keys)
         .build();
@@ -467,6 +718,7 @@ pub struct WasmTestBuilder {
     /// Engine state is wrapped in Rc<> to workaround missing `impl Clone for EngineState`
     engine_state: Rc<EngineState<InMemoryGlobalState>>,
     exec_responses: Vec<ExecResponse>,
+    upgrade_responses: Vec<UpgradeResponse>,
     genesis_hash: Option<Vec<u8>>,
     post_state_hash: Option<Vec<u8>>,
     /// Cached transform maps after subsequent successful runs
@@ -480,15 +732,22 @@ pub struct WasmTestBuilder {
     genesis_transforms: Option<HashMap<contract_ffi::key::Key, Transform>>,
     /// Mint contract uref
     mint_contract_uref: Option<contract_ffi::uref::URef>,
+    /// Protocol version of the deploys run by `exec_with_args`, the one of the last successful
+    /// upgrade
+    protocol_version: u64,
 }
 
 impl Default for WasmTestBuilder {
     fn default() -> WasmTestBuilder {
         let global_state = InMemoryGlobalState::empty().expect("should create global state");
-        let engine_state = EngineState::new(global_state, Default::default());
+        let engine_state = EngineState::new(
+            global_state,
+            EngineConfig::new().without_deploy_verification_for_tests(),
+        );
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
             transforms: Vec::new(),
@@ -496,6 +755,7 @@ impl Default for WasmTestBuilder {
             genesis_account: None,
             mint_contract_uref: None,
             genesis_transforms: None,
+            protocol_version: 1,
         }
     }
 }
@@ -517,6 +777,7 @@ impl WasmTestBuilder {
         WasmTestBuilder {
             engine_state: result.0.engine_state,
             exec_responses: Vec::new(),
+            upgrade_responses: Vec::new(),
             genesis_hash: result.0.genesis_hash,
             post_state_hash: result.0.post_state_hash,
             transforms: Vec::new(),
@@ -524,6 +785,7 @@ impl WasmTestBuilder {
             genesis_account: result.0.genesis_account,
             mint_contract_uref: result.0.mint_contract_uref,
             genesis_transforms: result.0.genesis_transforms,
+            protocol_version: result.0.protocol_version,
         }
     }
 
@@ -533,6 +795,7 @@ impl WasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
             transforms: Vec::new(),
@@ -540,6 +803,7 @@ impl WasmTestBuilder {
             genesis_account: None,
             mint_contract_uref: None,
             genesis_transforms: None,
+            protocol_version: 1,
         }
     }
 
@@ -664,6 +928,33 @@ impl WasmTestBuilder {
         self
     }
 
+    /// Runs an upgrade request on the latest post-state hash and, if it succeeds, overwrites
+    /// the cached post-state hash with the one it returns and runs later deploys of
+    /// `exec_with_args` at the upgraded protocol version.
+    pub fn upgrade_with_upgrade_request(
+        &mut self,
+        mut upgrade_request: UpgradeRequest,
+    ) -> &mut WasmTestBuilder {
+        let hash = self
+            .post_state_hash
+            .clone()
+            .expect("expected post_state_hash");
+        upgrade_request.set_parent_state_hash(hash);
+        let protocol_version = upgrade_request.get_protocol_version().value;
+        let upgrade_response = self
+            .engine_state
+            .upgrade(RequestOptions::new(), upgrade_request)
+            .wait_drop_metadata()
+            .expect("should upgrade");
+        if upgrade_response.has_success() {
+            self.post_state_hash =
+                Some(upgrade_response.get_success().get_poststate_hash().to_vec());
+            self.protocol_version = protocol_version;
+        }
+        self.upgrade_responses.push(upgrade_response);
+        self
+    }
+
     /// Runs a contract and after that runs actual WASM contract and expects
     /// transformations to happen at the end of execution.
     pub fn exec_with_args_and_keys(
@@ -671,21 +962,24 @@ impl WasmTestBuilder {
         address: [u8; 32],
         wasm_file: &str,
         block_time: u64,
-        nonce: u64,
+        deploy_index: u64,
         args: impl contract_ffi::contract_api::argsparser::ArgsParser,
         authorized_keys: Vec<contract_ffi::value::account::PublicKey>,
     ) -> &mut WasmTestBuilder {
-        let exec_request = create_exec_request(
+        let mut exec_request = create_exec_request(
             address,
             &wasm_file,
             self.post_state_hash
                 .as_ref()
                 .expect("Should have post state hash"),
             block_time,
-            nonce,
+            deploy_index,
             args,
             authorized_keys,
         );
+        let mut protocol_version = ProtocolVersion::new();
+        protocol_version.set_value(self.protocol_version);
+        exec_request.set_protocol_version(protocol_version);
         self.exec_with_exec_request(exec_request)
     }
 
@@ -694,14 +988,14 @@ impl WasmTestBuilder {
         address: [u8; 32],
         wasm_file: &str,
         block_time: u64,
-        nonce: u64,
+        deploy_index: u64,
         args: impl contract_ffi::contract_api::argsparser::ArgsParser,
     ) -> &mut WasmTestBuilder {
         self.exec_with_args_and_keys(
             address,
             wasm_file,
             block_time,
-            nonce,
+            deploy_index,
             args,
             // Exec with different account also implies the authorized keys should default to
             // the calling account.
@@ -714,9 +1008,9 @@ impl WasmTestBuilder {
         address: [u8; 32],
         wasm_file: &str,
         block_time: u64,
-        nonce: u64,
+        deploy_index: u64,
     ) -> &mut WasmTestBuilder {
-        self.exec_with_args(address, wasm_file, block_time, nonce, ())
+        self.exec_with_args(address, wasm_file, block_time, deploy_index, ())
     }
 
     /// Commit effects of previous exec call on the latest post-state hash.
@@ -854,6 +1148,10 @@ impl WasmTestBuilder {
         self.exec_responses.get(index)
     }
 
+    pub fn get_upgrade_response(&self, index: usize) -> Option<&UpgradeResponse> {
+        self.upgrade_responses.get(index)
+    }
+
     pub fn finish(&self) -> WasmTestResult {
         WasmTestResult(self.clone())
     }
@@ -15,7 +15,7 @@ use contract_ffi::key::Key;
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{PublicKey, PurseId};
 use contract_ffi::value::{Value, U512};
-use engine_core::engine_state::EngineState;
+use engine_core::engine_state::{EngineConfig, EngineState};
 use engine_shared::transform::Transform;
 use engine_storage::global_state::in_memory::InMemoryGlobalState;
 use test_support::DEFAULT_BLOCK_TIME;
@@ -87,7 +87,10 @@ fn should_transfer_to_account() {
     let account_key = Key::Account(ACCOUNT_1_ADDR);
 
     let global_state = InMemoryGlobalState::empty().unwrap();
-    let engine_state = EngineState::new(global_state, Default::default());
+    let engine_state = EngineState::new(
+        global_state,
+        EngineConfig::new().without_deploy_verification_for_tests(),
+    );
 
     // Run genesis
 
@@ -186,7 +189,10 @@ fn should_transfer_from_account_to_account() {
     let account_2_key = Key::Account(ACCOUNT_2_ADDR);
 
     let global_state = InMemoryGlobalState::empty().unwrap();
-    let engine_state = EngineState::new(global_state, Default::default());
+    let engine_state = EngineState::new(
+        global_state,
+        EngineConfig::new().without_deploy_verification_for_tests(),
+    );
 
     // Run genesis
 
@@ -337,7 +343,10 @@ fn should_transfer_to_existing_account() {
     let account_2_key = Key::Account(ACCOUNT_2_ADDR);
 
     let global_state = InMemoryGlobalState::empty().unwrap();
-    let engine_state = EngineState::new(global_state, Default::default());
+    let engine_state = EngineState::new(
+        global_state,
+        EngineConfig::new().without_deploy_verification_for_tests(),
+    );
 
     // Run genesis
 
@@ -494,7 +503,10 @@ fn should_transfer_to_existing_account() {
 #[test]
 fn should_fail_when_insufficient_funds() {
     let global_state = InMemoryGlobalState::empty().unwrap();
-    let engine_state = EngineState::new(global_state, Default::default());
+    let engine_state = EngineState::new(
+        global_state,
+        EngineConfig::new().without_deploy_verification_for_tests(),
+    );
 
     // Run genesis
 
@@ -610,7 +622,10 @@ fn should_create_purse() {
     let genesis_account_key = Key::Account(GENESIS_ADDR);
     let account_key = Key::Account(ACCOUNT_1_ADDR);
     let global_state = InMemoryGlobalState::empty().unwrap();
-    let engine_state = EngineState::new(global_state, Default::default());
+    let engine_state = EngineState::new(
+        global_state,
+        EngineConfig::new().without_deploy_verification_for_tests(),
+    );
 
     // Run genesis & set up an account
 
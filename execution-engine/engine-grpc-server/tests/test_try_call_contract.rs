@@ -0,0 +1,114 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use contract_ffi::contract_api::error::ApiError;
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+
+use test_support::{UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const TRY_CALL_CONTRACT_WASM: &str = "try_call_contract.wasm";
+const COUNTER_NAME: &str = "counter";
+const CALL_RESULT_NAME: &str = "call_result";
+
+/// Stores a contract whose entry points increment a counter and then return, revert or trap,
+/// then tries to call its `entry_point`.
+fn store_and_try_call(entry_point: &str) -> WasmTestBuilder {
+    let result = WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .exec_with_args(
+            GENESIS_ADDR,
+            TRY_CALL_CONTRACT_WASM,
+            DEFAULT_BLOCK_TIME,
+            1,
+            ("store",),
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args(
+            GENESIS_ADDR,
+            TRY_CALL_CONTRACT_WASM,
+            DEFAULT_BLOCK_TIME,
+            2,
+            (entry_point,),
+        )
+        .commit()
+        .finish();
+    WasmTestBuilder::from_result(result)
+}
+
+fn get_named_value(builder: &WasmTestBuilder, name: &str) -> Option<Value> {
+    builder.query(None, Key::Account(GENESIS_ADDR), &[name])
+}
+
+#[ignore]
+#[test]
+fn should_keep_effects_of_successful_call() {
+    let builder = store_and_try_call("increment_and_return");
+
+    assert!(!builder.is_error());
+    assert_eq!(
+        get_named_value(&builder, CALL_RESULT_NAME),
+        Some(Value::UInt64(42))
+    );
+    assert_eq!(
+        get_named_value(&builder, COUNTER_NAME),
+        Some(Value::UInt64(1))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_roll_back_effects_of_reverted_call() {
+    let builder = store_and_try_call("increment_and_revert");
+
+    assert!(!builder.is_error());
+    let expected_status = u32::from(ApiError::User(7));
+    assert_eq!(
+        get_named_value(&builder, CALL_RESULT_NAME),
+        Some(Value::UInt64(expected_status.into()))
+    );
+    assert_eq!(
+        get_named_value(&builder, COUNTER_NAME),
+        Some(Value::UInt64(0))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_roll_back_effects_of_trapped_call() {
+    let builder = store_and_try_call("increment_and_trap");
+
+    assert!(!builder.is_error());
+    assert_eq!(
+        get_named_value(&builder, CALL_RESULT_NAME),
+        Some(Value::UInt64(0))
+    );
+    assert_eq!(
+        get_named_value(&builder, COUNTER_NAME),
+        Some(Value::UInt64(0))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_report_call_to_unknown_entry_point_as_trap() {
+    let builder = store_and_try_call("unknown");
+
+    assert!(!builder.is_error());
+    assert_eq!(
+        get_named_value(&builder, CALL_RESULT_NAME),
+        Some(Value::UInt64(0))
+    );
+}
@@ -0,0 +1,231 @@
+extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_shared;
+extern crate engine_storage;
+extern crate engine_wasm_prep;
+extern crate grpc;
+
+use std::collections::HashMap;
+
+use contract_ffi::bytesrepr::ToBytes;
+use contract_ffi::key::Key;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::Value;
+use engine_core::engine_state::genesis::{
+    GenesisURefsSource, MINT_PRIVATE_ADDRESS, POS_PUBLIC_ADDRESS,
+};
+use engine_core::engine_state::upgrade::{
+    active_protocol_version_key, protocol_data_key, ProtocolData,
+};
+use engine_core::engine_state::SYSTEM_ACCOUNT_ADDR;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+use test_support::{
+    DeployBuilder, ExecRequestBuilder, UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME,
+};
+
+#[allow(dead_code)]
+mod test_support;
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+const MIGRATED_KEY: [u8; 32] = [77u8; 32];
+
+fn new_costs() -> WasmCosts {
+    WasmCosts {
+        regular: 2,
+        ..WasmCosts::from_version(1).expect("should have costs")
+    }
+}
+
+fn exec_at_version(builder: &mut WasmTestBuilder, protocol_version: u64) {
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_session_code("local_state.wasm", ())
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build();
+    let exec_request = ExecRequestBuilder::new()
+        .with_protocol_version(protocol_version)
+        .with_block_time(DEFAULT_BLOCK_TIME)
+        .push_deploy(deploy)
+        .build();
+    builder.exec_with_exec_request(exec_request);
+}
+
+fn assert_upgrade_failed(builder: &WasmTestBuilder, index: usize) {
+    let upgrade_response = builder
+        .get_upgrade_response(index)
+        .expect("should have upgrade response");
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "expected upgrade failure, got {:?}",
+        upgrade_response
+    );
+}
+
+#[ignore]
+#[test]
+fn should_record_protocol_version_and_costs() {
+    let mut builder = WasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    assert_eq!(
+        builder.query(None, active_protocol_version_key(), &[]),
+        Some(Value::UInt64(1))
+    );
+
+    builder.upgrade_with_upgrade_request(
+        UpgradeRequestBuilder::new()
+            .with_protocol_version(2)
+            .with_new_costs(new_costs())
+            .build(),
+    );
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have upgrade response");
+    assert!(upgrade_response.has_success());
+    assert_eq!(
+        upgrade_response.get_success().get_poststate_hash().to_vec(),
+        builder.get_poststate_hash()
+    );
+
+    assert_eq!(
+        builder.query(None, active_protocol_version_key(), &[]),
+        Some(Value::UInt64(2))
+    );
+    let expected_protocol_data = ProtocolData::new(new_costs())
+        .to_bytes()
+        .expect("should serialize");
+    assert_eq!(
+        builder.query(None, protocol_data_key(2), &[]),
+        Some(Value::ByteArray(expected_protocol_data))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_exec_only_at_active_protocol_version() {
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build());
+
+    exec_at_version(&mut builder, 1);
+    let deploy_result = builder
+        .get_exec_response(0)
+        .expect("should have exec response")
+        .get_success()
+        .get_deploy_results()
+        .get(0)
+        .expect("should have deploy result")
+        .clone();
+    assert!(deploy_result.has_precondition_failure());
+
+    exec_at_version(&mut builder, 2);
+    builder.expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_replace_system_contract_code_and_keep_its_uref() {
+    let mut builder = WasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let mint_contract_key = Key::URef(GenesisURefsSource::default().get_uref(MINT_PRIVATE_ADDRESS));
+    let old_mint_contract = match builder.query(None, mint_contract_key, &[]) {
+        Some(Value::Contract(contract)) => contract,
+        other => panic!("expected mint contract, got {:?}", other),
+    };
+
+    builder.upgrade_with_upgrade_request(
+        UpgradeRequestBuilder::new()
+            .with_new_mint_code("mint_token_test.wasm")
+            .build(),
+    );
+
+    let new_mint_contract = match builder.query(None, mint_contract_key, &[]) {
+        Some(Value::Contract(contract)) => contract,
+        other => panic!("expected mint contract, got {:?}", other),
+    };
+    assert_ne!(new_mint_contract.bytes(), old_mint_contract.bytes());
+    assert_eq!(new_mint_contract.protocol_version(), 2);
+    assert_eq!(
+        new_mint_contract.urefs_lookup(),
+        old_mint_contract.urefs_lookup()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_run_upgrade_installer() {
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(
+            UpgradeRequestBuilder::new()
+                .with_upgrade_installer("upgrade_installer.wasm")
+                .build(),
+        );
+
+    let migrated_key = Key::local(
+        SYSTEM_ACCOUNT_ADDR,
+        &MIGRATED_KEY.to_bytes().expect("should serialize"),
+    );
+    let pos_public_uref = GenesisURefsSource::default().get_uref(POS_PUBLIC_ADDRESS);
+    assert_eq!(
+        builder.query(None, migrated_key, &[]),
+        Some(Value::Key(Key::URef(pos_public_uref)))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_protocol_version_not_above_active_one() {
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(
+            UpgradeRequestBuilder::new()
+                .with_protocol_version(1)
+                .build(),
+        );
+    assert_upgrade_failed(&builder, 0);
+
+    builder
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build())
+        .upgrade_with_upgrade_request(UpgradeRequestBuilder::new().build());
+    assert_upgrade_failed(&builder, 2);
+}
+
+#[ignore]
+#[test]
+fn should_reject_unsupported_protocol_version() {
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(
+            UpgradeRequestBuilder::new()
+                .with_protocol_version(1_000)
+                .build(),
+        );
+    assert_upgrade_failed(&builder, 0);
+}
+
+#[ignore]
+#[test]
+fn should_reject_cost_table_with_zero_opcodes_div() {
+    let new_costs = WasmCosts {
+        opcodes_div: 0,
+        ..new_costs()
+    };
+    let mut builder = WasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(
+            UpgradeRequestBuilder::new()
+                .with_new_costs(new_costs)
+                .build(),
+        );
+    assert_upgrade_failed(&builder, 0);
+}
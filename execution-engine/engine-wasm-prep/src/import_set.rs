@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use parity_wasm::elements::ValueType::{self, I32, I64};
+
+/// A host function declaration: its name, parameter types and return type.
+type HostFunctionDeclaration = (&'static str, &'static [ValueType], Option<ValueType>);
+
+/// Host functions available since protocol version 1.
+const V1_HOST_FUNCTIONS: &[HostFunctionDeclaration] = &[
+    ("read_value", &[I32; 2], Some(I32)),
+    ("read_value_local", &[I32; 2], Some(I32)),
+    ("serialize_function", &[I32; 2], Some(I32)),
+    ("serialize_known_urefs", &[], Some(I32)),
+    ("write", &[I32; 4], None),
+    ("write_local", &[I32; 4], None),
+    ("get_read", &[I32; 1], None),
+    ("get_function", &[I32; 1], None),
+    ("add", &[I32; 4], None),
+    ("new_uref", &[I32; 3], None),
+    ("load_arg", &[I32; 1], Some(I32)),
+    ("get_arg", &[I32; 1], None),
+    ("ret", &[I32; 4], None),
+    ("call_contract", &[I32; 6], Some(I32)),
+    ("get_call_result", &[I32; 1], None),
+    ("get_uref", &[I32; 2], Some(I32)),
+    ("has_uref_name", &[I32; 2], Some(I32)),
+    ("add_uref", &[I32; 4], None),
+    ("gas", &[I32; 1], None),
+    ("store_function", &[I32; 5], None),
+    ("protocol_version", &[], Some(I64)),
+    ("is_valid", &[I32; 2], Some(I32)),
+    ("revert", &[I32; 1], None),
+    ("add_associated_key", &[I32; 2], Some(I32)),
+    ("remove_associated_key", &[I32; 1], Some(I32)),
+    ("update_associated_key", &[I32; 2], Some(I32)),
+    ("set_action_threshold", &[I32; 2], Some(I32)),
+    ("list_known_urefs", &[I32; 1], None),
+    ("remove_uref", &[I32; 2], None),
+    ("get_caller", &[I32; 1], None),
+    ("get_blocktime", &[I32; 1], None),
+    ("create_purse", &[I32; 2], Some(I32)),
+    ("transfer_to_account", &[I32; 4], Some(I32)),
+    ("transfer_from_purse_to_account", &[I32; 6], Some(I32)),
+    ("transfer_from_purse_to_purse", &[I32; 6], Some(I32)),
+    ("get_balance", &[I32; 2], Some(I32)),
+];
+
+/// Host functions added in protocol version 2.
+const V2_HOST_FUNCTIONS: &[HostFunctionDeclaration] = &[
+    ("try_call_contract", &[I32; 8], Some(I32)),
+    ("store_entry_points", &[I32; 5], None),
+    ("revert_with_payload", &[I32; 3], None),
+    ("blake2b", &[I32; 3], None),
+    ("verify_ed25519", &[I32; 4], Some(I32)),
+    ("random_bytes", &[I32; 2], None),
+    ("serialize_call_stack", &[], Some(I32)),
+    ("get_call_stack", &[I32; 1], None),
+    ("get_block_height", &[I32; 1], None),
+    ("get_parent_hash", &[I32; 1], None),
+    ("get_proposer", &[I32; 1], None),
+    ("create_contract_package", &[I32; 2], None),
+    ("add_contract_version", &[I32; 7], Some(I32)),
+    ("disable_contract_version", &[I32; 3], Some(I32)),
+    ("call_versioned_contract", &[I32; 6], Some(I32)),
+    ("add_recovery_key", &[I32; 2], Some(I32)),
+    ("remove_recovery_key", &[I32; 1], Some(I32)),
+    ("set_recovery_threshold", &[I32; 1], Some(I32)),
+    ("set_inactivity_period_limit", &[I32; 1], Some(I32)),
+    ("set_named_threshold", &[I32; 3], Some(I32)),
+    ("meets_named_threshold", &[I32; 2], Some(I32)),
+    ("propose_action", &[I32; 4], Some(I32)),
+    ("approve_action", &[I32; 1], Some(I32)),
+    ("cancel_action", &[I32; 1], Some(I32)),
+    ("call_contract_entry_point", &[I32; 8], Some(I32)),
+];
+
+/// Parameter types and return type of a host function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostFunctionSignature {
+    params: &'static [ValueType],
+    return_type: Option<ValueType>,
+}
+
+impl HostFunctionSignature {
+    pub fn params(&self) -> &'static [ValueType] {
+        self.params
+    }
+
+    pub fn return_type(&self) -> Option<ValueType> {
+        self.return_type
+    }
+}
+
+/// The host functions a module can import at a given protocol version, by name.
+///
+/// Each protocol version can only add functions to the set of the previous one, so that modules
+/// built for an older version keep resolving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportSet {
+    functions: BTreeMap<&'static str, HostFunctionSignature>,
+}
+
+impl ImportSet {
+    pub fn from_version(protocol_version: u64) -> Option<ImportSet> {
+        let declarations: &[&[HostFunctionDeclaration]] = match protocol_version {
+            1 => &[V1_HOST_FUNCTIONS],
+            2 => &[V1_HOST_FUNCTIONS, V2_HOST_FUNCTIONS],
+            _ => return None,
+        };
+        let functions = declarations
+            .iter()
+            .flat_map(|declarations| declarations.iter())
+            .map(|&(name, params, return_type)| {
+                (
+                    name,
+                    HostFunctionSignature {
+                        params,
+                        return_type,
+                    },
+                )
+            })
+            .collect();
+        Some(ImportSet { functions })
+    }
+
+    /// Returns the signature of the host function named `name`, if it is in this set.
+    pub fn get(&self, name: &str) -> Option<&HostFunctionSignature> {
+        self.functions.get(name)
+    }
+
+    /// Returns the names of the host functions in this set.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.functions.keys().cloned()
+    }
+}
@@ -1,17 +1,26 @@
 extern crate parity_wasm;
 extern crate pwasm_utils;
 
+#[cfg(test)]
+#[macro_use]
+extern crate proptest;
+#[cfg(test)]
+extern crate wabt;
+
+pub mod import_set;
+#[cfg(test)]
+mod tests;
 pub mod wasm_costs;
+pub mod wasm_limits;
 
-use parity_wasm::elements::{Error as ParityWasmError, Module};
+use import_set::ImportSet;
+use parity_wasm::elements::{Error as ParityWasmError, External, Module, Type};
 use pwasm_utils::{externalize_mem, inject_gas_counter, rules};
 use std::error::Error;
 use wasm_costs::WasmCosts;
+use wasm_limits::WasmLimits;
 
-//NOTE: size of Wasm memory page is 64 KiB
-pub const MEM_PAGES: u32 = 64;
-
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum PreprocessingError {
     InvalidImportsError(String),
     NoExportSection,
@@ -19,6 +28,26 @@ pub enum PreprocessingError {
     DeserializeError(String),
     OperationForbiddenByGasRules,
     StackLimiterError,
+    /// The module doesn't define its own memory.
+    NoMemorySection,
+    /// The module is larger than the maximum size allowed, which is the contained value.
+    ModuleTooLarge(usize),
+    /// The module defines more functions than the contained limit.
+    TooManyFunctions(usize),
+    /// A table of the module may grow beyond the contained number of elements.
+    TableTooLarge(u32),
+    /// The module defines more globals than the contained limit.
+    TooManyGlobals(usize),
+    /// The data segments of the module are larger in total than the contained number of bytes.
+    DataSegmentsTooLarge(usize),
+    /// The module has more exports than the contained limit.
+    TooManyExports(usize),
+    /// The module imports the named function, which is not a host function at the protocol
+    /// version it is run at.
+    UnavailableHostFunction(String),
+    /// The module imports the named host function with a signature different from the one the
+    /// host provides.
+    HostFunctionSignatureMismatch(String),
 }
 
 use PreprocessingError::*;
@@ -30,26 +59,36 @@ pub trait Preprocessor<A> {
 
 pub struct WasmiPreprocessor {
     wasm_costs: WasmCosts,
-    // Number of memory pages.
-    mem_pages: u32,
+    wasm_limits: WasmLimits,
+    import_set: ImportSet,
 }
 
 impl WasmiPreprocessor {
-    pub fn new(wasm_costs: WasmCosts) -> WasmiPreprocessor {
+    pub fn new(
+        wasm_costs: WasmCosts,
+        wasm_limits: WasmLimits,
+        import_set: ImportSet,
+    ) -> WasmiPreprocessor {
         WasmiPreprocessor {
             wasm_costs,
-            mem_pages: MEM_PAGES,
+            wasm_limits,
+            import_set,
         }
     }
 }
 
 impl Preprocessor<Module> for WasmiPreprocessor {
     fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
+        if module_bytes.len() > self.wasm_limits.max_module_size {
+            return Err(ModuleTooLarge(self.wasm_limits.max_module_size));
+        }
         let deserialized_module = self.deserialize(module_bytes)?;
-        let ext_mod = externalize_mem(deserialized_module, None, self.mem_pages);
+        validate_module(&deserialized_module, &self.wasm_limits)?;
+        validate_imports(&deserialized_module, &self.import_set)?;
+        let ext_mod = externalize_mem(deserialized_module, None, self.wasm_limits.max_memory_pages);
         let gas_mod = inject_gas_counters(ext_mod, &self.wasm_costs)?;
         let module =
-            pwasm_utils::stack_height::inject_limiter(gas_mod, self.wasm_costs.max_stack_height)
+            pwasm_utils::stack_height::inject_limiter(gas_mod, self.wasm_limits.max_stack_height)
                 .map_err(|_| StackLimiterError)?;
         Ok(module)
     }
@@ -63,6 +102,104 @@ impl Preprocessor<Module> for WasmiPreprocessor {
     }
 }
 
+/// Checks that the structure of `module` stays within `wasm_limits`, so that no unbounded work
+/// is done instantiating it before any gas is charged.
+fn validate_module(module: &Module, wasm_limits: &WasmLimits) -> Result<(), PreprocessingError> {
+    let memories = module
+        .memory_section()
+        .map_or(0, |section| section.entries().len());
+    if memories == 0 {
+        return Err(NoMemorySection);
+    }
+
+    let functions = module
+        .function_section()
+        .map_or(0, |section| section.entries().len());
+    if functions > wasm_limits.max_functions {
+        return Err(TooManyFunctions(wasm_limits.max_functions));
+    }
+
+    if let Some(section) = module.table_section() {
+        for table_type in section.entries() {
+            let limits = table_type.limits();
+            let effective_max = limits.maximum().unwrap_or_else(|| limits.initial());
+            if limits.initial() > wasm_limits.max_table_size
+                || effective_max > wasm_limits.max_table_size
+            {
+                return Err(TableTooLarge(wasm_limits.max_table_size));
+            }
+        }
+    }
+
+    let globals = module
+        .global_section()
+        .map_or(0, |section| section.entries().len());
+    if globals > wasm_limits.max_globals {
+        return Err(TooManyGlobals(wasm_limits.max_globals));
+    }
+
+    let data_segments_size: usize = module.data_section().map_or(0, |section| {
+        section
+            .entries()
+            .iter()
+            .map(|segment| segment.value().len())
+            .sum()
+    });
+    if data_segments_size > wasm_limits.max_data_segments_size {
+        return Err(DataSegmentsTooLarge(wasm_limits.max_data_segments_size));
+    }
+
+    let exports = module
+        .export_section()
+        .map_or(0, |section| section.entries().len());
+    if exports > wasm_limits.max_exports {
+        return Err(TooManyExports(wasm_limits.max_exports));
+    }
+
+    Ok(())
+}
+
+/// Checks that every function imported by `module` is a host function in `import_set`, with the
+/// same signature.
+///
+/// Runs before gas injection, which adds an import of its own.
+fn validate_imports(module: &Module, import_set: &ImportSet) -> Result<(), PreprocessingError> {
+    let imports = match module.import_section() {
+        Some(section) => section.entries(),
+        None => return Ok(()),
+    };
+    let types = module
+        .type_section()
+        .map_or(&[][..], |section| section.types());
+
+    for import in imports {
+        let type_index = match import.external() {
+            External::Function(type_index) => *type_index,
+            _ => continue,
+        };
+        if import.module() != "env" {
+            return Err(InvalidImportsError(format!(
+                "function {} imported from unknown module {}",
+                import.field(),
+                import.module()
+            )));
+        }
+        let expected = import_set
+            .get(import.field())
+            .ok_or_else(|| UnavailableHostFunction(import.field().to_owned()))?;
+        let Type::Function(function_type) = types
+            .get(type_index as usize)
+            .ok_or_else(|| HostFunctionSignatureMismatch(import.field().to_owned()))?;
+        if function_type.params() != expected.params()
+            || function_type.return_type() != expected.return_type()
+        {
+            return Err(HostFunctionSignatureMismatch(import.field().to_owned()));
+        }
+    }
+
+    Ok(())
+}
+
 fn gas_rules(wasm_costs: &WasmCosts) -> rules::Set {
     rules::Set::new(wasm_costs.regular, {
         let mut vals = ::std::collections::BTreeMap::new();
@@ -0,0 +1,250 @@
+use parity_wasm::elements::Module;
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use import_set::ImportSet;
+use wasm_costs::WasmCosts;
+use wasm_limits::WasmLimits;
+use PreprocessingError::*;
+use {PreprocessingError, Preprocessor, WasmiPreprocessor};
+
+const LIMIT: usize = 8;
+
+fn test_limits() -> WasmLimits {
+    WasmLimits {
+        max_memory_pages: 64,
+        max_stack_height: 64 * 1024,
+        max_call_depth: 16,
+        max_module_size: 64 * 1024,
+        max_functions: LIMIT,
+        max_table_size: LIMIT as u32,
+        max_globals: LIMIT,
+        max_data_segments_size: LIMIT,
+        max_exports: LIMIT,
+    }
+}
+
+/// Counts of the structural elements of a generated module.
+#[derive(Debug, Clone, Copy)]
+struct Shape {
+    functions: usize,
+    table_size: u32,
+    globals: usize,
+    data_size: usize,
+    exports: usize,
+}
+
+impl Default for Shape {
+    fn default() -> Self {
+        Shape {
+            functions: 1,
+            table_size: 0,
+            globals: 0,
+            data_size: 0,
+            exports: 0,
+        }
+    }
+}
+
+fn module_bytes(shape: Shape) -> Vec<u8> {
+    let mut wat = String::from("(module (memory 1)");
+    for _ in 0..shape.functions {
+        wat.push_str(" (func)");
+    }
+    if shape.table_size > 0 {
+        wat.push_str(&format!(" (table {} anyfunc)", shape.table_size));
+    }
+    for _ in 0..shape.globals {
+        wat.push_str(" (global i32 (i32.const 0))");
+    }
+    if shape.data_size > 0 {
+        wat.push_str(&format!(
+            " (data (i32.const 0) \"{}\")",
+            "a".repeat(shape.data_size)
+        ));
+    }
+    for index in 0..shape.exports {
+        wat.push_str(&format!(" (export \"e{}\" (func 0))", index));
+    }
+    wat.push(')');
+    wabt::wat2wasm(wat).expect("should parse wat")
+}
+
+fn preprocess(module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
+    let import_set = ImportSet::from_version(1).expect("should have import set");
+    WasmiPreprocessor::new(WasmCosts::free(), test_limits(), import_set).preprocess(module_bytes)
+}
+
+fn module_bytes_importing(import: &str) -> Vec<u8> {
+    let wat = format!("(module {} (memory 1) (func))", import);
+    wabt::wat2wasm(wat).expect("should parse wat")
+}
+
+#[test]
+fn should_reject_module_without_memory() {
+    let module_bytes = wabt::wat2wasm("(module (func))").expect("should parse wat");
+
+    assert_eq!(preprocess(&module_bytes).err(), Some(NoMemorySection));
+}
+
+#[test]
+fn should_reject_too_large_module() {
+    let shape = Shape {
+        data_size: 64 * 1024,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        preprocess(&module_bytes(shape)).err(),
+        Some(ModuleTooLarge(64 * 1024))
+    );
+}
+
+#[test]
+fn should_accept_host_function_import() {
+    let module_bytes = module_bytes_importing(r#"(import "env" "get_arg" (func (param i32)))"#);
+
+    assert!(preprocess(&module_bytes).is_ok());
+}
+
+#[test]
+fn should_reject_unavailable_host_function() {
+    let module_bytes =
+        module_bytes_importing(r#"(import "env" "not_a_host_function" (func (param i32)))"#);
+
+    assert_eq!(
+        preprocess(&module_bytes).err(),
+        Some(UnavailableHostFunction("not_a_host_function".to_string()))
+    );
+}
+
+#[test]
+fn should_only_accept_version_2_host_function_from_version_2() {
+    let module_bytes = module_bytes_importing(
+        r#"(import "env" "add_recovery_key" (func (param i32 i32) (result i32)))"#,
+    );
+
+    assert_eq!(
+        preprocess(&module_bytes).err(),
+        Some(UnavailableHostFunction("add_recovery_key".to_string()))
+    );
+    let import_set = ImportSet::from_version(2).expect("should have import set");
+    assert!(
+        WasmiPreprocessor::new(WasmCosts::free(), test_limits(), import_set)
+            .preprocess(&module_bytes)
+            .is_ok()
+    );
+}
+
+#[test]
+fn should_reject_host_function_with_wrong_signature() {
+    let module_bytes =
+        module_bytes_importing(r#"(import "env" "get_arg" (func (param i64) (result i32)))"#);
+
+    assert_eq!(
+        preprocess(&module_bytes).err(),
+        Some(HostFunctionSignatureMismatch("get_arg".to_string()))
+    );
+}
+
+#[test]
+fn should_reject_function_imported_from_other_module() {
+    let module_bytes = module_bytes_importing(r#"(import "other" "get_arg" (func (param i32)))"#);
+
+    assert!(match preprocess(&module_bytes).err() {
+        Some(InvalidImportsError(_)) => true,
+        _ => false,
+    });
+}
+
+#[test]
+fn should_reject_host_function_added_after_version_1() {
+    let module_bytes =
+        module_bytes_importing(r#"(import "env" "blake2b" (func (param i32 i32 i32)))"#);
+
+    assert_eq!(
+        preprocess(&module_bytes).err(),
+        Some(UnavailableHostFunction("blake2b".to_string()))
+    );
+    let v1 = ImportSet::from_version(1).expect("should have import set");
+    assert_eq!(v1.names().count(), 36);
+}
+
+#[test]
+fn import_sets_should_only_add_host_functions() {
+    let v1 = ImportSet::from_version(1).expect("should have import set");
+    let v2 = ImportSet::from_version(2).expect("should have import set");
+    for name in v1.names() {
+        assert_eq!(v1.get(name), v2.get(name), "{} changed in version 2", name);
+    }
+}
+
+#[test]
+fn should_not_have_import_set_for_unknown_version() {
+    assert!(ImportSet::from_version(0).is_none());
+    assert!(ImportSet::from_version(3).is_none());
+}
+
+proptest! {
+    #[test]
+    fn should_accept_modules_within_limits(
+        functions in 1..=LIMIT,
+        table_size in 0..=LIMIT as u32,
+        globals in 0..=LIMIT,
+        data_size in 0..=LIMIT,
+        exports in 0..=LIMIT,
+    ) {
+        let shape = Shape { functions, table_size, globals, data_size, exports };
+
+        prop_assert!(preprocess(&module_bytes(shape)).is_ok());
+    }
+
+    #[test]
+    fn should_reject_too_many_functions(functions in LIMIT + 1..4 * LIMIT) {
+        let shape = Shape { functions, ..Default::default() };
+
+        prop_assert_eq!(preprocess(&module_bytes(shape)).err(), Some(TooManyFunctions(LIMIT)));
+    }
+
+    #[test]
+    fn should_reject_too_large_table(table_size in LIMIT as u32 + 1..1024) {
+        let shape = Shape { table_size, ..Default::default() };
+
+        prop_assert_eq!(
+            preprocess(&module_bytes(shape)).err(),
+            Some(TableTooLarge(LIMIT as u32))
+        );
+    }
+
+    #[test]
+    fn should_reject_too_many_globals(globals in LIMIT + 1..4 * LIMIT) {
+        let shape = Shape { globals, ..Default::default() };
+
+        prop_assert_eq!(preprocess(&module_bytes(shape)).err(), Some(TooManyGlobals(LIMIT)));
+    }
+
+    #[test]
+    fn should_reject_too_large_data_segments(data_size in LIMIT + 1..1024) {
+        let shape = Shape { data_size, ..Default::default() };
+
+        prop_assert_eq!(
+            preprocess(&module_bytes(shape)).err(),
+            Some(DataSegmentsTooLarge(LIMIT))
+        );
+    }
+
+    #[test]
+    fn should_reject_too_many_exports(exports in LIMIT + 1..4 * LIMIT) {
+        let shape = Shape { exports, ..Default::default() };
+
+        prop_assert_eq!(preprocess(&module_bytes(shape)).err(), Some(TooManyExports(LIMIT)));
+    }
+
+    #[test]
+    fn should_not_panic_on_arbitrary_bytes(body in vec(any::<u8>(), 0..256)) {
+        let mut module_bytes = b"\0asm\x01\0\0\0".to_vec();
+        module_bytes.extend(body);
+
+        let _ = preprocess(&module_bytes);
+    }
+}
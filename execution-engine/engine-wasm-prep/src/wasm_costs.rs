@@ -1,5 +1,5 @@
 // Taken (partially) from parity-ethereum
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WasmCosts {
     /// Default opcode cost
     pub regular: u32,
@@ -15,8 +15,6 @@ pub struct WasmCosts {
     pub grow_mem: u32,
     /// Memory copy cost, per byte
     pub memcpy: u32,
-    /// Max stack height (native WebAssembly stack limiter)
-    pub max_stack_height: u32,
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` / `opcodes_div`
     pub opcodes_mul: u32,
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` / `opcodes_div`
@@ -34,7 +32,6 @@ impl WasmCosts {
                 initial_mem: 4096,
                 grow_mem: 8192,
                 memcpy: 1,
-                max_stack_height: 64 * 1024,
                 opcodes_mul: 3,
                 opcodes_div: 8,
             }),
@@ -51,7 +48,6 @@ impl WasmCosts {
             initial_mem: 4096,
             grow_mem: 8192,
             memcpy: 0,
-            max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
         }
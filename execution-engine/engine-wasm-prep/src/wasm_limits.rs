@@ -0,0 +1,41 @@
+/// Limits on the resources a Wasm module may use, defined per protocol version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmLimits {
+    /// Maximum number of memory pages (64 KiB each) a module can use.
+    pub max_memory_pages: u32,
+    /// Max stack height (native WebAssembly stack limiter)
+    pub max_stack_height: u32,
+    /// Maximum number of nested contract calls, counting the outermost session or payment code.
+    pub max_call_depth: usize,
+    /// Maximum size of a module in bytes, as submitted for preprocessing.
+    pub max_module_size: usize,
+    /// Maximum number of functions defined by a module, not counting imported ones.
+    pub max_functions: usize,
+    /// Maximum number of elements of a table, both initially and at its declared maximum.
+    pub max_table_size: u32,
+    /// Maximum number of globals defined by a module, not counting imported ones.
+    pub max_globals: usize,
+    /// Maximum total size in bytes of the data segments of a module.
+    pub max_data_segments_size: usize,
+    /// Maximum number of exports of a module.
+    pub max_exports: usize,
+}
+
+impl WasmLimits {
+    pub fn from_version(protocol_version: u64) -> Option<WasmLimits> {
+        match protocol_version {
+            1 | 2 => Some(WasmLimits {
+                max_memory_pages: 64,
+                max_stack_height: 64 * 1024,
+                max_call_depth: 16,
+                max_module_size: 2 * 1024 * 1024,
+                max_functions: 10_000,
+                max_table_size: 4096,
+                max_globals: 256,
+                max_data_segments_size: 512 * 1024,
+                max_exports: 128,
+            }),
+            _ => None,
+        }
+    }
+}
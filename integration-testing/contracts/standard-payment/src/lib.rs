@@ -5,6 +5,7 @@
 extern crate alloc;
 extern crate contract_ffi;
 
+use contract_ffi::contract_api::error::ApiError;
 use contract_ffi::contract_api::pointers::UPointer;
 use contract_ffi::contract_api::{self, PurseTransferResult};
 use contract_ffi::key::Key;
@@ -21,12 +22,13 @@ pub extern "C" fn call() {
     let main_purse = contract_api::main_purse();
 
     let pos_public: UPointer<Key> = contract_api::get_uref(POS_CONTRACT_NAME)
-        .and_then(contract_ffi::key::Key::to_u_ptr)
-        .unwrap_or_else(|| contract_api::revert(66));
+        .unwrap_or_else(|| contract_api::revert_with_error(ApiError::MissingKey))
+        .to_u_ptr()
+        .unwrap_or_else(|| contract_api::revert_with_error(ApiError::UnexpectedKeyVariant));
 
     let pos_contract = contract_api::read(pos_public)
         .to_c_ptr()
-        .unwrap_or_else(|| contract_api::revert(67));
+        .unwrap_or_else(|| contract_api::revert_with_error(ApiError::UnexpectedKeyVariant));
 
     let payment_purse: PurseId =
         contract_api::call_contract(pos_contract, &(GET_PAYMENT_PURSE), &vec![]);
@@ -34,6 +36,6 @@ pub extern "C" fn call() {
     if let PurseTransferResult::TransferError =
         contract_api::transfer_from_purse_to_purse(main_purse, payment_purse, amount)
     {
-        contract_api::revert(99);
+        contract_api::revert_with_error(ApiError::Transfer);
     }
 }
@@ -112,7 +112,7 @@ def test_invalid_bonding(one_node_network_fn):
 
     r = node1.client.show_deploys(block_hash)[0]
     assert r.is_error is True
-    assert r.error_message == "Exit code: 5"
+    assert r.error_message == "Exit code: 65285"  # ProofOfStake(BondTooLarge)
 
     block_ds = parse_show_block(block1)
     public_key = node1.genesis_account.public_key_hex
@@ -223,7 +223,7 @@ def test_invalid_unbonding(one_node_network_fn):
     assert block_hash2 is not None
     r = node1.client.show_deploys(block_hash2)[0]
     assert r.is_error is True
-    assert r.error_message == "Exit code: 6"
+    assert r.error_message == "Exit code: 65286"  # ProofOfStake(UnbondTooLarge)
     block2 = node1.client.show_block(block_hash2)
     block_ds = parse_show_block(block2)
     item = list(
@@ -243,7 +243,7 @@ def test_invalid_unbonding(one_node_network_fn):
     assert block_hash2 is not None
     r = node1.client.show_deploys(block_hash2)[0]
     assert r.is_error is True
-    assert r.error_message == "Exit code: 6"
+    assert r.error_message == "Exit code: 65286"  # ProofOfStake(UnbondTooLarge)
     block2 = node1.client.show_block(block_hash2)
     block_ds = parse_show_block(block2)
     item = list(
@@ -278,7 +278,7 @@ def test_unbonding_without_bonding(one_node_network_fn):
     assert block_hash is not None
     r = node1.client.show_deploys(block_hash)[0]
     assert r.is_error is True
-    assert r.error_message == "Exit code: 0"
+    assert r.error_message == "Exit code: 65280"  # ProofOfStake(NotBonded)
 
     block2 = node1.client.show_block(block_hash)
     block_ds = parse_show_block(block2)
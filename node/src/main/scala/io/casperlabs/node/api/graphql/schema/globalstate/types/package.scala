@@ -114,11 +114,11 @@ package object types {
         "value",
         KeyUnion,
         resolve = _.value.value match {
-          case state.Key.Value.Local(value)   => value
-          case state.Key.Value.Hash(value)    => value
-          case state.Key.Value.Address(value) => value
-          case state.Key.Value.Uref(value)    => value
-          case state.Key.Value.Empty          => ???
+          case state.Key.Value.Local(value)             => value
+          case state.Key.Value.Hash(value)              => value
+          case state.Key.Value.Address(value)           => value
+          case state.Key.Value.Uref(value)              => value
+          case state.Key.Value.Empty                    => ???
         }
       )
     )
@@ -132,12 +132,38 @@ package object types {
     )
   )
 
+  lazy val ContractEntryPoint = ObjectType(
+    "ContractEntryPoint",
+    fields[Unit, state.Contract.EntryPoint](
+      Field("name", StringType, resolve = _.value.name),
+      Field("args", ListType(StringType), resolve = _.value.args.map(_.name))
+    )
+  )
+
   lazy val Contract = ObjectType(
     "Contract",
     fields[Unit, state.Contract](
       Field("body", StringType, resolve = c => Base16.encode(c.value.body.toByteArray)),
       Field("knownUrefs", ListType(NamedKey), resolve = _.value.knownUrefs),
-      Field("protocolVersion", LongType, resolve = _.value.protocolVersion.get.value)
+      Field("protocolVersion", LongType, resolve = _.value.protocolVersion.get.value),
+      Field("entryPoints", ListType(ContractEntryPoint), resolve = _.value.entryPoints)
+    )
+  )
+
+  lazy val ContractPackageVersion = ObjectType(
+    "ContractPackageVersion",
+    fields[Unit, state.ContractPackage.Version](
+      Field("version", LongType, resolve = _.value.version.toLong),
+      Field("contract", Contract, resolve = _.value.contract.get),
+      Field("isEnabled", BooleanType, resolve = _.value.isEnabled)
+    )
+  )
+
+  lazy val ContractPackage = ObjectType(
+    "ContractPackage",
+    fields[Unit, state.ContractPackage](
+      Field("accessKey", KeyURef, resolve = _.value.accessKey.get),
+      Field("versions", ListType(ContractPackageVersion), resolve = _.value.versions)
     )
   )
 
@@ -268,7 +294,8 @@ package object types {
       NamedKey,
       RustBigInt,
       KeyType,
-      UnitType
+      UnitType,
+      ContractPackage
     )
   )
 
@@ -279,19 +306,20 @@ package object types {
         "value",
         ValueUnion,
         resolve = _.value.value match {
-          case state.Value.Value.Contract(value)    => value
-          case state.Value.Value.BytesValue(value)  => value
-          case state.Value.Value.BigInt(value)      => value
-          case state.Value.Value.LongValue(value)   => value
-          case value: state.Value.Value.StringValue => value
-          case state.Value.Value.Key(value)         => value
-          case state.Value.Value.Unit(value)        => value
-          case value: state.Value.Value.IntValue    => value
-          case state.Value.Value.NamedKey(value)    => value
-          case state.Value.Value.Account(value)     => value
-          case state.Value.Value.StringList(value)  => value
-          case state.Value.Value.IntList(value)     => value
-          case state.Value.Value.Empty              => ???
+          case state.Value.Value.Contract(value)        => value
+          case state.Value.Value.BytesValue(value)      => value
+          case state.Value.Value.BigInt(value)          => value
+          case state.Value.Value.LongValue(value)       => value
+          case value: state.Value.Value.StringValue     => value
+          case state.Value.Value.Key(value)             => value
+          case state.Value.Value.Unit(value)            => value
+          case value: state.Value.Value.IntValue        => value
+          case state.Value.Value.NamedKey(value)        => value
+          case state.Value.Value.Account(value)         => value
+          case state.Value.Value.StringList(value)      => value
+          case state.Value.Value.IntList(value)         => value
+          case state.Value.Value.ContractPackage(value) => value
+          case state.Value.Value.Empty                  => ???
         }
       )
     )
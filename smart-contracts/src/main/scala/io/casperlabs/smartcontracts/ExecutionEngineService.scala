@@ -28,7 +28,7 @@ import io.casperlabs.catscontrib.MonadThrowable
   ): F[Either[Throwable, GenesisResult]]
   def exec(
       prestate: ByteString,
-      blocktime: Long,
+      blockInfo: ExecutionEngineService.BlockInfo,
       deploys: Seq[Deploy],
       protocolVersion: ProtocolVersion
   ): F[Either[Throwable, Seq[DeployResult]]]
@@ -69,13 +69,21 @@ class GrpcExecutionEngineService[F[_]: Defer: Sync: Log: TaskLift: Metrics] priv
 
   override def exec(
       prestate: ByteString,
-      blocktime: Long,
+      blockInfo: ExecutionEngineService.BlockInfo,
       deploys: Seq[Deploy],
       protocolVersion: ProtocolVersion
   ): F[Either[Throwable, Seq[DeployResult]]] =
     for {
       result <- sendMessage(
-                 ExecRequest(prestate, blocktime, deploys, Some(protocolVersion)),
+                 ExecRequest(
+                   prestate,
+                   blockInfo.blocktime,
+                   deploys,
+                   Some(protocolVersion),
+                   blockInfo.blockHeight,
+                   blockInfo.parentBlockHash,
+                   blockInfo.proposer
+                 ),
                  _.exec
                ) {
                  _.result match {
@@ -189,6 +197,14 @@ class GrpcExecutionEngineService[F[_]: Defer: Sync: Log: TaskLift: Metrics] priv
 object ExecutionEngineService {
   type Stub = IpcGrpcMonix.ExecutionEngineServiceStub
 
+  /** The block in which deploys are executed, as seen by the contracts they run. */
+  final case class BlockInfo(
+      blocktime: Long,
+      blockHeight: Long,
+      parentBlockHash: ByteString,
+      proposer: ByteString
+  )
+
   class CommitResult private (val postStateHash: ByteString, val bondedValidators: Seq[Bond])
 
   object CommitResult {